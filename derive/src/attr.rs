@@ -0,0 +1,310 @@
+//! `#[sciter(...)]` attributes of containers, variants, fields and script methods, `#[script_api(...)]` arguments.
+
+use proc_macro2::TokenStream;
+use syn::parse::Parser;
+use syn::{Attribute, Error, Fields, Ident, LitStr, Path, Result, Token};
+
+
+/// How to rename fields or variants, `#[sciter(rename_all = "...")]`.
+#[derive(Clone, Copy, PartialEq)]
+pub enum RenameRule {
+	None,
+	Lower,
+	Upper,
+	Pascal,
+	Camel,
+	Snake,
+	ScreamingSnake,
+	Kebab,
+}
+
+impl RenameRule {
+	fn parse(lit: &LitStr) -> Result<Self> {
+		let rule = match lit.value().as_str() {
+			"lowercase" => RenameRule::Lower,
+			"UPPERCASE" => RenameRule::Upper,
+			"PascalCase" => RenameRule::Pascal,
+			"camelCase" => RenameRule::Camel,
+			"snake_case" => RenameRule::Snake,
+			"SCREAMING_SNAKE_CASE" => RenameRule::ScreamingSnake,
+			"kebab-case" => RenameRule::Kebab,
+			_ => return Err(Error::new(lit.span(), "unknown rename rule")),
+		};
+		Ok(rule)
+	}
+
+	/// Rename either a `snake_case` field or a `PascalCase` variant.
+	pub fn apply(self, name: &str) -> String {
+		let capitalize = |word: &str| {
+			let mut chars = word.chars();
+			chars.next().map(|c| c.to_uppercase().chain(chars).collect()).unwrap_or_default()
+		};
+		let words = split_words(name);
+		match self {
+			RenameRule::None => name.to_owned(),
+			RenameRule::Lower => name.to_lowercase(),
+			RenameRule::Upper => name.to_uppercase(),
+			RenameRule::Pascal => words.iter().map(|w| capitalize(w)).collect(),
+			RenameRule::Camel => words.iter().enumerate().map(|(i, w)| if i == 0 { w.clone() } else { capitalize(w) }).collect(),
+			RenameRule::Snake => words.join("_"),
+			RenameRule::ScreamingSnake => words.join("_").to_uppercase(),
+			RenameRule::Kebab => words.join("-"),
+		}
+	}
+}
+
+// `field_name` and `VariantName` -> `["field", "name"]` and `["variant", "name"]`
+fn split_words(name: &str) -> Vec<String> {
+	let mut words = Vec::new();
+	let mut word = String::new();
+	let mut prev_lower = false;
+	for c in name.chars() {
+		if c == '_' {
+			words.push(::std::mem::take(&mut word));
+			prev_lower = false;
+			continue;
+		}
+		if c.is_uppercase() && prev_lower {
+			words.push(::std::mem::take(&mut word));
+		}
+		prev_lower = c.is_lowercase() || c.is_numeric();
+		word.extend(c.to_lowercase());
+	}
+	words.push(word);
+	words.retain(|w| !w.is_empty());
+	words
+}
+
+
+/// Enum representation.
+#[derive(Clone, PartialEq)]
+pub enum Tagging {
+	/// `{ "Variant": content }`, the default.
+	External,
+	/// `{ tag: "Variant", ...fields }`, `#[sciter(tag = "...")]`.
+	Internal(String),
+	/// `{ tag: "Variant", content: content }`, `#[sciter(tag = "...", content = "...")]`.
+	Adjacent(String, String),
+	/// Just the content, `#[sciter(untagged)]`.
+	Untagged,
+}
+
+/// Attributes of a struct or an enum.
+pub struct Container {
+	pub rename_all: RenameRule,
+	pub tagging: Tagging,
+	pub default: bool,
+}
+
+impl Container {
+	pub fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+		let mut rename_all = RenameRule::None;
+		let mut tag = None;
+		let mut content = None;
+		let mut untagged = false;
+		let mut default = false;
+		for attr in attrs.iter().filter(|a| a.path().is_ident("sciter")) {
+			attr.parse_nested_meta(|meta| {
+				if meta.path.is_ident("rename_all") {
+					rename_all = RenameRule::parse(&meta.value()?.parse()?)?;
+				} else if meta.path.is_ident("tag") {
+					tag = Some(meta.value()?.parse::<LitStr>()?.value());
+				} else if meta.path.is_ident("content") {
+					content = Some(meta.value()?.parse::<LitStr>()?.value());
+				} else if meta.path.is_ident("untagged") {
+					untagged = true;
+				} else if meta.path.is_ident("default") {
+					default = true;
+				} else {
+					return Err(meta.error("unknown sciter container attribute"));
+				}
+				Ok(())
+			})?;
+		}
+		let tagging = match (tag, content, untagged) {
+			(None, None, false) => Tagging::External,
+			(None, None, true) => Tagging::Untagged,
+			(Some(tag), None, false) => Tagging::Internal(tag),
+			(Some(tag), Some(content), false) => Tagging::Adjacent(tag, content),
+			(None, Some(_), _) => return Err(Error::new_spanned(&attrs[0], "`content` requires `tag`")),
+			(Some(_), _, true) => return Err(Error::new_spanned(&attrs[0], "`untagged` conflicts with `tag`")),
+		};
+		Ok(Container {
+			rename_all: rename_all,
+			tagging: tagging,
+			default: default,
+		})
+	}
+}
+
+
+/// Attributes of an enum variant.
+pub struct Variant {
+	pub rename: Option<String>,
+}
+
+impl Variant {
+	pub fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+		let mut rename = None;
+		for attr in attrs.iter().filter(|a| a.path().is_ident("sciter")) {
+			attr.parse_nested_meta(|meta| {
+				if meta.path.is_ident("rename") {
+					rename = Some(meta.value()?.parse::<LitStr>()?.value());
+				} else {
+					return Err(meta.error("unknown sciter variant attribute"));
+				}
+				Ok(())
+			})?;
+		}
+		Ok(Variant { rename: rename })
+	}
+}
+
+
+/// Where the value of a missing field comes from.
+pub enum Default {
+	/// The field is required.
+	None,
+	/// `Default::default()`, `#[sciter(default)]`.
+	Trait,
+	/// The given function, `#[sciter(default = "path::to::function")]`.
+	Path(Path),
+}
+
+/// Attributes of a struct or a variant field.
+pub struct Field {
+	pub rename: Option<String>,
+	pub default: Default,
+	pub skip: bool,
+	pub flatten: bool,
+}
+
+impl Field {
+	pub fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+		let mut field = Field {
+			rename: None,
+			default: Default::None,
+			skip: false,
+			flatten: false,
+		};
+		for attr in attrs.iter().filter(|a| a.path().is_ident("sciter")) {
+			attr.parse_nested_meta(|meta| {
+				if meta.path.is_ident("rename") {
+					field.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+				} else if meta.path.is_ident("default") {
+					field.default = if meta.input.peek(Token![=]) {
+						Default::Path(meta.value()?.parse::<LitStr>()?.parse()?)
+					} else {
+						Default::Trait
+					};
+				} else if meta.path.is_ident("skip") {
+					field.skip = true;
+				} else if meta.path.is_ident("flatten") {
+					field.flatten = true;
+				} else {
+					return Err(meta.error("unknown sciter field attribute"));
+				}
+				Ok(())
+			})?;
+		}
+		Ok(field)
+	}
+
+	/// Attributes of all fields, checking the ones which make sense only for named fields.
+	pub fn all(fields: &Fields) -> Result<Vec<Field>> {
+		let named = matches!(*fields, Fields::Named(_));
+		let mut all = Vec::new();
+		for f in fields.iter() {
+			let attrs = Field::from_attrs(&f.attrs)?;
+			if !named && (attrs.rename.is_some() || attrs.flatten) {
+				return Err(Error::new_spanned(f, "`rename` and `flatten` are supported only for named fields"));
+			}
+			all.push(attrs);
+		}
+		Ok(all)
+	}
+}
+
+
+/// Arguments of `#[script_api(...)]`.
+pub struct Api {
+	pub rename_all: RenameRule,
+	pub dispatcher: Option<Ident>,
+}
+
+impl Api {
+	pub fn parse(args: TokenStream) -> Result<Self> {
+		let mut api = Api {
+			rename_all: RenameRule::None,
+			dispatcher: None,
+		};
+		let parser = syn::meta::parser(|meta| {
+			if meta.path.is_ident("rename_all") {
+				api.rename_all = RenameRule::parse(&meta.value()?.parse()?)?;
+			} else if meta.path.is_ident("dispatcher") {
+				api.dispatcher = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+			} else {
+				return Err(meta.error("unknown script_api attribute"));
+			}
+			Ok(())
+		});
+		parser.parse2(args)?;
+		Ok(api)
+	}
+}
+
+
+/// Attributes of a method exposed to script.
+pub struct Method {
+	pub rename: Option<String>,
+	pub skip: bool,
+}
+
+impl Method {
+	pub fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+		let mut method = Method {
+			rename: None,
+			skip: false,
+		};
+		for attr in attrs.iter().filter(|a| a.path().is_ident("sciter")) {
+			attr.parse_nested_meta(|meta| {
+				if meta.path.is_ident("rename") {
+					method.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+				} else if meta.path.is_ident("skip") {
+					method.skip = true;
+				} else {
+					return Err(meta.error("unknown sciter method attribute"));
+				}
+				Ok(())
+			})?;
+		}
+		Ok(method)
+	}
+}
+
+
+/// Attributes of a method argument.
+pub struct Arg {
+	pub default: Default,
+}
+
+impl Arg {
+	pub fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+		let mut default = Default::None;
+		for attr in attrs.iter().filter(|a| a.path().is_ident("sciter")) {
+			attr.parse_nested_meta(|meta| {
+				if meta.path.is_ident("default") {
+					default = if meta.input.peek(Token![=]) {
+						Default::Path(meta.value()?.parse::<LitStr>()?.parse()?)
+					} else {
+						Default::Trait
+					};
+				} else {
+					return Err(meta.error("unknown sciter argument attribute"));
+				}
+				Ok(())
+			})?;
+		}
+		Ok(Arg { default: default })
+	}
+}
@@ -0,0 +1,219 @@
+//! `#[derive(FromValue)]`: `sciter::FromValue`.
+
+use proc_macro2::TokenStream as Tokens;
+use syn::{Data, DeriveInput, Error, Fields, Result};
+
+use attr::{self, Container, Default, RenameRule, Tagging};
+use {field_key, variant_name, with_bound};
+
+
+pub fn expand(input: &DeriveInput) -> Result<Tokens> {
+	let cont = Container::from_attrs(&input.attrs)?;
+	let name = &input.ident;
+	let type_name = name.to_string();
+
+	let body = match input.data {
+		Data::Struct(ref data) => construct(cont.rename_all, cont.default, quote!(#name), &data.fields, quote!(v), &type_name)?,
+		Data::Enum(ref data) => {
+			let mut variants = Vec::new();
+			for variant in &data.variants {
+				let vname = variant_name(variant, &attr::Variant::from_attrs(&variant.attrs)?, cont.rename_all);
+				let ident = &variant.ident;
+				variants.push((vname, quote!(#name::#ident), variant));
+			}
+			enum_body(&cont.tagging, &variants, &type_name)?
+		},
+		Data::Union(_) => return Err(Error::new_spanned(input, "unions are not supported")),
+	};
+
+	let generics = with_bound(&input.generics, quote!(::sciter::FromValue));
+	let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+	Ok(quote! {
+		#[allow(clippy::redundant_closure_call)]
+		impl #impl_generics ::sciter::FromValue for #name #ty_generics #where_clause {
+			fn from_value(v: &::sciter::Value) -> ::std::option::Option<Self> {
+				<Self as ::sciter::FromValue>::try_from_value(v).ok()
+			}
+
+			fn try_from_value(v: &::sciter::Value) -> ::std::result::Result<Self, ::sciter::value::ConversionError> {
+				#body
+			}
+		}
+	})
+}
+
+/// Statements returning the matching variant.
+fn enum_body(tagging: &Tagging, variants: &[(String, Tokens, &syn::Variant)], type_name: &str) -> Result<Tokens> {
+	let mut checks = Vec::new();
+	let body = match *tagging {
+		Tagging::External => {
+			let mut units = Vec::new();
+			for &(ref vname, ref path, variant) in variants {
+				if let Fields::Unit = variant.fields {
+					units.push(quote!(if __name == #vname { return Ok(#path); }));
+				} else {
+					let build = construct(RenameRule::None, false, path.clone(), &variant.fields, quote!(&__content), type_name)?;
+					checks.push(quote!(if __name == #vname { return #build.map_err(|e| e.at_key(&__key)); }));
+				}
+			}
+			quote! {
+				if v.is_string() {
+					let __name = v.as_string().unwrap_or_default();
+					#(#units)*
+				} else if v.is_vmap() && v.len() == 1 {
+					let __key = v.key_at(0);
+					let __content = v.get(0);
+					let __name = __key.as_string().unwrap_or_default();
+					#(#checks)*
+				}
+				Err(::sciter::value::ConversionError::new(#type_name, v))
+			}
+		},
+		Tagging::Internal(ref tag) => {
+			for &(ref vname, ref path, variant) in variants {
+				let build = match variant.fields {
+					Fields::Unit => quote!(Ok(#path)),
+					Fields::Unnamed(ref f) if f.unnamed.len() == 1 => {
+						let ty = &f.unnamed[0].ty;
+						quote!(<#ty as ::sciter::FromValue>::try_from_value(v).map(#path))
+					},
+					Fields::Unnamed(_) => return Err(Error::new_spanned(variant, "tuple variants are not supported with internal tagging")),
+					Fields::Named(_) => construct(RenameRule::None, false, path.clone(), &variant.fields, quote!(v), type_name)?,
+				};
+				checks.push(quote!(if __name == #vname { return #build; }));
+			}
+			quote! {
+				if !v.is_vmap() {
+					return Err(::sciter::value::ConversionError::new(#type_name, v));
+				}
+				let __tag = v.get_item(#tag);
+				let __name = __tag.as_string().unwrap_or_default();
+				#(#checks)*
+				Err(::sciter::value::ConversionError::new(#type_name, &__tag).at_key(&::sciter::Value::from(#tag)))
+			}
+		},
+		Tagging::Adjacent(ref tag, ref content) => {
+			for &(ref vname, ref path, variant) in variants {
+				let build = match variant.fields {
+					Fields::Unit => quote!(Ok(#path)),
+					_ => {
+						let build = construct(RenameRule::None, false, path.clone(), &variant.fields, quote!(&__content), type_name)?;
+						quote!(#build.map_err(|e| e.at_key(&::sciter::Value::from(#content))))
+					},
+				};
+				checks.push(quote!(if __name == #vname { return #build; }));
+			}
+			quote! {
+				if !v.is_vmap() {
+					return Err(::sciter::value::ConversionError::new(#type_name, v));
+				}
+				let __tag = v.get_item(#tag);
+				let __content = v.get_item(#content);
+				let __name = __tag.as_string().unwrap_or_default();
+				#(#checks)*
+				Err(::sciter::value::ConversionError::new(#type_name, &__tag).at_key(&::sciter::Value::from(#tag)))
+			}
+		},
+		Tagging::Untagged => {
+			for &(_, ref path, variant) in variants {
+				let build = construct(RenameRule::None, false, path.clone(), &variant.fields, quote!(v), type_name)?;
+				checks.push(quote!(if let Ok(__result) = #build { return Ok(__result); }));
+			}
+			quote! {
+				#(#checks)*
+				Err(::sciter::value::ConversionError::new(#type_name, v))
+			}
+		},
+	};
+	Ok(body)
+}
+
+/// Expression converting `value` (a `&Value`) to the struct or variant at `path`, evaluates to `Result<Self, ConversionError>`.
+fn construct(rule: RenameRule, container_default: bool, path: Tokens, fields: &Fields, value: Tokens, type_name: &str) -> Result<Tokens> {
+	let attrs = attr::Field::all(fields)?;
+	let body = match *fields {
+		Fields::Named(_) => {
+			let mut inits = Vec::new();
+			for (field, attrs) in fields.iter().zip(&attrs) {
+				let member = &field.ident;
+				let ty = &field.ty;
+				let init = if attrs.skip {
+					quote!(::std::default::Default::default())
+				} else if attrs.flatten {
+					quote!(<#ty as ::sciter::FromValue>::try_from_value(__value)?)
+				} else {
+					let key = field_key(field, attrs, rule);
+					let fallback = match attrs.default {
+						Default::Trait => Some(quote!(::std::default::Default::default())),
+						Default::Path(ref function) => Some(quote!(#function())),
+						Default::None if container_default => Some(quote!(__default.#member)),
+						Default::None => None,
+					};
+					match fallback {
+						Some(fallback) => quote! {
+							match __value.try_get_item(#key) {
+								Some(ref __item) if !__item.is_undefined() => <#ty as ::sciter::FromValue>::try_from_value(__item)
+									.map_err(|e| e.at_key(&::sciter::Value::from(#key)))?,
+								_ => #fallback,
+							}
+						},
+						None => quote! {
+							<#ty as ::sciter::FromValue>::try_from_value(&__value.get_item(#key))
+								.map_err(|e| e.at_key(&::sciter::Value::from(#key)))?
+						},
+					}
+				};
+				inits.push(quote!(#member: #init,));
+			}
+			let default = if container_default {
+				quote!(let __default = <Self as ::std::default::Default>::default();)
+			} else {
+				quote!()
+			};
+			quote! {
+				if !__value.is_vmap() {
+					return Err(::sciter::value::ConversionError::new(#type_name, __value));
+				}
+				#default
+				Ok(#path { #(#inits)* })
+			}
+		},
+		Fields::Unnamed(_) if fields.len() == 1 && !attrs[0].skip => {
+			let ty = &fields.iter().next().expect("single field").ty;
+			quote!(<#ty as ::sciter::FromValue>::try_from_value(__value).map(#path))
+		},
+		Fields::Unnamed(_) => {
+			let count = attrs.iter().filter(|a| !a.skip).count();
+			let mut index = 0usize;
+			let mut inits = Vec::new();
+			for (field, attrs) in fields.iter().zip(&attrs) {
+				let ty = &field.ty;
+				if attrs.skip {
+					inits.push(quote!(::std::default::Default::default()));
+				} else {
+					inits.push(quote!(<#ty as ::sciter::FromValue>::try_from_value(&__value.get(#index)).map_err(|e| e.at_index(#index))?));
+					index += 1;
+				}
+			}
+			quote! {
+				if !__value.is_varray() || __value.len() != #count {
+					return Err(::sciter::value::ConversionError::new(#type_name, __value));
+				}
+				Ok(#path(#(#inits),*))
+			}
+		},
+		Fields::Unit => quote! {
+			if __value.is_null() || __value.is_undefined() {
+				Ok(#path)
+			} else {
+				Err(::sciter::value::ConversionError::new(#type_name, __value))
+			}
+		},
+	};
+	Ok(quote! {
+		(|| -> ::std::result::Result<Self, ::sciter::value::ConversionError> {
+			let __value: &::sciter::Value = #value;
+			#body
+		})()
+	})
+}
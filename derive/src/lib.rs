@@ -0,0 +1,162 @@
+//! Derive macros for [`sciter::Value`](https://docs.rs/sciter-rs/latest/sciter/value/struct.Value.html) conversions.
+//!
+//! `#[derive(ToValue)]` implements `From<T> for sciter::Value` and `#[derive(FromValue)]` implements
+//! [`sciter::FromValue`](https://docs.rs/sciter-rs/latest/sciter/value/trait.FromValue.html),
+//! building values directly, without the `serde` layer of `sciter-serde`.
+//! The macros are re-exported by `sciter-rs` with its `derive` feature enabled.
+//!
+//! ```rust,ignore
+//! #[macro_use] extern crate sciter_derive;
+//!
+//! #[derive(ToValue, FromValue)]
+//! #[sciter(rename_all = "camelCase")]
+//! struct Window {
+//!   title: String,
+//!   is_visible: bool,
+//!   #[sciter(default)]
+//!   tags: Vec<String>,
+//!   #[sciter(skip)]
+//!   cache: Option<Vec<u8>>,
+//!   #[sciter(flatten)]
+//!   size: Size,
+//! }
+//! ```
+//!
+//! Structs with named fields become maps, tuple structs become arrays
+//! (except the single-field ones which are converted as their field), unit structs are `null`.
+//!
+//! ## Attributes
+//!
+//! Containers:
+//!
+//! * `#[sciter(rename_all = "...")]` renames all fields (or variants of an enum):
+//!   `lowercase`, `UPPERCASE`, `PascalCase`, `camelCase`, `snake_case`, `SCREAMING_SNAKE_CASE` or `kebab-case`;
+//! * `#[sciter(default)]` takes missing fields of a struct from its `Default` implementation;
+//! * `#[sciter(tag = "type")]` stores an enum as `{ type: "Variant", ...fields }`;
+//! * `#[sciter(tag = "t", content = "c")]` stores an enum as `{ t: "Variant", c: content }`;
+//! * `#[sciter(untagged)]` stores only the variant content, the first matching variant wins on conversion back.
+//!
+//! By default enums are stored as `{ "Variant": content }` and unit variants as `"Variant"` strings.
+//!
+//! Variants:
+//!
+//! * `#[sciter(rename = "name")]`.
+//!
+//! Fields:
+//!
+//! * `#[sciter(rename = "name")]`;
+//! * `#[sciter(default)]` or `#[sciter(default = "path::to::function")]` for missing or `undefined` keys;
+//! * `#[sciter(skip)]` does not store the field and takes it from `Default` on conversion back;
+//! * `#[sciter(flatten)]` merges the fields of a nested struct (or a map) into the parent one.
+//!
+//! `Option` fields do not need a default: missing keys, `null` and `undefined` become `None`.
+//!
+//! Conversion errors carry the path to the failed field, e.g. `expected i32, found string:"x" at size.width`.
+//!
+//! ## Script API
+//!
+//! `#[script_api]` on an impl block implements
+//! [`sciter::EventHandler::dispatch_script_call`](https://docs.rs/sciter-rs/latest/sciter/dom/event/trait.EventHandler.html)
+//! for its `pub` methods taking `&self` or `&mut self`,
+//! a more capable alternative to the `dispatch_script_call!` macro:
+//!
+//! ```rust,ignore
+//! #[script_api(rename_all = "camelCase")]
+//! impl Handler {
+//!   // `findItems("a")`, `findItems("a", 10)` or `findItems("a", undefined, true)`
+//!   pub fn find_items(&self, name: String, #[sciter(default = "default_limit")] limit: usize, exact: Option<bool>) -> Vec<String> {
+//!     // ...
+//!   }
+//!
+//!   // `log(1, "2", [3])`, the root element is not a script argument
+//!   #[sciter(rename = "log")]
+//!   pub fn print(&mut self, root: Element, args: &[Value]) {
+//!     // ...
+//!   }
+//!
+//!   // `Err` becomes a script error
+//!   pub fn load(&mut self, path: String) -> Result<(), std::io::Error> {
+//!     // ...
+//!   }
+//! }
+//! ```
+//!
+//! Arguments are converted via `sciter::FromValue` and results via `From<T> for sciter::Value`,
+//! invalid calls return script errors like `findItems error: 0 of 1 to 3 arguments provided.`
+//!
+//! * `#[script_api(rename_all = "...")]` renames the methods with the same rules as above;
+//! * `#[script_api(dispatcher = "name")]` generates an inherent method with the given name instead of
+//!   the `EventHandler` implementation, so it can be called from a handwritten `dispatch_script_call`;
+//! * `#[sciter(rename = "name")]` on a method sets its script name, `#[sciter(skip)]` hides it;
+//! * `Option` arguments may be omitted (or `undefined`), as the ones with `#[sciter(default)]`
+//!   or `#[sciter(default = "path::to::function")]`; they must follow the required ones;
+//! * the last `&[Value]` argument takes the rest of script arguments;
+//! * `HELEMENT` and `Element` arguments get the behavior root.
+
+#![doc(html_logo_url = "https://sciter.com/screenshots/slide-sciter-osx.png",
+       html_favicon_url = "https://sciter.com/wp-content/themes/sciter/!images/favicon.ico")]
+#![allow(clippy::redundant_field_names)]
+
+extern crate proc_macro;
+extern crate proc_macro2;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+mod attr;
+mod from_value;
+mod script_api;
+mod to_value;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as Tokens;
+use syn::{DeriveInput, Generics};
+
+
+/// Implements `From<T> for sciter::Value`, see the [crate documentation](index.html).
+#[proc_macro_derive(ToValue, attributes(sciter))]
+pub fn derive_to_value(input: TokenStream) -> TokenStream {
+	let input = syn::parse_macro_input!(input as DeriveInput);
+	to_value::expand(&input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// Implements `sciter::FromValue`, see the [crate documentation](index.html).
+#[proc_macro_derive(FromValue, attributes(sciter))]
+pub fn derive_from_value(input: TokenStream) -> TokenStream {
+	let input = syn::parse_macro_input!(input as DeriveInput);
+	from_value::expand(&input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// Exposes the methods of an impl block to script, see the [crate documentation](index.html#script-api).
+#[proc_macro_attribute]
+pub fn script_api(args: TokenStream, input: TokenStream) -> TokenStream {
+	script_api::expand(args.into(), input.into()).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+
+/// Add the `bound` to every type parameter.
+fn with_bound(generics: &Generics, bound: Tokens) -> Generics {
+	let mut generics = generics.clone();
+	let params: Vec<_> = generics.type_params().map(|p| p.ident.clone()).collect();
+	let clause = generics.make_where_clause();
+	for param in params {
+		clause.predicates.push(syn::parse_quote!(#param: #bound));
+	}
+	generics
+}
+
+/// Key of the named field.
+fn field_key(field: &syn::Field, attrs: &attr::Field, rule: attr::RenameRule) -> String {
+	match attrs.rename {
+		Some(ref name) => name.clone(),
+		None => rule.apply(&field.ident.as_ref().expect("named field").to_string()),
+	}
+}
+
+/// Name of the enum variant.
+fn variant_name(variant: &syn::Variant, attrs: &attr::Variant, rule: attr::RenameRule) -> String {
+	match attrs.rename {
+		Some(ref name) => name.clone(),
+		None => rule.apply(&variant.ident.to_string()),
+	}
+}
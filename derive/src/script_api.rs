@@ -0,0 +1,240 @@
+//! `#[script_api]`: `sciter::EventHandler::dispatch_script_call` for the methods of an impl block.
+
+use std::collections::HashSet;
+
+use proc_macro2::TokenStream as Tokens;
+use syn::{Error, FnArg, ImplItem, ImplItemFn, ItemImpl, ReceiverKind, Result, ReturnType, Type, Visibility};
+
+use attr::{self, Api, Default, RenameRule};
+
+
+pub fn expand(args: Tokens, input: Tokens) -> Result<Tokens> {
+	let api = Api::parse(args)?;
+	let mut item: ItemImpl = syn::parse2(input)?;
+	if let Some((ref path, _)) = item.trait_ {
+		return Err(Error::new_spanned(path, "`script_api` expects an inherent impl block"));
+	}
+
+	let mut names = HashSet::new();
+	let mut arms = Vec::new();
+	for method in item.items.iter_mut() {
+		if let ImplItem::Fn(ref mut method) = *method {
+			if let Some((name, arm)) = dispatch_arm(method, api.rename_all)? {
+				if !names.insert(name.clone()) {
+					return Err(Error::new_spanned(&method.sig.ident, format!("duplicate script method `{}`", name)));
+				}
+				arms.push(arm);
+			}
+			strip_attrs(method);
+		}
+	}
+
+	let body = quote! {
+		match name {
+			#(#arms)*
+			_ => None,
+		}
+	};
+	let self_ty = &item.self_ty;
+	let (impl_generics, _, where_clause) = item.generics.split_for_impl();
+	let dispatcher = match api.dispatcher {
+		Some(ref dispatcher) => quote! {
+			impl #impl_generics #self_ty #where_clause {
+				/// Dispatch script calls to the methods exposed by `#[script_api]`.
+				#[allow(unused_variables)]
+				pub fn #dispatcher(&mut self, root: ::sciter::HELEMENT, name: &str, argv: &[::sciter::Value]) -> ::std::option::Option<::sciter::Value> {
+					#body
+				}
+			}
+		},
+		None => quote! {
+			impl #impl_generics ::sciter::EventHandler for #self_ty #where_clause {
+				#[allow(unused_variables)]
+				fn dispatch_script_call(&mut self, root: ::sciter::HELEMENT, name: &str, argv: &[::sciter::Value]) -> ::std::option::Option<::sciter::Value> {
+					#body
+				}
+			}
+		},
+	};
+	Ok(quote! {
+		#item
+		#dispatcher
+	})
+}
+
+/// Remove `#[sciter(...)]` attributes which are not known to the compiler.
+fn strip_attrs(method: &mut ImplItemFn) {
+	method.attrs.retain(|a| !a.path().is_ident("sciter"));
+	for arg in method.sig.inputs.iter_mut() {
+		if let FnArg::Typed(ref mut arg) = *arg {
+			arg.attrs.retain(|a| !a.path().is_ident("sciter"));
+		}
+	}
+}
+
+
+/// How a method argument is obtained.
+enum Source {
+	/// `HELEMENT` of the behavior root.
+	Root,
+	/// `Element` of the behavior root.
+	RootElement,
+	/// Script argument which must be provided.
+	Required,
+	/// Script argument which may be omitted or `undefined`.
+	Optional(Tokens),
+	/// `&[Value]` with the rest of script arguments.
+	Rest,
+}
+
+/// `match` arm calling the method, `None` for the methods not exposed to script.
+fn dispatch_arm(method: &ImplItemFn, rule: RenameRule) -> Result<Option<(String, Tokens)>> {
+	let attrs = attr::Method::from_attrs(&method.attrs)?;
+	let sig = &method.sig;
+	let exposed = !attrs.skip && sig.receiver().is_some() && !matches!(method.vis, Visibility::Inherited);
+	if !exposed {
+		if attrs.rename.is_some() {
+			return Err(Error::new_spanned(&sig.ident, "only `pub` methods taking `self` are exposed to script"));
+		}
+		return Ok(None);
+	}
+	if let Some(receiver) = sig.receiver() {
+		if !matches!(receiver.kind, ReceiverKind::Reference(..)) {
+			return Err(Error::new_spanned(receiver, "script methods must take `&self` or `&mut self`"));
+		}
+	}
+	if let Some(ref asyncness) = sig.asyncness {
+		return Err(Error::new_spanned(asyncness, "async methods are not supported"));
+	}
+
+	let ident = &sig.ident;
+	let name = attrs.rename.unwrap_or_else(|| rule.apply(&ident.to_string()));
+
+	let mut required = 0usize;
+	let mut count = 0usize;
+	let mut rest = false;
+	let mut values = Vec::new();
+	for arg in sig.inputs.iter() {
+		let arg = match *arg {
+			FnArg::Typed(ref arg) => arg,
+			FnArg::Receiver(_) => continue,
+		};
+		let ty = &arg.ty;
+		let source = match (root_kind(ty), attr::Arg::from_attrs(&arg.attrs)?.default) {
+			(Some(source), Default::None) => source,
+			(Some(_), _) => return Err(Error::new_spanned(arg, "root element arguments can not have a default")),
+			(None, Default::Trait) => Source::Optional(quote!(::std::default::Default::default())),
+			(None, Default::Path(ref function)) => Source::Optional(quote!(#function())),
+			(None, Default::None) if is_rest(ty) => Source::Rest,
+			(None, Default::None) if is_named(ty, "Option") => Source::Optional(quote!(::std::option::Option::None)),
+			(None, Default::None) => Source::Required,
+		};
+		let index = count;
+		let value = match source {
+			Source::Root => quote!(root),
+			Source::RootElement => quote!(::sciter::Element::from(root)),
+			_ if rest => return Err(Error::new_spanned(arg, "the `&[Value]` argument must be the last script argument")),
+			Source::Rest => {
+				rest = true;
+				quote!(argv.get(#index..).unwrap_or(&[]))
+			},
+			Source::Required => {
+				if required != count {
+					return Err(Error::new_spanned(arg, "required arguments can not follow optional ones"));
+				}
+				required += 1;
+				count += 1;
+				convert(&name, index, ty, quote!(&argv[#index]))
+			},
+			Source::Optional(fallback) => {
+				count += 1;
+				let value = convert(&name, index, ty, quote!(__arg));
+				quote! {
+					match argv.get(#index) {
+						Some(__arg) if !__arg.is_undefined() => #value,
+						_ => #fallback,
+					}
+				}
+			},
+		};
+		values.push(value);
+	}
+
+	let expected = if rest {
+		format!("at least {}", required)
+	} else if required == count {
+		count.to_string()
+	} else {
+		format!("{} to {}", required, count)
+	};
+	let too_many = if rest { quote!(false) } else { quote!(argv.len() > #count) };
+
+	let returns_result = match sig.output {
+		ReturnType::Type(_, ref ty) => is_named(ty, "Result"),
+		ReturnType::Default => false,
+	};
+	let result = if returns_result {
+		quote! {
+			match rv {
+				Ok(rv) => ::sciter::Value::from(rv),
+				Err(e) => ::sciter::Value::error(&e.to_string()),
+			}
+		}
+	} else {
+		quote!(::sciter::Value::from(rv))
+	};
+
+	let arm = quote! {
+		#name => {
+			if argv.len() < #required || #too_many {
+				return Some(::sciter::Value::error(&format!("{} error: {} of {} arguments provided.", #name, argv.len(), #expected)));
+			}
+			let rv = self.#ident(#(#values),*);
+			Some(#result)
+		},
+	};
+	Ok(Some((name, arm)))
+}
+
+/// Expression converting the `value` (a `&Value`) to the argument type or returning the script error.
+fn convert(name: &str, index: usize, ty: &Type, value: Tokens) -> Tokens {
+	quote! {
+		match <#ty as ::sciter::FromValue>::try_from_value(#value) {
+			Ok(arg) => arg,
+			Err(e) => {
+				return Some(::sciter::Value::error(&format!("{} error: invalid type of {} argument ({} expected): {}.",
+					#name, #index, stringify!(#ty), e)));
+			},
+		}
+	}
+}
+
+/// Whether the type path ends with `name`, like `std::option::Option<T>` does with `Option`.
+fn is_named(ty: &Type, name: &str) -> bool {
+	match *ty {
+		Type::Path(ref ty) if ty.qself.is_none() => ty.path.segments.last().is_some_and(|s| s.ident == name),
+		_ => false,
+	}
+}
+
+/// Arguments taking the behavior root instead of a script argument.
+fn root_kind(ty: &Type) -> Option<Source> {
+	if is_named(ty, "HELEMENT") {
+		Some(Source::Root)
+	} else if is_named(ty, "Element") {
+		Some(Source::RootElement)
+	} else {
+		None
+	}
+}
+
+/// `&[Value]`.
+fn is_rest(ty: &Type) -> bool {
+	match *ty {
+		Type::Reference(ref r) if r.mutability.is_none() => match *r.elem {
+			Type::Slice(ref slice) => is_named(&slice.elem, "Value"),
+			_ => false,
+		},
+		_ => false,
+	}
+}
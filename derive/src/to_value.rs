@@ -0,0 +1,155 @@
+//! `#[derive(ToValue)]`: `From<T> for sciter::Value`.
+
+use proc_macro2::TokenStream as Tokens;
+use syn::{Data, DeriveInput, Error, Fields, Ident, Result};
+
+use attr::{self, Container, RenameRule, Tagging};
+use {field_key, variant_name, with_bound};
+
+
+pub fn expand(input: &DeriveInput) -> Result<Tokens> {
+	let cont = Container::from_attrs(&input.attrs)?;
+	let name = &input.ident;
+
+	let body = match input.data {
+		Data::Struct(ref data) => {
+			let access: Vec<Tokens> = data.fields.members().map(|m| quote!(val.#m)).collect();
+			fields_value(cont.rename_all, &data.fields, &access, quote!())?
+		},
+		Data::Enum(ref data) => {
+			let mut arms = Vec::new();
+			for variant in &data.variants {
+				let vname = variant_name(variant, &attr::Variant::from_attrs(&variant.attrs)?, cont.rename_all);
+				let ident = &variant.ident;
+				let bindings: Vec<Ident> = (0..variant.fields.len()).map(|i| format_ident!("__f{}", i)).collect();
+				let members = variant.fields.members();
+				let pattern = match variant.fields {
+					Fields::Named(_) => quote!(#name::#ident { #(#members: #bindings),* }),
+					Fields::Unnamed(_) => quote!(#name::#ident(#(#bindings),*)),
+					Fields::Unit => quote!(#name::#ident),
+				};
+				let access: Vec<Tokens> = bindings.iter().map(|b| quote!(#b)).collect();
+				let value = variant_value(&cont.tagging, &vname, variant, &access)?;
+				arms.push(quote!(#pattern => #value,));
+			}
+			quote! {
+				match val {
+					#(#arms)*
+				}
+			}
+		},
+		Data::Union(_) => return Err(Error::new_spanned(input, "unions are not supported")),
+	};
+
+	let generics = with_bound(&input.generics, quote!(::std::convert::Into<::sciter::Value>));
+	let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+	Ok(quote! {
+		impl #impl_generics ::std::convert::From<#name #ty_generics> for ::sciter::Value #where_clause {
+			#[allow(unused_variables)]
+			fn from(val: #name #ty_generics) -> Self {
+				#body
+			}
+		}
+	})
+}
+
+/// Value of the variant with the content wrapped according to the enum representation.
+fn variant_value(tagging: &Tagging, vname: &str, variant: &syn::Variant, access: &[Tokens]) -> Result<Tokens> {
+	let fields = &variant.fields;
+	let value = match (tagging, fields) {
+		(Tagging::External, Fields::Unit) => quote!(::sciter::Value::from(#vname)),
+		(Tagging::External, _) => {
+			let content = fields_value(RenameRule::None, fields, access, quote!())?;
+			quote!({
+				let mut __map = ::sciter::Value::map();
+				__map.set_item(#vname, #content);
+				__map
+			})
+		},
+		(Tagging::Internal(tag), Fields::Unit) => quote!({
+			let mut __map = ::sciter::Value::map();
+			__map.set_item(#tag, #vname);
+			__map
+		}),
+		(Tagging::Internal(tag), Fields::Named(_)) => {
+			let prefix = quote!(__map.set_item(#tag, #vname););
+			fields_value(RenameRule::None, fields, access, prefix)?
+		},
+		(Tagging::Internal(tag), Fields::Unnamed(f)) if f.unnamed.len() == 1 => {
+			let inner = &access[0];
+			quote!({
+				let mut __map = ::sciter::Value::map();
+				__map.set_item(#tag, #vname);
+				for (__k, __v) in ::sciter::Value::from(#inner).items() {
+					__map.set_item(__k, __v);
+				}
+				__map
+			})
+		},
+		(Tagging::Internal(_), Fields::Unnamed(_)) => {
+			return Err(Error::new_spanned(variant, "tuple variants are not supported with internal tagging"));
+		},
+		(Tagging::Adjacent(tag, _), Fields::Unit) => quote!({
+			let mut __map = ::sciter::Value::map();
+			__map.set_item(#tag, #vname);
+			__map
+		}),
+		(Tagging::Adjacent(tag, content_key), _) => {
+			let content = fields_value(RenameRule::None, fields, access, quote!())?;
+			quote!({
+				let mut __map = ::sciter::Value::map();
+				__map.set_item(#tag, #vname);
+				__map.set_item(#content_key, #content);
+				__map
+			})
+		},
+		(Tagging::Untagged, Fields::Unit) => quote!(::sciter::Value::null()),
+		(Tagging::Untagged, _) => fields_value(RenameRule::None, fields, access, quote!())?,
+	};
+	Ok(value)
+}
+
+/// Map, array or `null` of the fields; `prefix` statements are inserted before the fields of a map.
+fn fields_value(rule: RenameRule, fields: &Fields, access: &[Tokens], prefix: Tokens) -> Result<Tokens> {
+	let attrs = attr::Field::all(fields)?;
+	let value = match *fields {
+		Fields::Named(_) => {
+			let mut stmts = Vec::new();
+			for ((field, attrs), access) in fields.iter().zip(&attrs).zip(access) {
+				if attrs.skip {
+					continue;
+				}
+				if attrs.flatten {
+					stmts.push(quote! {
+						for (__k, __v) in ::sciter::Value::from(#access).items() {
+							__map.set_item(__k, __v);
+						}
+					});
+				} else {
+					let key = field_key(field, attrs, rule);
+					stmts.push(quote!(__map.set_item(#key, #access);));
+				}
+			}
+			quote!({
+				let mut __map = ::sciter::Value::map();
+				#prefix
+				#(#stmts)*
+				__map
+			})
+		},
+		Fields::Unnamed(_) if fields.len() == 1 && !attrs[0].skip => {
+			let inner = &access[0];
+			quote!(::sciter::Value::from(#inner))
+		},
+		Fields::Unnamed(_) => {
+			let items = access.iter().zip(&attrs).filter(|&(_, attrs)| !attrs.skip).map(|(access, _)| access);
+			quote!({
+				let mut __array = ::sciter::Value::array(0);
+				#(__array.push(#items);)*
+				__array
+			})
+		},
+		Fields::Unit => quote!(::sciter::Value::null()),
+	};
+	Ok(value)
+}
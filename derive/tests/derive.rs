@@ -0,0 +1,196 @@
+#[macro_use]
+extern crate sciter;
+#[macro_use]
+extern crate sciter_derive;
+
+use std::collections::HashMap;
+
+use sciter::value::{FromValue, Value};
+
+fn setup() {
+	sciter::fake::install();
+}
+
+fn round_trip<T: Clone + Into<Value> + FromValue + PartialEq + ::std::fmt::Debug>(v: T) -> Value {
+	let value: Value = v.clone().into();
+	assert_eq!(T::try_from_value(&value), Ok(v));
+	value
+}
+
+
+#[derive(Debug, Clone, PartialEq, Default, ToValue, FromValue)]
+struct Size {
+	width: i32,
+	height: i32,
+}
+
+fn default_title() -> String {
+	"untitled".to_owned()
+}
+
+#[derive(Debug, Clone, PartialEq, ToValue, FromValue)]
+#[sciter(rename_all = "camelCase")]
+struct Window {
+	#[sciter(default = "default_title")]
+	title: String,
+	is_visible: bool,
+	#[sciter(rename = "labels", default)]
+	tags: Vec<String>,
+	#[sciter(skip)]
+	cache: Option<Vec<u8>>,
+	#[sciter(flatten)]
+	size: Size,
+	parent: Option<Box<str>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, ToValue, FromValue)]
+#[sciter(default)]
+struct Settings {
+	volume: f64,
+	muted: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, ToValue, FromValue)]
+struct Meters(f64);
+
+#[derive(Debug, Clone, PartialEq, ToValue, FromValue)]
+struct Point(i32, i32, #[sciter(skip)] u8);
+
+#[derive(Debug, Clone, PartialEq, ToValue, FromValue)]
+struct Marker;
+
+#[derive(Debug, Clone, PartialEq, ToValue, FromValue)]
+struct Wrapper<T> {
+	inner: T,
+}
+
+
+#[test]
+fn structs_work() {
+	setup();
+
+	let window = Window {
+		title: "main".to_owned(),
+		is_visible: true,
+		tags: vec!["a".to_owned()],
+		cache: None,
+		size: Size { width: 10, height: 20 },
+		parent: None,
+	};
+	let value = round_trip(window.clone());
+	assert_eq!(value, vmap! {
+		"title" => "main",
+		"isVisible" => true,
+		"labels" => varray!["a"],
+		"width" => 10,
+		"height" => 20,
+		"parent" => Value::null(),
+	});
+
+	let parsed = Window::try_from_value(&vmap! { "isVisible" => false, "width" => 1, "height" => 2 }).unwrap();
+	assert_eq!((parsed.title.as_str(), parsed.tags.len(), parsed.parent), ("untitled", 0, None));
+
+	let mut with_cache = window.clone();
+	with_cache.cache = Some(vec![1]);
+	assert_eq!(Window::from_value(&Value::from(with_cache)), Some(window));
+
+	assert_eq!(Settings::from_value(&vmap! { "muted" => true }), Some(Settings { volume: 0.0, muted: true }));
+	round_trip(Settings { volume: 0.5, muted: false });
+}
+
+#[test]
+fn tuple_structs_work() {
+	setup();
+
+	assert_eq!(round_trip(Meters(1.5)), Value::from(1.5));
+	assert_eq!(Value::from(Point(1, 2, 3)), varray![1, 2]);
+	assert_eq!(Point::from_value(&varray![1, 2]), Some(Point(1, 2, 0)));
+	assert_eq!(Point::from_value(&varray![1, 2, 3]), None);
+	assert_eq!(round_trip(Marker), Value::null());
+	assert_eq!(round_trip(Wrapper { inner: vec![1, 2] }), vmap! { "inner" => varray![1, 2] });
+}
+
+
+#[derive(Debug, Clone, PartialEq, ToValue, FromValue)]
+enum External {
+	Empty,
+	Single(i32),
+	Pair(i32, String),
+	#[sciter(rename = "rect")]
+	Rect { size: Size },
+}
+
+#[derive(Debug, Clone, PartialEq, ToValue, FromValue)]
+#[sciter(tag = "type", rename_all = "snake_case")]
+enum Internal {
+	Empty,
+	Sized(Size),
+	NamedShape { name: String },
+}
+
+#[derive(Debug, Clone, PartialEq, ToValue, FromValue)]
+#[sciter(tag = "t", content = "c")]
+enum Adjacent {
+	Empty,
+	Pair(i32, i32),
+	Named { x: i32 },
+}
+
+#[derive(Debug, Clone, PartialEq, ToValue, FromValue)]
+#[sciter(untagged)]
+enum Untagged {
+	Number(i32),
+	Text(String),
+	Size(Size),
+	Nothing,
+}
+
+#[test]
+fn enums_work() {
+	setup();
+
+	assert_eq!(round_trip(External::Empty), Value::from("Empty"));
+	assert_eq!(round_trip(External::Single(1)), vmap! { "Single" => 1 });
+	assert_eq!(round_trip(External::Pair(1, "a".to_owned())), vmap! { "Pair" => varray![1, "a"] });
+	assert_eq!(round_trip(External::Rect { size: Size::default() }), vmap! { "rect" => vmap! { "size" => vmap! { "width" => 0, "height" => 0 } } });
+
+	assert_eq!(round_trip(Internal::Empty), vmap! { "type" => "empty" });
+	assert_eq!(round_trip(Internal::Sized(Size { width: 1, height: 2 })), vmap! { "type" => "sized", "width" => 1, "height" => 2 });
+	assert_eq!(round_trip(Internal::NamedShape { name: "x".to_owned() }), vmap! { "type" => "named_shape", "name" => "x" });
+
+	assert_eq!(round_trip(Adjacent::Empty), vmap! { "t" => "Empty" });
+	assert_eq!(round_trip(Adjacent::Pair(1, 2)), vmap! { "t" => "Pair", "c" => varray![1, 2] });
+	assert_eq!(round_trip(Adjacent::Named { x: 1 }), vmap! { "t" => "Named", "c" => vmap! { "x" => 1 } });
+
+	assert_eq!(round_trip(Untagged::Number(1)), Value::from(1));
+	assert_eq!(round_trip(Untagged::Text("a".to_owned())), Value::from("a"));
+	assert_eq!(round_trip(Untagged::Size(Size::default())), vmap! { "width" => 0, "height" => 0 });
+	assert_eq!(round_trip(Untagged::Nothing), Value::null());
+
+	let mut shapes = HashMap::new();
+	shapes.insert("first".to_owned(), External::Single(1));
+	round_trip(shapes);
+}
+
+#[test]
+fn errors_work() {
+	setup();
+
+	let err = Window::try_from_value(&vmap! { "isVisible" => true, "width" => "wide", "height" => 2 }).unwrap_err();
+	assert_eq!(err.to_string(), r#"expected i32, found string:"wide" at width"#);
+
+	let err = Wrapper::<Vec<Size>>::try_from_value(&vmap! { "inner" => varray![vmap! { "width" => 1 }] }).unwrap_err();
+	assert_eq!(err.to_string(), "expected i32, found undefined at inner[0].height");
+
+	let err = External::try_from_value(&vmap! { "Single" => "x" }).unwrap_err();
+	assert_eq!(err.path(), "Single");
+	assert_eq!(External::from_value(&Value::from("Missing")), None);
+
+	let err = Internal::try_from_value(&vmap! { "type" => "circle" }).unwrap_err();
+	assert_eq!(err.to_string(), r#"expected Internal, found string:"circle" at type"#);
+
+	let err = Adjacent::try_from_value(&vmap! { "t" => "Named", "c" => vmap! { "x" => "1" } }).unwrap_err();
+	assert_eq!(err.path(), "c.x");
+
+	assert_eq!(Untagged::try_from_value(&Value::from(1.5)).unwrap_err().expected(), "Untagged");
+}
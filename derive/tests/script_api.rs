@@ -0,0 +1,185 @@
+#[macro_use]
+extern crate sciter;
+#[macro_use]
+extern crate sciter_derive;
+
+use std::fmt;
+
+use sciter::{Element, EventHandler, HELEMENT, Value};
+
+
+#[derive(Debug)]
+struct NotFound(String);
+
+impl fmt::Display for NotFound {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} is not found", self.0)
+	}
+}
+
+fn default_limit() -> usize {
+	2
+}
+
+#[derive(Default)]
+struct Handler {
+	calls: usize,
+	logged: Vec<String>,
+}
+
+#[script_api(rename_all = "camelCase")]
+impl Handler {
+	pub fn find_items(&mut self, name: String, #[sciter(default = "default_limit")] limit: usize, upper: Option<bool>) -> Vec<String> {
+		self.calls += 1;
+		let name = if upper.unwrap_or(false) { name.to_uppercase() } else { name };
+		vec![name; limit]
+	}
+
+	pub fn sum(&self, #[sciter(default)] start: i32, rest: &[Value]) -> i32 {
+		start + rest.iter().filter_map(|v| v.to_int()).sum::<i32>()
+	}
+
+	#[sciter(rename = "log")]
+	pub fn print(&mut self, root: Element, args: &[Value]) {
+		let text: Vec<String> = args.iter().map(|v| v.to_string()).collect();
+		self.logged.push(format!("{}: {}", root.get_tag(), text.join(" ")));
+	}
+
+	pub fn is_root(&self, root: HELEMENT, other: Value) -> bool {
+		!root.is_null() && other.is_undefined()
+	}
+
+	pub fn load(&mut self, path: String) -> Result<i32, NotFound> {
+		if path.is_empty() {
+			Err(NotFound("nothing".to_owned()))
+		} else {
+			Ok(path.len() as i32)
+		}
+	}
+
+	pub fn reset(&mut self) {
+		self.calls = 0;
+	}
+
+	#[allow(dead_code)]
+	#[sciter(skip)]
+	pub fn hidden(&self) {}
+
+	#[allow(dead_code)]
+	fn private(&self) {}
+}
+
+#[derive(Default)]
+struct Manual {
+	events: usize,
+}
+
+#[script_api(dispatcher = "dispatch_api")]
+impl Manual {
+	pub fn events(&self) -> i32 {
+		self.events as i32
+	}
+}
+
+impl EventHandler for Manual {
+	fn dispatch_script_call(&mut self, root: HELEMENT, name: &str, argv: &[Value]) -> Option<Value> {
+		self.events += 1;
+		self.dispatch_api(root, name, argv)
+	}
+}
+
+fn call<H: EventHandler>(handler: &mut H, name: &str, args: &[Value]) -> Option<Value> {
+	handler.dispatch_script_call(::std::ptr::null_mut(), name, args)
+}
+
+
+#[test]
+fn calls_work() {
+	sciter::fake::install();
+	let mut handler = Handler::default();
+
+	let rv = call(&mut handler, "findItems", &make_args!("a")).unwrap();
+	assert_eq!(rv, varray!["a", "a"]);
+
+	let rv = call(&mut handler, "findItems", &make_args!("a", 3)).unwrap();
+	assert_eq!(rv, varray!["a", "a", "a"]);
+
+	let rv = call(&mut handler, "findItems", &[Value::from("a"), Value::new(), Value::from(true)]).unwrap();
+	assert_eq!(rv, varray!["A", "A"]);
+
+	let rv = call(&mut handler, "findItems", &make_args!("a", 1, Value::null())).unwrap();
+	assert_eq!(rv, varray!["a"]);
+
+	assert_eq!(handler.calls, 4);
+	assert_eq!(call(&mut handler, "reset", &[]), Some(Value::new()));
+	assert_eq!(handler.calls, 0);
+
+	assert_eq!(call(&mut handler, "sum", &[]), Some(Value::from(0)));
+	assert_eq!(call(&mut handler, "sum", &make_args!(1, 2, 3, 4)), Some(Value::from(10)));
+
+	assert_eq!(call(&mut handler, "load", &make_args!("abc")), Some(Value::from(3)));
+
+	// not exposed
+	assert_eq!(call(&mut handler, "print", &[]), None);
+	assert_eq!(call(&mut handler, "hidden", &[]), None);
+	assert_eq!(call(&mut handler, "private", &[]), None);
+	assert_eq!(call(&mut handler, "find_items", &make_args!("a")), None);
+
+	// dispatcher called from the handwritten handler
+	let mut manual = Manual::default();
+	assert_eq!(call(&mut manual, "events", &[]), Some(Value::from(1)));
+	assert_eq!(call(&mut manual, "unknown", &[]), None);
+	assert_eq!(manual.events, 2);
+}
+
+#[test]
+fn root_works() {
+	sciter::fake::install();
+	let mut handler = Handler::default();
+	let root = Element::create("section").unwrap();
+
+	let rv = handler.dispatch_script_call(root.as_ptr(), "log", &make_args!(1, "two")).unwrap();
+	assert!(rv.is_undefined());
+	assert_eq!(handler.logged, vec!["section: 1 \"two\"".to_owned()]);
+
+	let rv = handler.dispatch_script_call(root.as_ptr(), "isRoot", &[Value::new()]).unwrap();
+	assert_eq!(rv, Value::from(true));
+}
+
+#[test]
+fn errors_work() {
+	sciter::fake::install();
+	let mut handler = Handler::default();
+
+	// returned error
+	let rv = call(&mut handler, "load", &make_args!("")).unwrap();
+	assert!(rv.is_error_string());
+	assert_eq!(rv.as_string(), Some("nothing is not found".to_owned()));
+
+	// arguments count
+	let rv = call(&mut handler, "findItems", &[]).unwrap();
+	assert!(rv.is_error_string());
+	assert_eq!(rv.as_string(), Some("findItems error: 0 of 1 to 3 arguments provided.".to_owned()));
+
+	let rv = call(&mut handler, "findItems", &make_args!("a", 1, true, 4)).unwrap();
+	assert_eq!(rv.as_string(), Some("findItems error: 4 of 1 to 3 arguments provided.".to_owned()));
+
+	let rv = call(&mut handler, "load", &[]).unwrap();
+	assert_eq!(rv.as_string(), Some("load error: 0 of 1 arguments provided.".to_owned()));
+
+	let rv = call(&mut handler, "isRoot", &[]).unwrap();
+	assert_eq!(rv.as_string(), Some("isRoot error: 0 of 1 arguments provided.".to_owned()));
+
+	// argument types
+	let rv = call(&mut handler, "findItems", &make_args!("a", "b")).unwrap();
+	assert!(rv.is_error_string());
+	let message = rv.as_string().unwrap();
+	assert!(message.starts_with("findItems error: invalid type of 1 argument (usize expected): "), "{}", message);
+
+	let rv = call(&mut handler, "findItems", &make_args!(1)).unwrap();
+	let message = rv.as_string().unwrap();
+	assert!(message.starts_with("findItems error: invalid type of 0 argument (String expected): "), "{}", message);
+
+	// none of them reached the handler
+	assert_eq!(handler.calls, 0);
+}
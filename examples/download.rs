@@ -22,7 +22,7 @@ impl sciter::EventHandler for Handler {
           println!("running on {}", name);
         }
         Err(e) => {
-          println!("error! {}", e.as_string().unwrap_or("?".to_string()));
+          println!("error! {}", e);
         }
       }
     }
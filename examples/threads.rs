@@ -1,24 +1,29 @@
 #[macro_use]
 extern crate sciter;
 use sciter::Value;
+use sciter::dispatch::Dispatcher;
 
-struct EventHandler;
+struct EventHandler {
+	ui: Dispatcher,
+}
 
 impl EventHandler {
 	// script handler
 	fn exec_task(&self, task_no: i32, progress: sciter::Value, done: sciter::Value) -> bool {
 
 		use std::{thread, time};
+		let ui = self.ui.clone();
 		thread::spawn(move || {
 
 			for i in 1..100 {
-				// call `onProgress` callback
+				// call `onProgress` callback on the UI thread
 				thread::sleep(time::Duration::from_millis(100));
-				progress.call(None, &make_args!(i), None).unwrap();
+				let progress = progress.clone();
+				ui.post(move || { progress.call(None, &make_args!(i), None).unwrap(); }).unwrap();
 			}
 
-			// call `onDone` callback
-			done.call(None, &make_args!(task_no), None).unwrap();
+			// call `onDone` callback on the UI thread and wait for it
+			ui.call(move || { done.call(None, &make_args!(task_no), None).unwrap(); }).unwrap();
 		});
 		true
 	}
@@ -36,7 +41,8 @@ fn main() {
   let mut frame = sciter::WindowBuilder::main_window()
   	.with_size((1200, 900))
   	.create();
-	frame.event_handler(EventHandler);
+	let ui = frame.dispatcher();
+	frame.event_handler(EventHandler { ui });
 	frame.load_html(html, None);
 	frame.run_app();
 }
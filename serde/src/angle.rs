@@ -0,0 +1,81 @@
+//! Angles as Sciter `T_ANGLE` values.
+//!
+//! Use it as `#[serde(with = "sciter_serde::angle")]` on `f64` fields with angles in radians
+//! or wrap them into [`Angle`](struct.Angle.html), see [`sciter_serde::date`](../date/index.html) for details.
+//! Human-readable serializers get a string like `"1.5rad"`, the rest get the number of radians.
+
+use std::fmt;
+
+use serde::ser::{Serialize, Serializer};
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+
+
+// Name of the newtype struct which `Serializer` and `Deserializer` handle as an angle.
+pub(crate) const TOKEN: &str = "$sciter::angle";
+
+/// Serialize an angle.
+pub fn serialize<S: Serializer>(value: &f64, serializer: S) -> Result<S::Ok, S::Error> {
+	serializer.serialize_newtype_struct(TOKEN, &Repr(*value))
+}
+
+/// Deserialize an angle.
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+	deserializer.deserialize_newtype_struct(TOKEN, AngleVisitor)
+}
+
+/// Angle in radians, serialized as a Sciter angle.
+///
+/// Unlike the `with` attribute it works inside of containers like `Vec<Angle>`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Angle(pub f64);
+
+impl Serialize for Angle {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serialize(&self.0, serializer)
+	}
+}
+
+impl<'de> Deserialize<'de> for Angle {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		deserialize(deserializer).map(Angle)
+	}
+}
+
+// Inner value of the newtype struct: text like `1.5rad` for human-readable formats, the radians otherwise.
+struct Repr(f64);
+
+impl Serialize for Repr {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		if serializer.is_human_readable() {
+			serializer.collect_str(&format_args!("{}rad", self.0))
+		} else {
+			serializer.serialize_f64(self.0)
+		}
+	}
+}
+
+impl<'de> Deserialize<'de> for Repr {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		if !deserializer.is_human_readable() {
+			return f64::deserialize(deserializer).map(Repr);
+		}
+		let text = String::deserialize(deserializer)?;
+		let radians = text.strip_suffix("rad").and_then(|r| r.parse::<f64>().ok())
+			.or_else(|| text.strip_suffix("deg").and_then(|d| d.parse::<f64>().ok()).map(f64::to_radians));
+		radians.map(Repr).ok_or_else(|| de::Error::custom(format!("invalid angle {:?}", text)))
+	}
+}
+
+struct AngleVisitor;
+
+impl<'de> Visitor<'de> for AngleVisitor {
+	type Value = f64;
+
+	fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("an angle")
+	}
+
+	fn visit_newtype_struct<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+		Repr::deserialize(deserializer).map(|a| a.0)
+	}
+}
@@ -0,0 +1,593 @@
+/*! Binary encoding of `sciter::Value` in [CBOR](https://www.rfc-editor.org/rfc/rfc8949.html).
+
+Unlike JSON, it keeps every data type of the value: bytes, dates, lengths, durations, angles, colors,
+currency, symbols and errors, and tells integers from floats and `undefined` from `null`.
+
+```rust
+extern crate sciter;
+extern crate sciter_serde;
+
+use sciter::Value;
+use sciter_serde::cbor::ValueCodec;
+
+fn main() {
+	sciter::fake::install();
+
+	let mut state = Value::map();
+	state.set_item("color", Value::color(0xFF00_00FF));
+	state.set_item("blob", Value::from(b"\x00\x01".as_ref()));
+
+	let mut buffer = Vec::new();
+	state.encode_into(&mut buffer).unwrap();
+	assert_eq!(Value::decode_from(buffer.as_slice()).unwrap(), state);
+}
+```
+
+Script objects are encoded as arrays and maps, while functions, DOM elements, assets
+and other non-data values can not be encoded.
+
+Large arrays can be written and read item by item with [`ArrayEncoder`](struct.ArrayEncoder.html)
+and [`ArrayDecoder`](struct.ArrayDecoder.html).
+
+Both directions do many small reads and writes, so wrap files and sockets
+in `std::io::BufReader` and `std::io::BufWriter`.
+
+## Encoding
+
+Plain data uses the standard CBOR types: `undefined`, `null`, booleans, integers, 64-bit floats,
+text and byte strings, arrays and maps (with keys of any type).
+The rest is wrapped in the following tags:
+
+| Tag | Value | Content |
+|-----|-------|---------|
+| `0x5343_0001` | `nothing` | `undefined` |
+| `0x5343_0002` | symbol | text |
+| `0x5343_0003` | error string | text |
+| `0x5343_0004` | currency or Sciter.JS big integer | integer, in 1/10000 units for currency |
+| `0x5343_0005` | date | `[flags, ticks]` |
+| `0x5343_0006` | length | `[unit, float]`, see `VALUE_UNIT_TYPE_LENGTH` |
+| `0x5343_0007` | duration | float, in seconds |
+| `0x5343_0008` | angle | float, in radians |
+| `0x5343_0009` | color | integer, `0xAABBGGRR` |
+
+Integers beyond the script number range are decoded as `Value::from(i64)` does, i.e. rounded to floats.
+Other tags are ignored on decoding, i.e. the tagged item is decoded as is.
+*/
+
+use std::convert::TryFrom;
+use std::io::{self, Read, Write};
+
+use sciter::Value;
+use sciter::value::{Currency, Length, LengthUnit, ValueKind};
+
+
+const TAG_NOTHING: u64 = 0x5343_0001;
+const TAG_SYMBOL: u64 = 0x5343_0002;
+const TAG_ERROR: u64 = 0x5343_0003;
+const TAG_CURRENCY: u64 = 0x5343_0004;
+const TAG_DATE: u64 = 0x5343_0005;
+const TAG_LENGTH: u64 = 0x5343_0006;
+const TAG_DURATION: u64 = 0x5343_0007;
+const TAG_ANGLE: u64 = 0x5343_0008;
+const TAG_COLOR: u64 = 0x5343_0009;
+
+// Major types.
+const UINT: u8 = 0;
+const NEGINT: u8 = 1;
+const BYTES: u8 = 2;
+const TEXT: u8 = 3;
+const ARRAY: u8 = 4;
+const MAP: u8 = 5;
+const TAG: u8 = 6;
+
+const FALSE: u8 = 0xF4;
+const TRUE: u8 = 0xF5;
+const NULL: u8 = 0xF6;
+const UNDEFINED: u8 = 0xF7;
+const FLOAT16: u8 = 0xF9;
+const FLOAT32: u8 = 0xFA;
+const FLOAT64: u8 = 0xFB;
+const BREAK: u8 = 0xFF;
+
+/// Additional information of indefinite length items.
+const INDEFINITE: u8 = 31;
+
+/// Nesting limit of decoded containers.
+const MAX_DEPTH: usize = 256;
+
+
+/// CBOR encoding of `sciter::Value`.
+pub trait ValueCodec: Sized {
+	/// Encode the value into the writer.
+	///
+	/// Fails with `InvalidInput` for non-data values like functions or DOM elements.
+	fn encode_into<W: Write>(&self, writer: W) -> io::Result<()>;
+
+	/// Decode a single value from the reader.
+	///
+	/// Fails with `InvalidData` for malformed input and `UnexpectedEof` for truncated one.
+	fn decode_from<R: Read>(reader: R) -> io::Result<Self>;
+}
+
+impl ValueCodec for Value {
+	fn encode_into<W: Write>(&self, mut writer: W) -> io::Result<()> {
+		encode(&mut writer, self)
+	}
+
+	fn decode_from<R: Read>(mut reader: R) -> io::Result<Self> {
+		let initial = read_u8(&mut reader)?;
+		Decoder { reader: &mut reader, depth: 0 }.value(initial)
+	}
+}
+
+/// Encode the value into a new buffer.
+pub fn to_vec(value: &Value) -> io::Result<Vec<u8>> {
+	let mut buffer = Vec::new();
+	encode(&mut buffer, value)?;
+	Ok(buffer)
+}
+
+/// Decode a value from the buffer, which must contain nothing else.
+pub fn from_slice(mut data: &[u8]) -> io::Result<Value> {
+	let value = Value::decode_from(&mut data)?;
+	if !data.is_empty() {
+		return Err(invalid(format!("{} trailing bytes", data.len())));
+	}
+	Ok(value)
+}
+
+
+/// Writes an array of unknown length item by item.
+///
+/// ```rust
+/// # extern crate sciter;
+/// # extern crate sciter_serde;
+/// use sciter_serde::cbor::{ArrayDecoder, ArrayEncoder};
+///
+/// # fn main() {
+/// # sciter::fake::install();
+/// let mut array = ArrayEncoder::new(Vec::new()).unwrap();
+/// for i in 0..1000 {
+///   array.push(&sciter::Value::from(i)).unwrap();
+/// }
+/// let buffer = array.finish().unwrap();
+///
+/// let items = ArrayDecoder::new(buffer.as_slice()).unwrap();
+/// assert_eq!(items.map(|item| item.unwrap().to_int().unwrap()).sum::<i32>(), 499_500);
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ArrayEncoder<W: Write> {
+	writer: W,
+	count: usize,
+}
+
+impl<W: Write> ArrayEncoder<W> {
+	/// Start an array of indefinite length.
+	pub fn new(mut writer: W) -> io::Result<Self> {
+		writer.write_all(&[ARRAY << 5 | INDEFINITE])?;
+		Ok(ArrayEncoder { writer, count: 0 })
+	}
+
+	/// Append an item to the array.
+	pub fn push(&mut self, item: &Value) -> io::Result<()> {
+		encode(&mut self.writer, item)?;
+		self.count += 1;
+		Ok(())
+	}
+
+	/// Number of the written items.
+	pub fn len(&self) -> usize {
+		self.count
+	}
+
+	/// Whether no items were written.
+	pub fn is_empty(&self) -> bool {
+		self.count == 0
+	}
+
+	/// Finish the array and return the writer.
+	pub fn finish(mut self) -> io::Result<W> {
+		self.writer.write_all(&[BREAK])?;
+		Ok(self.writer)
+	}
+}
+
+
+/// Reads the items of an encoded array one by one, see [`ArrayEncoder`](struct.ArrayEncoder.html).
+///
+/// Both definite and indefinite length arrays are accepted.
+/// The iteration stops after the first error.
+#[derive(Debug)]
+pub struct ArrayDecoder<R: Read> {
+	reader: R,
+	// `None` for indefinite length arrays.
+	remaining: Option<u64>,
+	done: bool,
+}
+
+impl<R: Read> ArrayDecoder<R> {
+	/// Read the array header.
+	pub fn new(mut reader: R) -> io::Result<Self> {
+		let initial = read_u8(&mut reader)?;
+		if initial >> 5 != ARRAY {
+			return Err(invalid(format!("expected an array, found 0x{:02x}", initial)));
+		}
+		let remaining = if initial & 0x1F == INDEFINITE {
+			None
+		} else {
+			Some(read_argument(&mut reader, initial)?)
+		};
+		Ok(ArrayDecoder { reader, remaining, done: false })
+	}
+
+	/// Return the reader, positioned after the last read item.
+	pub fn into_inner(self) -> R {
+		self.reader
+	}
+
+	fn next_item(&mut self) -> io::Result<Option<Value>> {
+		match self.remaining {
+			Some(0) => return Ok(None),
+			Some(ref mut n) => *n -= 1,
+			None => {},
+		}
+		let initial = read_u8(&mut self.reader)?;
+		if initial == BREAK && self.remaining.is_none() {
+			return Ok(None);
+		}
+		Decoder { reader: &mut self.reader, depth: 1 }.value(initial).map(Some)
+	}
+}
+
+impl<R: Read> Iterator for ArrayDecoder<R> {
+	type Item = io::Result<Value>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.done {
+			return None;
+		}
+		let item = self.next_item();
+		if !matches!(item, Ok(Some(_))) {
+			self.done = true;
+		}
+		item.transpose()
+	}
+}
+
+
+fn invalid<S: Into<String>>(message: S) -> io::Error {
+	io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
+
+fn write_head<W: Write>(writer: &mut W, major: u8, argument: u64) -> io::Result<()> {
+	let major = major << 5;
+	if argument < 24 {
+		writer.write_all(&[major | argument as u8])
+	} else if argument <= u64::from(u8::MAX) {
+		writer.write_all(&[major | 24, argument as u8])
+	} else if argument <= u64::from(u16::MAX) {
+		writer.write_all(&[major | 25])?;
+		writer.write_all(&(argument as u16).to_be_bytes())
+	} else if argument <= u64::from(u32::MAX) {
+		writer.write_all(&[major | 26])?;
+		writer.write_all(&(argument as u32).to_be_bytes())
+	} else {
+		writer.write_all(&[major | 27])?;
+		writer.write_all(&argument.to_be_bytes())
+	}
+}
+
+fn write_int<W: Write>(writer: &mut W, value: i64) -> io::Result<()> {
+	if value < 0 {
+		write_head(writer, NEGINT, !value as u64)
+	} else {
+		write_head(writer, UINT, value as u64)
+	}
+}
+
+fn write_float<W: Write>(writer: &mut W, value: f64) -> io::Result<()> {
+	writer.write_all(&[FLOAT64])?;
+	writer.write_all(&value.to_bits().to_be_bytes())
+}
+
+fn write_text<W: Write>(writer: &mut W, text: &str) -> io::Result<()> {
+	write_head(writer, TEXT, text.len() as u64)?;
+	writer.write_all(text.as_bytes())
+}
+
+fn encode<W: Write>(writer: &mut W, value: &Value) -> io::Result<()> {
+	match value.kind() {
+		ValueKind::Undefined => writer.write_all(&[UNDEFINED]),
+		ValueKind::Nothing => {
+			write_head(writer, TAG, TAG_NOTHING)?;
+			writer.write_all(&[UNDEFINED])
+		},
+		ValueKind::Null => writer.write_all(&[NULL]),
+		ValueKind::Bool(b) => writer.write_all(&[if b { TRUE } else { FALSE }]),
+		ValueKind::Int(i) => write_int(writer, i64::from(i)),
+		ValueKind::Float(f) => write_float(writer, f),
+		ValueKind::Currency(c) => {
+			write_head(writer, TAG, TAG_CURRENCY)?;
+			write_int(writer, c.raw())
+		},
+		ValueKind::String(s) => write_text(writer, &s),
+		ValueKind::Symbol(s) => {
+			write_head(writer, TAG, TAG_SYMBOL)?;
+			write_text(writer, &s)
+		},
+		ValueKind::Error(s) => {
+			write_head(writer, TAG, TAG_ERROR)?;
+			write_text(writer, &s)
+		},
+		ValueKind::Bytes(b) => {
+			write_head(writer, BYTES, b.len() as u64)?;
+			writer.write_all(b)
+		},
+		ValueKind::Date { ticks, flags } => {
+			write_head(writer, TAG, TAG_DATE)?;
+			write_head(writer, ARRAY, 2)?;
+			write_int(writer, i64::from(flags))?;
+			write_int(writer, ticks)
+		},
+		ValueKind::Length(length) => {
+			write_head(writer, TAG, TAG_LENGTH)?;
+			write_head(writer, ARRAY, 2)?;
+			write_int(writer, i64::from(length.unit.to_raw()))?;
+			write_float(writer, length.value)
+		},
+		ValueKind::Duration(d) => {
+			write_head(writer, TAG, TAG_DURATION)?;
+			write_float(writer, d)
+		},
+		ValueKind::Angle(a) => {
+			write_head(writer, TAG, TAG_ANGLE)?;
+			write_float(writer, a)
+		},
+		ValueKind::Color(c) => {
+			write_head(writer, TAG, TAG_COLOR)?;
+			write_int(writer, i64::from(c))
+		},
+		ValueKind::Array(v) => encode_array(writer, v),
+		ValueKind::Object(v) if v.is_object_array() => encode_array(writer, v),
+		ValueKind::Map(v) => encode_map(writer, v),
+		ValueKind::Object(v) if v.is_object_map() => encode_map(writer, v),
+		_ => Err(io::Error::new(io::ErrorKind::InvalidInput, format!("can not encode {:?}", value))),
+	}
+}
+
+fn encode_array<W: Write>(writer: &mut W, value: &Value) -> io::Result<()> {
+	write_head(writer, ARRAY, value.len() as u64)?;
+	for item in value.values() {
+		encode(writer, &item)?;
+	}
+	Ok(())
+}
+
+fn encode_map<W: Write>(writer: &mut W, value: &Value) -> io::Result<()> {
+	let items = value.items();
+	write_head(writer, MAP, items.len() as u64)?;
+	for (key, item) in items {
+		encode(writer, &key)?;
+		encode(writer, &item)?;
+	}
+	Ok(())
+}
+
+
+fn read_u8<R: Read>(reader: &mut R) -> io::Result<u8> {
+	let mut byte = [0u8];
+	reader.read_exact(&mut byte)?;
+	Ok(byte[0])
+}
+
+fn read_argument<R: Read>(reader: &mut R, initial: u8) -> io::Result<u64> {
+	let info = initial & 0x1F;
+	let size = match info {
+		0..=23 => return Ok(u64::from(info)),
+		24 => 1,
+		25 => 2,
+		26 => 4,
+		27 => 8,
+		_ => return Err(invalid(format!("invalid item 0x{:02x}", initial))),
+	};
+	let mut bytes = [0u8; 8];
+	reader.read_exact(&mut bytes[8 - size..])?;
+	Ok(u64::from_be_bytes(bytes))
+}
+
+/// Half precision float to `f64`.
+fn from_f16(half: u16) -> f64 {
+	let exponent = (half >> 10) & 0x1F;
+	let mantissa = f64::from(half & 0x3FF);
+	let value = match exponent {
+		0 => mantissa * 2f64.powi(-24),
+		31 if mantissa == 0.0 => f64::INFINITY,
+		31 => f64::NAN,
+		_ => (1024.0 + mantissa) * 2f64.powi(i32::from(exponent) - 25),
+	};
+	if half & 0x8000 != 0 { -value } else { value }
+}
+
+struct Decoder<'a, R: 'a> {
+	reader: &'a mut R,
+	depth: usize,
+}
+
+impl<'a, R: Read> Decoder<'a, R> {
+	fn next(&mut self) -> io::Result<Value> {
+		let initial = read_u8(self.reader)?;
+		self.value(initial)
+	}
+
+	/// Length of a definite item or `None` for indefinite one.
+	fn length(&mut self, initial: u8) -> io::Result<Option<u64>> {
+		if initial & 0x1F == INDEFINITE {
+			Ok(None)
+		} else {
+			read_argument(self.reader, initial).map(Some)
+		}
+	}
+
+	/// Read items until the count or the break code, calling `f` with the initial byte of each.
+	fn items<F: FnMut(&mut Self, u8) -> io::Result<()>>(&mut self, length: Option<u64>, mut f: F) -> io::Result<()> {
+		self.depth += 1;
+		if self.depth > MAX_DEPTH {
+			return Err(invalid("too deep nesting"));
+		}
+		match length {
+			Some(n) => for _ in 0..n {
+				let initial = read_u8(self.reader)?;
+				f(self, initial)?;
+			},
+			None => loop {
+				let initial = read_u8(self.reader)?;
+				if initial == BREAK {
+					break;
+				}
+				f(self, initial)?;
+			},
+		}
+		self.depth -= 1;
+		Ok(())
+	}
+
+	fn bytes(&mut self, initial: u8) -> io::Result<Vec<u8>> {
+		let major = initial >> 5;
+		let mut data = Vec::new();
+		match self.length(initial)? {
+			Some(n) => {
+				let read = self.reader.take(n).read_to_end(&mut data)?;
+				if (read as u64) < n {
+					return Err(io::ErrorKind::UnexpectedEof.into());
+				}
+			},
+			None => self.items(None, |me, chunk| {
+				if chunk >> 5 != major || chunk & 0x1F == INDEFINITE {
+					return Err(invalid("invalid string chunk"));
+				}
+				data.extend(me.bytes(chunk)?);
+				Ok(())
+			})?,
+		}
+		Ok(data)
+	}
+
+	fn text(&mut self, initial: u8) -> io::Result<String> {
+		String::from_utf8(self.bytes(initial)?).map_err(|e| invalid(e.to_string()))
+	}
+
+	fn int(&mut self) -> io::Result<i64> {
+		let initial = read_u8(self.reader)?;
+		let n = read_argument(self.reader, initial)?;
+		let n = i64::try_from(n).map_err(|_| invalid("integer is out of range"))?;
+		match initial >> 5 {
+			UINT => Ok(n),
+			NEGINT => Ok(-1 - n),
+			_ => Err(invalid(format!("expected an integer, found 0x{:02x}", initial))),
+		}
+	}
+
+	fn float(&mut self) -> io::Result<f64> {
+		let value = self.next()?;
+		value.to_float().or_else(|| value.to_int().map(f64::from)).ok_or_else(|| invalid(format!("expected a float, found {:?}", value)))
+	}
+
+	/// `[integer, item]` pair of the tagged values.
+	fn pair<T, F: FnOnce(&mut Self, i64) -> io::Result<T>>(&mut self, f: F) -> io::Result<T> {
+		let initial = read_u8(self.reader)?;
+		if initial != (ARRAY << 5 | 2) {
+			return Err(invalid(format!("expected a pair, found 0x{:02x}", initial)));
+		}
+		let first = self.int()?;
+		f(self, first)
+	}
+
+	fn tagged(&mut self, tag: u64) -> io::Result<Value> {
+		// tags can wrap each other as deep as containers
+		self.depth += 1;
+		if self.depth > MAX_DEPTH {
+			return Err(invalid("too deep nesting"));
+		}
+		let value = match tag {
+			TAG_NOTHING => {
+				self.next()?;
+				Value::nothing()
+			},
+			TAG_SYMBOL | TAG_ERROR => {
+				let initial = read_u8(self.reader)?;
+				if initial >> 5 != TEXT {
+					return Err(invalid("expected a text"));
+				}
+				let text = self.text(initial)?;
+				if tag == TAG_SYMBOL { Value::symbol(&text) } else { Value::error(&text) }
+			},
+			TAG_CURRENCY => Value::currency(Currency::from_raw(self.int()?)),
+			TAG_DATE => self.pair(|me, flags| {
+				let flags = u32::try_from(flags).map_err(|_| invalid("invalid date flags"))?;
+				Ok(Value::date(me.int()?, flags))
+			})?,
+			TAG_LENGTH => self.pair(|me, unit| {
+				let unit = u32::try_from(unit).ok().and_then(LengthUnit::from_raw).ok_or_else(|| invalid("invalid length unit"))?;
+				Ok(Value::length(Length::new(me.float()?, unit)))
+			})?,
+			TAG_DURATION => Value::duration(self.float()?),
+			TAG_ANGLE => Value::angle(self.float()?),
+			TAG_COLOR => {
+				let color = self.int()?;
+				Value::color(u32::try_from(color).map_err(|_| invalid("invalid color"))?)
+			},
+			_ => self.next()?,
+		};
+		self.depth -= 1;
+		Ok(value)
+	}
+
+	fn value(&mut self, initial: u8) -> io::Result<Value> {
+		let value = match initial >> 5 {
+			UINT | NEGINT => {
+				let n = read_argument(self.reader, initial)?;
+				let n = i64::try_from(n).map_err(|_| invalid("integer is out of range"))?;
+				let n = if initial >> 5 == NEGINT { -1 - n } else { n };
+				Value::from(n)
+			},
+			BYTES => Value::from(self.bytes(initial)?.as_slice()),
+			TEXT => Value::from(self.text(initial)?),
+			ARRAY => {
+				let length = self.length(initial)?;
+				let mut array = Value::array(0);
+				self.items(length, |me, initial| {
+					array.push(me.value(initial)?);
+					Ok(())
+				})?;
+				array
+			},
+			MAP => {
+				let length = self.length(initial)?;
+				let mut map = Value::map();
+				self.items(length, |me, initial| {
+					let key = me.value(initial)?;
+					let item = me.next()?;
+					map.set_item(key, item);
+					Ok(())
+				})?;
+				map
+			},
+			TAG => {
+				let tag = read_argument(self.reader, initial)?;
+				self.tagged(tag)?
+			},
+			_ => match initial {
+				FALSE => Value::from(false),
+				TRUE => Value::from(true),
+				NULL => Value::null(),
+				UNDEFINED => Value::new(),
+				FLOAT16 => Value::from(from_f16(read_argument(self.reader, initial)? as u16)),
+				FLOAT32 => Value::from(f64::from(f32::from_bits(read_argument(self.reader, initial)? as u32))),
+				FLOAT64 => Value::from(f64::from_bits(read_argument(self.reader, initial)?)),
+				_ => return Err(invalid(format!("unexpected item 0x{:02x}", initial))),
+			},
+		};
+		Ok(value)
+	}
+}
@@ -0,0 +1,97 @@
+//! Colors as Sciter `T_COLOR` values.
+//!
+//! Use it as `#[serde(with = "sciter_serde::color")]` on `u32` fields with colors in the `0xAABBGGRR` form
+//! (see [`Value::color`](https://docs.rs/sciter-rs/latest/sciter/value/struct.Value.html#method.color))
+//! or wrap them into [`Color`](struct.Color.html), see [`sciter_serde::date`](../date/index.html) for details.
+//! Human-readable serializers get a CSS string like `"#ff0000"` or `"#ff000080"`, the rest get the number.
+
+use std::fmt;
+
+use serde::ser::{Serialize, Serializer};
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+
+
+// Name of the newtype struct which `Serializer` and `Deserializer` handle as a color.
+pub(crate) const TOKEN: &str = "$sciter::color";
+
+/// Serialize a color.
+pub fn serialize<S: Serializer>(value: &u32, serializer: S) -> Result<S::Ok, S::Error> {
+	serializer.serialize_newtype_struct(TOKEN, &Repr(*value))
+}
+
+/// Deserialize a color.
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u32, D::Error> {
+	deserializer.deserialize_newtype_struct(TOKEN, ColorVisitor)
+}
+
+/// Color in the `0xAABBGGRR` form, serialized as a Sciter color.
+///
+/// Unlike the `with` attribute it works inside of containers like `Vec<Color>`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Color(pub u32);
+
+impl Serialize for Color {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serialize(&self.0, serializer)
+	}
+}
+
+impl<'de> Deserialize<'de> for Color {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		deserialize(deserializer).map(Color)
+	}
+}
+
+// Inner value of the newtype struct: CSS text for human-readable formats, the number otherwise.
+struct Repr(u32);
+
+impl Serialize for Repr {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		if !serializer.is_human_readable() {
+			return serializer.serialize_u32(self.0);
+		}
+		let (r, g, b, a) = (self.0 & 0xFF, (self.0 >> 8) & 0xFF, (self.0 >> 16) & 0xFF, self.0 >> 24);
+		if a == 0xFF {
+			serializer.collect_str(&format_args!("#{:02x}{:02x}{:02x}", r, g, b))
+		} else {
+			serializer.collect_str(&format_args!("#{:02x}{:02x}{:02x}{:02x}", r, g, b, a))
+		}
+	}
+}
+
+impl<'de> Deserialize<'de> for Repr {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		if !deserializer.is_human_readable() {
+			return u32::deserialize(deserializer).map(Repr);
+		}
+		let text = String::deserialize(deserializer)?;
+		parse(&text).map(Repr).ok_or_else(|| de::Error::custom(format!("invalid color {:?}", text)))
+	}
+}
+
+// `#rgb`, `#rgba`, `#rrggbb` or `#rrggbbaa`.
+fn parse(text: &str) -> Option<u32> {
+	let hex = text.strip_prefix('#').filter(|hex| hex.chars().all(|c| c.is_ascii_hexdigit()))?;
+	let digits: Vec<u32> = hex.chars().filter_map(|c| c.to_digit(16)).collect();
+	let channels: Vec<u32> = match digits.len() {
+		3 | 4 => digits.iter().map(|d| d * 0x11).collect(),
+		6 | 8 => digits.chunks(2).map(|d| d[0] * 16 + d[1]).collect(),
+		_ => return None,
+	};
+	let a = channels.get(3).cloned().unwrap_or(0xFF);
+	Some(channels[0] | channels[1] << 8 | channels[2] << 16 | a << 24)
+}
+
+struct ColorVisitor;
+
+impl<'de> Visitor<'de> for ColorVisitor {
+	type Value = u32;
+
+	fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("a color")
+	}
+
+	fn visit_newtype_struct<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+		Repr::deserialize(deserializer).map(|c| c.0)
+	}
+}
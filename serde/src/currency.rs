@@ -0,0 +1,58 @@
+//! Currency as Sciter `T_CURRENCY` values.
+//!
+//! Use it as `#[serde(with = "sciter_serde::currency")]` on fields of the `sciter::value::Currency` type,
+//! or wrap them into [`Currency`](struct.Currency.html), see [`sciter_serde::date`](../date/index.html) for details.
+//! Other serializers get the number of 1/10000 units.
+
+use std::fmt;
+
+use serde::ser::{Serialize, Serializer};
+use serde::de::{Deserialize, Deserializer, Visitor};
+
+use sciter::value;
+
+
+// Name of the newtype struct which `Serializer` and `Deserializer` handle as a currency.
+pub(crate) const TOKEN: &str = "$sciter::currency";
+
+/// Serialize a currency.
+pub fn serialize<S: Serializer>(value: &value::Currency, serializer: S) -> Result<S::Ok, S::Error> {
+	serializer.serialize_newtype_struct(TOKEN, &value.raw())
+}
+
+/// Deserialize a currency.
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<value::Currency, D::Error> {
+	deserializer.deserialize_newtype_struct(TOKEN, CurrencyVisitor).map(value::Currency::from_raw)
+}
+
+/// Currency serialized as a Sciter currency.
+///
+/// Unlike the `with` attribute it works inside of containers like `Vec<Currency>`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Currency(pub value::Currency);
+
+impl Serialize for Currency {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serialize(&self.0, serializer)
+	}
+}
+
+impl<'de> Deserialize<'de> for Currency {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		deserialize(deserializer).map(Currency)
+	}
+}
+
+struct CurrencyVisitor;
+
+impl<'de> Visitor<'de> for CurrencyVisitor {
+	type Value = i64;
+
+	fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("a currency")
+	}
+
+	fn visit_newtype_struct<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+		Deserialize::deserialize(deserializer)
+	}
+}
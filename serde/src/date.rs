@@ -0,0 +1,80 @@
+//! Dates as Sciter `T_DATE` values.
+//!
+//! Date types do not have a representation in the Serde data model,
+//! so fields of such types should be annotated with `#[serde(with = "sciter_serde::date")]`:
+//!
+//! ```rust,ignore
+//! #[derive(Serialize, Deserialize)]
+//! struct Event {
+//! 	#[serde(with = "sciter_serde::date")]
+//! 	at: SystemTime,
+//! }
+//! ```
+//!
+//! Inside of containers like `Vec<SystemTime>`, where the attribute can not be used, wrap the dates into [`Date`](struct.Date.html).
+//!
+//! It works for every type convertible to and from a date value: `SystemTime`,
+//! and `chrono::DateTime<Utc>`, `chrono::NaiveDate` or `time::OffsetDateTime` with the `chrono` and `time` features.
+//! Other serializers get a `(ticks, flags)` tuple, see [`Value::date`](https://docs.rs/sciter-rs/latest/sciter/value/struct.Value.html#method.date).
+
+use std::fmt;
+
+use serde::ser::{self, Serialize, Serializer};
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+
+use sciter::Value;
+use sciter::value::FromValue;
+
+
+// Name of the newtype struct which `Serializer` and `Deserializer` handle as a date.
+pub(crate) const TOKEN: &str = "$sciter::date";
+
+/// Serialize a date.
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+	where T: Clone + Into<Value>, S: Serializer
+{
+	let v: Value = value.clone().into();
+	match v.to_date() {
+		Some(ticks) => serializer.serialize_newtype_struct(TOKEN, &(ticks, v.full_type().1)),
+		None => Err(ser::Error::custom(format!("expected date, given {:?}", v))),
+	}
+}
+
+/// Deserialize a date.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+	where T: FromValue, D: Deserializer<'de>
+{
+	let (ticks, flags) = deserializer.deserialize_newtype_struct(TOKEN, DateVisitor)?;
+	let v = Value::date(ticks, flags);
+	T::from_value(&v).ok_or_else(|| de::Error::custom(format!("date {:?} is out of range", v)))
+}
+
+/// Date serialized as a Sciter date, see the [module](index.html) for the supported types.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date<T>(pub T);
+
+impl<T: Clone + Into<Value>> Serialize for Date<T> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serialize(&self.0, serializer)
+	}
+}
+
+impl<'de, T: FromValue> Deserialize<'de> for Date<T> {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		deserialize(deserializer).map(Date)
+	}
+}
+
+struct DateVisitor;
+
+impl<'de> Visitor<'de> for DateVisitor {
+	type Value = (i64, u32);
+
+	fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("a date")
+	}
+
+	fn visit_newtype_struct<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+		Deserialize::deserialize(deserializer)
+	}
+}
@@ -1,34 +1,101 @@
 /// Deserialization.
+use std::convert::TryFrom;
+
 use serde::de::{self, Deserialize, Visitor};
 
+use {angle, color, currency, date, duration, length};
 use error::{Error, Result};
 use sciter::{Value};
+use sciter::value::{IntOverflow, MAX_SAFE_INTEGER};
 
 
 /// Deserializes a Sciter value to the specific Rust type.
+///
+/// A byte array input can be borrowed (e.g. as `&[u8]` via `serde_bytes`, also in a newtype struct or `Option`),
+/// the ones nested in arrays and maps are copied (`&[u8]` fails for them).
+/// Strings are stored as UTF-16, so they are converted and can not be borrowed
+/// (`&str` fails and `Cow<str>` is always owned).
+///
+/// Errors of nested elements carry the path to them, see [`Error::path`](enum.Error.html#method.path).
 pub fn from_value<'a, T>(input: &'a Value) -> Result<T>
 	where T: Deserialize<'a>
 {
-	let p = Deserializer::from_value(input.clone());
+	from_value_with(input, IntOverflow::default())
+}
+
+/// Deserializes a Sciter value to the specific Rust type using the specified policy for large 64-bit integers.
+///
+/// Floats beyond `MAX_SAFE_INTEGER` may have been rounded, so they are accepted as `i64`/`u64` only with `IntOverflow::Float`
+/// (the default). Big integers share the type with currency and are accepted only with `IntOverflow::BigInt`.
+pub fn from_value_with<'a, T>(input: &'a Value, overflow: IntOverflow) -> Result<T>
+	where T: Deserialize<'a>
+{
+	let p = Deserializer { input: input.clone(), overflow: overflow, readable: true, source: Some(input) };
 	T::deserialize(p)
 }
 
 
 /// Implementation of deserialization.
-pub struct Deserializer {
+pub struct Deserializer<'de> {
 	input: Value,
+	overflow: IntOverflow,
+	// Whether the special types are expected as text, see `Serializer`.
+	readable: bool,
+	// The input itself if it is borrowed for `'de`.
+	// Nested items are copies, which can be changed or dropped while `'de` lasts.
+	source: Option<&'de Value>,
 }
 
 
-impl<'de> Deserializer {
+impl<'de> Deserializer<'de> {
 
 	pub fn from_value(input: Value) -> Self {
-		Deserializer { input: input }
+		Deserializer { input: input, overflow: IntOverflow::default(), readable: true, source: None }
+	}
+
+	// Deserializer of an inner value with the same settings.
+	fn nested(&self, input: Value) -> Self {
+		Deserializer { input: input, overflow: self.overflow, readable: self.readable, source: None }
+	}
+
+	// Deserializer of the parts of a special type in their compact form, 64-bit ones are carried as big integers.
+	fn parts(&self, input: Value) -> Self {
+		Deserializer { input: input, overflow: IntOverflow::BigInt, readable: false, source: None }
+	}
+
+	// Bytes of the input, borrowed for `'de` if possible.
+	fn visit_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+		match self.source.and_then(Value::as_bytes) {
+			Some(bytes) => visitor.visit_borrowed_bytes(bytes),
+			None => visitor.visit_bytes(self.input.as_bytes().unwrap_or_default()),
+		}
+	}
+
+	// 64-bit integer, big integers are read only when requested by the policy.
+	fn to_i64(&self) -> Option<i64> {
+		if self.input.is_currency() {
+			self.input.to_big_int().filter(|_| self.overflow == IntOverflow::BigInt)
+		} else {
+			self.input.to_i64()
+		}
+	}
+
+	fn to_u64(&self) -> Option<u64> {
+		if self.input.is_currency() {
+			self.to_i64().and_then(|i| u64::try_from(i).ok())
+		} else {
+			self.input.to_u64()
+		}
+	}
+
+	// Whether a 64-bit integer is allowed to come from this value.
+	fn is_exact(&self, v: i128) -> bool {
+		!self.input.is_float() || v.abs() <= MAX_SAFE_INTEGER as i128 || self.overflow == IntOverflow::Float
 	}
 }
 
 
-impl<'de, 'a> ::serde::de::Deserializer<'de> for Deserializer {
+impl<'de> ::serde::de::Deserializer<'de> for Deserializer<'de> {
 	type Error = Error;
 
 
@@ -40,10 +107,14 @@ impl<'de, 'a> ::serde::de::Deserializer<'de> for Deserializer {
 			VALUE_TYPE::T_BOOL => visitor.visit_bool(self.input.to_bool().unwrap()),
 			VALUE_TYPE::T_INT => visitor.visit_i32(self.input.to_int().unwrap()),
 			VALUE_TYPE::T_FLOAT => visitor.visit_f64(self.input.to_float().unwrap()),
+			VALUE_TYPE::T_CURRENCY => match self.to_i64() {
+				Some(v) => visitor.visit_i64(v),
+				None => visitor.visit_f64(self.input.to_currency().unwrap().to_f64()),
+			},
 			VALUE_TYPE::T_STRING => visitor.visit_str(&self.input.as_string().unwrap()),
 			VALUE_TYPE::T_ARRAY => visitor.visit_seq(SeqAccess::new(self)),
 			VALUE_TYPE::T_MAP => self.deserialize_map(visitor),
-			VALUE_TYPE::T_BYTES => visitor.visit_bytes(self.input.as_bytes().unwrap()),
+			VALUE_TYPE::T_BYTES => self.visit_bytes(visitor),
 			VALUE_TYPE::T_OBJECT => self.deserialize_map(visitor),
 			_ => Err(Error::UnsupportedType),
 		}
@@ -61,8 +132,22 @@ impl<'de, 'a> ::serde::de::Deserializer<'de> for Deserializer {
 		}
 	}
 
+	fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+		match self.to_i64() {
+			Some(v) if self.is_exact(v as i128) => visitor.visit_i64(v),
+			_ => Err(Error::ExpectedType(format!("expected {:?}, given {:?}", "i64", self.input))),
+		}
+	}
+
+	fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+		match self.to_u64() {
+			Some(v) if self.is_exact(v as i128) => visitor.visit_u64(v),
+			_ => Err(Error::ExpectedType(format!("expected {:?}, given {:?}", "u64", self.input))),
+		}
+	}
+
 	forward_to_deserialize_any! {
-		i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string bytes	byte_buf
+		i8 i16 i32 u8 u16 u32 f32 f64 char str string bytes	byte_buf
 	}
 
 	fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
@@ -85,15 +170,39 @@ impl<'de, 'a> ::serde::de::Deserializer<'de> for Deserializer {
 		self.deserialize_unit(visitor)
 	}
 
-	fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &str, visitor: V) -> Result<V::Value> {
-		visitor.visit_newtype_struct(self)
+	fn deserialize_newtype_struct<V: Visitor<'de>>(self, name: &str, visitor: V) -> Result<V::Value> {
+		// special types, see `sciter_serde::{date, length, currency, color, duration, angle}`
+		let (parts, expected) = match name {
+			date::TOKEN => {
+				let flags = self.input.full_type().1 as i32;
+				(self.input.to_date().map(|ticks| pair(Value::big_int(ticks), flags)), "date")
+			},
+			length::TOKEN => {
+				(self.input.to_length().map(|l| pair(l.value, l.unit.to_raw() as i32)), "length")
+			},
+			currency::TOKEN => {
+				(self.input.to_currency().map(|c| Value::big_int(c.raw())), "currency")
+			},
+			color::TOKEN => {
+				(self.input.to_color().filter(|_| self.input.is_color()).map(|c| Value::big_int(c as i64)), "color")
+			},
+			duration::TOKEN => {
+				(self.input.to_duration().filter(|_| self.input.is_duration()).map(Value::from), "duration")
+			},
+			angle::TOKEN => {
+				(self.input.to_angle().filter(|_| self.input.is_angle()).map(Value::from), "angle")
+			},
+			_ => return visitor.visit_newtype_struct(self),
+		};
+		match parts {
+			Some(parts) => visitor.visit_newtype_struct(self.parts(parts)),
+			None => Err(Error::ExpectedType(format!("expected {:?}, given {:?}", expected, self.input))),
+		}
 	}
 
 	fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value>	{
 		if self.input.is_array() {
-			let it = self.input.values();
-			let sq = de::value::SeqDeserializer::new(it);
-			visitor.visit_seq(sq)
+			visitor.visit_seq(SeqAccess::new(self))
 		} else {
 			Err(Error::ExpectedType(format!("expected {:?}, given {:?}", "sequence", self.input)))
 		}
@@ -109,9 +218,7 @@ impl<'de, 'a> ::serde::de::Deserializer<'de> for Deserializer {
 
 	fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
 		if self.input.is_map() {
-			let it = self.input.items().into_iter();
-			let sq = de::value::MapDeserializer::new(it);
-			visitor.visit_map(sq)
+			visitor.visit_map(SeqAccess::new(self))
 		} else {
 			Err(Error::ExpectedType(format!("expected {:?}, given {:?}", "map", self.input)))
 		}
@@ -122,6 +229,10 @@ impl<'de, 'a> ::serde::de::Deserializer<'de> for Deserializer {
 		self.deserialize_map(visitor)
 	}
 
+	fn is_human_readable(&self) -> bool {
+		self.readable
+	}
+
 	fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value>
 	{
 		self.deserialize_str(visitor)
@@ -149,8 +260,16 @@ impl<'de, 'a> ::serde::de::Deserializer<'de> for Deserializer {
 }
 
 
+fn pair<A: Into<Value>, B: Into<Value>>(a: A, b: B) -> Value {
+	let mut v = Value::array(0);
+	v.push(a);
+	v.push(b);
+	v
+}
+
+
 impl<'de> de::IntoDeserializer<'de, Error> for Value {
-	type Deserializer = Deserializer;
+	type Deserializer = Deserializer<'de>;
 
 	fn into_deserializer(self) -> Self::Deserializer {
 		Deserializer::from_value(self)
@@ -159,15 +278,15 @@ impl<'de> de::IntoDeserializer<'de, Error> for Value {
 
 
 #[doc(hidden)]
-struct SeqAccess {
-	de: Deserializer,
+struct SeqAccess<'de> {
+	de: Deserializer<'de>,
 	pos: usize,
 	len: usize,
 	key: Option<Value>,
 }
 
-impl SeqAccess {
-	fn new(d: Deserializer) -> Self	{
+impl<'de> SeqAccess<'de> {
+	fn new(d: Deserializer<'de>) -> Self	{
 		let len = d.input.len();
 		SeqAccess {
 			de: d,
@@ -176,9 +295,16 @@ impl SeqAccess {
 			key: None,
 		}
 	}
+
+	// Deserializer of the enum variant content and the variant key.
+	fn content(self) -> (Deserializer<'de>, Value) {
+		let key = self.key.unwrap();
+		let content = self.de.nested(self.de.input.get_item(key.clone()));
+		(content, key)
+	}
 }
 
-impl<'de> de::SeqAccess<'de> for SeqAccess {
+impl<'de> de::SeqAccess<'de> for SeqAccess<'de> {
 	type Error = Error;
 
 	fn size_hint(&self) -> Option<usize> {
@@ -191,15 +317,15 @@ impl<'de> de::SeqAccess<'de> for SeqAccess {
 		if self.pos < self.len {
 			self.pos += 1;
 			let v = self.de.input.get(self.pos - 1);
-			let inner = Deserializer::from_value(v);
-			seed.deserialize(inner).map(Some)
+			let inner = self.de.nested(v);
+			seed.deserialize(inner).map(Some).map_err(|e| e.at_index(self.pos - 1))
 		} else {
 			Ok(None)
 		}
 	}
 }
 
-impl<'de> de::MapAccess<'de> for SeqAccess {
+impl<'de> de::MapAccess<'de> for SeqAccess<'de> {
 	type Error = Error;
 
 	fn size_hint(&self) -> Option<usize> {
@@ -212,8 +338,8 @@ impl<'de> de::MapAccess<'de> for SeqAccess {
 		if self.pos < self.len {
 			self.pos += 1;
 			let v = self.de.input.key_at(self.pos - 1);
-			let inner = Deserializer::from_value(v);
-			seed.deserialize(inner).map(Some)
+			let inner = self.de.nested(v.clone());
+			seed.deserialize(inner).map(Some).map_err(|e| e.at_key(&v))
 		} else {
 			Ok(None)
 		}
@@ -223,56 +349,60 @@ impl<'de> de::MapAccess<'de> for SeqAccess {
 		where V: de::DeserializeSeed<'de>
 	{
 		let v = self.de.input.get(self.pos - 1);
-		let inner = Deserializer::from_value(v);
-		seed.deserialize(inner)
+		let inner = self.de.nested(v);
+		seed.deserialize(inner).map_err(|e| e.at_key(&self.de.input.key_at(self.pos - 1)))
 	}
 }
 
-impl<'de> de::EnumAccess<'de> for SeqAccess {
+impl<'de> de::EnumAccess<'de> for SeqAccess<'de> {
 	type Error = Error;
 	type Variant = Self;
 
 	fn variant_seed<V>(mut self, seed: V) -> Result<(V::Value, Self::Variant)>
 		where V: de::DeserializeSeed<'de>
 	{
-		// `{ "N": ... }`
-		// Here I suppose to deserialize the variant key.
+		// `{ "N": ... }`, a single key is the variant name.
+		if self.len != 1 {
+			return Err(Error::ExpectedType(format!("expected enum (as map with a single key), given {:?}", self.de.input)));
+		}
 		let v = self.de.input.key_at(0);
 		self.key = Some(v.clone());
-		let vkey = seed.deserialize( Deserializer::from_value(v) )?;
+		let vkey = seed.deserialize( self.de.nested(v) )?;
 		Ok((vkey, self))
 	}
 }
 
-impl<'de> de::VariantAccess<'de> for SeqAccess {
+impl<'de> de::VariantAccess<'de> for SeqAccess<'de> {
 	type Error = Error;
 
 	fn unit_variant(self) -> Result<()> {
-		de::Deserialize::deserialize(self.de)
+		// `{ "A": null }`
+		let (content, key) = self.content();
+		de::Deserialize::deserialize(content).map_err(|e| e.at_key(&key))
 	}
 
 	fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value>
 		where T: de::DeserializeSeed<'de>
 	{
 		// `{ "N": u8 }`
-		let v = self.de.input.get_item(self.key.unwrap());
-		seed.deserialize( Deserializer::from_value(v) )
+		let (content, key) = self.content();
+		seed.deserialize(content).map_err(|e| e.at_key(&key))
 	}
 
 	fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value>
 		where V: de::Visitor<'de>
 	{
 		// `{ "T": [u8, u8] }`
-		let v = self.de.input.get_item(self.key.unwrap());
-		de::Deserializer::deserialize_tuple(Deserializer::from_value(v), len, visitor)
+		let (content, key) = self.content();
+		de::Deserializer::deserialize_tuple(content, len, visitor).map_err(|e| e.at_key(&key))
 	}
 
 	fn struct_variant<V>(self, fields: &'static [&'static str], visitor: V) -> Result<V::Value>
 		where V: de::Visitor<'de>
 	{
 		// `{ "S": {r: u8, g: u8, b: u8} }`
-		let v = self.de.input.get_item(self.key.unwrap());
-		de::Deserializer::deserialize_struct(Deserializer::from_value(v), "", fields, visitor)
+		let (content, key) = self.content();
+		de::Deserializer::deserialize_struct(content, "", fields, visitor).map_err(|e| e.at_key(&key))
 	}
 
 }
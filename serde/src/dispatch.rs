@@ -0,0 +1,70 @@
+//! Support of [`dispatch_serde_call!`](../macro.dispatch_serde_call.html).
+
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+pub use sciter::{HELEMENT, Value};
+
+use {from_value, to_value};
+
+
+/// Convert the script call argument, or make the script error about it.
+pub fn argument<'a, T: Deserialize<'a>>(call: &str, argv: &'a [Value], index: usize, ty: &str) -> Result<T, Value> {
+	from_value(&argv[index]).map_err(|e| {
+		Value::error(&format!("{} error: invalid type of {} argument ({} expected): {}.", call, index, ty, e))
+	})
+}
+
+/// Convert the result of a successful call.
+fn returned<T: Serialize>(call: &str, value: &T) -> Value {
+	match to_value(value) {
+		Ok(v) => v,
+		Err(e) => Value::error(&format!("{} error: invalid result: {}.", call, e)),
+	}
+}
+
+
+// Return values are converted via "autoref specialization":
+// `(&rv).returns()` picks `ReturnsResult` for `Result<T, E>` and falls back to `ReturnsValue` otherwise.
+
+/// Marker of the returned `Result<T, E>`.
+pub struct ResultKind;
+
+/// Marker of the other returned values.
+pub struct ValueKind;
+
+/// Recognize a returned `Result<T, E>`.
+pub trait ReturnsResult {
+	fn returns(&self) -> ResultKind {
+		ResultKind
+	}
+}
+
+impl<T: Serialize, E: Display> ReturnsResult for Result<T, E> {}
+
+/// Recognize the other returned values.
+pub trait ReturnsValue {
+	fn returns(&self) -> ValueKind {
+		ValueKind
+	}
+}
+
+impl<T: Serialize> ReturnsValue for &T {}
+
+impl ResultKind {
+	/// `Ok` is serialized and `Err` becomes a script error.
+	pub fn into_value<T: Serialize, E: Display>(self, call: &str, rv: Result<T, E>) -> Value {
+		match rv {
+			Ok(v) => returned(call, &v),
+			Err(e) => Value::error(&e.to_string()),
+		}
+	}
+}
+
+impl ValueKind {
+	/// Serialize the returned value.
+	pub fn into_value<T: Serialize>(self, call: &str, rv: T) -> Value {
+		returned(call, &rv)
+	}
+}
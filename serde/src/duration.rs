@@ -0,0 +1,85 @@
+//! Durations as Sciter `T_DURATION` values.
+//!
+//! Use it as `#[serde(with = "sciter_serde::duration")]` on fields of the `std::time::Duration` type
+//! or wrap them into [`Duration`](struct.Duration.html), see [`sciter_serde::date`](../date/index.html) for details.
+//! Human-readable serializers get a string like `"0.25s"`, the rest get the number of seconds.
+
+use std::fmt;
+use std::time;
+
+use serde::ser::{Serialize, Serializer};
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+
+
+// Name of the newtype struct which `Serializer` and `Deserializer` handle as a duration.
+pub(crate) const TOKEN: &str = "$sciter::duration";
+
+/// Serialize a duration.
+pub fn serialize<S: Serializer>(value: &time::Duration, serializer: S) -> Result<S::Ok, S::Error> {
+	serializer.serialize_newtype_struct(TOKEN, &Repr(value.as_secs_f64()))
+}
+
+/// Deserialize a duration.
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<time::Duration, D::Error> {
+	let seconds = deserializer.deserialize_newtype_struct(TOKEN, DurationVisitor)?;
+	time::Duration::try_from_secs_f64(seconds).map_err(|_| de::Error::custom(format!("invalid duration {}s", seconds)))
+}
+
+/// Duration serialized as a Sciter duration.
+///
+/// Unlike the `with` attribute it works inside of containers like `Vec<Duration>`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Duration(pub time::Duration);
+
+impl Serialize for Duration {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serialize(&self.0, serializer)
+	}
+}
+
+impl<'de> Deserialize<'de> for Duration {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		deserialize(deserializer).map(Duration)
+	}
+}
+
+// Inner value of the newtype struct: text like `0.25s` for human-readable formats, the seconds otherwise.
+struct Repr(f64);
+
+impl Serialize for Repr {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		if serializer.is_human_readable() {
+			serializer.collect_str(&format_args!("{}s", self.0))
+		} else {
+			serializer.serialize_f64(self.0)
+		}
+	}
+}
+
+impl<'de> Deserialize<'de> for Repr {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		if !deserializer.is_human_readable() {
+			return f64::deserialize(deserializer).map(Repr);
+		}
+		let text = String::deserialize(deserializer)?;
+		let seconds = match text.strip_suffix("ms") {
+			Some(ms) => ms.parse::<f64>().map(|ms| ms / 1000.0),
+			None => text.strip_suffix('s').unwrap_or("").parse::<f64>(),
+		};
+		seconds.map(Repr).map_err(|_| de::Error::custom(format!("invalid duration {:?}", text)))
+	}
+}
+
+struct DurationVisitor;
+
+impl<'de> Visitor<'de> for DurationVisitor {
+	type Value = f64;
+
+	fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("a duration")
+	}
+
+	fn visit_newtype_struct<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+		Repr::deserialize(deserializer).map(|d| d.0)
+	}
+}
@@ -3,6 +3,8 @@ use std::fmt::{self, Display};
 
 use serde::{ser, de};
 
+use sciter::Value;
+
 
 /// Result type for serialization.
 pub type Result<T> = std::result::Result<T, Error>;
@@ -14,6 +16,50 @@ pub enum Error {
 	Unimplemented,
 	UnsupportedType,
 	ExpectedType(String),
+	/// Error of a nested element with the path to it, like `settings.windows[2].title`.
+	At(String, Box<Error>),
+}
+
+impl Error {
+	/// Prepend the array index to the path of the offending element.
+	pub fn at_index(self, index: usize) -> Self {
+		self.prepend(format!("[{}]", index))
+	}
+
+	/// Prepend the map key to the path of the offending element.
+	pub fn at_key(self, key: &Value) -> Self {
+		let name = key.as_string().filter(|s| key.is_string() && !s.is_empty() && s.chars().all(|c| c.is_alphanumeric() || c == '_'));
+		self.prepend(name.unwrap_or_else(|| format!("[{}]", key)))
+	}
+
+	fn prepend(self, segment: String) -> Self {
+		match self {
+			Error::At(mut path, inner) => {
+				if !path.starts_with('[') {
+					path.insert(0, '.');
+				}
+				path.insert_str(0, &segment);
+				Error::At(path, inner)
+			},
+			inner => Error::At(segment, Box::new(inner)),
+		}
+	}
+
+	/// Path to the offending element inside the deserialized value, like `items[2].name`; empty for the value itself.
+	pub fn path(&self) -> &str {
+		match *self {
+			Error::At(ref path, _) => path,
+			_ => "",
+		}
+	}
+
+	/// The error itself without its path.
+	pub fn inner(&self) -> &Error {
+		match *self {
+			Error::At(_, ref inner) => inner,
+			_ => self,
+		}
+	}
 }
 
 impl ser::Error for Error {
@@ -29,12 +75,14 @@ impl de::Error for Error {
 }
 
 impl std::error::Error for Error {
+	#[allow(deprecated)]
 	fn description(&self) -> &str {
 		match *self {
 			Error::Message(ref msg) => msg,
 			Error::ExpectedType(ref msg) => msg,
 			Error::Unimplemented => "unimplemented",
 			Error::UnsupportedType => "unsupported",
+			Error::At(_, ref inner) => inner.description(),
 		}
 	}
 }
@@ -46,6 +94,7 @@ impl Display for Error {
 			Error::ExpectedType(ref msg) => write!(f, "expected: {}", msg),
 			Error::UnsupportedType => write!(f, "unsupported type"),
 			Error::Unimplemented => write!(f, "unimplemented"),
+			Error::At(ref path, ref inner) => write!(f, "{} at {}", inner, path),
 		}
 	}
 }
@@ -0,0 +1,92 @@
+//! Lengths as Sciter `T_LENGTH` values.
+//!
+//! Use it as `#[serde(with = "sciter_serde::length")]` on fields of the `sciter::value::Length` type
+//! or wrap them into [`Length`](struct.Length.html), see [`sciter_serde::date`](../date/index.html) for details.
+//! Human-readable serializers get a CSS string like `"1.5em"`,
+//! the rest get a `(value, unit)` tuple with the raw `VALUE_UNIT_TYPE_LENGTH` unit.
+
+use std::fmt;
+
+use serde::ser::{Serialize, Serializer};
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+
+use sciter::value::{self, LengthUnit};
+
+
+// Name of the newtype struct which `Serializer` and `Deserializer` handle as a length.
+pub(crate) const TOKEN: &str = "$sciter::length";
+
+/// Serialize a length.
+pub fn serialize<S: Serializer>(value: &value::Length, serializer: S) -> Result<S::Ok, S::Error> {
+	serializer.serialize_newtype_struct(TOKEN, &Repr(*value))
+}
+
+/// Deserialize a length.
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<value::Length, D::Error> {
+	deserializer.deserialize_newtype_struct(TOKEN, LengthVisitor)
+}
+
+/// Length serialized as a Sciter length.
+///
+/// Unlike the `with` attribute it works inside of containers like `Vec<Length>`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Length(pub value::Length);
+
+impl Serialize for Length {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serialize(&self.0, serializer)
+	}
+}
+
+impl<'de> Deserialize<'de> for Length {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		deserialize(deserializer).map(Length)
+	}
+}
+
+// Inner value of the newtype struct: CSS text for human-readable formats, `(value, unit)` otherwise.
+struct Repr(value::Length);
+
+impl Serialize for Repr {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		if serializer.is_human_readable() {
+			serializer.collect_str(&self.0)
+		} else {
+			(self.0.value, self.0.unit.to_raw()).serialize(serializer)
+		}
+	}
+}
+
+impl<'de> Deserialize<'de> for Repr {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		if !deserializer.is_human_readable() {
+			let (value, unit) = <(f64, u32)>::deserialize(deserializer)?;
+			return match LengthUnit::from_raw(unit) {
+				Some(unit) => Ok(Repr(value::Length::new(value, unit))),
+				None => Err(de::Error::custom(format!("unknown length unit {}", unit))),
+			};
+		}
+		let text = String::deserialize(deserializer)?;
+		// suffixes are 1 to 3 characters long, like `%`, `px` or `dip`
+		let length = (1..4).filter(|&n| n < text.len() && text.is_char_boundary(text.len() - n)).find_map(|n| {
+			let (value, suffix) = text.split_at(text.len() - n);
+			Some(value::Length::new(value.parse().ok()?, LengthUnit::from_suffix(suffix)?))
+		});
+		let length = length.ok_or_else(|| de::Error::custom(format!("invalid length {:?}", text)))?;
+		Ok(Repr(length))
+	}
+}
+
+struct LengthVisitor;
+
+impl<'de> Visitor<'de> for LengthVisitor {
+	type Value = value::Length;
+
+	fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("a length")
+	}
+
+	fn visit_newtype_struct<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+		Repr::deserialize(deserializer).map(|l| l.0)
+	}
+}
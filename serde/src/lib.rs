@@ -10,49 +10,176 @@ While technically you could just use the `serde_json` crate and perform serializ
 an intermediate string (something like `sciter::Value::from_str(&serde_json::to_string(<your data>)?)?`),
 you can also use direct serialization between your data and `sciter::Value`.
 
+Data which is already a `serde_json::Value` can be converted without serialization at all:
+enable the `serde_json` feature and use `sciter::Value::from(json)` and `serde_json::Value::try_from(&value)`
+(see [`sciter::json`](https://docs.rs/sciter-rs/latest/sciter/json/index.html) for the mapping of the Sciter-only types).
+
 ## Supported types of Sciter value
 
 + Bool (`bool`)
-+ Integer (`i8`-`i32`)
++ Integer (`i8`-`i64`, `u8`-`u64`)
++ BigInt (`i64`, `u64` beyond the script number range with [`IntOverflow::BigInt`](#64-bit-integers))
 +	Float (`f32`-`f64`)
 + String (`&str`, `String`)
 + Bytes (`&[u8]`)
 + Array (`&[T]`, `Vec<T>`)
 + Object (key-value mapping like `struct` or `HashMap`, `BTreeMap`, etc.)
 
++ Date (`SystemTime`, `chrono` and `time` types via [`sciter_serde::date`](date/index.html))
++ Length (`sciter::value::Length` via [`sciter_serde::length`](length/index.html))
++ Currency (`sciter::value::Currency` via [`sciter_serde::currency`](currency/index.html))
++ Duration (`std::time::Duration` via [`sciter_serde::duration`](duration/index.html))
++ Angle (`f64` radians via [`sciter_serde::angle`](angle/index.html))
++ Color (`u32` in the `0xAABBGGRR` form via [`sciter_serde::color`](color/index.html))
+
+These types need either a `#[serde(with = "sciter_serde::color")]` attribute on the field
+or a wrapper like `sciter_serde::color::Color` and become strings like `"#ff0000"` or `"0.25s"`
+in other human-readable formats (e.g. JSON).
+
 Unsupported:
 
-- Date
-- Currency
-- Length
 - Range
-- Duration
-- Angle
-- Color
+
+Values of every data type, including durations, angles and colors,
+can be stored in a compact binary form via the [CBOR encoding](cbor/index.html).
 
 ## Supported types of the Serde data model
 
 * [x] `bool`
-* [x] integer types except the following:
-* [-] `i64`/`u64` - 64-bit integers stored as `f64` in Sciter
+* [x] integer types
+* [x] `i64`/`u64` - stored as numbers while they fit, see [64-bit integers](#64-bit-integers) below
 * [x] strings
 * [x] byte arrays
 * [x] option
 * [x] unit (stored as `null`)
 * [x] unit struct (stored as `null`)
-* [x] unit variant (aka `enum`, stored just as enum index of `i32` type)
+* [x] unit variant (aka `enum`, stored as the variant name)
 * [x] newtype struct (aka `struct Io(u32)`, stored as underlaying value)
-* [-] newtype variant
+* [x] newtype variant (stored as `{ "Name": value }`)
 * [x] seq, like vector (stored as array)
 * [x] tuple (stored as array)
 * [x] tuple struct (stored as array)
-* [-] tuple variant
+* [x] tuple variant (stored as `{ "Name": [values] }`)
 * [x] map (stored as map)
 * [x] struct (stored as map)
-* [-] struct variant
+* [x] struct variant (stored as `{ "Name": { fields } }`)
 
 See the [Serde data model](https://serde.rs/data-model.html) for reference.
 
+## Enums
+
+Enums with data are externally tagged by default, and the other
+[representations](https://serde.rs/enum-representations.html) are selected by the usual attributes,
+producing the same values as `serde_json` does:
+
+```rust
+# #![doc(test(no_crate_inject))]
+#[macro_use]
+extern crate serde_derive;
+extern crate serde;
+
+extern crate sciter;
+extern crate sciter_serde;
+
+use sciter::Value;
+use sciter_serde::{from_value, to_value};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(tag = "type")]
+enum Shape {
+	Circle { radius: f64 },
+	Square { side: f64 },
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(untagged)]
+enum Id {
+	Number(i32),
+	Name(String),
+}
+
+fn main() {
+	sciter::fake::install();
+
+	let v = to_value(&Shape::Circle { radius: 0.5 }).unwrap();
+	assert_eq!(v, Value::parse(r#"{ "type": "Circle", "radius": 0.5 }"#).unwrap());
+	assert_eq!(from_value::<Shape>(&v), Ok(Shape::Circle { radius: 0.5 }));
+
+	assert_eq!(from_value::<Id>(&Value::from("main")), Ok(Id::Name("main".to_owned())));
+}
+```
+
+Adjacent tagging is `#[serde(tag = "t", content = "c")]`.
+
+## Borrowing and errors
+
+[`from_value`](fn.from_value.html) can borrow the bytes of a byte array input only
+(e.g. `&[u8]` or `Cow<[u8]>` with `serde_bytes`, also wrapped in a newtype struct or `Option`). Byte arrays nested in arrays and maps are copies
+which can be changed or dropped by the engine, so they are copied (`Cow<[u8]>` is owned, `&[u8]` fails).
+Strings are kept by Sciter in UTF-16, so they are never borrowed either: they are always converted
+and need `String` or `Cow<str>`.
+
+Deserialization errors of nested elements carry the path to the offending element,
+so a malformed object from script points to the wrong key:
+
+```rust
+# #![doc(test(no_crate_inject))]
+#[macro_use]
+extern crate serde_derive;
+extern crate serde;
+
+extern crate sciter;
+extern crate sciter_serde;
+
+#[derive(Deserialize, Debug)]
+struct Window {
+	title: String,
+}
+
+fn main() {
+	sciter::fake::install();
+
+	let v = sciter::Value::parse(r#"[{ "title": "main" }, { "title": 2 }]"#).unwrap();
+	let err = sciter_serde::from_value::<Vec<Window>>(&v).unwrap_err();
+	assert_eq!(err.path(), "[1].title");
+}
+```
+
+## 64-bit integers
+
+Script numbers are either 32-bit integers or doubles, so `i64`/`u64` values are stored as `T_INT` or `T_FLOAT` as long as
+they are not larger than `2^53 - 1`. The rest is handled according to the [`IntOverflow`](https://docs.rs/sciter-rs/latest/sciter/value/enum.IntOverflow.html)
+policy passed to [`to_value_with`](fn.to_value_with.html) and [`from_value_with`](fn.from_value_with.html):
+
+* `Float` (the default) - rounded to the nearest `f64`,
+* `BigInt` - stored losslessly as script `BigInt` (`u64` above `i64::MAX` is an error);
+  Sciter.JS only, as TIScript reads the same type as currency,
+* `Error` - serialization fails.
+
+```rust
+extern crate sciter;
+extern crate sciter_serde;
+
+use sciter::value::IntOverflow;
+use sciter_serde::{from_value, from_value_with, to_value, to_value_with};
+
+fn main() {
+	sciter::fake::install();
+
+	let rounded = to_value(&9_007_199_254_740_993_i64).unwrap();
+	assert!(rounded.is_float());
+	assert_eq!(from_value::<i64>(&rounded), Ok(9_007_199_254_740_992));
+	assert!(from_value_with::<i64>(&rounded, IntOverflow::Error).is_err());
+
+	let big = to_value_with(&9_007_199_254_740_993_i64, IntOverflow::BigInt).unwrap();
+	assert_eq!(big.to_big_int(), Some(9_007_199_254_740_993));
+	assert!(from_value::<i64>(&big).is_err());
+	assert_eq!(from_value_with::<i64>(&big, IntOverflow::BigInt), Ok(9_007_199_254_740_993));
+
+	assert!(to_value_with(&u64::MAX, IntOverflow::Error).is_err());
+}
+```
+
 # Examples
 
 ```rust
@@ -69,6 +196,7 @@ fn back_and_forth() {
 }
 
 fn main() {
+	sciter::fake::install();
 
 	// bool
 	let v: Value = to_value(&true).unwrap();
@@ -119,6 +247,7 @@ use sciter::Value;
 use sciter_serde::to_value;
 
 fn main() {
+	sciter::fake::install();
 
 	// structs
 	#[derive(Serialize)]
@@ -145,11 +274,123 @@ extern crate sciter;
 mod error;
 mod ser;
 mod de;
+pub mod date;
+pub mod length;
+pub mod currency;
+pub mod color;
+pub mod duration;
+pub mod angle;
+pub mod cbor;
+
+#[doc(hidden)]
+pub mod dispatch;
 
 #[doc(inline)]
-pub use ser::to_value;
+pub use ser::{to_value, to_value_with};
 
 #[doc(inline)]
-pub use de::from_value;
+pub use de::{from_value, from_value_with};
 
 pub use error::{Result, Error};
+
+
+/// Dispatch script calls to native methods with `Deserialize` arguments and a `Serialize` result.
+///
+/// It is a counterpart of [`sciter::dispatch_script_call!`](https://docs.rs/sciter-rs/latest/sciter/macro.dispatch_script_call.html)
+/// which converts the arguments via [`from_value`](fn.from_value.html) and the returned value via [`to_value`](fn.to_value.html),
+/// so request and response structs can be used as method signatures directly.
+/// A returned `Result<T, E>` with `E: Display` throws the error in script.
+///
+/// ```rust
+/// # #![doc(test(no_crate_inject))]
+/// #[macro_use]
+/// extern crate serde_derive;
+/// extern crate serde;
+///
+/// extern crate sciter;
+/// #[macro_use]
+/// extern crate sciter_serde;
+///
+/// #[derive(Deserialize)]
+/// struct Query {
+///   name: String,
+///   limit: Option<usize>,
+/// }
+///
+/// #[derive(Serialize)]
+/// struct Found {
+///   items: Vec<String>,
+/// }
+///
+/// struct Handler;
+///
+/// impl Handler {
+///   fn find(&self, query: Query) -> Result<Found, String> {
+///     if query.name.is_empty() {
+///       return Err("empty query".to_owned());
+///     }
+///     Ok(Found { items: vec![query.name; query.limit.unwrap_or(1)] })
+///   }
+///
+///   fn count(&self, items: Vec<i32>, sum: bool) -> i32 {
+///     if sum { items.iter().sum() } else { items.len() as i32 }
+///   }
+/// }
+///
+/// impl sciter::EventHandler for Handler {
+///   dispatch_serde_call! {
+///     fn find(Query);
+///     fn count(Vec<i32>, bool);
+///   }
+/// }
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! dispatch_serde_call {
+
+	(
+		$(
+			fn $name:ident ( $( $argt:ty ),* );
+		 )*
+	) => {
+
+		fn dispatch_script_call(&mut self, _root: $crate::dispatch::HELEMENT, name: &str, argv: &[$crate::dispatch::Value]) -> Option<$crate::dispatch::Value>
+		{
+			#[allow(unused_imports)]
+			use $crate::dispatch::{ReturnsResult, ReturnsValue};
+
+			match name {
+				$(
+					stringify!($name) => {
+
+						// args count
+						let argc: usize = 0 $( + { let _ = stringify!($argt); 1 } )*;
+
+						if argv.len() != argc {
+							return Some($crate::dispatch::Value::error(&format!("{} error: {} of {} arguments provided.", stringify!($name), argv.len(), argc)));
+						}
+
+						// call function
+						let mut _i = 0;
+						let rv = self.$name(
+							$(
+								match $crate::dispatch::argument::<$argt>(stringify!($name), argv, _i, stringify!($argt)) {
+									Ok(arg) => { _i += 1; arg },
+									Err(e) => return Some(e),
+								}
+							 ),*
+						);
+
+						// return result value
+						return Some((&rv).returns().into_value(stringify!($name), rv));
+					},
+				 )*
+
+				_ => ()
+			};
+
+			// script call not handled
+			return None;
+		}
+	};
+}
@@ -1,13 +1,25 @@
 /// Serialization.
+use std::convert::TryFrom;
+
 use serde::ser::{self, Serialize};
 
+use {angle, color, currency, date, duration, length};
 use error::{Error, Result};
 use sciter::{Value};
+use sciter::value::{Currency, IntOverflow, Length, LengthUnit};
 
 
 /// Serialize the given data structure into Sciter value.
+///
+/// 64-bit integers which do not fit into a script number are rounded to floats,
+/// use [`to_value_with`](fn.to_value_with.html) to choose another policy.
 pub fn to_value<T: ?Sized + Serialize>(value: &T) -> Result<Value> {
-	let mut p = Serializer { output: Value::new() };
+	to_value_with(value, IntOverflow::default())
+}
+
+/// Serialize the given data structure into Sciter value using the specified policy for large 64-bit integers.
+pub fn to_value_with<T: ?Sized + Serialize>(value: &T, overflow: IntOverflow) -> Result<Value> {
+	let mut p = Serializer { output: Value::new(), overflow: overflow, readable: true };
 	value.serialize(&mut p)?;
 	Ok(p.output)
 }
@@ -15,6 +27,52 @@ pub fn to_value<T: ?Sized + Serialize>(value: &T) -> Result<Value> {
 /// Implementation of serialization.
 pub struct Serializer {
 	output: Value,
+	overflow: IntOverflow,
+	// Whether the special types should be written as text for other formats.
+	readable: bool,
+}
+
+impl Serializer {
+	// Serialize an inner value with the same settings.
+	fn nested<T: ?Sized + Serialize>(&self, value: &T) -> Result<Value> {
+		let mut p = Serializer { output: Value::new(), overflow: self.overflow, readable: self.readable };
+		value.serialize(&mut p)?;
+		Ok(p.output)
+	}
+
+	// Serialize the parts of a special type in their compact form, 64-bit ones are kept as big integers.
+	fn parts<T: ?Sized + Serialize>(value: &T) -> Result<Value> {
+		let mut p = Serializer { output: Value::new(), overflow: IntOverflow::BigInt, readable: false };
+		value.serialize(&mut p)?;
+		Ok(p.output)
+	}
+}
+
+// Special types represented as newtype structs, see `sciter_serde::{date, length, currency}`.
+fn special(name: &str) -> Option<fn(&Value) -> Option<Value>> {
+	match name {
+		// `(ticks, flags)`
+		date::TOKEN => Some(|parts| Some(Value::date(int64(&parts.get(0))?, parts.get(1).to_int()? as u32))),
+		// `(value, unit)`
+		length::TOKEN => Some(|parts| {
+			let unit = LengthUnit::from_raw(parts.get(1).to_int()? as u32)?;
+			Some(Value::length(Length::new(parts.get(0).to_float()?, unit)))
+		}),
+		// `raw`
+		currency::TOKEN => Some(|raw| Some(Value::currency(Currency::from_raw(int64(raw)?)))),
+		// `0xAABBGGRR`
+		color::TOKEN => Some(|c| Some(Value::color(u32::try_from(c.to_u64()?).ok()?))),
+		// seconds
+		duration::TOKEN => Some(|d| Some(Value::duration(d.to_float()?))),
+		// radians
+		angle::TOKEN => Some(|a| Some(Value::angle(a.to_float()?))),
+		_ => None,
+	}
+}
+
+// 64-bit part of a special type, see `Serializer::parts`.
+fn int64(v: &Value) -> Option<i64> {
+	v.to_i64().or_else(|| v.to_big_int())
 }
 
 // Helper structure for serialization of sequence data types (array, map, tuple ans so on).
@@ -58,7 +116,7 @@ impl<'a> ser::SerializeSeq for SeqSerializer<'a> {
 	}
 
 	fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<()> {
-		let dst = to_value(value)?;
+		let dst = self.ser.nested(value)?;
 		self.output.push(dst);
 		Ok(())
 	}
@@ -73,18 +131,18 @@ impl<'a> ser::SerializeMap for SeqSerializer<'a> {
 	}
 
 	fn serialize_entry<K, V>(&mut self, key: &K, value: &V) -> Result<()> where K: ?Sized + Serialize, V: ?Sized + Serialize {
-		self.output.set_item(to_value(key)?, to_value(value)?);
+		self.output.set_item(self.ser.nested(key)?, self.ser.nested(value)?);
 		Ok(())
 	}
 
 	fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<()> {
-		self.key = Some(to_value(key)?);
+		self.key = Some(self.ser.nested(key)?);
 		Ok(())
 	}
 
 	fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<()> {
 		let key = self.key.take();
-		self.output.set_item(key.unwrap(), to_value(value)?);
+		self.output.set_item(key.unwrap(), self.ser.nested(value)?);
 		Ok(())
 	}
 }
@@ -98,7 +156,7 @@ impl<'a> ser::SerializeStruct for SeqSerializer<'a> {
 	}
 
 	fn serialize_field<T: ?Sized + Serialize>(&mut self, key: &'static str, value: &T) -> Result<()> {
-		self.output.set_item(key, to_value(value)?);
+		self.output.set_item(key, self.ser.nested(value)?);
 		Ok(())
 	}
 }
@@ -110,14 +168,14 @@ impl<'a> ser::SerializeStructVariant for SeqSerializer<'a> {
 	fn end(self) -> Result<()> {
 		// self.output: map
 		// self.outer: left key
-		let mut result = Value::new();
+		let mut result = Value::map();
 		result.set_item(self.outer.unwrap(), self.output);
 		self.ser.output = result;
 		Ok(())
 	}
 
 	fn serialize_field<T: ?Sized + Serialize>(&mut self, key: &'static str, value: &T) -> Result<()> {
-		self.output.set_item(key, to_value(value)?);
+		self.output.set_item(key, self.ser.nested(value)?);
 		Ok(())
 	}
 }
@@ -155,7 +213,7 @@ impl<'a> ser::SerializeTupleVariant for SeqSerializer<'a> {
 	fn end(self) -> Result<()> {
 		// self.output: array
 		// self.outer: left key
-		let mut result = Value::new();
+		let mut result = Value::map();
 		result.set_item(self.outer.unwrap(), self.output);
 		self.ser.output = result;
 		Ok(())
@@ -214,12 +272,18 @@ impl<'a> ser::Serializer for &'a mut Serializer {
   	}
   }
 
-  fn serialize_i64(self, _v: i64) -> Result<()> {
-  	Err(Error::UnsupportedType)
+  fn serialize_i64(self, v: i64) -> Result<()> {
+  	match Value::from_i64(v, self.overflow) {
+  		Some(x) => { self.output = x; Ok(()) },
+  		None => Err(Error::Message(format!("integer {} is out of the script number range", v))),
+  	}
   }
 
-  fn serialize_u64(self, _v: u64) -> Result<()> {
-  	Err(Error::UnsupportedType)
+  fn serialize_u64(self, v: u64) -> Result<()> {
+  	match Value::from_u64(v, self.overflow) {
+  		Some(x) => { self.output = x; Ok(()) },
+  		None => Err(Error::Message(format!("integer {} is out of the script number range", v))),
+  	}
   }
 
   // Float values.
@@ -286,7 +350,9 @@ impl<'a> ser::Serializer for &'a mut Serializer {
   	-> Result<()> where T: ?Sized + Serialize
   {
   	// `{ "N": u8 }`
-  	self.output.set_item(to_value(variant)?, to_value(value)?);
+  	let mut result = Value::map();
+  	result.set_item(self.nested(variant)?, self.nested(value)?);
+  	self.output = result;
   	Ok(())
   }
 
@@ -295,7 +361,7 @@ impl<'a> ser::Serializer for &'a mut Serializer {
   	 -> Result<Self::SerializeTupleVariant>
   {
   	// `{ "T": [u8, u8] }`
-  	let left = to_value(value)?;
+  	let left = self.nested(value)?;
   	Ok(SeqSerializer::with_outer(self, left, Value::array(0)))
   }
 
@@ -304,14 +370,19 @@ impl<'a> ser::Serializer for &'a mut Serializer {
   	 -> Result<Self::SerializeStructVariant>
   {
   	// `{ "S": {r: u8, g: u8, b: u8} }`
-  	let left = to_value(value)?;
+  	let left = self.nested(value)?;
   	Ok(SeqSerializer::with_outer(self, left, Value::map()))
   }
 
   // New-type struct, like `struct Celcius(u32)`.
-  fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<()>
+  fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> Result<()>
   	where T: ?Sized + Serialize
   {
+  	if let Some(make) = special(name) {
+  		let parts = Serializer::parts(value)?;
+  		self.output = make(&parts).ok_or(Error::UnsupportedType)?;
+  		return Ok(());
+  	}
   	// Serialize the inner itself.
   	value.serialize(self)
   }
@@ -342,4 +413,8 @@ impl<'a> ser::Serializer for &'a mut Serializer {
   	self.serialize_map(Some(len))
   }
 
+  fn is_human_readable(&self) -> bool {
+  	self.readable
+  }
+
 }
@@ -0,0 +1,69 @@
+extern crate sciter;
+extern crate sciter_serde;
+
+#[macro_use]
+extern crate serde_derive;
+extern crate serde;
+extern crate serde_bytes;
+
+use std::borrow::Cow;
+
+use serde_bytes::{ByteBuf, Bytes};
+
+use sciter::Value;
+use sciter_serde::from_value;
+
+
+#[test]
+fn borrowed_bytes() {
+	sciter::fake::install();
+
+	// the input itself is borrowed
+	let v = Value::from(b"\x01\x02".as_ref());
+	let bytes: &Bytes = from_value(&v).unwrap();
+	assert_eq!(bytes.as_ref(), b"\x01\x02");
+	assert_eq!(bytes.as_ptr(), v.as_bytes().unwrap().as_ptr());
+
+	// so is the input wrapped in a newtype struct or an option
+	#[derive(Deserialize)]
+	struct Body<'a>(#[serde(borrow, with = "serde_bytes")] &'a [u8]);
+
+	let body: Body = from_value(&v).unwrap();
+	assert_eq!(body.0.as_ptr(), v.as_bytes().unwrap().as_ptr());
+
+	let bytes: Option<&Bytes> = from_value(&v).unwrap();
+	assert_eq!(bytes.unwrap().as_ptr(), v.as_bytes().unwrap().as_ptr());
+
+	#[derive(Deserialize)]
+	struct Packet<'a> {
+		header: ByteBuf,
+		#[serde(borrow, with = "serde_bytes")]
+		body: Cow<'a, [u8]>,
+		name: Cow<'a, str>,
+	}
+
+	let mut v = Value::map();
+	v.set_item("header", Value::from(b"\x01\x02".as_ref()));
+	v.set_item("body", Value::from(b"data".as_ref()));
+	v.set_item("name", "packet");
+
+	// nested items are copied
+	let packet: Packet = from_value(&v).unwrap();
+	assert_eq!(packet.header.as_ref(), b"\x01\x02");
+	assert!(matches!(packet.body, Cow::Owned(_)));
+	assert_eq!(packet.body.as_ref(), b"data");
+	assert!(matches!(packet.name, Cow::Owned(_)));
+	assert_eq!(packet.name, "packet");
+
+	#[derive(Deserialize)]
+	#[allow(dead_code)]
+	struct Header<'a> {
+		#[serde(with = "serde_bytes")]
+		header: &'a [u8],
+	}
+	assert!(from_value::<Header>(&v).is_err());
+
+	// strings are stored as UTF-16
+	assert!(from_value::<&str>(&Value::from("text")).is_err());
+	assert!(matches!(from_value::<Cow<str>>(&Value::from("text")), Ok(Cow::Owned(_))));
+}
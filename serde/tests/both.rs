@@ -16,6 +16,7 @@ use sciter_serde::{from_value, to_value};
 fn the_same<V>(actual: V, expr: &'static str)
 	where V: serde::Serialize + serde::de::DeserializeOwned + PartialEq + std::fmt::Debug + 'static
 {
+	sciter::fake::install();
 	let sv = to_value(&actual).expect(&format!("to_value({})", expr));
 	let dv = from_value(&sv).expect(&format!("from_value({})", expr));
 	let decoded = dv;
@@ -36,18 +37,18 @@ fn basic_types() {
 	the_same!(7i8);
 	the_same!(7i16);
 	the_same!(7i32);
-	// the_same!(7i64); there are no 64-bit integers in Sciter, only floats.
+	the_same!(7i64);
 
 	the_same!(7u8);
 	the_same!(7u16);
 	the_same!(7u32);
-	// the_same!(7u64); ditto
+	the_same!(7u64);
 
 	the_same!(7f32);
 	the_same!(7f64);
 
 	the_same!(-7i32);
-	// the_same!(-7isize);
+	the_same!(-7isize);
 
 
 	the_same!(Box::new(7));
@@ -127,9 +128,9 @@ fn enums() {
 	}
 
 	the_same!(Test::Zero);
-	// the_same!(Test::One(7));
-	// the_same!(Test::Two(7, 7));
-	// the_same!(Test::Three { x: 1, y: 2, z: 3});
+	the_same!(Test::One(7));
+	the_same!(Test::Two(7, 7));
+	the_same!(Test::Three { x: 1, y: 2, z: 3});
 	the_same!(Test::Five);
 }
 
@@ -143,21 +144,12 @@ fn arrays() {
 }
 
 #[test]
-#[should_panic]
-fn unsupported_u64() {
-	the_same!(7u64);
-}
-
-#[test]
-#[should_panic]
-fn unsupported_i64() {
-	the_same!(-7i64);
-}
-
-#[test]
-#[should_panic]
-fn unsupported_usize() {
+fn int64() {
 	the_same!(7usize);
+	the_same!(-(1i64 << 40));
+	the_same!(i64::MIN);
+	the_same!(1i64 << 62);
+	the_same!(1u64 << 63);
 }
 
 #[test]
@@ -199,3 +191,97 @@ fn struct_variant() {
 
 	the_same!(Test::Three { x: 1, y: 2, z: 3 });
 }
+
+// serialize, check the representation and deserialize back.
+fn the_shape<V>(actual: V, json: &str)
+	where V: serde::Serialize + serde::de::DeserializeOwned + PartialEq + std::fmt::Debug + 'static
+{
+	sciter::fake::install();
+	let sv = to_value(&actual).unwrap();
+	assert_eq!(sv, sciter::Value::parse(json).unwrap(), "the_shape({:?})", actual);
+	let decoded: V = from_value(&sv).unwrap();
+	assert_eq!(actual, decoded);
+}
+
+#[test]
+fn enum_representations() {
+	#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+	struct Point {
+		x: i32,
+		y: i32,
+	}
+
+	#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+	enum External {
+		Unit,
+		One(u32),
+		Two(u32, String),
+		Three { x: u32, y: Option<bool> },
+	}
+
+	the_shape(External::Unit, r#""Unit""#);
+	the_shape(External::One(7), r#"{"One": 7}"#);
+	the_shape(External::Two(7, "b".to_string()), r#"{"Two": [7, "b"]}"#);
+	the_shape(External::Three { x: 1, y: None }, r#"{"Three": {"x": 1, "y": null}}"#);
+
+	#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+	#[serde(tag = "type")]
+	enum Internal {
+		Unit,
+		Point(Point),
+		Three { x: u32, y: Option<bool> },
+	}
+
+	the_shape(Internal::Unit, r#"{"type": "Unit"}"#);
+	the_shape(Internal::Point(Point { x: 1, y: 2 }), r#"{"type": "Point", "x": 1, "y": 2}"#);
+	the_shape(Internal::Three { x: 1, y: Some(true) }, r#"{"type": "Three", "x": 1, "y": true}"#);
+
+	#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+	#[serde(tag = "t", content = "c")]
+	enum Adjacent {
+		Unit,
+		One(u32),
+		Two(u32, String),
+		Three { x: u32, y: Option<bool> },
+	}
+
+	the_shape(Adjacent::Unit, r#"{"t": "Unit"}"#);
+	the_shape(Adjacent::One(7), r#"{"t": "One", "c": 7}"#);
+	the_shape(Adjacent::Two(7, "b".to_string()), r#"{"t": "Two", "c": [7, "b"]}"#);
+	the_shape(Adjacent::Three { x: 1, y: None }, r#"{"t": "Three", "c": {"x": 1, "y": null}}"#);
+
+	#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+	#[serde(untagged)]
+	enum Untagged {
+		Unit,
+		Number(i64),
+		Text(String),
+		Pair(u32, String),
+		Point(Point),
+		List(Vec<Untagged>),
+	}
+
+	the_shape(Untagged::Unit, "null");
+	the_shape(Untagged::Number(-7), "-7");
+	the_shape(Untagged::Text("a".to_string()), r#""a""#);
+	the_shape(Untagged::Pair(7, "b".to_string()), r#"[7, "b"]"#);
+	the_shape(Untagged::Point(Point { x: 1, y: 2 }), r#"{"x": 1, "y": 2}"#);
+	the_shape(Untagged::List(vec![Untagged::Number(1), Untagged::Unit]), "[1, null]");
+}
+
+#[test]
+fn enum_errors() {
+	#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+	enum Test {
+		Unit,
+		One(u32),
+	}
+
+	sciter::fake::install();
+	let parse = |json: &str| from_value::<Test>(&sciter::Value::parse(json).unwrap());
+	assert_eq!(parse(r#"{"Unit": null}"#), Ok(Test::Unit));
+	assert!(parse(r#""Two""#).is_err());
+	assert!(parse(r#"{"One": 1, "Unit": null}"#).is_err());
+	assert!(parse(r#"{}"#).is_err());
+	assert!(parse("[1]").is_err());
+}
@@ -0,0 +1,126 @@
+#[macro_use]
+extern crate sciter;
+extern crate sciter_serde;
+
+use std::io::ErrorKind;
+
+use sciter::Value;
+use sciter::value::{Currency, Length, VALUE_UNIT_TYPE_DATE};
+use sciter_serde::cbor::{self, ArrayDecoder, ArrayEncoder, ValueCodec};
+
+
+fn round_trip(v: &Value) -> Value {
+	let mut buffer = Vec::new();
+	v.encode_into(&mut buffer).unwrap();
+	Value::decode_from(buffer.as_slice()).unwrap()
+}
+
+#[test]
+fn types_work() {
+	sciter::fake::install();
+
+	let items = [
+		Value::new(),
+		Value::nothing(),
+		Value::null(),
+		Value::from(true),
+		Value::from(-7),
+		Value::from(i32::MIN),
+		Value::from(0.5),
+		Value::big_int(i64::MIN),
+		Value::currency(Currency::from_f64(19.99).unwrap()),
+		Value::from("text"),
+		Value::symbol("name"),
+		Value::error("failed"),
+		Value::from(b"\x00\xFF".as_ref()),
+		Value::date(133_000_000_000_000_000, VALUE_UNIT_TYPE_DATE::DT_HAS_DATE as u32),
+		Value::length(Length::em(1.5)),
+		Value::duration(2.5),
+		Value::angle(3.0),
+		Value::color(0x8000_FF00),
+	];
+	for v in &items {
+		let copy = round_trip(v);
+		assert_eq!(copy.full_type(), v.full_type(), "{:?}", v);
+		assert_eq!(&copy, v);
+	}
+
+	let v = vmap! {
+		"list" => varray![1, "two", Value::null()],
+		7 => vmap! { "nested" => Value::duration(1.0) },
+	};
+	assert_eq!(round_trip(&v), v);
+}
+
+#[test]
+fn standard_items_work() {
+	sciter::fake::install();
+
+	assert_eq!(cbor::to_vec(&Value::from(500)).unwrap(), b"\x19\x01\xF4");
+	assert_eq!(cbor::to_vec(&Value::from("a")).unwrap(), b"\x61a");
+	assert_eq!(cbor::to_vec(&varray![1, Value::null()]).unwrap(), b"\x82\x01\xF6");
+
+	// half and single floats, a 64-bit integer and indefinite strings
+	assert_eq!(cbor::from_slice(b"\xF9\x3E\x00").unwrap(), Value::from(1.5));
+	assert_eq!(cbor::from_slice(b"\xFA\x3F\x80\x00\x00").unwrap(), Value::from(1.0));
+	assert_eq!(cbor::from_slice(b"\x1B\x00\x00\x00\x01\x00\x00\x00\x00").unwrap(), Value::from((1i64 << 32) as f64));
+	assert_eq!(cbor::from_slice(b"\x1B\x7F\xFF\xFF\xFF\xFF\xFF\xFF\xFF").unwrap(), Value::from(i64::MAX as f64));
+	assert_eq!(cbor::from_slice(b"\x7F\x62ab\x61c\xFF").unwrap(), Value::from("abc"));
+
+	// an unknown tag
+	assert_eq!(cbor::from_slice(b"\xC1\x1A\x00\x01\x00\x00").unwrap(), Value::from(65536));
+}
+
+#[test]
+fn streaming_works() {
+	sciter::fake::install();
+
+	let mut array = ArrayEncoder::new(Vec::new()).unwrap();
+	for i in 0..100 {
+		array.push(&varray![i, Value::color(i as u32)]).unwrap();
+	}
+	assert_eq!(array.len(), 100);
+	let mut buffer = array.finish().unwrap();
+	buffer.push(0xF6);
+
+	let mut items = ArrayDecoder::new(buffer.as_slice()).unwrap();
+	for i in 0..100 {
+		assert_eq!(items.next().unwrap().unwrap(), varray![i, Value::color(i as u32)]);
+	}
+	assert!(items.next().is_none());
+	assert_eq!(items.into_inner(), b"\xF6");
+
+	// the whole array at once
+	let v = Value::decode_from(buffer.as_slice()).unwrap();
+	assert_eq!(v.len(), 100);
+
+	let definite = cbor::to_vec(&varray![1, 2]).unwrap();
+	let items: Vec<_> = ArrayDecoder::new(definite.as_slice()).unwrap().map(Result::unwrap).collect();
+	assert_eq!(items, [Value::from(1), Value::from(2)]);
+}
+
+#[test]
+fn errors_work() {
+	sciter::fake::install();
+
+	let f = Value::from(|_: &[Value]| Value::null());
+	assert_eq!(cbor::to_vec(&varray![f]).unwrap_err().kind(), ErrorKind::InvalidInput);
+
+	assert_eq!(cbor::from_slice(b"\x82\x01").unwrap_err().kind(), ErrorKind::UnexpectedEof);
+	assert_eq!(cbor::from_slice(b"\x63ab").unwrap_err().kind(), ErrorKind::UnexpectedEof);
+	assert_eq!(cbor::from_slice(b"\x61\xFF").unwrap_err().kind(), ErrorKind::InvalidData);
+	assert_eq!(cbor::from_slice(b"\x01\x02").unwrap_err().kind(), ErrorKind::InvalidData);
+	assert_eq!(cbor::from_slice(b"\xFF").unwrap_err().kind(), ErrorKind::InvalidData);
+	assert_eq!(cbor::from_slice(&[0x81; 1000]).unwrap_err().kind(), ErrorKind::InvalidData);
+
+	// nested tags: unknown ones, `nothing` and the ones reading a float
+	let mut nested = b"\xD8\xC8".repeat(2_000_000);
+	nested.push(0x01);
+	assert_eq!(cbor::from_slice(&nested).unwrap_err().kind(), ErrorKind::InvalidData);
+	assert_eq!(cbor::from_slice(&b"\xDA\x53\x43\x00\x01".repeat(100_000)).unwrap_err().kind(), ErrorKind::InvalidData);
+	assert_eq!(cbor::from_slice(&b"\xDA\x53\x43\x00\x07".repeat(100_000)).unwrap_err().kind(), ErrorKind::InvalidData);
+	let mut shallow = b"\xD8\xC8".repeat(100);
+	shallow.push(0x01);
+	assert_eq!(cbor::from_slice(&shallow).unwrap(), Value::from(1));
+	assert_eq!(ArrayDecoder::new(b"\x01".as_ref()).unwrap_err().kind(), ErrorKind::InvalidData);
+}
@@ -0,0 +1,66 @@
+extern crate sciter;
+extern crate sciter_serde;
+
+#[macro_use]
+extern crate serde_derive;
+extern crate serde;
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use sciter::Value;
+use sciter::value::{IntOverflow, VALUE_UNIT_TYPE_DATE};
+use sciter_serde::{from_value, to_value, to_value_with};
+
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Event {
+	name: String,
+	#[serde(with = "sciter_serde::date")]
+	at: SystemTime,
+}
+
+#[test]
+fn dates_work() {
+	sciter::fake::install();
+
+	let at = UNIX_EPOCH + Duration::from_millis(1_600_000_000_123);
+	let event = Event { name: "release".to_owned(), at };
+
+	let v = to_value(&event).unwrap();
+	assert_eq!(v.get_item("at"), Value::from(at));
+	assert!(v.get_item("at").has_date_flag(VALUE_UNIT_TYPE_DATE::DT_UTC));
+	assert_eq!(from_value::<Event>(&v), Ok(event));
+
+	// ticks do not fit into a script number, but that does not matter for dates
+	let before = Event { name: "past".to_owned(), at: UNIX_EPOCH - Duration::from_secs(86400) };
+	let v = to_value_with(&before, IntOverflow::Error).unwrap();
+	assert_eq!(v.get_item("at").to_date(), Some(-864_000_000_000));
+
+	let mut v = Value::map();
+	v.set_item("name", "wrong");
+	v.set_item("at", "2020-01-01");
+	assert!(from_value::<Event>(&v).is_err());
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn chrono_works() {
+	extern crate chrono;
+	sciter::fake::install();
+
+	#[derive(Serialize, Deserialize, Debug, PartialEq)]
+	struct Day {
+		#[serde(with = "sciter_serde::date")]
+		date: chrono::NaiveDate,
+		#[serde(with = "sciter_serde::date")]
+		time: chrono::DateTime<chrono::Utc>,
+	}
+
+	let day = Day {
+		date: chrono::NaiveDate::from_ymd_opt(2020, 2, 29).unwrap(),
+		time: chrono::DateTime::from_timestamp(1_582_934_400, 500).unwrap(),
+	};
+	let v = to_value(&day).unwrap();
+	assert!(v.get_item("date").has_date_flag(VALUE_UNIT_TYPE_DATE::DT_HAS_DATE));
+	assert_eq!(from_value::<Day>(&v), Ok(day));
+}
@@ -14,6 +14,7 @@ use sciter_serde::{from_value, to_value};
 
 #[test]
 fn basic_types() {
+	sciter::fake::install();
 	// bool
 	let v: bool = from_value(&Value::from(true)).unwrap();
 	assert_eq!(v, true);
@@ -44,6 +45,7 @@ fn basic_types() {
 
 #[test]
 fn strings() {
+	sciter::fake::install();
 	let v: char = from_value(&Value::from("7")).unwrap();
 	assert_eq!(v, '7');
 
@@ -57,6 +59,7 @@ fn strings() {
 
 #[test]
 fn arrays() {
+	sciter::fake::install();
 	let it = [1,2,3].iter();
 	let v: Value = it.cloned().collect();
 	let v: Vec<i32> = from_value(&v).unwrap();
@@ -65,6 +68,7 @@ fn arrays() {
 
 #[test]
 fn structs() {
+	sciter::fake::install();
 	#[derive(Serialize, Deserialize, PartialEq, Debug)]
 	struct Test {
 		int: u32,
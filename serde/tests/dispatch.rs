@@ -0,0 +1,111 @@
+#[macro_use]
+extern crate sciter;
+#[macro_use]
+extern crate sciter_serde;
+
+#[macro_use]
+extern crate serde_derive;
+extern crate serde;
+
+use sciter::{EventHandler, Value};
+
+
+#[derive(Deserialize)]
+struct Query {
+	name: String,
+	limit: Option<usize>,
+}
+
+#[derive(Serialize)]
+struct Found {
+	items: Vec<String>,
+}
+
+#[derive(Default)]
+struct Handler {
+	calls: usize,
+}
+
+impl Handler {
+	fn find(&mut self, query: Query) -> Result<Found, String> {
+		self.calls += 1;
+		if query.name.is_empty() {
+			return Err("empty query".to_owned());
+		}
+		Ok(Found { items: vec![query.name; query.limit.unwrap_or(1)] })
+	}
+
+	fn count(&self, items: Vec<i32>, sum: bool) -> i32 {
+		if sum { items.iter().sum() } else { items.len() as i32 }
+	}
+
+	fn reset(&mut self) {
+		self.calls = 0;
+	}
+
+	fn pair(&self, bytes: &[u8]) -> (usize, Option<u8>) {
+		(bytes.len(), bytes.first().cloned())
+	}
+}
+
+impl EventHandler for Handler {
+	dispatch_serde_call! {
+		fn find(Query);
+		fn count(Vec<i32>, bool);
+		fn reset();
+		fn pair(&[u8]);
+	}
+}
+
+fn call(handler: &mut Handler, name: &str, args: &[Value]) -> Option<Value> {
+	handler.dispatch_script_call(::std::ptr::null_mut(), name, args)
+}
+
+
+#[test]
+fn calls_work() {
+	sciter::fake::install();
+	let mut handler = Handler::default();
+
+	let rv = call(&mut handler, "find", &make_args!(vmap! { "name" => "a", "limit" => 2 })).unwrap();
+	assert_eq!(rv, vmap! { "items" => varray!["a", "a"] });
+
+	let rv = call(&mut handler, "count", &make_args!(varray![1, 2, 3], true)).unwrap();
+	assert_eq!(rv, Value::from(6));
+
+	let rv = call(&mut handler, "pair", &make_args!(b"\x07\x08".as_ref())).unwrap();
+	assert_eq!(rv, varray![2, 7]);
+
+	assert_eq!(handler.calls, 1);
+	assert_eq!(call(&mut handler, "reset", &[]), Some(Value::null()));
+	assert_eq!(handler.calls, 0);
+
+	assert_eq!(call(&mut handler, "unknown", &[]), None);
+}
+
+#[test]
+fn errors_work() {
+	sciter::fake::install();
+	let mut handler = Handler::default();
+
+	// returned error
+	let rv = call(&mut handler, "find", &make_args!(vmap! { "name" => "" })).unwrap();
+	assert!(rv.is_error_string());
+	assert_eq!(rv.as_string(), Some("empty query".to_owned()));
+
+	// invalid arguments
+	let rv = call(&mut handler, "count", &make_args!(1)).unwrap();
+	assert_eq!(rv.as_string(), Some("count error: 1 of 2 arguments provided.".to_owned()));
+
+	let rv = call(&mut handler, "count", &make_args!(varray![1, "2"], true)).unwrap();
+	assert!(rv.is_error_string());
+	let message = rv.as_string().unwrap();
+	assert!(message.starts_with("count error: invalid type of 0 argument (Vec<i32> expected): "), "{}", message);
+	assert!(message.ends_with(" at [1]."), "{}", message);
+
+	let rv = call(&mut handler, "find", &make_args!(vmap! { "limit" => 1 })).unwrap();
+	assert!(rv.as_string().unwrap().contains("missing field `name`"));
+
+	// only the first call reached the handler
+	assert_eq!(handler.calls, 1);
+}
@@ -0,0 +1,57 @@
+extern crate sciter;
+extern crate sciter_serde;
+
+#[macro_use]
+extern crate serde_derive;
+extern crate serde;
+
+use sciter::Value;
+use sciter_serde::from_value;
+
+
+#[test]
+fn paths_work() {
+	sciter::fake::install();
+
+	#[derive(Deserialize, Debug)]
+	#[allow(dead_code)]
+	struct Window {
+		title: String,
+	}
+
+	#[derive(Deserialize, Debug)]
+	#[allow(dead_code)]
+	struct Settings {
+		windows: Vec<Window>,
+		#[serde(default)]
+		options: ::std::collections::BTreeMap<i32, bool>,
+	}
+
+	#[derive(Deserialize, Debug)]
+	#[allow(dead_code)]
+	struct Config {
+		settings: Settings,
+	}
+
+	let v = Value::parse(r#"{ "settings": { "windows": [{ "title": "a" }, { "title": "b" }, { "title": 3 }] } }"#).unwrap();
+	let e = from_value::<Config>(&v).unwrap_err();
+	assert_eq!(e.path(), "settings.windows[2].title");
+	assert!(matches!(e.inner(), sciter_serde::Error::ExpectedType(_) | sciter_serde::Error::Message(_)));
+	assert!(e.to_string().ends_with(" at settings.windows[2].title"), "{}", e);
+
+	let v = Value::parse(r#"{ "settings": { "windows": [{}] } }"#).unwrap();
+	let e = from_value::<Config>(&v).unwrap_err();
+	assert_eq!(e.path(), "settings.windows[0]");
+	assert_eq!(e.inner(), &sciter_serde::Error::Message("missing field `title`".to_owned()));
+
+	let mut options = Value::map();
+	options.set_item(1, "yes");
+	let mut settings = Value::map();
+	settings.set_item("windows", Value::array(0));
+	settings.set_item("options", options);
+	let mut v = Value::map();
+	v.set_item("settings", settings);
+	assert_eq!(from_value::<Config>(&v).unwrap_err().path(), "settings.options[1]");
+
+	assert_eq!(from_value::<i32>(&Value::from("1")).unwrap_err().path(), "");
+}
@@ -0,0 +1,52 @@
+extern crate sciter;
+extern crate sciter_serde;
+
+use sciter::Value;
+use sciter::value::IntOverflow;
+use sciter_serde::{from_value, from_value_with, to_value, to_value_with};
+
+const BIG: i64 = 9_007_199_254_740_993;
+
+
+#[test]
+fn big_integers_are_lossless() {
+	sciter::fake::install();
+
+	let v = to_value_with(&BIG, IntOverflow::BigInt).unwrap();
+	assert!(v.is_currency());
+	assert_eq!(v, Value::big_int(BIG));
+	assert_eq!(from_value_with::<i64>(&v, IntOverflow::BigInt), Ok(BIG));
+	assert_eq!(from_value_with::<u64>(&v, IntOverflow::BigInt), Ok(BIG as u64));
+
+	let v = to_value_with(&vec![1, BIG, -BIG], IntOverflow::BigInt).unwrap();
+	assert_eq!(from_value_with::<Vec<i64>>(&v, IntOverflow::BigInt), Ok(vec![1, BIG, -BIG]));
+
+	assert!(to_value_with(&u64::MAX, IntOverflow::BigInt).is_err());
+	assert!(from_value_with::<u64>(&Value::big_int(-1), IntOverflow::BigInt).is_err());
+
+	// big integers are currency values for the other policies
+	assert!(from_value::<i64>(&Value::big_int(BIG)).is_err());
+	assert!(from_value_with::<i64>(&Value::big_int(BIG), IntOverflow::Error).is_err());
+	assert_eq!(from_value::<f64>(&Value::big_int(25_000)), Ok(2.5));
+}
+
+#[test]
+fn overflow_policies_work() {
+	sciter::fake::install();
+
+	let v = to_value(&vec![BIG]).unwrap();
+	assert!(v.get(0).is_float());
+	assert!(from_value_with::<Vec<i64>>(&v, IntOverflow::BigInt).is_err());
+	assert!(from_value_with::<Vec<i64>>(&v, IntOverflow::Error).is_err());
+	assert_eq!(from_value::<Vec<i64>>(&v), Ok(vec![BIG - 1]));
+
+	let v = to_value(&u64::MAX).unwrap();
+	assert_eq!(v.to_float(), Some(u64::MAX as f64));
+
+	assert!(to_value_with(&BIG, IntOverflow::Error).is_err());
+	assert_eq!(to_value_with(&(1i64 << 40), IntOverflow::Error), Ok(Value::from((1i64 << 40) as f64)));
+
+	// exact floats are fine for any policy
+	assert_eq!(from_value::<i64>(&Value::from(3e9)), Ok(3_000_000_000));
+	assert!(from_value::<i64>(&Value::from(1.5)).is_err());
+}
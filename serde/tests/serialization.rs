@@ -14,6 +14,7 @@ use sciter_serde::{to_value};
 
 #[test]
 fn basic_types() {
+	sciter::fake::install();
 	// bool
 	let v = to_value(&true).unwrap();
 	assert!(v.is_bool());
@@ -46,9 +47,11 @@ fn basic_types() {
 	assert!(v.is_float());
 
 	// 64-bit
-	// let v = to_value(&7u64).unwrap();
-	// assert!(v.is_float());
-	// assert_eq!(v, Value::from(7.0));
+	let v = to_value(&7u64).unwrap();
+	assert_eq!(v, Value::from(7));
+
+	let v = to_value(&(1i64 << 40)).unwrap();
+	assert!(v.is_float());
 
 	// Option
 	// let v = to_value(&Some(7)).unwrap();
@@ -60,6 +63,7 @@ fn basic_types() {
 
 #[test]
 fn strings() {
+	sciter::fake::install();
 	// strings
 	let v = to_value(&'h').unwrap();
 	assert!(v.is_string());
@@ -84,6 +88,7 @@ fn strings() {
 
 #[test]
 fn arrays() {
+	sciter::fake::install();
 	let a = [1,2,3];
 	let v = to_value(&a).unwrap();
 	assert!(v.is_array());
@@ -97,6 +102,7 @@ fn arrays() {
 
 #[test]
 fn structs() {
+	sciter::fake::install();
 
 	#[derive(Serialize)]
 	struct Test {
@@ -0,0 +1,121 @@
+extern crate sciter;
+extern crate sciter_serde;
+
+#[macro_use]
+extern crate serde_derive;
+extern crate serde;
+#[macro_use]
+extern crate serde_json;
+
+use std::time::Duration;
+
+use sciter::Value;
+use sciter::value::{Currency, Length, LengthUnit};
+use sciter_serde::{from_value, to_value};
+use sciter_serde::angle::Angle;
+use sciter_serde::color::Color;
+
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Item {
+	#[serde(with = "sciter_serde::length")]
+	width: Length,
+	#[serde(with = "sciter_serde::currency")]
+	price: Currency,
+}
+
+#[test]
+fn units_work() {
+	sciter::fake::install();
+
+	let item = Item { width: Length::new(1.5, LengthUnit::Em), price: Currency::from_raw(12_345_678_901_234_567) };
+	let v = to_value(&item).unwrap();
+	assert_eq!(v.get_item("width"), Value::length(Length::em(1.5)));
+	assert_eq!(v.get_item("price"), Value::currency(Currency::from_raw(12_345_678_901_234_567)));
+	assert_eq!(from_value::<Item>(&v), Ok(item));
+
+	let mut v = Value::map();
+	v.set_item("width", 10);
+	v.set_item("price", Value::currency(Currency::from(1)));
+	assert!(from_value::<Item>(&v).is_err());
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Style {
+	#[serde(with = "sciter_serde::color")]
+	color: u32,
+	#[serde(with = "sciter_serde::duration")]
+	delay: Duration,
+	#[serde(with = "sciter_serde::angle")]
+	rotation: f64,
+	#[serde(with = "sciter_serde::length")]
+	margin: Length,
+	stops: Vec<Color>,
+	frames: Vec<sciter_serde::duration::Duration>,
+	skew: Option<Angle>,
+}
+
+fn style() -> Style {
+	Style {
+		color: 0xFF00_00FF,
+		delay: Duration::from_millis(250),
+		rotation: 1.5,
+		margin: Length::new(2.0, LengthUnit::Springs),
+		stops: vec![Color(0x8000_FF00), Color(0xFFFF_0000)],
+		frames: vec![sciter_serde::duration::Duration(Duration::from_secs(1))],
+		skew: Some(Angle(0.0)),
+	}
+}
+
+#[test]
+fn styles_work() {
+	sciter::fake::install();
+
+	let v = to_value(&style()).unwrap();
+	assert_eq!(v.get_item("color"), Value::color(0xFF00_00FF));
+	assert_eq!(v.get_item("delay"), Value::duration(0.25));
+	assert_eq!(v.get_item("rotation"), Value::angle(1.5));
+	assert_eq!(v.get_item("margin"), Value::length(Length::new(2.0, LengthUnit::Springs)));
+	assert_eq!(v.get_item("stops").get(0), Value::color(0x8000_FF00));
+	assert_eq!(v.get_item("frames").get(0), Value::duration(1.0));
+	assert_eq!(v.get_item("skew"), Value::angle(0.0));
+	assert_eq!(from_value::<Style>(&v), Ok(style()));
+
+	let mut v = v;
+	v.set_item("color", 0x00FF);
+	assert!(from_value::<Style>(&v).is_err());
+}
+
+#[test]
+fn other_formats_work() {
+	sciter::fake::install();
+
+	let json = serde_json::to_value(style()).unwrap();
+	assert_eq!(json, json!({
+		"color": "#ff0000",
+		"delay": "0.25s",
+		"rotation": "1.5rad",
+		"margin": "2%%",
+		"stops": ["#00ff0080", "#0000ff"],
+		"frames": ["1s"],
+		"skew": "0rad",
+	}));
+	assert_eq!(serde_json::from_value::<Style>(json).unwrap(), style());
+
+	let item = Item { width: Length::em(1.5), price: Currency::from_raw(10_000) };
+	assert_eq!(serde_json::to_string(&item).unwrap(), r#"{"width":"1.5em","price":10000}"#);
+
+	let style: Style = serde_json::from_str(r##"{
+		"color": "#f008", "delay": "250ms", "rotation": "90deg", "margin": "1e1dip",
+		"stops": [], "frames": [], "skew": null
+	}"##).unwrap();
+	assert_eq!(style.color, 0x8800_00FF);
+	assert_eq!(style.delay, Duration::from_millis(250));
+	assert_eq!(style.rotation, 90f64.to_radians());
+	assert_eq!(style.margin, Length::dip(10.0));
+
+	assert!(serde_json::from_str::<Item>(r#"{"width":"1.5","price":1}"#).is_err());
+	assert!(serde_json::from_str::<Color>(r##""#ff00""##).is_ok());
+	assert!(serde_json::from_str::<Color>(r##""#ff00f""##).is_err());
+	assert!(serde_json::from_str::<sciter_serde::duration::Duration>(r#""-1s""#).is_err());
+}
@@ -204,6 +204,11 @@ pub enum EVENT_GROUPS
 	/// All of them.
 	HANDLE_ALL                   = 0xFFFF,
 
+	/// Default subscription of the native event handlers,
+	/// see [`default_events()`](../dom/event/fn.default_events.html).
+	#[doc(hidden)]
+	HANDLE_DEFAULT               = 0x0700,
+
 	/// Special value for getting subscription flags.
 	SUBSCRIPTIONS_REQUEST        = -1,
 }
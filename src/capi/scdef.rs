@@ -302,6 +302,23 @@ pub struct SCN_INVALIDATE_RECT
 	pub invalid_rect: RECT,
 }
 
+#[repr(C)]
+/// This notification is sent to the UI thread in response to `SciterPostCallback`.
+pub struct SCN_POSTED_NOTIFICATION
+{
+	/// `SC_POSTED_NOTIFICATION` here.
+  pub code: UINT,
+  /// `HWINDOW` of the window this callback was attached to.
+  pub hwnd: HWINDOW,
+
+	/// [in] `wparam` passed to `SciterPostCallback`.
+	pub wparam: UINT_PTR,
+	/// [in] `lparam` passed to `SciterPostCallback`.
+	pub lparam: UINT_PTR,
+	/// [out] result returned to the `SciterPostCallback` caller if it waits for it.
+	pub lreturn: UINT_PTR,
+}
+
 #[repr(C)]
 pub struct SCITER_CALLBACK_NOTIFICATION
 {
@@ -8,7 +8,7 @@ MAKE_HANDLE!(#[doc = "Element native handle."] HELEMENT, _HELEMENT);
 MAKE_HANDLE!(#[doc = "Node native handle."] HNODE, _HNODE);
 
 #[repr(C)]
-#[derive(Debug, PartialOrd, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialOrd, PartialEq)]
 /// Type of the result value for Sciter DOM functions.
 pub enum SCDOM_RESULT {
 	/// Function completed successfully.
@@ -34,7 +34,7 @@ impl VALUE {
 }
 
 #[repr(C)]
-#[derive(Debug, PartialOrd, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialOrd, PartialEq)]
 pub enum VALUE_RESULT
 {
   OK_TRUE = -1,
@@ -78,8 +78,7 @@ pub enum VALUE_TYPE {
 	T_FLOAT,
 	/// Data is a Sciter internal string, unit is [`VALUE_UNIT_TYPE_STRING`].
 	T_STRING,
-	/// Data is `FILETIME` (64-bit value in 100ns since the unix epoch).
-	/// No unit is stored but `is_utc` boolean is used during creation.
+	/// Data is `FILETIME` (64-bit value in 100ns since the unix epoch), unit is [`VALUE_UNIT_TYPE_DATE`] flags.
 	T_DATE,
 	/// Data is a 64-bit number, no units.
 	T_CURRENCY,
@@ -146,7 +145,7 @@ pub enum VALUE_UNIT_TYPE_STRING
 
 /// Length sub-types.
 #[repr(C)]
-#[derive(Debug, PartialOrd, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialOrd, PartialEq)]
 pub enum VALUE_UNIT_TYPE_LENGTH
 {
 	EM = 1, //height of the element's font.
@@ -163,6 +162,41 @@ pub enum VALUE_UNIT_TYPE_LENGTH
 	URL   = 16,  // url in string
 }
 
+/// Date flags, can be OR'ed as `DT_HAS_DATE | DT_UTC` into a `UINT` mask.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialOrd, PartialEq)]
+pub enum VALUE_UNIT_TYPE_DATE
+{
+	/// The date contains the date portion.
+	DT_HAS_DATE = 0x01,
+	/// The date contains the time portion `HH:MM`.
+	DT_HAS_TIME = 0x02,
+	/// The date contains the time with seconds `HH:MM:SS`.
+	DT_HAS_SECONDS = 0x04,
+	/// The date is known to be UTC, otherwise it is a local date/time.
+	DT_UTC = 0x10,
+}
+
+impl From<VALUE_UNIT_TYPE_DATE> for UINT {
+	fn from(flag: VALUE_UNIT_TYPE_DATE) -> UINT {
+		flag as UINT
+	}
+}
+
+impl ::std::ops::BitOr for VALUE_UNIT_TYPE_DATE {
+	type Output = UINT;
+	fn bitor(self, rhs: VALUE_UNIT_TYPE_DATE) -> UINT {
+		self as UINT | rhs as UINT
+	}
+}
+
+impl ::std::ops::BitOr<VALUE_UNIT_TYPE_DATE> for UINT {
+	type Output = UINT;
+	fn bitor(self, rhs: VALUE_UNIT_TYPE_DATE) -> UINT {
+		self | rhs as UINT
+	}
+}
+
 /// Array sub-types.
 #[repr(C)]
 #[derive(Debug, PartialOrd, PartialEq)]
@@ -0,0 +1,167 @@
+/*! Running code on the UI thread of a Sciter window.
+
+Sciter engine, its DOM and script values must be used from the UI thread only.
+A [`Dispatcher`](struct.Dispatcher.html) is a cloneable and `Send` handle of a window
+which can be moved to background threads to post closures to the UI thread
+via [`SciterPostCallback`](../capi/scapi/struct.ISciterAPI.html):
+
+```rust,no_run
+# use std::time::Duration;
+use sciter::dom::Element;
+
+let frame = sciter::Window::new();
+let ui = frame.dispatcher();
+
+std::thread::spawn(move || {
+  let report = String::from("done"); // a long operation
+
+  // fire and forget
+  let window = ui.clone();
+  ui.post(move || {
+    let mut root = Element::from_window(window.get_hwnd()).unwrap();
+    root.set_attribute("report", &report).ok();
+  }).ok();
+
+  // wait for the result
+  let window = ui.clone();
+  let title = ui.call_timeout(Duration::from_secs(1), move || {
+    let root = Element::from_window(window.get_hwnd()).unwrap();
+    root.eval_script("document.title").ok()
+  });
+  println!("{:?}", title);
+});
+```
+
+The posted closures are executed by the host callback of the window,
+so the window must be created via [`sciter::Window`](../window/struct.Window.html)
+or attached via [`sciter::Host`](../host/struct.Host.html).
+
+*/
+
+use std::sync::mpsc;
+use std::thread::{self, ThreadId};
+use std::time::Duration;
+
+use ::{_API};
+use capi::sctypes::{HWINDOW, UINT_PTR};
+use dom::Element;
+use error::{Code, Error, Result};
+
+
+/// `lparam` of the posted closures, to tell them from other posted notifications.
+const TASK_TAG: UINT_PTR = 0x5C17_7A5C;
+
+type Task = Box<dyn FnOnce() + Send>;
+
+
+/// A handle to the UI thread of a Sciter window.
+///
+/// It can be cloned and sent to other threads.
+#[derive(Debug, Clone)]
+pub struct Dispatcher {
+	hwnd: usize,
+	thread: ThreadId,
+}
+
+impl Dispatcher {
+	/// Create a dispatcher for the window.
+	///
+	/// Must be called on the UI thread of the window.
+	pub fn new(hwnd: HWINDOW) -> Dispatcher {
+		Dispatcher {
+			hwnd: hwnd as usize,
+			thread: thread::current().id(),
+		}
+	}
+
+	/// Create a dispatcher for the window of the element.
+	///
+	/// Must be called on the UI thread of the window.
+	pub fn for_element(element: &Element) -> Dispatcher {
+		Dispatcher::new(element.get_hwnd(true))
+	}
+
+	/// Native window handle.
+	pub fn get_hwnd(&self) -> HWINDOW {
+		self.hwnd as HWINDOW
+	}
+
+	/// Whether the current thread is the UI thread of the window.
+	pub fn is_ui_thread(&self) -> bool {
+		thread::current().id() == self.thread
+	}
+
+	/// Post the closure to run on the UI thread and return immediately.
+	///
+	/// Fails if the engine does not accept the post (e.g. the window is already destroyed),
+	/// the closure is dropped then.
+	/// The closure is never executed if the window is destroyed before.
+	pub fn post<F>(&self, f: F) -> Result<()>
+		where F: FnOnce() + Send + 'static
+	{
+		let task: Box<Task> = Box::new(Box::new(f));
+		let ptr = Box::into_raw(task);	// dropped in `run_posted`
+		let ok = (_API.SciterPostCallback)(self.get_hwnd(), ptr as UINT_PTR, TASK_TAG, 0);
+		if ok == 0 {
+			// not posted, so it is still ours
+			drop(unsafe { Box::from_raw(ptr) });
+			return Err(Error::new(Code::Failed).with_call("Dispatcher::post").with_message("the task was not accepted"));
+		}
+		Ok(())
+	}
+
+	/// Run the closure on the UI thread and wait for its result.
+	///
+	/// On the UI thread itself the closure is executed immediately.
+	///
+	/// Fails if the closure can not be posted or was dropped without execution.
+	pub fn call<F, R>(&self, f: F) -> Result<R>
+		where F: FnOnce() -> R + Send + 'static, R: Send + 'static
+	{
+		if self.is_ui_thread() {
+			return Ok(f());
+		}
+		let rx = self.send(f)?;
+		rx.recv().map_err(|_| Error::new(Code::Failed).with_call("Dispatcher::call").with_message("the task was dropped"))
+	}
+
+	/// Run the closure on the UI thread and wait for its result up to the `timeout`.
+	///
+	/// On the UI thread itself the closure is executed immediately.
+	///
+	/// If the timeout expires, the closure still can be executed later,
+	/// but its result is discarded.
+	pub fn call_timeout<F, R>(&self, timeout: Duration, f: F) -> Result<R>
+		where F: FnOnce() -> R + Send + 'static, R: Send + 'static
+	{
+		if self.is_ui_thread() {
+			return Ok(f());
+		}
+		let rx = self.send(f)?;
+		rx.recv_timeout(timeout).map_err(|e| match e {
+			mpsc::RecvTimeoutError::Timeout => Error::new(Code::Timeout).with_call("Dispatcher::call_timeout"),
+			mpsc::RecvTimeoutError::Disconnected => Error::new(Code::Failed).with_call("Dispatcher::call_timeout").with_message("the task was dropped"),
+		})
+	}
+
+	fn send<F, R>(&self, f: F) -> Result<mpsc::Receiver<R>>
+		where F: FnOnce() -> R + Send + 'static, R: Send + 'static
+	{
+		let (tx, rx) = mpsc::channel();
+		self.post(move || {
+			tx.send(f()).ok();
+		})?;
+		Ok(rx)
+	}
+}
+
+
+/// Run the closure of `SC_POSTED_NOTIFICATION` if it was posted by `Dispatcher`.
+pub(crate) fn run_posted(wparam: UINT_PTR, lparam: UINT_PTR) -> bool {
+	if lparam != TASK_TAG || wparam == 0 {
+		return false;
+	}
+	let task = unsafe { Box::from_raw(wparam as *mut Task) };
+	task();
+	true
+}
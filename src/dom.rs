@@ -161,6 +161,7 @@ if (var num = self.select("input[type=number]")) {
 use ::{_API};
 use capi::sctypes::*;
 use value::Value;
+use error::{Error, ScriptError};
 
 use capi::screquest::{REQUEST_PARAM, REQUEST_TYPE};
 use capi::scdef::RESOURCE_TYPE;
@@ -172,7 +173,7 @@ pub use dom::event::{EventHandler, EventReason};
 
 
 /// A specialized `Result` type for DOM operations.
-pub type Result<T> = ::std::result::Result<T, SCDOM_RESULT>;
+pub type Result<T> = ::std::result::Result<T, Error>;
 
 
 /// Initialize HELEMENT by nullptr.
@@ -182,26 +183,38 @@ macro_rules! HELEMENT {
 
 
 macro_rules! ok_or {
-	($rv:expr, $ok:ident) => {
+	($rv:expr, $ok:ident, $call:expr) => {
 		if $ok == SCDOM_RESULT::OK {
 			Ok($rv)
 		} else {
-			Err($ok)
+			Err(Error::from($ok).with_call($call))
 		}
 	};
 
-	// for DOM access not_handled is ok
-	// for calling function operation_failed is also ok
-	($rv:expr, $ok:ident, $skip_not_handled:expr) => {
-		if $ok == SCDOM_RESULT::OK || ($ok == $skip_not_handled) {
+	($rv:expr, $ok:ident, $call:expr, $el:expr) => {
+		if $ok == SCDOM_RESULT::OK {
 			Ok($rv)
 		} else {
-			Err($ok)
+			Err(Error::from($ok).with_call($call).with_element($el))
 		}
 	};
 }
 
 
+/// Convert the result of a script call.
+///
+/// `OPERATION_FAILED` is returned for script exceptions, but also for some successful calls,
+/// so the returned value decides.
+fn script_result(rv: Value, ok: SCDOM_RESULT, call: &'static str, e: &Element) -> ::std::result::Result<Value, ScriptError> {
+	match ok {
+		SCDOM_RESULT::OK => Ok(rv),
+		SCDOM_RESULT::OPERATION_FAILED if !rv.is_verror() => Ok(rv),
+		SCDOM_RESULT::OPERATION_FAILED => Err(ScriptError::from(rv)),
+		_ => Err(ScriptError::from(Error::from(ok).with_call(call).with_element(e))),
+	}
+}
+
+
 trait ElementVisitor {
 	fn on_element(&mut self, el: Element) -> bool;
 	fn result(&self) -> Vec<Element>;
@@ -263,15 +276,15 @@ const DOM_UNWRAP_API_VERSION: u32 = 0x0000_0007;
 ///
 /// Since 4.4.3.26, perhaps.
 impl std::convert::TryFrom<Element> for Value {
-	type Error = SCDOM_RESULT;
+	type Error = Error;
 	fn try_from(e: Element) -> Result<Value> {
 		let mut v = Value::new();
-		let ok = if crate::api_version() >= DOM_UNWRAP_API_VERSION {
-			(_API.SciterElementWrap)(v.as_mut_ptr(), e.as_ptr())
+		let (ok, call) = if crate::api_version() >= DOM_UNWRAP_API_VERSION {
+			((_API.SciterElementWrap)(v.as_mut_ptr(), e.as_ptr()), "SciterElementWrap")
 		} else {
-			(_API.SciterGetExpando)(e.as_ptr(), v.as_ptr(), true as BOOL)
+			((_API.SciterGetExpando)(e.as_ptr(), v.as_ptr(), true as BOOL), "SciterGetExpando")
 		};
-		ok_or!(v, ok)
+		ok_or!(v, ok, call, &e)
 	}
 }
 
@@ -309,7 +322,7 @@ impl Element {
 		let tag = s2u!(tag);
 		let text = 0 as LPCWSTR;
 		let ok = (_API.SciterCreateElement)(tag.as_ptr(), text, &mut e.he);
-		ok_or!(e, ok)
+		ok_or!(e, ok, "SciterCreateElement")
 	}
 
 	/// Create new element as child of `parent`.
@@ -334,7 +347,7 @@ impl Element {
 		let tag = s2u!(tag);
 		let text = s2w!(text);
 		let ok = (_API.SciterCreateElement)(tag.as_ptr(), text.as_ptr(), &mut e.he);
-		ok_or!(e, ok)
+		ok_or!(e, ok, "SciterCreateElement")
 	}
 
 	/// Create new element with specified `type`, which is useful for controls and widgets (initially disconnected).
@@ -347,7 +360,7 @@ impl Element {
 			let r = e.set_attribute("type", el_type);
 			r.map(|_| e)
 		} else {
-			Err(ok)
+			Err(Error::from(ok).with_call("SciterCreateElement"))
 		}
 	}
 
@@ -362,7 +375,7 @@ impl Element {
 	/// https://github.com/sciter-sdk/rust-sciter/issues/27
 	fn forbid_null(e: Element) -> Result<Element> {
 		if e.he.is_null() {
-			Err(SCDOM_RESULT::OK_NOT_HANDLED)
+			Err(Error::from(SCDOM_RESULT::OK_NOT_HANDLED))
 		} else {
 			Ok(e)
 		}
@@ -374,7 +387,7 @@ impl Element {
 	pub fn from_window(hwnd: HWINDOW) -> Result<Element> {
 		let mut p = HELEMENT!();
 		let ok = (_API.SciterGetRootElement)(hwnd, &mut p);
-		ok_or!(Element::from(p), ok).and_then(Element::forbid_null)
+		ok_or!(Element::from(p), ok, "SciterGetRootElement").and_then(Element::forbid_null)
 	}
 
 	/// Get focus DOM element of the Sciter document.
@@ -383,7 +396,7 @@ impl Element {
 	pub fn from_focus(hwnd: HWINDOW) -> Result<Element> {
 		let mut p = HELEMENT!();
 		let ok = (_API.SciterGetFocusElement)(hwnd, &mut p);
-		ok_or!(Element::from(p), ok).and_then(Element::forbid_null)
+		ok_or!(Element::from(p), ok, "SciterGetFocusElement").and_then(Element::forbid_null)
 	}
 
 	/// Get highlighted element.
@@ -392,7 +405,7 @@ impl Element {
 	pub fn from_highlighted(hwnd: HWINDOW) -> Result<Element> {
 		let mut p = HELEMENT!();
 		let ok = (_API.SciterGetHighlightedElement)(hwnd, &mut p);
-		ok_or!(Element::from(p), ok).and_then(Element::forbid_null)
+		ok_or!(Element::from(p), ok, "SciterGetHighlightedElement").and_then(Element::forbid_null)
 	}
 
 	/// Find DOM element of the Sciter document by coordinates.
@@ -401,7 +414,7 @@ impl Element {
 	pub fn from_point(hwnd: HWINDOW, pt: POINT) -> Result<Element> {
 		let mut p = HELEMENT!();
 		let ok = (_API.SciterFindElement)(hwnd, pt, &mut p);
-		ok_or!(Element::from(p), ok).and_then(Element::forbid_null)
+		ok_or!(Element::from(p), ok, "SciterFindElement").and_then(Element::forbid_null)
 	}
 
 	/// Get element handle by its UID.
@@ -410,7 +423,7 @@ impl Element {
 	pub fn from_uid(hwnd: HWINDOW, uid: u32) -> Result<Element> {
 		let mut p = HELEMENT!();
 		let ok = (_API.SciterGetElementByUID)(hwnd, uid, &mut p);
-		ok_or!(Element::from(p), ok).and_then(Element::forbid_null)
+		ok_or!(Element::from(p), ok, "SciterGetElementByUID").and_then(Element::forbid_null)
 	}
 
 	#[doc(hidden)]
@@ -456,7 +469,7 @@ impl Element {
 	pub fn set_text(&mut self, text: &str) -> Result<()> {
 		let (s,n) = s2wn!(text);
 		let ok = (_API.SciterSetElementText)(self.he, s.as_ptr(), n);
-		ok_or!((), ok)
+		ok_or!((), ok, "SciterSetElementText", self)
 	}
 
 	/// Get html representation of the element as utf-8 bytes.
@@ -472,7 +485,7 @@ impl Element {
 			return self.clear();
 		}
 		let ok = (_API.SciterSetElementHtml)(self.he, html.as_ptr(), html.len() as UINT, how.unwrap_or(SET_ELEMENT_HTML::SIH_REPLACE_CONTENT) as UINT);
-		ok_or!((), ok)
+		ok_or!((), ok, "SciterSetElementHtml", self)
 	}
 
 	/// Get value of the element.
@@ -485,7 +498,7 @@ impl Element {
 	/// Set value of the element.
 	pub fn set_value<T: Into<Value>>(&mut self, val: T) -> Result<()> {
 		let ok = (_API.SciterSetValue)(self.he, val.into().as_cptr());
-		ok_or!((), ok)
+		ok_or!((), ok, "SciterSetValue", self)
 	}
 
 	/// Checks if particular UI state bits are set in the element.
@@ -500,7 +513,7 @@ impl Element {
 	pub fn set_state(&mut self, set: ELEMENT_STATE_BITS, clear: Option<ELEMENT_STATE_BITS>, update: bool) -> Result<()> {
 		let clear = clear.unwrap_or(ELEMENT_STATE_BITS::STATE_NONE);
 		let ok = (_API.SciterSetElementState)(self.he, set as UINT, clear as UINT, update as BOOL);
-		ok_or!((), ok)
+		ok_or!((), ok, "SciterSetElementState", self)
 	}
 
 	/// Get `HWINDOW` of containing window.
@@ -513,27 +526,27 @@ impl Element {
 	/// Attach a native window to the element as a child.
 	pub fn attach_hwnd(&mut self, child: HWINDOW) -> Result<()> {
 		let ok = (_API.SciterAttachHwndToElement)(self.he, child);
-		ok_or!((), ok)
+		ok_or!((), ok, "SciterAttachHwndToElement", self)
 	}
 
 	/// Detach a child native window (if any) from the element.
 	pub fn detach_hwnd(&mut self) -> Result<()> {
 		let ok = (_API.SciterAttachHwndToElement)(self.he, 0 as HWINDOW);
-		ok_or!((), ok)
+		ok_or!((), ok, "SciterAttachHwndToElement", self)
 	}
 
 	/// Get bounding rectangle of the element. See the [`ELEMENT_AREAS`](enum.ELEMENT_AREAS.html) enum for `kind` flags.
 	pub fn get_location(&self, kind: u32) -> Result<RECT> {
 		let mut rc = RECT::default();
 		let ok = (_API.SciterGetElementLocation)(self.he, &mut rc as *mut _, kind as u32);
-		ok_or!(rc, ok)
+		ok_or!(rc, ok, "SciterGetElementLocation", self)
 	}
 
 	/// Request data download for this element.
 	pub fn request_data(&self, url: &str, data_type: RESOURCE_TYPE, initiator: Option<HELEMENT>) -> Result<()> {
 		let url = s2w!(url);
 		let ok = (_API.SciterRequestElementData)(self.he, url.as_ptr(), data_type as u32, initiator.unwrap_or(HELEMENT!()));
-		ok_or!((), ok)
+		ok_or!((), ok, "SciterRequestElementData", self)
 	}
 
 	/// Request HTML data download for this element.
@@ -548,7 +561,7 @@ impl Element {
 		let url = s2w!(url);
 		let no_params = ::std::ptr::null();
 		let ok = (_API.SciterHttpRequest)(self.he, url.as_ptr(), RESOURCE_TYPE::HTML as u32, REQUEST_TYPE::AsyncGet as u32, no_params, 0);
-		ok_or!((), ok)
+		ok_or!((), ok, "SciterHttpRequest", self)
 	}
 
 	/// Send an HTTP GET or POST request for the element.
@@ -583,7 +596,7 @@ impl Element {
 		}
 
 		let ok = (_API.SciterHttpRequest)(self.he, url.as_ptr(), data_type, method, call_params.as_ptr(), call_params.len() as u32);
-		ok_or!((), ok)
+		ok_or!((), ok, "SciterHttpRequest", self)
 	}
 
 	/// Sends sinking/bubbling event to the child/parent chain of the element.
@@ -592,7 +605,7 @@ impl Element {
 		let r = reason.unwrap_or(CLICK_REASON::SYNTHESIZED);
 		let s = source.unwrap_or(self.he);
 		let ok = (_API.SciterSendEvent)(self.he, code as u32, s, r as UINT_PTR, &mut handled);
-		ok_or!(handled != 0, ok)
+		ok_or!(handled != 0, ok, "SciterSendEvent", self)
 	}
 
 	/// Post asynchronously a sinking/bubbling event to the child/parent chain of the element.
@@ -600,7 +613,7 @@ impl Element {
 		let r = reason.unwrap_or(CLICK_REASON::SYNTHESIZED);
 		let s = source.unwrap_or(self.he);
 		let ok = (_API.SciterPostEvent)(self.he, code as u32, s, r as UINT_PTR);
-		ok_or!((), ok)
+		ok_or!((), ok, "SciterPostEvent", self)
 	}
 
 	/// Send or posts event to the child/parent chain of the element.
@@ -618,14 +631,14 @@ impl Element {
 			data.pack_to(&mut params.data);
 		}
 		let ok = (_API.SciterFireEvent)(&params, post as BOOL, &mut handled);
-		ok_or!(handled != 0, ok)
+		ok_or!(handled != 0, ok, "SciterFireEvent", self)
 	}
 
 	/// Send or posts event with specified params to the child/parent chain of the element.
 	pub fn fire_event_params(evt: &BEHAVIOR_EVENT_PARAMS, post: bool) -> Result<bool> {
 		let mut handled = false as BOOL;
 		let ok = (_API.SciterFireEvent)(evt, post as BOOL, &mut handled);
-		ok_or!(handled != 0, ok)
+		ok_or!(handled != 0, ok, "SciterFireEvent")
 	}
 
 	/// Broadcast a custom named event to all windows.
@@ -644,40 +657,51 @@ impl Element {
 		}
 		let mut handled = false as BOOL;
 		let ok = (_API.SciterFireEvent)(&params, post as BOOL, &mut handled);
-		ok_or!(handled != 0, ok)
+		ok_or!(handled != 0, ok, "SciterFireEvent", self)
 	}
 
 
 	/// Evaluate the given script in context of the element.
-	pub fn eval_script(&self, script: &str) -> Result<Value> {
+	pub fn eval_script(&self, script: &str) -> ::std::result::Result<Value, ScriptError> {
+		self.eval_impl(script, None)
+	}
+
+	/// Evaluate the given script named by the `url` in context of the element.
+	///
+	/// See [`Host::eval_script_with_url`](../host/struct.Host.html#method.eval_script_with_url).
+	pub fn eval_script_with_url(&self, script: &str, url: &str) -> ::std::result::Result<Value, ScriptError> {
+		self.eval_impl(script, Some(url))
+	}
+
+	fn eval_impl(&self, script: &str, url: Option<&str>) -> ::std::result::Result<Value, ScriptError> {
 		let mut rv = Value::new();
 		let (s,n) = s2wn!(script);
 		let ok = (_API.SciterEvalElementScript)(self.he, s.as_ptr(), n, rv.as_ptr());
-		return ok_or!(rv, ok, SCDOM_RESULT::OPERATION_FAILED);
+		return script_result(rv, ok, "SciterEvalElementScript", self).map_err(|e| e.with_source_url(url));
 	}
 
 	/// Call scripting function defined in the namespace of the element (a.k.a. global function).
 	///
 	/// You can use the [`make_args!(args...)`](../macro.make_args.html) macro which helps you
 	/// to construct script arguments from Rust types.
-	pub fn call_function(&self, name: &str, args: &[Value]) -> Result<Value> {
+	pub fn call_function(&self, name: &str, args: &[Value]) -> ::std::result::Result<Value, ScriptError> {
 		let mut rv = Value::new();
 		let name = s2u!(name);
 		let argv = Value::pack_args(args);
 		let ok = (_API.SciterCallScriptingFunction)(self.he, name.as_ptr(), argv.as_ptr(), argv.len() as UINT, rv.as_ptr());
-		return ok_or!(rv, ok, SCDOM_RESULT::OPERATION_FAILED);
+		return script_result(rv, ok, "SciterCallScriptingFunction", self);
 	}
 
 	/// Call scripting method defined for the element.
 	///
 	/// You can use the [`make_args!(args...)`](../macro.make_args.html) macro which helps you
 	/// to construct script arguments from Rust types.
-	pub fn call_method(&self, name: &str, args: &[Value]) -> Result<Value> {
+	pub fn call_method(&self, name: &str, args: &[Value]) -> ::std::result::Result<Value, ScriptError> {
 		let mut rv = Value::new();
 		let name = s2u!(name);
 		let argv = Value::pack_args(args);
 		let ok = (_API.SciterCallScriptingMethod)(self.he, name.as_ptr(), argv.as_ptr(), argv.len() as UINT, rv.as_ptr());
-		return ok_or!(rv, ok, SCDOM_RESULT::OPERATION_FAILED);
+		return script_result(rv, ok, "SciterCallScriptingMethod", self);
 	}
 
   /// Call behavior specific method.
@@ -709,7 +733,7 @@ impl Element {
         };
         let ok = call(&mut p as *mut _ as *mut METHOD_PARAMS);
         if ok != SCDOM_RESULT::OK {
-          return Err(ok);
+          return Err(Error::from(ok).with_call("SciterCallBehaviorMethod").with_element(self));
         }
         *retv = Value::from(&p.value);
         ok
@@ -721,7 +745,7 @@ impl Element {
         };
         let ok = call(&mut p as *mut _ as *mut METHOD_PARAMS);
         if ok != SCDOM_RESULT::OK {
-          return Err(ok);
+          return Err(Error::from(ok).with_call("SciterCallBehaviorMethod").with_element(self));
         }
         *retv = p.is_empty != 0;
         ok
@@ -732,7 +756,7 @@ impl Element {
         SCDOM_RESULT::INVALID_PARAMETER
       },
     };
-    ok_or!((), ok)
+    ok_or!((), ok, "SciterCallBehaviorMethod", self)
   }
 
 
@@ -775,7 +799,7 @@ impl Element {
 		let name = s2u!(name);
 		let value = s2w!(value);
 		let ok = (_API.SciterSetAttributeByName)(self.he, name.as_ptr(), value.as_ptr());
-		ok_or!((), ok)
+		ok_or!((), ok, "SciterSetAttributeByName", self)
 	}
 
 	/// Remove attribute.
@@ -783,7 +807,7 @@ impl Element {
 		let name = s2u!(name);
 		let value = ::std::ptr::null();
 		let ok = (_API.SciterSetAttributeByName)(self.he, name.as_ptr(), value);
-		ok_or!((), ok)
+		ok_or!((), ok, "SciterSetAttributeByName", self)
 	}
 
 	/// Toggle attribute.
@@ -798,7 +822,7 @@ impl Element {
 	/// Remove all attributes from the element.
 	pub fn clear_attributes(&mut self) -> Result<()> {
 		let ok = (_API.SciterClearAttributes)(self.he);
-		ok_or!((), ok)
+		ok_or!((), ok, "SciterClearAttributes", self)
 	}
 
 
@@ -817,7 +841,7 @@ impl Element {
 		let name = s2u!(name);
 		let value = s2w!(value);
 		let ok = (_API.SciterSetStyleAttribute)(self.he, name.as_ptr(), value.as_ptr());
-		ok_or!((), ok)
+		ok_or!((), ok, "SciterSetStyleAttribute", self)
 	}
 
 	//\name State methods
@@ -956,7 +980,7 @@ impl Element {
 	/// Clear content of the element.
 	pub fn clear(&mut self) -> Result<()> {
 		let ok = (_API.SciterSetElementText)(self.he, ::std::ptr::null(), 0);
-		ok_or!((), ok)
+		ok_or!((), ok, "SciterSetElementText", self)
 	}
 
 	/// Create new element as copy of existing element.
@@ -976,7 +1000,7 @@ impl Element {
 	/// because the newly created `Element` is unusable before it will be inserted at DOM.
 	pub fn insert(&mut self, index: usize, child: &Element) -> Result<()> {
 		let ok = (_API.SciterInsertElement)(child.he, self.he, index as UINT);
-		ok_or!((), ok)
+		ok_or!((), ok, "SciterInsertElement", self)
 	}
 
 	/// Append element as last child of this element.
@@ -1005,21 +1029,22 @@ impl Element {
 	/// Take element out of its container (and DOM tree).
 	pub fn detach(&mut self) -> Result<()> {
 		let ok = (_API.SciterDetachElement)(self.he);
-		ok_or!((), ok)
+		ok_or!((), ok, "SciterDetachElement", self)
 	}
 
 	/// Take element out of its container (and DOM tree) and force destruction of all behaviors.
 	pub fn destroy(&mut self) -> Result<()> {
-		let mut p = HELEMENT!();
-		::std::mem::swap(&mut self.he, &mut p);
-		let ok = (_API.SciterDeleteElement)(p);
-		ok_or!((), ok)
+		let ok = (_API.SciterDeleteElement)(self.he);
+		// the context is taken while the handle is still there
+		let rv = ok_or!((), ok, "SciterDeleteElement", self);
+		self.he = HELEMENT!();
+		rv
 	}
 
 	/// Swap element positions.
 	pub fn swap(&mut self, other: &mut Element) -> Result<()> {
 		let ok = (_API.SciterSwapElements)(self.he, other.he);
-		ok_or!((), ok)
+		ok_or!((), ok, "SciterSwapElements", self)
 	}
 
 	//\name Selectors
@@ -1047,7 +1072,7 @@ impl Element {
 		let ok = (_API.SciterSelectElements)(self.he, s.as_ptr(), inner::<T>, param as LPVOID);
     let handler = unsafe { Box::from_raw(param) };
 		if ok != SCDOM_RESULT::OK {
-			return Err(ok);
+			return Err(Error::from(ok).with_call("SciterSelectElements").with_element(self).with_message(format!("selector {:?}", selector)));
 		}
 		return Ok(handler.result());
 	}
@@ -1058,7 +1083,7 @@ impl Element {
 		let s = s2u!(selector);
 		let ok = (_API.SciterSelectParent)(self.he, s.as_ptr(), 0, &mut p);
 		if ok != SCDOM_RESULT::OK {
-			return Err(ok);
+			return Err(Error::from(ok).with_call("SciterSelectParent").with_element(self).with_message(format!("selector {:?}", selector)));
 		}
 		if p.is_null() { Ok(None) } else { Ok(Some(Element::from(p))) }
 	}
@@ -1084,7 +1109,7 @@ impl Element {
 	/// Apply changes and refresh element area in its window.
 	pub fn update(&self, render_now: bool) -> Result<()> {
 		let ok = (_API.SciterUpdateElement)(self.he, render_now as BOOL);
-		ok_or!((), ok)
+		ok_or!((), ok, "SciterUpdateElement", self)
 	}
 
 	/// Refresh element area in its window.
@@ -1093,7 +1118,7 @@ impl Element {
 	pub fn refresh(&self) -> Result<()> {
 		let rect = self.get_location(ELEMENT_AREAS::self_content())?;
 		let ok = (_API.SciterRefreshElementArea)(self.he, rect);
-		ok_or!((), ok)
+		ok_or!((), ok, "SciterRefreshElementArea", self)
 	}
 
 	/// Start Timer for the element.
@@ -1103,14 +1128,14 @@ impl Element {
 	/// Note that timer events are not bubbling, so you need attach handler to the target element directly.
 	pub fn start_timer(&self, period_ms: u32, timer_id: u64) -> Result<()> {
 		let ok = (_API.SciterSetTimer)(self.he, period_ms as UINT, timer_id as ::capi::sctypes::UINT_PTR);
-		ok_or!((), ok)
+		ok_or!((), ok, "SciterSetTimer", self)
 	}
 
 	/// Stop Timer for the element.
 	pub fn stop_timer(&self, timer_id: u64) -> Result<()> {
 		if !self.he.is_null() {
 			let ok = (_API.SciterSetTimer)(self.he, 0, timer_id as ::capi::sctypes::UINT_PTR);
-			ok_or!((), ok)
+			ok_or!((), ok, "SciterSetTimer", self)
 		} else {
 			Ok(())
 		}
@@ -1123,14 +1148,14 @@ impl Element {
 		let ptr = Box::into_raw(boxed);	// dropped in `_event_handler_proc`
 		let token = ptr as usize as u64;
 		let ok = (_API.SciterAttachEventHandler)(self.he, ::eventhandler::_event_handler_proc::<Handler>, ptr as LPVOID);
-		ok_or!(token, ok)
+		ok_or!(token, ok, "SciterAttachEventHandler", self)
 	}
 
 	/// Detach your handler from the element. Handlers identified by `token` from `attach_handler()` result.
 	pub fn detach_handler<Handler: EventHandler>(&mut self, token: u64) -> Result<()> {
 		let ptr = token as usize as *mut Handler;
 		let ok = (_API.SciterDetachEventHandler)(self.he, ::eventhandler::_event_handler_proc::<Handler>, ptr as LPVOID);
-		ok_or!((), ok)
+		ok_or!((), ok, "SciterDetachEventHandler", self)
 	}
 }
 
@@ -1485,7 +1510,8 @@ This way you can establish interaction between scipt and native code inside your
 	/// Default are `HANDLE_BEHAVIOR_EVENT | HANDLE_SCRIPTING_METHOD_CALL | HANDLE_METHOD_CALL` which cover behavior events
 	/// (like `document_complete` or `button_click`) and TIScript/JavaScript calls to native window.
 	pub fn default_events() -> EVENT_GROUPS {
-		return EVENT_GROUPS::HANDLE_BEHAVIOR_EVENT | EVENT_GROUPS::HANDLE_SCRIPTING_METHOD_CALL | EVENT_GROUPS::HANDLE_METHOD_CALL;
+		// `HANDLE_BEHAVIOR_EVENT | HANDLE_SCRIPTING_METHOD_CALL | HANDLE_METHOD_CALL`
+		return EVENT_GROUPS::HANDLE_DEFAULT;
 	}
 
 	/// UI action causing change.
@@ -0,0 +1,478 @@
+/*! Unified error type of the Sciter API wrappers.
+
+Every fallible function of the `dom`, `graphics`, `host`, `request` and `video` modules
+returns a [`sciter::Error`](struct.Error.html), so `?` works across all of them in a single function:
+
+```rust,no_run
+# use sciter::dom::Element;
+# use sciter::graphics::Image;
+fn snapshot(root: &Element) -> sciter::Result<Vec<u8>> {
+  let mut canvas = root.find_first("canvas")?.expect("canvas element");
+  let image = Image::create((64, 64), false)?;
+  canvas.set_attribute("data-snapshot", "1")?;
+  image.save(sciter::graphics::SaveImageEncoding::Png)
+}
+```
+
+Besides the raw result code of the engine, the error carries the name of the failed API function
+and, where it is known, the context of the call: the element, URL or path involved.
+
+Script failures of `eval_script`, `call_function` and `call_method` are reported
+as a [`ScriptError`](struct.ScriptError.html) instead, which carries the script message and location:
+
+```rust,no_run
+# let host = sciter::Host::attach(::std::ptr::null_mut());
+if let Err(e) = host.eval_script_with_url("undefined_function()", "app://snippet.js") {
+  println!("{} in {}", e.message(), e.url().or(e.source_url()).unwrap_or("?"));
+}
+```
+
+The raw codes can be compared directly:
+
+```rust,no_run
+# use sciter::dom::{Element, SCDOM_RESULT};
+# let hwnd = ::std::ptr::null_mut();
+match Element::from_window(hwnd) {
+  Err(ref e) if *e == SCDOM_RESULT::OK_NOT_HANDLED => println!("no document loaded"),
+  Err(e) => println!("error: {}", e),
+  Ok(root) => println!("root is <{}>", root.get_tag()),
+}
+```
+*/
+
+use std::convert::TryFrom;
+
+use capi::scdom::SCDOM_RESULT;
+use capi::scgraphics::GRAPHIN_RESULT;
+use capi::screquest::REQUEST_RESULT;
+use capi::scvalue::VALUE_RESULT;
+use dom::Element;
+use value::{ConversionError, FromValue, Value};
+
+
+/// A specialized `Result` type for Sciter operations.
+pub type Result<T> = ::std::result::Result<T, Error>;
+
+
+/// Raw result code of the failed operation.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Code {
+	/// DOM function has failed.
+	Dom(SCDOM_RESULT),
+	/// Value function has failed.
+	Value(VALUE_RESULT),
+	/// Graphics function has failed.
+	Graphics(GRAPHIN_RESULT),
+	/// Request function has failed.
+	Request(REQUEST_RESULT),
+	/// Function that reports only success or failure (e.g. returns `BOOL`) has failed.
+	Failed,
+	/// Sciter library can not be loaded.
+	Library,
+	/// Script has thrown an exception, see [`ScriptError`](struct.ScriptError.html).
+	Script,
+	/// Operation has not completed in time.
+	Timeout,
+}
+
+impl ::std::fmt::Display for Code {
+	fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+		match *self {
+			Code::Dom(ref code) => write!(f, "{}", code),
+			Code::Value(ref code) => write!(f, "{}", code),
+			Code::Graphics(ref code) => write!(f, "{}", code),
+			Code::Request(ref code) => write!(f, "{}", code),
+			Code::Failed => f.write_str("operation failed"),
+			Code::Library => f.write_str("library is not loaded"),
+			Code::Script => f.write_str("script error"),
+			Code::Timeout => f.write_str("operation timed out"),
+		}
+	}
+}
+
+
+/// Context of the failed operation.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Context {
+	/// The operation was performed on a DOM element.
+	Element {
+		/// Element tag name.
+		tag: String,
+		/// Element UID, see [`Element::get_uid`](../dom/struct.Element.html#method.get_uid).
+		uid: u32,
+	},
+	/// The operation was performed on an URL.
+	Url(String),
+	/// The operation was performed on a variable or a filesystem path.
+	Path(String),
+}
+
+impl ::std::fmt::Display for Context {
+	fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+		match *self {
+			Context::Element { ref tag, uid } => write!(f, "on <{}> #{}", tag, uid),
+			Context::Url(ref url) => write!(f, "for {:?}", url),
+			Context::Path(ref path) => write!(f, "at {:?}", path),
+		}
+	}
+}
+
+
+/// Error of the Sciter API call.
+///
+/// Carries the raw result code, the name of the failed API function and the context of the call.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Error {
+	code: Code,
+	call: Option<&'static str>,
+	context: Option<Context>,
+	message: Option<String>,
+}
+
+impl Error {
+	/// Construct an error from the raw result code.
+	pub fn new(code: Code) -> Error {
+		Error {
+			code: code,
+			call: None,
+			context: None,
+			message: None,
+		}
+	}
+
+	/// Set the name of the failed API function.
+	pub fn with_call(mut self, call: &'static str) -> Self {
+		self.call = Some(call);
+		self
+	}
+
+	/// Set the context of the failed operation.
+	pub fn with_context(mut self, context: Context) -> Self {
+		self.context = Some(context);
+		self
+	}
+
+	/// Set the element on which the operation has failed.
+	pub fn with_element(self, e: &Element) -> Self {
+		let context = Context::Element {
+			tag: e.get_tag(),
+			uid: e.get_uid(),
+		};
+		self.with_context(context)
+	}
+
+	/// Set the URL on which the operation has failed.
+	pub fn with_url(self, url: &str) -> Self {
+		self.with_context(Context::Url(url.to_owned()))
+	}
+
+	/// Set the path on which the operation has failed.
+	pub fn with_path(self, path: &str) -> Self {
+		self.with_context(Context::Path(path.to_owned()))
+	}
+
+	/// Set a human readable description of the failure.
+	pub fn with_message<S: Into<String>>(mut self, message: S) -> Self {
+		self.message = Some(message.into());
+		self
+	}
+
+	/// Raw result code of the failed operation.
+	pub fn code(&self) -> Code {
+		self.code
+	}
+
+	/// Name of the failed API function, if known.
+	pub fn call(&self) -> Option<&'static str> {
+		self.call
+	}
+
+	/// Context of the failed operation, if known.
+	pub fn context(&self) -> Option<&Context> {
+		self.context.as_ref()
+	}
+
+	/// Human readable description of the failure, if any.
+	pub fn message(&self) -> Option<&str> {
+		self.message.as_deref()
+	}
+}
+
+impl ::std::fmt::Display for Error {
+	fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+		match (self.call, self.code) {
+			(Some(call), Code::Failed) => write!(f, "{} failed", call)?,
+			(Some(call), code) => write!(f, "{} failed with {}", call, code)?,
+			(None, code) => write!(f, "{}", code)?,
+		}
+		if let Some(ref context) = self.context {
+			write!(f, " {}", context)?;
+		}
+		if let Some(ref message) = self.message {
+			write!(f, ": {}", message)?;
+		}
+		Ok(())
+	}
+}
+
+impl ::std::error::Error for Error {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self.code {
+			Code::Dom(ref code) => Some(code),
+			Code::Value(ref code) => Some(code),
+			Code::Graphics(ref code) => Some(code),
+			Code::Request(ref code) => Some(code),
+			Code::Failed | Code::Library | Code::Script | Code::Timeout => None,
+		}
+	}
+}
+
+impl From<()> for Error {
+	/// Failure of a function that does not report the reason.
+	fn from(_: ()) -> Self {
+		Error::new(Code::Failed)
+	}
+}
+
+macro_rules! from_code {
+	($($code:ident => $kind:ident),+) => {
+		$(
+			impl From<$code> for Error {
+				fn from(code: $code) -> Self {
+					Error::new(Code::$kind(code))
+				}
+			}
+
+			impl PartialEq<$code> for Error {
+				fn eq(&self, other: &$code) -> bool {
+					self.code == Code::$kind(*other)
+				}
+			}
+		)+
+	};
+}
+
+from_code! {
+	SCDOM_RESULT => Dom,
+	VALUE_RESULT => Value,
+	GRAPHIN_RESULT => Graphics,
+	REQUEST_RESULT => Request
+}
+
+impl From<ConversionError> for Error {
+	/// The value has an unexpected type.
+	fn from(e: ConversionError) -> Self {
+		Error::new(Code::Value(VALUE_RESULT::INCOMPATIBLE_TYPE)).with_message(e.to_string())
+	}
+}
+
+
+/// Script exception thrown by
+/// [`Host::eval_script`](../host/struct.Host.html#method.eval_script),
+/// [`Element::call_function`](../dom/struct.Element.html#method.call_function) and similar functions.
+///
+/// It is extracted from the error value returned by the engine:
+/// the `message`, `fileName`, `lineNumber`, `columnNumber` and `stack` properties of the error object,
+/// or the first line (message) and the rest (stack trace) of the error string;
+/// the location is taken from the first frame of the stack trace when it is not reported explicitly.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptError {
+	// boxed strings keep `Result<_, ScriptError>` small
+	message: Box<str>,
+	url: Option<Box<str>>,
+	line: Option<u32>,
+	column: Option<u32>,
+	stack: Option<Box<str>>,
+	source_url: Option<Box<str>>,
+	value: Value,
+	cause: Option<Box<Error>>,
+}
+
+impl ScriptError {
+	/// Error message.
+	pub fn message(&self) -> &str {
+		&self.message
+	}
+
+	/// URL of the script source, if reported by the engine.
+	pub fn url(&self) -> Option<&str> {
+		self.url.as_deref()
+	}
+
+	/// Line number (1-based), if known.
+	pub fn line(&self) -> Option<u32> {
+		self.line
+	}
+
+	/// Column number (1-based), if known.
+	pub fn column(&self) -> Option<u32> {
+		self.column
+	}
+
+	/// Stack trace, if available.
+	pub fn stack(&self) -> Option<&str> {
+		self.stack.as_deref()
+	}
+
+	/// URL of the evaluated snippet as given by the caller of
+	/// [`Host::eval_script_with_url`](../host/struct.Host.html#method.eval_script_with_url), not by the engine.
+	pub fn source_url(&self) -> Option<&str> {
+		self.source_url.as_deref()
+	}
+
+	/// The original error value returned by the engine.
+	pub fn value(&self) -> &Value {
+		&self.value
+	}
+
+	/// The API error, if the script was not called at all (e.g. because of an invalid element).
+	pub fn api_error(&self) -> Option<&Error> {
+		self.cause.as_deref()
+	}
+
+	/// Attach the URL of the evaluated snippet given by the caller.
+	pub(crate) fn with_source_url(mut self, url: Option<&str>) -> Self {
+		self.source_url = url.map(Box::from);
+		self
+	}
+}
+
+impl From<Value> for ScriptError {
+	/// Extract the script exception from the error value.
+	fn from(value: Value) -> Self {
+		let property = |names: &[&str]| {
+			names.iter().map(|&name| value.get_item(name)).find(|v| !v.is_undefined() && !v.is_null())
+		};
+		let text = |v: Value| v.as_string().unwrap_or_else(|| v.to_string());
+		// line and column numbers are 1-based, the rest are not numbers at all
+		let number = |v: Value| match v.to_int() {
+			Some(n) => u32::try_from(n).ok().filter(|&n| n > 0),
+			None => v.to_float().filter(|n| *n >= 1.0 && *n <= f64::from(u32::MAX)).map(|n| n as u32),
+		};
+
+		let (message, mut url, mut line, mut column, stack);
+		if value.is_map() || value.is_object() {
+			message = property(&["message"]).map(text).unwrap_or_else(|| value.to_string());
+			url = property(&["fileName", "url"]).map(text);
+			line = property(&["lineNumber", "line"]).and_then(number);
+			column = property(&["columnNumber", "column"]).and_then(number);
+			stack = property(&["stack"]).map(text);
+		} else {
+			let all = text(value.clone());
+			let mut lines = all.splitn(2, '\n');
+			message = lines.next().unwrap_or_default().trim_end().to_owned();
+			url = None;
+			line = None;
+			column = None;
+			stack = lines.next().map(|rest| rest.trim_end().to_owned()).filter(|rest| !rest.is_empty());
+		}
+
+		if url.is_none() && line.is_none() {
+			if let Some((file, row, col)) = stack.as_ref().and_then(|s| s.lines().find_map(parse_frame)) {
+				url = Some(file);
+				line = Some(row);
+				column = col;
+			}
+		}
+
+		ScriptError {
+			message: message.into_boxed_str(),
+			url: url.map(String::into_boxed_str),
+			line: line,
+			column: column,
+			stack: stack.map(String::into_boxed_str),
+			source_url: None,
+			value: value,
+			cause: None,
+		}
+	}
+}
+
+/// Error strings and objects.
+impl FromValue for ScriptError {
+	fn from_value(v: &Value) -> Option<Self> {
+		if v.is_verror() {
+			Some(ScriptError::from(v.clone()))
+		} else {
+			None
+		}
+	}
+}
+
+impl From<Error> for ScriptError {
+	/// The script was not called because of the API error.
+	fn from(e: Error) -> Self {
+		ScriptError {
+			message: e.to_string().into_boxed_str(),
+			url: None,
+			line: None,
+			column: None,
+			stack: None,
+			source_url: None,
+			value: Value::new(),
+			cause: Some(Box::new(e)),
+		}
+	}
+}
+
+impl From<ScriptError> for Error {
+	fn from(e: ScriptError) -> Self {
+		if let Some(cause) = e.cause {
+			return *cause;
+		}
+		let err = Error::new(Code::Script);
+		let err = match e.url.or(e.source_url) {
+			Some(ref url) => err.with_url(url),
+			None => err,
+		};
+		err.with_message(String::from(e.message))
+	}
+}
+
+impl ::std::fmt::Display for ScriptError {
+	fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+		f.write_str(&self.message)?;
+		if let Some(ref url) = self.url {
+			write!(f, " at {}", url)?;
+			if let Some(line) = self.line {
+				write!(f, ":{}", line)?;
+				if let Some(column) = self.column {
+					write!(f, ":{}", column)?;
+				}
+			}
+		} else if let Some(ref url) = self.source_url {
+			write!(f, " in {}", url)?;
+		}
+		Ok(())
+	}
+}
+
+impl ::std::error::Error for ScriptError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		self.cause.as_ref().map(|e| &**e as &(dyn std::error::Error + 'static))
+	}
+}
+
+/// Parse a stack frame location like `at foo (app://main.js:12:5)`, `app://main.js:12` or `app://main.js(12)`.
+fn parse_frame(frame: &str) -> Option<(String, u32, Option<u32>)> {
+	let mut frame = frame.trim();
+	if frame.starts_with("at ") {
+		frame = frame[3..].trim();
+	}
+	if frame.ends_with(')') {
+		let open = frame.rfind('(')?;
+		let inner = &frame[open + 1 .. frame.len() - 1];
+		// `file(line)`
+		if let Ok(line) = inner.parse() {
+			return Some((frame[..open].trim().to_owned(), line, None));
+		}
+		frame = inner;
+	}
+	let mut parts = frame.rsplitn(3, ':');
+	let last: u32 = parts.next()?.parse().ok()?;
+	let rest = parts.next()?;
+	match rest.parse() {
+		Ok(line) => Some((parts.next()?.to_owned(), line, Some(last))),
+		Err(_) => Some((frame[..frame.len() - last.to_string().len() - 1].to_owned(), last, None)),
+	}
+}
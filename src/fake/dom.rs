@@ -0,0 +1,1812 @@
+//! Element tree, event dispatching and timers of the fake engine.
+//!
+//! Elements live in a global table and are identified by their uid,
+//! which is used as the `HELEMENT` handle value (uids are never reused).
+//!
+//! An element is destroyed when nothing references it: it has no parent,
+//! it is not a document root and no `Element` or `Value` holds it.
+
+#![allow(non_snake_case)]
+
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::ffi::CString;
+use std::sync::Mutex;
+
+use capi::sctypes::*;
+use capi::scdef::*;
+use capi::scdom::*;
+use capi::scvalue::*;
+use capi::scbehavior::*;
+use capi::sctiscript::tiscript_value;
+use capi::scom::{som_asset_t, som_atom_t};
+
+use super::html::{self, Selector};
+use super::value::Slot;
+
+
+/// Handle of the element with the given uid.
+pub(crate) fn he_of(uid: u32) -> HELEMENT {
+	uid as usize as HELEMENT
+}
+
+/// Uid of the given handle.
+pub(crate) fn uid_of(he: HELEMENT) -> u32 {
+	he as usize as u32
+}
+
+/// Handle of the window with the given id.
+pub(crate) fn hwnd_of(id: usize) -> HWINDOW {
+	id as HWINDOW
+}
+
+/// Id of the given window handle.
+pub(crate) fn id_of(hwnd: HWINDOW) -> usize {
+	hwnd as usize
+}
+
+
+/// Attached event handler.
+#[derive(Clone, Copy)]
+pub(crate) struct Handler {
+	pub proc_: ElementEventProc,
+	pub tag: usize,
+	pub groups: UINT,
+}
+
+impl Handler {
+	fn is(&self, proc_: ElementEventProc, tag: LPVOID) -> bool {
+		self.proc_ as usize == proc_ as usize && self.tag == tag as usize
+	}
+
+	fn wants(&self, group: EVENT_GROUPS) -> bool {
+		group == EVENT_GROUPS::HANDLE_INITIALIZATION || self.groups & group as UINT != 0
+	}
+}
+
+/// Element content.
+pub(crate) enum Node {
+	Text(String),
+	Element(u32),
+}
+
+/// DOM element.
+pub(crate) struct Elem {
+	pub tag: CString,
+	pub attrs: Vec<(String, String)>,
+	pub styles: Vec<(String, String)>,
+	pub nodes: Vec<Node>,
+	pub parent: Option<u32>,
+	/// The window this element is the document root of.
+	pub root_of: Option<usize>,
+	pub refs: usize,
+	pub state: UINT,
+	pub value: Option<Slot>,
+	pub expando: Option<Slot>,
+	pub handlers: Vec<Handler>,
+	pub hwnd: usize,
+}
+
+impl Elem {
+	fn new(tag: &str) -> Elem {
+		Elem {
+			tag: CString::new(tag.replace('\0', "")).unwrap_or_default(),
+			attrs: Vec::new(),
+			styles: Vec::new(),
+			nodes: Vec::new(),
+			parent: None,
+			root_of: None,
+			refs: 0,
+			state: 0,
+			value: None,
+			expando: None,
+			handlers: Vec::new(),
+			hwnd: 0,
+		}
+	}
+
+	pub fn tag(&self) -> &str {
+		self.tag.to_str().unwrap_or("")
+	}
+
+	pub fn attribute(&self, name: &str) -> Option<&str> {
+		self.attrs.iter().find(|a| a.0 == name).map(|a| a.1.as_str())
+	}
+
+	/// Element children.
+	pub fn children(&self) -> Vec<u32> {
+		self.nodes.iter().filter_map(|n| match *n {
+			Node::Element(uid) => Some(uid),
+			_ => None,
+		}).collect()
+	}
+}
+
+/// Sciter window.
+#[derive(Default)]
+pub(crate) struct Window {
+	pub root: Option<u32>,
+	pub callback: Option<(SciterHostCallback, usize)>,
+	pub handlers: Vec<Handler>,
+	pub vars: Vec<(String, Slot)>,
+	/// Data delivered via `SciterDataReady`.
+	pub ready: Vec<(String, Vec<u8>)>,
+	pub focus: Option<u32>,
+	pub highlighted: Option<u32>,
+	pub expando: Option<Slot>,
+	pub url: String,
+	/// Thread which created the window and processes its posted callbacks.
+	pub thread: Option<::std::thread::ThreadId>,
+}
+
+/// Owner of an event handler.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum Owner {
+	Element(u32),
+	Window(usize),
+}
+
+/// Handler to call along with the element passed to it.
+#[derive(Clone, Copy)]
+pub(crate) struct Listener {
+	owner: Owner,
+	handler: Handler,
+	he: u32,
+}
+
+/// The whole engine state.
+#[derive(Default)]
+pub(crate) struct Dom {
+	pub elements: HashMap<u32, Elem>,
+	pub windows: HashMap<usize, Window>,
+	last_uid: u32,
+	last_hwnd: usize,
+	pub atoms: Vec<String>,
+	pub globals: Vec<(String, Slot)>,
+	/// Values to release after unlocking.
+	pub garbage: Vec<Slot>,
+	/// Handlers to notify about detaching after unlocking.
+	detached: Vec<(u32, Handler)>,
+}
+
+lazy_static! {
+	static ref DOM: Mutex<Dom> = Mutex::new(Dom::default());
+}
+
+thread_local! {
+	/// Element references changed while the current thread holds the DOM lock.
+	static PENDING: RefCell<Option<Vec<(u32, bool)>>> = const { RefCell::new(None) };
+}
+
+/// Resets the pending references on unlocking (even on panic).
+struct Locked;
+
+impl Drop for Locked {
+	fn drop(&mut self) {
+		PENDING.with(|p| p.borrow_mut().take());
+	}
+}
+
+/// Access the engine state.
+///
+/// Released values and detach notifications are processed after unlocking,
+/// so they can call the API again.
+pub(crate) fn with_dom<R, F: FnOnce(&mut Dom) -> R>(f: F) -> R {
+	let mut dom = DOM.lock().unwrap_or_else(|e| e.into_inner());
+	let locked = Locked;
+	PENDING.with(|p| *p.borrow_mut() = Some(Vec::new()));
+	let r = f(&mut dom);
+	loop {
+		let pending = PENDING.with(|p| p.borrow_mut().as_mut().map(::std::mem::take).unwrap_or_default());
+		if pending.is_empty() {
+			break;
+		}
+		for (uid, used) in pending {
+			if used {
+				dom.use_ref(uid);
+			} else {
+				dom.unuse_ref(uid);
+			}
+		}
+	}
+	drop(locked);
+	let garbage = ::std::mem::take(&mut dom.garbage);
+	let detached = ::std::mem::take(&mut dom.detached);
+	drop(dom);
+	drop(garbage);
+	for (uid, handler) in detached {
+		let mut params = INITIALIZATION_PARAMS { cmd: INITIALIZATION_EVENTS::BEHAVIOR_DETACH };
+		(handler.proc_)(handler.tag as LPVOID, he_of(uid), EVENT_GROUPS::HANDLE_INITIALIZATION as UINT, &mut params as *mut _ as LPVOID);
+	}
+	r
+}
+
+fn is_locked() -> bool {
+	PENDING.with(|p| p.borrow().is_some())
+}
+
+/// Retain the element referenced by a `Value`.
+pub(crate) fn use_uid(uid: u32) {
+	if is_locked() {
+		PENDING.with(|p| p.borrow_mut().as_mut().map(|v| v.push((uid, true))));
+	} else {
+		with_dom(|dom| dom.use_ref(uid));
+	}
+}
+
+/// Release the element referenced by a `Value`.
+pub(crate) fn unuse_uid(uid: u32) {
+	if is_locked() {
+		PENDING.with(|p| p.borrow_mut().as_mut().map(|v| v.push((uid, false))));
+	} else {
+		with_dom(|dom| dom.unuse_ref(uid));
+	}
+}
+
+impl Dom {
+	pub fn create(&mut self, tag: &str) -> u32 {
+		self.last_uid += 1;
+		self.elements.insert(self.last_uid, Elem::new(tag));
+		self.last_uid
+	}
+
+	pub fn create_window(&mut self) -> usize {
+		self.last_hwnd += 1;
+		self.windows.insert(self.last_hwnd, Window {
+			thread: Some(::std::thread::current().id()),
+			.. Window::default()
+		});
+		self.last_hwnd
+	}
+
+	pub fn register_window(&mut self, id: usize) {
+		self.windows.entry(id).or_default();
+	}
+
+	pub fn destroy_window(&mut self, id: usize) {
+		if let Some(mut window) = self.windows.remove(&id) {
+			if let Some(root) = window.root.take() {
+				self.set_root_of(root, None);
+			}
+			for handler in window.handlers.drain(..) {
+				self.detached.push((0, handler));
+			}
+			self.garbage.extend(window.vars.drain(..).map(|v| v.1));
+			self.garbage.extend(window.expando.take());
+		}
+	}
+
+	pub fn elem(&self, uid: u32) -> Option<&Elem> {
+		self.elements.get(&uid)
+	}
+
+	pub fn elem_mut(&mut self, uid: u32) -> Option<&mut Elem> {
+		self.elements.get_mut(&uid)
+	}
+
+	fn use_ref(&mut self, uid: u32) {
+		if let Some(e) = self.elem_mut(uid) {
+			e.refs += 1;
+		}
+	}
+
+	fn unuse_ref(&mut self, uid: u32) {
+		if let Some(e) = self.elem_mut(uid) {
+			e.refs = e.refs.saturating_sub(1);
+			self.collect(uid);
+		}
+	}
+
+	/// Destroy the element if it is not referenced anymore.
+	pub fn collect(&mut self, uid: u32) {
+		let unused = self.elem(uid).is_some_and(|e| e.refs == 0 && e.parent.is_none() && e.root_of.is_none());
+		if unused {
+			self.destroy(uid);
+		}
+	}
+
+	/// Destroy the element and the unreferenced part of its subtree.
+	pub fn destroy(&mut self, uid: u32) {
+		self.detach(uid);
+		let mut e = match self.elements.remove(&uid) {
+			Some(e) => e,
+			None => return,
+		};
+		if let Some(id) = e.root_of {
+			if let Some(w) = self.windows.get_mut(&id) {
+				w.root = None;
+			}
+		}
+		for handler in e.handlers.drain(..) {
+			self.detached.push((uid, handler));
+		}
+		self.garbage.extend(e.value.take());
+		self.garbage.extend(e.expando.take());
+		for child in e.children() {
+			if let Some(c) = self.elem_mut(child) {
+				c.parent = None;
+			}
+			self.collect(child);
+		}
+	}
+
+	pub fn set_root_of(&mut self, uid: u32, window: Option<usize>) {
+		if let Some(e) = self.elem_mut(uid) {
+			e.root_of = window;
+		}
+		if window.is_none() {
+			self.collect(uid);
+		}
+	}
+
+	/// Remove the element from its parent, does not destroy it.
+	pub fn detach(&mut self, uid: u32) {
+		let parent = self.elem_mut(uid).and_then(|e| e.parent.take());
+		if let Some(p) = parent.and_then(|p| self.elem_mut(p)) {
+			p.nodes.retain(|n| match *n {
+				Node::Element(x) => x != uid,
+				_ => true,
+			});
+		}
+	}
+
+	/// Insert the element at the given position among the element children of the parent.
+	pub fn insert(&mut self, uid: u32, parent: u32, index: usize) -> SCDOM_RESULT {
+		if !self.elements.contains_key(&uid) || !self.elements.contains_key(&parent) {
+			return SCDOM_RESULT::INVALID_HANDLE;
+		}
+		// do not insert an element into itself
+		let mut p = Some(parent);
+		while let Some(x) = p {
+			if x == uid {
+				return SCDOM_RESULT::INVALID_PARAMETER;
+			}
+			p = self.elem(x).and_then(|e| e.parent);
+		}
+		if self.elem(uid).is_some_and(|e| e.root_of.is_some()) {
+			return SCDOM_RESULT::INVALID_PARAMETER;
+		}
+		self.detach(uid);
+		let e = self.elem_mut(parent).unwrap();
+		let pos = e.nodes.iter()
+			.enumerate()
+			.filter(|&(_, n)| matches!(*n, Node::Element(_)))
+			.nth(index)
+			.map(|(i, _)| i)
+			.unwrap_or(e.nodes.len());
+		e.nodes.insert(pos, Node::Element(uid));
+		self.elem_mut(uid).unwrap().parent = Some(parent);
+		SCDOM_RESULT::OK
+	}
+
+	/// Replace the element content, previous children are released.
+	pub fn set_nodes(&mut self, uid: u32, nodes: Vec<Node>) {
+		let prev = match self.elem_mut(uid) {
+			Some(e) => ::std::mem::take(&mut e.nodes),
+			None => return,
+		};
+		for n in prev {
+			if let Node::Element(child) = n {
+				if let Some(c) = self.elem_mut(child) {
+					c.parent = None;
+				}
+				self.collect(child);
+			}
+		}
+		self.splice(uid, 0, nodes);
+	}
+
+	/// Insert nodes at the given node position.
+	pub fn splice(&mut self, uid: u32, pos: usize, nodes: Vec<Node>) {
+		for n in &nodes {
+			if let Node::Element(child) = *n {
+				if let Some(c) = self.elem_mut(child) {
+					c.parent = Some(uid);
+				}
+			}
+		}
+		if let Some(e) = self.elem_mut(uid) {
+			let pos = pos.min(e.nodes.len());
+			e.nodes.splice(pos..pos, nodes);
+		}
+	}
+
+	/// Create elements from the parsed HTML, created elements are collected as well.
+	pub fn build(&mut self, nodes: Vec<html::Node>, created: &mut Vec<u32>) -> Vec<Node> {
+		nodes.into_iter().map(|n| match n {
+			html::Node::Text(text) => Node::Text(text),
+			html::Node::Element(tag) => {
+				let uid = self.create(&tag.name);
+				created.push(uid);
+				let nodes = self.build(tag.children, created);
+				self.elem_mut(uid).unwrap().attrs = tag.attrs;
+				self.splice(uid, 0, nodes);
+				Node::Element(uid)
+			},
+		}).collect()
+	}
+
+	/// Deep copy of the element, without handlers and state.
+	pub fn clone_tree(&mut self, uid: u32) -> Option<u32> {
+		let (tag, attrs, styles, nodes) = {
+			let e = self.elem(uid)?;
+			let nodes: Vec<Result<String, u32>> = e.nodes.iter().map(|n| match *n {
+				Node::Text(ref s) => Ok(s.clone()),
+				Node::Element(x) => Err(x),
+			}).collect();
+			(e.tag().to_owned(), e.attrs.clone(), e.styles.clone(), nodes)
+		};
+		let copy = self.create(&tag);
+		let nodes = nodes.into_iter().filter_map(|n| match n {
+			Ok(s) => Some(Node::Text(s)),
+			Err(x) => self.clone_tree(x).map(Node::Element),
+		}).collect();
+		{
+			let e = self.elem_mut(copy).unwrap();
+			e.attrs = attrs;
+			e.styles = styles;
+		}
+		self.splice(copy, 0, nodes);
+		Some(copy)
+	}
+
+	/// Text content of the element.
+	pub fn text(&self, uid: u32) -> String {
+		let mut out = String::new();
+		self.write_text(uid, &mut out);
+		out
+	}
+
+	fn write_text(&self, uid: u32, out: &mut String) {
+		if let Some(e) = self.elem(uid) {
+			for n in &e.nodes {
+				match *n {
+					Node::Text(ref s) => out.push_str(s),
+					Node::Element(x) => self.write_text(x, out),
+				}
+			}
+		}
+	}
+
+	/// HTML of the element.
+	pub fn html(&self, uid: u32, outer: bool) -> String {
+		let mut out = String::new();
+		self.write_html(uid, outer, &mut out);
+		out
+	}
+
+	fn write_html(&self, uid: u32, outer: bool, out: &mut String) {
+		let e = match self.elem(uid) {
+			Some(e) => e,
+			None => return,
+		};
+		if outer {
+			out.push('<');
+			out.push_str(e.tag());
+			for (name, value) in &e.attrs {
+				out.push(' ');
+				out.push_str(name);
+				out.push_str("=\"");
+				out.push_str(&html::escape(value, true));
+				out.push('"');
+			}
+			out.push('>');
+			if html::is_void(e.tag()) && e.nodes.is_empty() {
+				return;
+			}
+		}
+		for n in &e.nodes {
+			match *n {
+				Node::Text(ref s) => out.push_str(&html::escape(s, false)),
+				Node::Element(x) => self.write_html(x, true, out),
+			}
+		}
+		if outer {
+			out.push_str("</");
+			out.push_str(e.tag());
+			out.push('>');
+		}
+	}
+
+	/// The window containing the element.
+	pub fn window_of(&self, uid: u32) -> Option<usize> {
+		let mut uid = uid;
+		loop {
+			let e = self.elem(uid)?;
+			if e.root_of.is_some() {
+				return e.root_of;
+			}
+			uid = e.parent?;
+		}
+	}
+
+	/// Parents chain starting from the document root and ending with the element.
+	pub fn path(&self, uid: u32) -> Vec<u32> {
+		let mut path = Vec::new();
+		let mut p = Some(uid);
+		while let Some(x) = p {
+			path.push(x);
+			p = self.elem(x).and_then(|e| e.parent);
+		}
+		path.reverse();
+		path
+	}
+
+	/// All descendants in document order.
+	pub fn descendants(&self, uid: u32, out: &mut Vec<u32>) {
+		if let Some(e) = self.elem(uid) {
+			for child in e.children() {
+				out.push(child);
+				self.descendants(child, out);
+			}
+		}
+	}
+
+	/// Handlers of the element which are subscribed to the event group.
+	fn element_listeners(&self, uid: u32, group: EVENT_GROUPS) -> Vec<Listener> {
+		self.elem(uid).map(|e| e.handlers.iter()
+			.filter(|h| h.wants(group))
+			.map(|&handler| Listener { owner: Owner::Element(uid), handler, he: uid })
+			.collect()
+		).unwrap_or_default()
+	}
+
+	/// Handlers of the window which are subscribed to the event group.
+	fn window_listeners(&self, id: usize, group: EVENT_GROUPS) -> Vec<Listener> {
+		self.windows.get(&id).map(|w| {
+			let root = w.root.unwrap_or(0);
+			w.handlers.iter()
+				.filter(|h| h.wants(group))
+				.map(|&handler| Listener { owner: Owner::Window(id), handler, he: root })
+				.collect()
+		}).unwrap_or_default()
+	}
+
+	fn is_attached(&self, l: &Listener) -> bool {
+		let handlers = match l.owner {
+			Owner::Element(uid) => self.elem(uid).map(|e| &e.handlers),
+			Owner::Window(id) => self.windows.get(&id).map(|w| &w.handlers),
+		};
+		handlers.is_some_and(|list| list.iter().any(|h| h.is(l.handler.proc_, l.handler.tag as LPVOID)))
+	}
+
+	fn handlers_mut(&mut self, owner: Owner) -> Option<&mut Vec<Handler>> {
+		match owner {
+			Owner::Element(uid) => self.elem_mut(uid).map(|e| &mut e.handlers),
+			Owner::Window(id) => self.windows.get_mut(&id).map(|w| &mut w.handlers),
+		}
+	}
+
+	/// Look up a variable of the window or a global one.
+	pub fn variable(&self, window: Option<usize>, path: &str) -> Option<Slot> {
+		let scopes = [window.and_then(|id| self.windows.get(&id)).map(|w| &w.vars), Some(&self.globals)];
+		for vars in scopes.iter().flatten() {
+			if let Some(v) = vars.iter().find(|v| v.0 == path) {
+				return Some(v.1.clone());
+			}
+		}
+		// `name.key.key` of a map variable
+		let mut parts = path.split('.');
+		let head = parts.next()?;
+		if head == path {
+			return None;
+		}
+		let mut value = self.variable(window, head)?;
+		for key in parts {
+			let item = super::value::items(&value.0).into_iter()
+				.find(|pair| pair.0.as_str().as_deref() == Some(key))
+				.map(|pair| pair.1)?;
+			value = item;
+		}
+		Some(value)
+	}
+}
+
+impl html::Tree for Dom {
+	fn tag(&self, e: u32) -> &str {
+		self.elem(e).map_or("", |e| e.tag())
+	}
+
+	fn attribute(&self, e: u32, name: &str) -> Option<&str> {
+		self.elem(e).and_then(|e| e.attribute(name))
+	}
+
+	fn parent(&self, e: u32) -> Option<u32> {
+		self.elem(e).and_then(|e| e.parent)
+	}
+
+	fn siblings(&self, e: u32) -> Vec<u32> {
+		match self.parent(e).and_then(|p| self.elem(p)) {
+			Some(p) => p.children(),
+			None => vec![e],
+		}
+	}
+
+	fn is_empty(&self, e: u32) -> bool {
+		self.elem(e).is_none_or(|e| e.nodes.is_empty())
+	}
+
+	fn state(&self, e: u32) -> u32 {
+		self.elem(e).map_or(0, |e| e.state)
+	}
+}
+
+
+/// Call the listeners until one of them handles the event.
+fn notify(listeners: Vec<Listener>, group: EVENT_GROUPS, params: LPVOID) -> bool {
+	for l in listeners {
+		// a previous handler could detach this one
+		if !with_dom(|dom| dom.is_attached(&l)) {
+			continue;
+		}
+		if (l.handler.proc_)(l.handler.tag as LPVOID, he_of(l.he), group as UINT, params) != 0 {
+			return true;
+		}
+	}
+	false
+}
+
+/// Attach a handler to the element or to the window.
+pub(crate) fn attach(owner: Owner, proc_: ElementEventProc, tag: LPVOID, subscription: Option<UINT>) -> SCDOM_RESULT {
+	let he = match owner {
+		Owner::Element(uid) => uid,
+		Owner::Window(_) => 0,
+	};
+	let groups = match subscription {
+		Some(groups) => groups,
+		None => {
+			let mut groups = EVENT_GROUPS::HANDLE_ALL as UINT;
+			if proc_(tag, he_of(he), EVENT_GROUPS::SUBSCRIPTIONS_REQUEST as UINT, &mut groups as *mut UINT as LPVOID) == 0 {
+				groups = EVENT_GROUPS::HANDLE_ALL as UINT;
+			}
+			groups
+		},
+	};
+	let handler = Handler { proc_, tag: tag as usize, groups };
+	let ok = with_dom(|dom| match dom.handlers_mut(owner) {
+		Some(list) => {
+			if !list.iter().any(|h| h.is(proc_, tag)) {
+				list.push(handler);
+			}
+			true
+		},
+		None => false,
+	});
+	if !ok {
+		return if he == 0 { SCDOM_RESULT::INVALID_HWND } else { SCDOM_RESULT::INVALID_HANDLE };
+	}
+	if let Owner::Element(_) = owner {
+		let mut params = INITIALIZATION_PARAMS { cmd: INITIALIZATION_EVENTS::BEHAVIOR_ATTACH };
+		proc_(tag, he_of(he), EVENT_GROUPS::HANDLE_INITIALIZATION as UINT, &mut params as *mut _ as LPVOID);
+	}
+	SCDOM_RESULT::OK
+}
+
+/// Detach a handler from the element or from the window.
+pub(crate) fn detach(owner: Owner, proc_: ElementEventProc, tag: LPVOID) -> SCDOM_RESULT {
+	let he = match owner {
+		Owner::Element(uid) => uid,
+		Owner::Window(_) => 0,
+	};
+	with_dom(|dom| {
+		let found = match dom.handlers_mut(owner) {
+			Some(list) => {
+				let pos = list.iter().position(|h| h.is(proc_, tag));
+				pos.map(|n| list.remove(n))
+			},
+			None => return if he == 0 { SCDOM_RESULT::INVALID_HWND } else { SCDOM_RESULT::INVALID_HANDLE },
+		};
+		match found {
+			Some(handler) => {
+				dom.detached.push((he, handler));
+				SCDOM_RESULT::OK
+			},
+			None => SCDOM_RESULT::OK_NOT_HANDLED,
+		}
+	})
+}
+
+/// Send a behavior event: sinking from the window to the target and then bubbling back.
+pub(crate) fn send_behavior_event(params: &mut BEHAVIOR_EVENT_PARAMS) -> bool {
+	let target = uid_of(params.heTarget);
+	let group = EVENT_GROUPS::HANDLE_BEHAVIOR_EVENT;
+	let (sinking, bubbling) = with_dom(|dom| {
+		let path = dom.path(target);
+		let window = dom.window_of(target).map(|id| dom.window_listeners(id, group)).unwrap_or_default();
+		let mut sinking = window.clone();
+		let mut bubbling = Vec::new();
+		for &uid in &path {
+			sinking.extend(dom.element_listeners(uid, group));
+		}
+		for &uid in path.iter().rev() {
+			bubbling.extend(dom.element_listeners(uid, group));
+		}
+		bubbling.extend(window);
+		(sinking, bubbling)
+	});
+	let code = params.cmd & !(PHASE_MASK::SINKING_HANDLED as UINT);
+	let ptr = params as *mut BEHAVIOR_EVENT_PARAMS as LPVOID;
+
+	params.cmd = code | PHASE_MASK::SINKING as UINT;
+	let handled = notify(sinking, group, ptr) || {
+		params.cmd = code;
+		notify(bubbling, group, ptr)
+	};
+	params.cmd = code;
+	handled
+}
+
+/// Call a scripting method of the element or of the window handlers.
+pub(crate) fn call_scripting_method(owner: Owner, name: &str, args: &[Slot]) -> Option<Slot> {
+	let group = EVENT_GROUPS::HANDLE_SCRIPTING_METHOD_CALL;
+	let listeners = with_dom(|dom| match owner {
+		Owner::Element(uid) => dom.element_listeners(uid, group),
+		Owner::Window(id) => dom.window_listeners(id, group),
+	});
+	let name = CString::new(name.replace('\0', "")).unwrap_or_default();
+	let argv: Vec<VALUE> = args.iter().map(|a| a.0.clone()).collect();
+	let mut params = SCRIPTING_METHOD_PARAMS {
+		name: name.as_ptr(),
+		argv: argv.as_ptr(),
+		argc: argv.len() as UINT,
+		result: VALUE::new(),
+	};
+	let handled = notify(listeners, group, &mut params as *mut _ as LPVOID);
+	let result = Slot::adopt(params.result);
+	if handled { Some(result) } else { None }
+}
+
+
+/// Posted behavior event.
+struct Posted {
+	cmd: UINT,
+	target: u32,
+	source: u32,
+	reason: UINT_PTR,
+	data: Slot,
+	name: Option<Vec<u16>>,
+}
+
+/// Element timer.
+struct Timer {
+	uid: u32,
+	id: UINT_PTR,
+	period: u32,
+	due: u64,
+}
+
+thread_local! {
+	static POSTED: RefCell<VecDeque<Posted>> = const { RefCell::new(VecDeque::new()) };
+	static TIMERS: RefCell<(u64, Vec<Timer>)> = const { RefCell::new((0, Vec::new())) };
+}
+
+/// Dispatch posted events of the current thread, returns the number of dispatched events.
+pub(crate) fn pump() -> usize {
+	let mut count = 0;
+	while let Some(event) = POSTED.with(|q| q.borrow_mut().pop_front()) {
+		if !with_dom(|dom| dom.elements.contains_key(&event.target)) {
+			continue;
+		}
+		let mut params = BEHAVIOR_EVENT_PARAMS {
+			cmd: event.cmd,
+			heTarget: he_of(event.target),
+			he: he_of(event.source),
+			reason: event.reason,
+			data: event.data.0.clone(),
+			name: event.name.as_ref().map_or(::std::ptr::null(), |n| n.as_ptr()),
+		};
+		send_behavior_event(&mut params);
+		count += 1;
+	}
+	count
+}
+
+/// Move the timers clock of the current thread forward, firing the due timers.
+pub(crate) fn advance(ms: u32) {
+	let end = TIMERS.with(|t| t.borrow().0) + ms as u64;
+	loop {
+		// the earliest due timer
+		let next = TIMERS.with(|t| {
+			let mut t = t.borrow_mut();
+			let found = t.1.iter()
+				.filter(|x| x.due <= end)
+				.min_by_key(|x| x.due)
+				.map(|x| (x.uid, x.id, x.due));
+			if let Some((_, _, due)) = found {
+				t.0 = due;
+			}
+			found
+		});
+		let (uid, id, _) = match next {
+			Some(next) => next,
+			None => break,
+		};
+		let listeners = with_dom(|dom| dom.element_listeners(uid, EVENT_GROUPS::HANDLE_TIMER));
+		let mut params = TIMER_PARAMS { timerId: id };
+		let keep = !listeners.is_empty() && notify(listeners, EVENT_GROUPS::HANDLE_TIMER, &mut params as *mut _ as LPVOID);
+		TIMERS.with(|t| {
+			let mut t = t.borrow_mut();
+			let now = t.0;
+			if let Some(pos) = t.1.iter().position(|x| x.uid == uid && x.id == id) {
+				if keep {
+					let timer = &mut t.1[pos];
+					timer.due = now + timer.period.max(1) as u64;
+				} else {
+					t.1.remove(pos);
+				}
+			}
+		});
+	}
+	TIMERS.with(|t| t.borrow_mut().0 = end);
+}
+
+
+macro_rules! check {
+	($e:expr) => {
+		match $e {
+			Ok(v) => v,
+			Err(e) => return e,
+		}
+	};
+}
+
+/// Access the element by its handle.
+fn element<R, F: FnOnce(&mut Dom, u32) -> R>(he: HELEMENT, f: F) -> Result<R, SCDOM_RESULT> {
+	if he.is_null() {
+		return Err(SCDOM_RESULT::INVALID_HANDLE);
+	}
+	let uid = uid_of(he);
+	with_dom(|dom| {
+		if dom.elements.contains_key(&uid) {
+			Ok(f(dom, uid))
+		} else {
+			Err(SCDOM_RESULT::INVALID_HANDLE)
+		}
+	})
+}
+
+fn store<T>(p: *mut T, v: T) {
+	if !p.is_null() {
+		unsafe { *p = v };
+	}
+}
+
+pub(crate) fn wide(s: &str) -> Vec<u16> {
+	let mut w: Vec<u16> = s.encode_utf16().collect();
+	w.push(0);
+	w
+}
+
+fn send_wide(s: &str, rcv: LPCWSTR_RECEIVER, param: LPVOID) {
+	let w = wide(s);
+	rcv(w.as_ptr(), w.len() as UINT - 1, param);
+}
+
+fn send_utf8(s: &str, rcv: LPCSTR_RECEIVER, param: LPVOID) {
+	let c = CString::new(s.replace('\0', "")).unwrap_or_default();
+	rcv(c.as_ptr(), c.as_bytes().len() as UINT, param);
+}
+
+pub(crate) fn utf8(p: LPCSTR) -> String {
+	::utf::u2s(p)
+}
+
+pub(crate) fn utf16(p: LPCWSTR) -> String {
+	::utf::w2s(p)
+}
+
+pub(crate) fn utf16n(p: LPCWSTR, n: UINT) -> String {
+	::utf::w2sn(p, n as usize)
+}
+
+fn select(text: &str) -> Result<Selector, SCDOM_RESULT> {
+	Selector::parse(text).ok_or(SCDOM_RESULT::INVALID_PARAMETER)
+}
+
+
+pub(crate) extern "system" fn Sciter_UseElement(he: HELEMENT) -> SCDOM_RESULT {
+	check!(element(he, |dom, uid| dom.use_ref(uid)));
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn Sciter_UnuseElement(he: HELEMENT) -> SCDOM_RESULT {
+	check!(element(he, |dom, uid| dom.unuse_ref(uid)));
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterGetRootElement(hwnd: HWINDOW, phe: *mut HELEMENT) -> SCDOM_RESULT {
+	let root = with_dom(|dom| dom.windows.get(&id_of(hwnd)).map(|w| w.root));
+	match root {
+		Some(root) => {
+			store(phe, he_of(root.unwrap_or(0)));
+			SCDOM_RESULT::OK
+		},
+		None => SCDOM_RESULT::INVALID_HWND,
+	}
+}
+
+pub(crate) extern "system" fn SciterGetFocusElement(hwnd: HWINDOW, phe: *mut HELEMENT) -> SCDOM_RESULT {
+	let focus = with_dom(|dom| dom.windows.get(&id_of(hwnd)).map(|w| w.focus));
+	match focus {
+		Some(focus) => {
+			store(phe, he_of(focus.unwrap_or(0)));
+			SCDOM_RESULT::OK
+		},
+		None => SCDOM_RESULT::INVALID_HWND,
+	}
+}
+
+pub(crate) extern "system" fn SciterFindElement(hwnd: HWINDOW, _pt: POINT, phe: *mut HELEMENT) -> SCDOM_RESULT {
+	// there is no layout, so everything is at the root
+	SciterGetRootElement(hwnd, phe)
+}
+
+pub(crate) extern "system" fn SciterGetChildrenCount(he: HELEMENT, count: *mut UINT) -> SCDOM_RESULT {
+	let n = check!(element(he, |dom, uid| dom.elem(uid).unwrap().children().len()));
+	store(count, n as UINT);
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterGetNthChild(he: HELEMENT, n: UINT, phe: *mut HELEMENT) -> SCDOM_RESULT {
+	let child = check!(element(he, |dom, uid| dom.elem(uid).unwrap().children().get(n as usize).cloned()));
+	match child {
+		Some(child) => {
+			store(phe, he_of(child));
+			SCDOM_RESULT::OK
+		},
+		None => SCDOM_RESULT::INVALID_PARAMETER,
+	}
+}
+
+pub(crate) extern "system" fn SciterGetParentElement(he: HELEMENT, p_parent_he: *mut HELEMENT) -> SCDOM_RESULT {
+	let parent = check!(element(he, |dom, uid| dom.elem(uid).unwrap().parent));
+	store(p_parent_he, he_of(parent.unwrap_or(0)));
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterGetElementHtmlCB(he: HELEMENT, outer: BOOL, rcv: LPCBYTE_RECEIVER, rcv_param: LPVOID) -> SCDOM_RESULT {
+	let text = check!(element(he, |dom, uid| dom.html(uid, outer != 0)));
+	rcv(text.as_ptr(), text.len() as UINT, rcv_param);
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterGetElementTextCB(he: HELEMENT, rcv: LPCWSTR_RECEIVER, rcv_param: LPVOID) -> SCDOM_RESULT {
+	let text = check!(element(he, |dom, uid| dom.text(uid)));
+	send_wide(&text, rcv, rcv_param);
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterSetElementText(he: HELEMENT, utf16: LPCWSTR, length: UINT) -> SCDOM_RESULT {
+	let text = utf16n(utf16, length);
+	check!(element(he, |dom, uid| {
+		let nodes = if text.is_empty() { Vec::new() } else { vec![Node::Text(text)] };
+		dom.set_nodes(uid, nodes);
+	}));
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterGetAttributeCount(he: HELEMENT, p_count: LPUINT) -> SCDOM_RESULT {
+	let n = check!(element(he, |dom, uid| dom.elem(uid).unwrap().attrs.len()));
+	store(p_count, n as UINT);
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterGetNthAttributeNameCB(he: HELEMENT, n: UINT, rcv: LPCSTR_RECEIVER, rcv_param: LPVOID) -> SCDOM_RESULT {
+	let name = check!(element(he, |dom, uid| dom.elem(uid).unwrap().attrs.get(n as usize).map(|a| a.0.clone())));
+	match name {
+		Some(name) => {
+			send_utf8(&name, rcv, rcv_param);
+			SCDOM_RESULT::OK
+		},
+		None => SCDOM_RESULT::INVALID_PARAMETER,
+	}
+}
+
+pub(crate) extern "system" fn SciterGetNthAttributeValueCB(he: HELEMENT, n: UINT, rcv: LPCWSTR_RECEIVER, rcv_param: LPVOID) -> SCDOM_RESULT {
+	let value = check!(element(he, |dom, uid| dom.elem(uid).unwrap().attrs.get(n as usize).map(|a| a.1.clone())));
+	match value {
+		Some(value) => {
+			send_wide(&value, rcv, rcv_param);
+			SCDOM_RESULT::OK
+		},
+		None => SCDOM_RESULT::INVALID_PARAMETER,
+	}
+}
+
+pub(crate) extern "system" fn SciterGetAttributeByNameCB(he: HELEMENT, name: LPCSTR, rcv: LPCWSTR_RECEIVER, rcv_param: LPVOID) -> SCDOM_RESULT {
+	let name = utf8(name);
+	let value = check!(element(he, |dom, uid| dom.elem(uid).unwrap().attribute(&name).map(|s| s.to_owned())));
+	match value {
+		Some(value) => {
+			send_wide(&value, rcv, rcv_param);
+			SCDOM_RESULT::OK
+		},
+		None => SCDOM_RESULT::OK_NOT_HANDLED,
+	}
+}
+
+pub(crate) extern "system" fn SciterSetAttributeByName(he: HELEMENT, name: LPCSTR, value: LPCWSTR) -> SCDOM_RESULT {
+	let name = utf8(name);
+	if name.is_empty() {
+		return SCDOM_RESULT::INVALID_PARAMETER;
+	}
+	let value = if value.is_null() { None } else { Some(utf16(value)) };
+	check!(element(he, |dom, uid| {
+		let attrs = &mut dom.elem_mut(uid).unwrap().attrs;
+		match value {
+			Some(value) => {
+				if let Some(a) = attrs.iter_mut().find(|a| a.0 == name) {
+					a.1 = value;
+				} else {
+					attrs.push((name, value));
+				}
+			},
+			None => attrs.retain(|a| a.0 != name),
+		}
+	}));
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterClearAttributes(he: HELEMENT) -> SCDOM_RESULT {
+	check!(element(he, |dom, uid| dom.elem_mut(uid).unwrap().attrs.clear()));
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterGetElementIndex(he: HELEMENT, p_index: LPUINT) -> SCDOM_RESULT {
+	let index = check!(element(he, |dom, uid| {
+		let parent = dom.elem(uid).unwrap().parent;
+		parent.and_then(|p| dom.elem(p)).and_then(|p| p.children().iter().position(|&x| x == uid)).unwrap_or(0)
+	}));
+	store(p_index, index as UINT);
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterGetElementType(he: HELEMENT, p_type: *mut LPCSTR) -> SCDOM_RESULT {
+	// the tag name is kept alive by the element
+	let tag = check!(element(he, |dom, uid| dom.elem(uid).unwrap().tag.as_ptr()));
+	store(p_type, tag);
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterGetElementTypeCB(he: HELEMENT, rcv: LPCSTR_RECEIVER, rcv_param: LPVOID) -> SCDOM_RESULT {
+	let tag = check!(element(he, |dom, uid| dom.elem(uid).unwrap().tag().to_owned()));
+	send_utf8(&tag, rcv, rcv_param);
+	SCDOM_RESULT::OK
+}
+
+/// Style attribute either set explicitly or declared in the inline style.
+fn style_of(e: &Elem, name: &str) -> Option<String> {
+	if let Some(s) = e.styles.iter().find(|s| s.0 == name) {
+		return Some(s.1.clone());
+	}
+	e.attribute("style").and_then(|style| {
+		style.rsplit(';')
+			.filter_map(|decl| {
+				let mut parts = decl.splitn(2, ':');
+				Some((parts.next()?.trim(), parts.next()?.trim()))
+			})
+			.find(|&(key, _)| key == name)
+			.map(|(_, value)| value.to_owned())
+	})
+}
+
+pub(crate) extern "system" fn SciterGetStyleAttributeCB(he: HELEMENT, name: LPCSTR, rcv: LPCWSTR_RECEIVER, rcv_param: LPVOID) -> SCDOM_RESULT {
+	let name = utf8(name);
+	let value = check!(element(he, |dom, uid| style_of(dom.elem(uid).unwrap(), &name)));
+	match value {
+		Some(value) => {
+			send_wide(&value, rcv, rcv_param);
+			SCDOM_RESULT::OK
+		},
+		None => SCDOM_RESULT::OK_NOT_HANDLED,
+	}
+}
+
+pub(crate) extern "system" fn SciterSetStyleAttribute(he: HELEMENT, name: LPCSTR, value: LPCWSTR) -> SCDOM_RESULT {
+	let name = utf8(name);
+	let value = if value.is_null() { None } else { Some(utf16(value)) };
+	check!(element(he, |dom, uid| {
+		let styles = &mut dom.elem_mut(uid).unwrap().styles;
+		styles.retain(|s| s.0 != name);
+		if let Some(value) = value {
+			styles.push((name, value));
+		}
+	}));
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterGetElementLocation(he: HELEMENT, p_location: LPRECT, _areas: UINT) -> SCDOM_RESULT {
+	check!(element(he, |_, _| ()));
+	store(p_location, RECT::default());
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterScrollToView(he: HELEMENT, _flags: UINT) -> SCDOM_RESULT {
+	check!(element(he, |_, _| ()));
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterUpdateElement(he: HELEMENT, _andForceRender: BOOL) -> SCDOM_RESULT {
+	check!(element(he, |_, _| ()));
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterRefreshElementArea(he: HELEMENT, _rc: RECT) -> SCDOM_RESULT {
+	check!(element(he, |_, _| ()));
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterSetCapture(he: HELEMENT) -> SCDOM_RESULT {
+	check!(element(he, |_, _| ()));
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterReleaseCapture(he: HELEMENT) -> SCDOM_RESULT {
+	check!(element(he, |_, _| ()));
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterGetElementHwnd(he: HELEMENT, p_hwnd: *mut HWINDOW, rootWindow: BOOL) -> SCDOM_RESULT {
+	let hwnd = check!(element(he, |dom, uid| {
+		let own = dom.elem(uid).unwrap().hwnd;
+		if rootWindow == 0 && own != 0 {
+			own
+		} else {
+			dom.window_of(uid).unwrap_or(0)
+		}
+	}));
+	store(p_hwnd, hwnd_of(hwnd));
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterCombineURL(he: HELEMENT, _szUrlBuffer: LPWSTR, _UrlBufferSize: UINT) -> SCDOM_RESULT {
+	// URLs are kept as is
+	check!(element(he, |_, _| ()));
+	SCDOM_RESULT::OK
+}
+
+fn select_elements(he: HELEMENT, selector: &str, callback: SciterElementCallback, param: LPVOID) -> SCDOM_RESULT {
+	let selector = check!(select(selector));
+	let found = check!(element(he, |dom, uid| {
+		let mut all = Vec::new();
+		dom.descendants(uid, &mut all);
+		all.retain(|&e| selector.matches(&*dom, e));
+		all
+	}));
+	for uid in found {
+		if callback(he_of(uid), param) != 0 {
+			break;
+		}
+	}
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterSelectElements(he: HELEMENT, CSS_selectors: LPCSTR, callback: SciterElementCallback, param: LPVOID) -> SCDOM_RESULT {
+	select_elements(he, &utf8(CSS_selectors), callback, param)
+}
+
+pub(crate) extern "system" fn SciterSelectElementsW(he: HELEMENT, CSS_selectors: LPCWSTR, callback: SciterElementCallback, param: LPVOID) -> SCDOM_RESULT {
+	select_elements(he, &utf16(CSS_selectors), callback, param)
+}
+
+fn select_parent(he: HELEMENT, selector: &str, depth: UINT, found: *mut HELEMENT) -> SCDOM_RESULT {
+	let selector = check!(select(selector));
+	let parent = check!(element(he, |dom, uid| {
+		let mut p = Some(uid);
+		let mut level = 0;
+		while let Some(x) = p {
+			if depth != 0 && level >= depth {
+				break;
+			}
+			if selector.matches(&*dom, x) {
+				return Some(x);
+			}
+			p = dom.elem(x).and_then(|e| e.parent);
+			level += 1;
+		}
+		None
+	}));
+	store(found, he_of(parent.unwrap_or(0)));
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterSelectParent(he: HELEMENT, selector: LPCSTR, depth: UINT, heFound: *mut HELEMENT) -> SCDOM_RESULT {
+	select_parent(he, &utf8(selector), depth, heFound)
+}
+
+pub(crate) extern "system" fn SciterSelectParentW(he: HELEMENT, selector: LPCWSTR, depth: UINT, heFound: *mut HELEMENT) -> SCDOM_RESULT {
+	select_parent(he, &utf16(selector), depth, heFound)
+}
+
+pub(crate) extern "system" fn SciterSetElementHtml(he: HELEMENT, html: *const BYTE, htmlLength: UINT, how: UINT) -> SCDOM_RESULT {
+	let text = if html.is_null() {
+		String::new()
+	} else {
+		String::from_utf8_lossy(unsafe { ::std::slice::from_raw_parts(html, htmlLength as usize) }).into_owned()
+	};
+	let nodes = html::parse(&text);
+	let r = element(he, |dom, uid| {
+		let mut created = Vec::new();
+		let nodes = dom.build(nodes, &mut created);
+		const REPLACE_CONTENT: UINT = SET_ELEMENT_HTML::SIH_REPLACE_CONTENT as UINT;
+		const INSERT_AT_START: UINT = SET_ELEMENT_HTML::SIH_INSERT_AT_START as UINT;
+		const APPEND_AFTER_LAST: UINT = SET_ELEMENT_HTML::SIH_APPEND_AFTER_LAST as UINT;
+		const REPLACE: UINT = SET_ELEMENT_HTML::SOH_REPLACE as UINT;
+		const INSERT_BEFORE: UINT = SET_ELEMENT_HTML::SOH_INSERT_BEFORE as UINT;
+		const INSERT_AFTER: UINT = SET_ELEMENT_HTML::SOH_INSERT_AFTER as UINT;
+		let ok = match how {
+			REPLACE_CONTENT => {
+				dom.set_nodes(uid, nodes);
+				Ok(())
+			},
+			INSERT_AT_START => {
+				dom.splice(uid, 0, nodes);
+				Ok(())
+			},
+			APPEND_AFTER_LAST => {
+				dom.splice(uid, usize::MAX, nodes);
+				Ok(())
+			},
+			REPLACE | INSERT_BEFORE | INSERT_AFTER => {
+				let parent = dom.elem(uid).unwrap().parent;
+				match parent {
+					Some(parent) => {
+						let pos = dom.elem(parent).unwrap().nodes.iter().position(|n| match *n {
+							Node::Element(x) => x == uid,
+							_ => false,
+						}).unwrap_or(0);
+						let pos = if how == INSERT_AFTER { pos + 1 } else { pos };
+						dom.splice(parent, pos, nodes);
+						if how == REPLACE {
+							dom.detach(uid);
+							dom.collect(uid);
+						}
+						Ok(())
+					},
+					None => Err((SCDOM_RESULT::OPERATION_FAILED, nodes)),
+				}
+			},
+			_ => Err((SCDOM_RESULT::INVALID_PARAMETER, nodes)),
+		};
+		match ok {
+			Ok(()) => Ok((dom.window_of(created.first().cloned().unwrap_or(0)), created)),
+			Err((e, nodes)) => {
+				// release the unused elements
+				for n in nodes {
+					if let Node::Element(x) = n {
+						dom.collect(x);
+					}
+				}
+				Err(e)
+			},
+		}
+	});
+	let (window, created) = check!(check!(r));
+	if let Some(id) = window {
+		super::host::attach_behaviors(id, &created);
+	}
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterGetElementUID(he: HELEMENT, puid: *mut UINT) -> SCDOM_RESULT {
+	let uid = check!(element(he, |_, uid| uid));
+	store(puid, uid);
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterGetElementByUID(hwnd: HWINDOW, uid: UINT, phe: *mut HELEMENT) -> SCDOM_RESULT {
+	let found = with_dom(|dom| dom.window_of(uid) == Some(id_of(hwnd)));
+	if found {
+		store(phe, he_of(uid));
+		SCDOM_RESULT::OK
+	} else {
+		SCDOM_RESULT::INVALID_PARAMETER
+	}
+}
+
+pub(crate) extern "system" fn SciterShowPopup(hePopup: HELEMENT, heAnchor: HELEMENT, _placement: UINT) -> SCDOM_RESULT {
+	check!(element(heAnchor, |_, _| ()));
+	check!(element(hePopup, |dom, uid| dom.elem_mut(uid).unwrap().state |= ELEMENT_STATE_BITS::STATE_POPUP as UINT));
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterShowPopupAt(hePopup: HELEMENT, _pos: POINT, _placement: UINT) -> SCDOM_RESULT {
+	check!(element(hePopup, |dom, uid| dom.elem_mut(uid).unwrap().state |= ELEMENT_STATE_BITS::STATE_POPUP as UINT));
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterHidePopup(he: HELEMENT) -> SCDOM_RESULT {
+	check!(element(he, |dom, uid| dom.elem_mut(uid).unwrap().state &= !(ELEMENT_STATE_BITS::STATE_POPUP as UINT)));
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterGetElementState(he: HELEMENT, pstateBits: *mut UINT) -> SCDOM_RESULT {
+	let state = check!(element(he, |dom, uid| dom.elem(uid).unwrap().state));
+	store(pstateBits, state);
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterSetElementState(he: HELEMENT, stateBitsToSet: UINT, stateBitsToClear: UINT, _updateView: BOOL) -> SCDOM_RESULT {
+	check!(element(he, |dom, uid| {
+		let e = dom.elem_mut(uid).unwrap();
+		e.state = (e.state & !stateBitsToClear) | stateBitsToSet;
+		if stateBitsToSet & ELEMENT_STATE_BITS::STATE_FOCUS as UINT != 0 {
+			if let Some(id) = dom.window_of(uid) {
+				dom.windows.get_mut(&id).unwrap().focus = Some(uid);
+			}
+		}
+	}));
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterCreateElement(tagname: LPCSTR, textOrNull: LPCWSTR, phe: *mut HELEMENT) -> SCDOM_RESULT {
+	let tag = utf8(tagname);
+	if tag.is_empty() || phe.is_null() {
+		return SCDOM_RESULT::INVALID_PARAMETER;
+	}
+	let text = utf16(textOrNull);
+	let uid = with_dom(|dom| {
+		let uid = dom.create(&tag);
+		let e = dom.elem_mut(uid).unwrap();
+		// the caller owns the new element
+		e.refs = 1;
+		if !text.is_empty() {
+			e.nodes.push(Node::Text(text));
+		}
+		uid
+	});
+	store(phe, he_of(uid));
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterCloneElement(he: HELEMENT, phe: *mut HELEMENT) -> SCDOM_RESULT {
+	let copy = check!(element(he, |dom, uid| {
+		let copy = dom.clone_tree(uid).unwrap();
+		dom.elem_mut(copy).unwrap().refs = 1;
+		copy
+	}));
+	store(phe, he_of(copy));
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterInsertElement(he: HELEMENT, hparent: HELEMENT, index: UINT) -> SCDOM_RESULT {
+	check!(element(hparent, |_, _| ()));
+	let (ok, window) = check!(element(he, |dom, uid| {
+		let ok = dom.insert(uid, uid_of(hparent), index as usize);
+		let mut subtree = vec![uid];
+		dom.descendants(uid, &mut subtree);
+		(ok, dom.window_of(uid).map(|id| (id, subtree)))
+	}));
+	if ok == SCDOM_RESULT::OK {
+		if let Some((id, subtree)) = window {
+			super::host::attach_behaviors(id, &subtree);
+		}
+	}
+	ok
+}
+
+pub(crate) extern "system" fn SciterDetachElement(he: HELEMENT) -> SCDOM_RESULT {
+	check!(element(he, |dom, uid| {
+		dom.detach(uid);
+		dom.collect(uid);
+	}));
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterDeleteElement(he: HELEMENT) -> SCDOM_RESULT {
+	check!(element(he, |dom, uid| dom.destroy(uid)));
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterSetTimer(he: HELEMENT, milliseconds: UINT, timer_id: UINT_PTR) -> SCDOM_RESULT {
+	let uid = check!(element(he, |_, uid| uid));
+	TIMERS.with(|t| {
+		let mut t = t.borrow_mut();
+		let now = t.0;
+		t.1.retain(|x| !(x.uid == uid && x.id == timer_id));
+		if milliseconds != 0 {
+			t.1.push(Timer { uid, id: timer_id, period: milliseconds, due: now + milliseconds as u64 });
+		}
+	});
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterDetachEventHandler(he: HELEMENT, pep: ElementEventProc, tag: LPVOID) -> SCDOM_RESULT {
+	check!(element(he, |_, _| ()));
+	detach(Owner::Element(uid_of(he)), pep, tag)
+}
+
+pub(crate) extern "system" fn SciterAttachEventHandler(he: HELEMENT, pep: ElementEventProc, tag: LPVOID) -> SCDOM_RESULT {
+	check!(element(he, |_, _| ()));
+	attach(Owner::Element(uid_of(he)), pep, tag, None)
+}
+
+pub(crate) extern "system" fn SciterWindowAttachEventHandler(hwndLayout: HWINDOW, pep: ElementEventProc, tag: LPVOID, subscription: UINT) -> SCDOM_RESULT {
+	attach(Owner::Window(id_of(hwndLayout)), pep, tag, Some(subscription))
+}
+
+pub(crate) extern "system" fn SciterWindowDetachEventHandler(hwndLayout: HWINDOW, pep: ElementEventProc, tag: LPVOID) -> SCDOM_RESULT {
+	detach(Owner::Window(id_of(hwndLayout)), pep, tag)
+}
+
+pub(crate) extern "system" fn SciterSendEvent(he: HELEMENT, appEventCode: UINT, heSource: HELEMENT, reason: UINT_PTR, handled: *mut BOOL) -> SCDOM_RESULT {
+	check!(element(he, |_, _| ()));
+	let mut params = BEHAVIOR_EVENT_PARAMS {
+		cmd: appEventCode,
+		heTarget: he,
+		he: heSource,
+		reason,
+		data: VALUE::new(),
+		name: ::std::ptr::null(),
+	};
+	let yes = send_behavior_event(&mut params);
+	drop(Slot::adopt(params.data));
+	store(handled, yes as BOOL);
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterPostEvent(he: HELEMENT, appEventCode: UINT, heSource: HELEMENT, reason: UINT_PTR) -> SCDOM_RESULT {
+	check!(element(he, |_, _| ()));
+	let event = Posted {
+		cmd: appEventCode,
+		target: uid_of(he),
+		source: uid_of(heSource),
+		reason,
+		data: Slot::new(),
+		name: None,
+	};
+	POSTED.with(|q| q.borrow_mut().push_back(event));
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterFireEvent(evt: *const BEHAVIOR_EVENT_PARAMS, post: BOOL, handled: *mut BOOL) -> SCDOM_RESULT {
+	if evt.is_null() {
+		return SCDOM_RESULT::INVALID_PARAMETER;
+	}
+	let evt = unsafe { &*evt };
+	check!(element(evt.heTarget, |_, _| ()));
+	let name = if evt.name.is_null() { None } else { Some(wide(&utf16(evt.name))) };
+	if post != 0 {
+		let event = Posted {
+			cmd: evt.cmd,
+			target: uid_of(evt.heTarget),
+			source: uid_of(evt.he),
+			reason: evt.reason,
+			data: Slot::copy(&evt.data),
+			name,
+		};
+		POSTED.with(|q| q.borrow_mut().push_back(event));
+		store(handled, false as BOOL);
+		return SCDOM_RESULT::OK;
+	}
+	let data = Slot::copy(&evt.data);
+	let mut params = BEHAVIOR_EVENT_PARAMS {
+		cmd: evt.cmd,
+		heTarget: evt.heTarget,
+		he: evt.he,
+		reason: evt.reason,
+		data: data.0.clone(),
+		name: name.as_ref().map_or(::std::ptr::null(), |n| n.as_ptr()),
+	};
+	let yes = send_behavior_event(&mut params);
+	store(handled, yes as BOOL);
+	SCDOM_RESULT::OK
+}
+
+/// Stored value of the element or its text.
+fn default_value(dom: &Dom, uid: u32) -> Slot {
+	match dom.elem(uid).unwrap().value {
+		Some(ref v) => v.clone(),
+		None => Slot::string(&dom.text(uid), 0),
+	}
+}
+
+pub(crate) extern "system" fn SciterCallBehaviorMethod(he: HELEMENT, params: *const METHOD_PARAMS) -> SCDOM_RESULT {
+	if params.is_null() {
+		return SCDOM_RESULT::INVALID_PARAMETER;
+	}
+	let listeners = check!(element(he, |dom, uid| dom.element_listeners(uid, EVENT_GROUPS::HANDLE_METHOD_CALL)));
+	if notify(listeners, EVENT_GROUPS::HANDLE_METHOD_CALL, params as LPVOID) {
+		return SCDOM_RESULT::OK;
+	}
+
+	// intrinsic behavior of the plain elements
+	let method = unsafe { (*params).method };
+	const GET_VALUE: UINT = BEHAVIOR_METHOD_IDENTIFIERS::GET_VALUE as UINT;
+	const SET_VALUE: UINT = BEHAVIOR_METHOD_IDENTIFIERS::SET_VALUE as UINT;
+	const IS_EMPTY: UINT = BEHAVIOR_METHOD_IDENTIFIERS::IS_EMPTY as UINT;
+	match method {
+		GET_VALUE => {
+			let params = params as *mut VALUE_PARAMS;
+			let value = check!(element(he, |dom, uid| default_value(dom, uid)));
+			value.store(unsafe { &mut (*params).value });
+			SCDOM_RESULT::OK
+		},
+		SET_VALUE => {
+			let params = params as *const VALUE_PARAMS;
+			let value = Slot::copy(unsafe { &(*params).value });
+			check!(element(he, |dom, uid| {
+				let prev = dom.elem_mut(uid).unwrap().value.replace(value);
+				dom.garbage.extend(prev);
+			}));
+			SCDOM_RESULT::OK
+		},
+		IS_EMPTY => {
+			let params = params as *mut IS_EMPTY_PARAMS;
+			let empty = check!(element(he, |dom, uid| dom.elem(uid).unwrap().nodes.is_empty()));
+			unsafe { (*params).is_empty = empty as UINT };
+			SCDOM_RESULT::OK
+		},
+		_ => SCDOM_RESULT::OK_NOT_HANDLED,
+	}
+}
+
+pub(crate) extern "system" fn SciterRequestElementData(he: HELEMENT, _url: LPCWSTR, _dataType: UINT, _initiator: HELEMENT) -> SCDOM_RESULT {
+	check!(element(he, |_, _| ()));
+	SCDOM_RESULT::OPERATION_FAILED
+}
+
+pub(crate) extern "system" fn SciterHttpRequest(he: HELEMENT, _url: LPCWSTR, _dataType: UINT, _requestType: UINT, _requestParams: *const ::capi::screquest::REQUEST_PARAM, _nParams: UINT) -> SCDOM_RESULT {
+	check!(element(he, |_, _| ()));
+	SCDOM_RESULT::OPERATION_FAILED
+}
+
+pub(crate) extern "system" fn SciterGetScrollInfo(he: HELEMENT, scrollPos: LPPOINT, viewRect: LPRECT, contentSize: LPSIZE) -> SCDOM_RESULT {
+	check!(element(he, |_, _| ()));
+	store(scrollPos, POINT::default());
+	store(viewRect, RECT::default());
+	store(contentSize, SIZE::default());
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterSetScrollPos(he: HELEMENT, _scrollPos: POINT, _smooth: BOOL) -> SCDOM_RESULT {
+	check!(element(he, |_, _| ()));
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterGetElementIntrinsicWidths(he: HELEMENT, pMinWidth: *mut INT, pMaxWidth: *mut INT) -> SCDOM_RESULT {
+	check!(element(he, |_, _| ()));
+	store(pMinWidth, 0);
+	store(pMaxWidth, 0);
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterGetElementIntrinsicHeight(he: HELEMENT, _forWidth: INT, pHeight: *mut INT) -> SCDOM_RESULT {
+	check!(element(he, |_, _| ()));
+	store(pHeight, 0);
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterIsElementVisible(he: HELEMENT, pVisible: *mut BOOL) -> SCDOM_RESULT {
+	let visible = check!(element(he, |dom, uid| {
+		dom.path(uid).iter().all(|&x| {
+			let e = dom.elem(x).unwrap();
+			style_of(e, "display").as_deref() != Some("none")
+				&& style_of(e, "visibility").as_deref() != Some("hidden")
+		})
+	}));
+	store(pVisible, visible as BOOL);
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterIsElementEnabled(he: HELEMENT, pEnabled: *mut BOOL) -> SCDOM_RESULT {
+	let enabled = check!(element(he, |dom, uid| {
+		dom.path(uid).iter().all(|&x| dom.elem(x).unwrap().state & ELEMENT_STATE_BITS::STATE_DISABLED as UINT == 0)
+	}));
+	store(pEnabled, enabled as BOOL);
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterSortElements(he: HELEMENT, _firstIndex: UINT, _lastIndex: UINT, _cmpFunc: *mut ELEMENT_COMPARATOR, _cmpFuncParam: LPVOID) -> SCDOM_RESULT {
+	check!(element(he, |_, _| ()));
+	SCDOM_RESULT::OPERATION_FAILED
+}
+
+pub(crate) extern "system" fn SciterSwapElements(he1: HELEMENT, he2: HELEMENT) -> SCDOM_RESULT {
+	check!(element(he2, |_, _| ()));
+	check!(element(he1, |dom, a| {
+		let b = uid_of(he2);
+		let pa = dom.elem(a).unwrap().parent;
+		let pb = dom.elem(b).unwrap().parent;
+		let (pa, pb) = match (pa, pb) {
+			(Some(pa), Some(pb)) => (pa, pb),
+			_ => return Err(SCDOM_RESULT::INVALID_PARAMETER),
+		};
+		for &(p, from, to) in &[(pa, a, b), (pb, b, a)] {
+			let e = dom.elem_mut(p).unwrap();
+			if let Some(n) = e.nodes.iter().position(|n| match *n { Node::Element(x) => x == from, _ => false }) {
+				e.nodes[n] = Node::Element(to);
+			}
+			if pa == pb {
+				break;
+			}
+		}
+		if pa == pb {
+			// both are in the same list, swap the remaining one
+			let e = dom.elem_mut(pa).unwrap();
+			if let Some(n) = e.nodes.iter().rposition(|n| match *n { Node::Element(x) => x == b, _ => false }) {
+				let first = e.nodes.iter().position(|n| match *n { Node::Element(x) => x == b, _ => false });
+				if first != Some(n) {
+					e.nodes[n] = Node::Element(a);
+				}
+			}
+		}
+		dom.elem_mut(a).unwrap().parent = Some(pb);
+		dom.elem_mut(b).unwrap().parent = Some(pa);
+		Ok(())
+	}).and_then(|r| r));
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterTraverseUIEvent(_evt: UINT, _eventCtlStruct: LPVOID, bOutProcessed: *mut BOOL) -> SCDOM_RESULT {
+	store(bOutProcessed, false as BOOL);
+	SCDOM_RESULT::OPERATION_FAILED
+}
+
+fn unpack(argv: *const VALUE, argc: UINT) -> Vec<Slot> {
+	if argv.is_null() {
+		return Vec::new();
+	}
+	let args = unsafe { ::std::slice::from_raw_parts(argv, argc as usize) };
+	args.iter().map(Slot::copy).collect()
+}
+
+pub(crate) extern "system" fn SciterCallScriptingMethod(he: HELEMENT, name: LPCSTR, argv: *const VALUE, argc: UINT, retval: *mut VALUE) -> SCDOM_RESULT {
+	let uid = check!(element(he, |_, uid| uid));
+	let name = utf8(name);
+	match call_scripting_method(Owner::Element(uid), &name, &unpack(argv, argc)) {
+		Some(rv) => {
+			rv.store(retval);
+			SCDOM_RESULT::OK
+		},
+		None => {
+			Slot::error(&format!("{} is not a function", name)).store(retval);
+			SCDOM_RESULT::OPERATION_FAILED
+		},
+	}
+}
+
+pub(crate) extern "system" fn SciterCallScriptingFunction(he: HELEMENT, name: LPCSTR, argv: *const VALUE, argc: UINT, retval: *mut VALUE) -> SCDOM_RESULT {
+	let window = check!(element(he, |dom, uid| dom.window_of(uid)));
+	let name = utf8(name);
+	match super::host::call_function(window, &name, &unpack(argv, argc)) {
+		Ok(rv) => {
+			rv.store(retval);
+			SCDOM_RESULT::OK
+		},
+		Err(error) => {
+			error.store(retval);
+			SCDOM_RESULT::OPERATION_FAILED
+		},
+	}
+}
+
+pub(crate) extern "system" fn SciterEvalElementScript(he: HELEMENT, script: LPCWSTR, scriptLength: UINT, retval: *mut VALUE) -> SCDOM_RESULT {
+	let (uid, window) = check!(element(he, |dom, uid| (uid, dom.window_of(uid))));
+	let script = utf16n(script, scriptLength);
+	match super::host::eval(window, Some(uid), &script) {
+		Ok(rv) => {
+			rv.store(retval);
+			SCDOM_RESULT::OK
+		},
+		Err(error) => {
+			error.store(retval);
+			SCDOM_RESULT::OPERATION_FAILED
+		},
+	}
+}
+
+pub(crate) extern "system" fn SciterAttachHwndToElement(he: HELEMENT, hwnd: HWINDOW) -> SCDOM_RESULT {
+	check!(element(he, |dom, uid| dom.elem_mut(uid).unwrap().hwnd = id_of(hwnd)));
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterControlGetType(he: HELEMENT, pType: *mut UINT) -> SCDOM_RESULT {
+	check!(element(he, |_, _| ()));
+	// CTL_NO
+	store(pType, 0);
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterGetValue(he: HELEMENT, pval: *mut VALUE) -> SCDOM_RESULT {
+	if pval.is_null() {
+		return SCDOM_RESULT::INVALID_PARAMETER;
+	}
+	let mut params = VALUE_PARAMS { method: BEHAVIOR_METHOD_IDENTIFIERS::GET_VALUE as UINT, value: VALUE::new() };
+	let ok = SciterCallBehaviorMethod(he, &mut params as *mut VALUE_PARAMS as *const METHOD_PARAMS);
+	Slot::adopt(params.value).store(pval);
+	ok
+}
+
+pub(crate) extern "system" fn SciterSetValue(he: HELEMENT, pval: *const VALUE) -> SCDOM_RESULT {
+	if pval.is_null() {
+		return SCDOM_RESULT::INVALID_PARAMETER;
+	}
+	let value = Slot::copy(unsafe { &*pval });
+	let params = VALUE_PARAMS { method: BEHAVIOR_METHOD_IDENTIFIERS::SET_VALUE as UINT, value: value.0.clone() };
+	SciterCallBehaviorMethod(he, &params as *const VALUE_PARAMS as *const METHOD_PARAMS)
+}
+
+pub(crate) extern "system" fn SciterGetExpando(he: HELEMENT, pval: *mut VALUE, forceCreation: BOOL) -> SCDOM_RESULT {
+	let expando = check!(element(he, |dom, uid| {
+		let e = dom.elem_mut(uid).unwrap();
+		if e.expando.is_none() && forceCreation != 0 {
+			e.expando = Some(Slot::map(Vec::new()));
+		}
+		e.expando.clone()
+	}));
+	match expando {
+		Some(v) => {
+			v.store(pval);
+			SCDOM_RESULT::OK
+		},
+		None => SCDOM_RESULT::OK_NOT_HANDLED,
+	}
+}
+
+pub(crate) extern "system" fn SciterGetObject(_he: HELEMENT, _pval: *mut tiscript_value, _forceCreation: BOOL) -> SCDOM_RESULT {
+	SCDOM_RESULT::OPERATION_FAILED
+}
+
+pub(crate) extern "system" fn SciterGetElementNamespace(_he: HELEMENT, _pval: *mut tiscript_value) -> SCDOM_RESULT {
+	SCDOM_RESULT::OPERATION_FAILED
+}
+
+pub(crate) extern "system" fn SciterGetHighlightedElement(hwnd: HWINDOW, phe: *mut HELEMENT) -> SCDOM_RESULT {
+	let found = with_dom(|dom| dom.windows.get(&id_of(hwnd)).map(|w| w.highlighted));
+	match found {
+		Some(e) => {
+			store(phe, he_of(e.unwrap_or(0)));
+			SCDOM_RESULT::OK
+		},
+		None => SCDOM_RESULT::INVALID_HWND,
+	}
+}
+
+pub(crate) extern "system" fn SciterSetHighlightedElement(hwnd: HWINDOW, he: HELEMENT) -> SCDOM_RESULT {
+	let uid = if he.is_null() { None } else { Some(uid_of(he)) };
+	let found = with_dom(|dom| dom.windows.get_mut(&id_of(hwnd)).map(|w| w.highlighted = uid));
+	if found.is_some() { SCDOM_RESULT::OK } else { SCDOM_RESULT::INVALID_HWND }
+}
+
+pub(crate) extern "system" fn SciterElementUnwrap(pval: *const VALUE, ppElement: *mut HELEMENT) -> SCDOM_RESULT {
+	if pval.is_null() {
+		return SCDOM_RESULT::INVALID_PARAMETER;
+	}
+	let v = unsafe { &*pval };
+	if v.t != VALUE_TYPE::T_DOM_OBJECT {
+		return SCDOM_RESULT::INVALID_PARAMETER;
+	}
+	let uid = v.d as u32;
+	if !with_dom(|dom| dom.elements.contains_key(&uid)) {
+		return SCDOM_RESULT::INVALID_HANDLE;
+	}
+	store(ppElement, he_of(uid));
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterElementWrap(pval: *mut VALUE, pElement: HELEMENT) -> SCDOM_RESULT {
+	if pval.is_null() {
+		return SCDOM_RESULT::INVALID_PARAMETER;
+	}
+	let uid = check!(element(pElement, |_, uid| uid));
+	let v = Slot::copy(&VALUE { t: VALUE_TYPE::T_DOM_OBJECT, u: 0, d: uid as u64 });
+	v.store(pval);
+	SCDOM_RESULT::OK
+}
+
+pub(crate) extern "system" fn SciterGetElementAsset(el: HELEMENT, _atomv: som_atom_t, pass: *mut *mut som_asset_t) -> SCDOM_RESULT {
+	let listeners = check!(element(el, |dom, uid| dom.element_listeners(uid, EVENT_GROUPS::HANDLE_SOM)));
+	let mut params = SOM_PARAMS {
+		cmd: SOM_EVENTS::SOM_GET_ASSET,
+		result: SOM_PARAMS_DATA { asset: ::std::ptr::null() },
+	};
+	if notify(listeners, EVENT_GROUPS::HANDLE_SOM, &mut params as *mut _ as LPVOID) {
+		let asset = unsafe { params.result.asset };
+		if !asset.is_null() {
+			store(pass, asset as *mut som_asset_t);
+			return SCDOM_RESULT::OK;
+		}
+	}
+	SCDOM_RESULT::OK_NOT_HANDLED
+}
@@ -0,0 +1,671 @@
+//! Window level functions of the fake engine: documents, host callbacks and scripting.
+//!
+//! There is no script engine, `SciterEval` understands only a tiny subset of it:
+//! JSON literals, variable paths and calls like `name(args)`, `view.method(args)`
+//! or `this.method(args)` (the latter two are dispatched to native event handlers).
+
+#![allow(non_snake_case)]
+
+use std::ffi::CString;
+
+use capi::sctypes::*;
+use capi::scdef::*;
+use capi::scdom::*;
+use capi::scvalue::*;
+use capi::scbehavior::*;
+use capi::sctiscript::{HVM, tiscript_value, tiscript_native_interface};
+use capi::scgraphics::SciterGraphicsAPI;
+use capi::screquest::{SciterRequestAPI, HREQUEST};
+use capi::scmsg::{SCITER_X_MSG, SCITER_X_MSG_CODE};
+use capi::scom::{som_asset_t, som_atom_t};
+
+use super::dom::{self, with_dom, Owner, Node, he_of, id_of, hwnd_of, utf8, utf16, utf16n, wide};
+use super::html;
+use super::value::{self, Slot};
+
+
+/// Window handle which is known to the engine.
+fn window(hwnd: HWINDOW) -> Option<usize> {
+	let id = id_of(hwnd);
+	if with_dom(|dom| dom.windows.contains_key(&id)) { Some(id) } else { None }
+}
+
+/// Send a notification to the host callback of the window.
+fn notify_host<T>(id: usize, scnm: &mut T) -> Option<UINT> {
+	let callback = with_dom(|dom| dom.windows.get(&id).and_then(|w| w.callback));
+	callback.map(|(cb, param)| cb(scnm as *mut T as LPSCITER_CALLBACK_NOTIFICATION, param as LPVOID))
+}
+
+extern "system" fn no_proc(_tag: LPVOID, _he: HELEMENT, _evtg: UINT, _prms: LPVOID) -> BOOL {
+	0
+}
+
+/// Names listed in the inline `behavior` style of the element.
+fn behaviors_of(style: &str) -> Vec<String> {
+	style.split(';')
+		.filter_map(|decl| {
+			let mut parts = decl.splitn(2, ':');
+			match (parts.next(), parts.next()) {
+				(Some(key), Some(value)) if key.trim() == "behavior" => Some(value),
+				_ => None,
+			}
+		})
+		.flat_map(|value| value.split_whitespace().map(|s| s.to_owned()).collect::<Vec<_>>())
+		.collect()
+}
+
+/// Ask the host to attach behaviors declared by the given elements.
+pub(crate) fn attach_behaviors(id: usize, elements: &[u32]) {
+	for &uid in elements {
+		let names = with_dom(|dom| dom.elem(uid).and_then(|e| e.attribute("style")).map(behaviors_of).unwrap_or_default());
+		for name in names {
+			let cname = CString::new(name).unwrap_or_default();
+			let mut scnm = SCN_ATTACH_BEHAVIOR {
+				code: SCITER_NOTIFICATION::SC_ATTACH_BEHAVIOR as UINT,
+				hwnd: hwnd_of(id),
+				element: he_of(uid),
+				name: cname.as_ptr(),
+				elementProc: no_proc,
+				elementTag: ::std::ptr::null_mut(),
+			};
+			let attached = notify_host(id, &mut scnm).unwrap_or(0) != 0;
+			if attached && scnm.elementProc as usize != no_proc as ElementEventProc as usize {
+				dom::attach(Owner::Element(uid), scnm.elementProc, scnm.elementTag, None);
+			}
+		}
+	}
+}
+
+/// Replace the window document with the given HTML.
+fn load_document(id: usize, text: &str, url: &str) {
+	let nodes = html::parse(text);
+	let (root, created) = with_dom(|dom| {
+		let mut created = Vec::new();
+		let mut nodes = dom.build(nodes, &mut created);
+		let single = nodes.len() == 1 && match nodes[0] {
+			Node::Element(uid) => dom.elem(uid).is_some_and(|e| e.tag() == "html"),
+			_ => false,
+		};
+		let root = match nodes.pop() {
+			Some(Node::Element(uid)) if single => uid,
+			last => {
+				nodes.extend(last);
+				let uid = dom.create("html");
+				created.insert(0, uid);
+				dom.splice(uid, 0, nodes);
+				uid
+			},
+		};
+		let prev = dom.windows.get_mut(&id).and_then(|w| {
+			w.url = url.to_owned();
+			w.focus = None;
+			w.highlighted = None;
+			w.root.replace(root)
+		});
+		dom.set_root_of(root, Some(id));
+		if let Some(prev) = prev {
+			dom.set_root_of(prev, None);
+		}
+		(root, created)
+	});
+	attach_behaviors(id, &created);
+
+	let mut params = BEHAVIOR_EVENT_PARAMS {
+		cmd: BEHAVIOR_EVENTS::DOCUMENT_COMPLETE as UINT,
+		heTarget: he_of(root),
+		he: he_of(root),
+		reason: 0,
+		data: VALUE::new(),
+		name: ::std::ptr::null(),
+	};
+	dom::send_behavior_event(&mut params);
+	drop(Slot::adopt(params.data));
+}
+
+/// Call a function stored in a variable.
+pub(crate) fn call_function(window: Option<usize>, name: &str, args: &[Slot]) -> Result<Slot, Slot> {
+	let func = with_dom(|dom| dom.variable(window, name));
+	match func.and_then(|f| value::invoke(&f, args)) {
+		Some(rv) => Ok(rv),
+		None => Err(Slot::error(&format!("{} is not a function", name))),
+	}
+}
+
+fn is_path(s: &str) -> bool {
+	!s.is_empty() && s.split('.').all(|part| {
+		let mut chars = part.chars();
+		chars.next().is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+			&& chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+	})
+}
+
+/// Evaluate the script in the window context.
+pub(crate) fn eval(window: Option<usize>, this: Option<u32>, script: &str) -> Result<Slot, Slot> {
+	let script = script.trim().trim_end_matches(';').trim();
+
+	// `path(args)`
+	if let Some(open) = script.find('(') {
+		let path = script[..open].trim();
+		if script.ends_with(')') && is_path(path) {
+			let args = match value::parse(&format!("[{}]", &script[open + 1 .. script.len() - 1])) {
+				Ok(list) => value::items(&list.0).into_iter().map(|pair| pair.1).collect::<Vec<_>>(),
+				Err(_) => return Err(Slot::error(&format!("syntax error: {}", script))),
+			};
+			let mut parts = path.splitn(2, '.');
+			let (head, method) = (parts.next().unwrap(), parts.next());
+			let owner = match (head, method) {
+				("view", Some(method)) => window.map(|id| (Owner::Window(id), method)),
+				("this", Some(method)) => this.map(|uid| (Owner::Element(uid), method)),
+				_ => None,
+			};
+			return match owner {
+				Some((owner, method)) => dom::call_scripting_method(owner, method, &args)
+					.ok_or_else(|| Slot::error(&format!("{} is not a function", path))),
+				None => call_function(window, path, &args),
+			};
+		}
+	}
+
+	if is_path(script) && !["null", "undefined", "true", "false"].contains(&script) {
+		return with_dom(|dom| dom.variable(window, script))
+			.ok_or_else(|| Slot::error(&format!("{} is not defined", script)));
+	}
+
+	value::parse(script).map_err(|_| Slot::error(&format!("syntax error: {}", script)))
+}
+
+
+pub(crate) extern "system" fn SciterClassName() -> LPCWSTR {
+	static NAME: [u16; 7] = [b'S' as u16, b'c' as u16, b'i' as u16, b't' as u16, b'e' as u16, b'r' as u16, 0];
+	NAME.as_ptr()
+}
+
+pub(crate) extern "system" fn SciterVersion(major: BOOL) -> UINT {
+	if major != 0 { 0x0004_0004 } else { 0x0008_0000 }
+}
+
+pub(crate) extern "system" fn SciterDataReady(hwnd: HWINDOW, uri: LPCWSTR, data: LPCBYTE, dataLength: UINT) -> BOOL {
+	let id = match window(hwnd) {
+		Some(id) => id,
+		None => return false as BOOL,
+	};
+	let uri = utf16(uri);
+	let data = if data.is_null() { Vec::new() } else { unsafe { ::std::slice::from_raw_parts(data, dataLength as usize) }.to_vec() };
+	with_dom(|dom| {
+		let ready = &mut dom.windows.get_mut(&id).unwrap().ready;
+		ready.retain(|r| r.0 != uri);
+		ready.push((uri, data));
+	});
+	true as BOOL
+}
+
+pub(crate) extern "system" fn SciterDataReadyAsync(hwnd: HWINDOW, uri: LPCWSTR, data: LPCBYTE, dataLength: UINT, _requestId: HREQUEST) -> BOOL {
+	SciterDataReady(hwnd, uri, data, dataLength)
+}
+
+pub(crate) extern "system" fn SciterProc(_hwnd: HWINDOW, _msg: UINT, _wParam: WPARAM, _lParam: LPARAM) -> LRESULT {
+	0
+}
+
+pub(crate) extern "system" fn SciterProcND(_hwnd: HWINDOW, _msg: UINT, _wParam: WPARAM, _lParam: LPARAM, pbHandled: *mut BOOL) -> LRESULT {
+	if !pbHandled.is_null() {
+		unsafe { *pbHandled = false as BOOL };
+	}
+	0
+}
+
+pub(crate) extern "system" fn SciterLoadFile(hWndSciter: HWINDOW, filename: LPCWSTR) -> BOOL {
+	let id = match window(hWndSciter) {
+		Some(id) => id,
+		None => return false as BOOL,
+	};
+	let uri = utf16(filename);
+	let wuri = wide(&uri);
+	let mut scnm = SCN_LOAD_DATA {
+		code: SCITER_NOTIFICATION::SC_LOAD_DATA as UINT,
+		hwnd: hWndSciter,
+		uri: wuri.as_ptr(),
+		outData: ::std::ptr::null(),
+		outDataSize: 0,
+		dataType: RESOURCE_TYPE::HTML,
+		request_id: ::std::ptr::null_mut(),
+		principal: ::std::ptr::null_mut(),
+		initiator: ::std::ptr::null_mut(),
+	};
+	let result = notify_host(id, &mut scnm).unwrap_or(LOAD_RESULT::LOAD_DEFAULT as UINT);
+	if result == LOAD_RESULT::LOAD_DISCARD as UINT {
+		return false as BOOL;
+	}
+
+	let ready = with_dom(|dom| {
+		let w = dom.windows.get_mut(&id)?;
+		let pos = w.ready.iter().position(|r| r.0 == uri)?;
+		Some(w.ready.remove(pos).1)
+	});
+	let data = match ready {
+		Some(data) => data,
+		None if !scnm.outData.is_null() => unsafe { ::std::slice::from_raw_parts(scnm.outData, scnm.outDataSize as usize) }.to_vec(),
+		None => {
+			let path = uri.strip_prefix("file://").unwrap_or(&uri);
+			match ::std::fs::read(path) {
+				Ok(data) => data,
+				Err(_) => return false as BOOL,
+			}
+		},
+	};
+	load_document(id, &String::from_utf8_lossy(&data), &uri);
+	true as BOOL
+}
+
+pub(crate) extern "system" fn SciterLoadHtml(hWndSciter: HWINDOW, html: LPCBYTE, htmlSize: UINT, baseUrl: LPCWSTR) -> BOOL {
+	let id = match window(hWndSciter) {
+		Some(id) => id,
+		None => return false as BOOL,
+	};
+	let text = if html.is_null() { Vec::new() } else { unsafe { ::std::slice::from_raw_parts(html, htmlSize as usize) }.to_vec() };
+	load_document(id, &String::from_utf8_lossy(&text), &utf16(baseUrl));
+	true as BOOL
+}
+
+pub(crate) extern "system" fn SciterSetCallback(hWndSciter: HWINDOW, cb: SciterHostCallback, cbParam: LPVOID) -> VOID {
+	let id = id_of(hWndSciter);
+	with_dom(|dom| {
+		if let Some(w) = dom.windows.get_mut(&id) {
+			w.callback = Some((cb, cbParam as usize));
+		}
+	});
+	unsafe { ::std::mem::zeroed() }
+}
+
+pub(crate) extern "system" fn SciterSetMasterCSS(_utf8: LPCBYTE, _numBytes: UINT) -> BOOL {
+	true as BOOL
+}
+
+pub(crate) extern "system" fn SciterAppendMasterCSS(_utf8: LPCBYTE, _numBytes: UINT) -> BOOL {
+	true as BOOL
+}
+
+pub(crate) extern "system" fn SciterSetCSS(hWndSciter: HWINDOW, _utf8: LPCBYTE, _numBytes: UINT, _baseUrl: LPCWSTR, _mediaType: LPCWSTR) -> BOOL {
+	window(hWndSciter).is_some() as BOOL
+}
+
+pub(crate) extern "system" fn SciterSetMediaType(hWndSciter: HWINDOW, _mediaType: LPCWSTR) -> BOOL {
+	window(hWndSciter).is_some() as BOOL
+}
+
+pub(crate) extern "system" fn SciterSetMediaVars(hWndSciter: HWINDOW, _mediaVars: *const VALUE) -> BOOL {
+	window(hWndSciter).is_some() as BOOL
+}
+
+pub(crate) extern "system" fn SciterGetMinWidth(_hWndSciter: HWINDOW) -> UINT {
+	0
+}
+
+pub(crate) extern "system" fn SciterGetMinHeight(_hWndSciter: HWINDOW, _width: UINT) -> UINT {
+	0
+}
+
+pub(crate) extern "system" fn SciterCall(hWnd: HWINDOW, functionName: LPCSTR, argc: UINT, argv: *const VALUE, retval: *mut VALUE) -> BOOL {
+	let args = if argv.is_null() { Vec::new() } else {
+		unsafe { ::std::slice::from_raw_parts(argv, argc as usize) }.iter().map(Slot::copy).collect()
+	};
+	let (ok, rv) = match call_function(window(hWnd), &utf8(functionName), &args) {
+		Ok(rv) => (true, rv),
+		Err(rv) => (false, rv),
+	};
+	rv.store(retval);
+	ok as BOOL
+}
+
+pub(crate) extern "system" fn SciterEval(hwnd: HWINDOW, script: LPCWSTR, scriptLength: UINT, pretval: *mut VALUE) -> BOOL {
+	let (ok, rv) = match eval(window(hwnd), None, &utf16n(script, scriptLength)) {
+		Ok(rv) => (true, rv),
+		Err(rv) => (false, rv),
+	};
+	rv.store(pretval);
+	ok as BOOL
+}
+
+pub(crate) extern "system" fn SciterUpdateWindow(_hwnd: HWINDOW) -> VOID {
+	unsafe { ::std::mem::zeroed() }
+}
+
+pub(crate) extern "system" fn SciterTranslateMessage(_lpMsg: LPMSG) -> BOOL {
+	false as BOOL
+}
+
+pub(crate) extern "system" fn SciterSetOption(_hWnd: HWINDOW, _option: SCITER_RT_OPTIONS, _value: UINT_PTR) -> BOOL {
+	true as BOOL
+}
+
+pub(crate) extern "system" fn SciterGetPPI(_hWndSciter: HWINDOW, px: *mut UINT, py: *mut UINT) -> VOID {
+	for p in &[px, py] {
+		if !p.is_null() {
+			unsafe { **p = 96 };
+		}
+	}
+	unsafe { ::std::mem::zeroed() }
+}
+
+pub(crate) extern "system" fn SciterGetViewExpando(hwnd: HWINDOW, pval: *mut VALUE) -> BOOL {
+	let id = id_of(hwnd);
+	let expando = with_dom(|dom| dom.windows.get_mut(&id).map(|w| w.expando.get_or_insert_with(|| Slot::map(Vec::new())).clone()));
+	match expando {
+		Some(v) => {
+			v.store(pval);
+			true as BOOL
+		},
+		None => false as BOOL,
+	}
+}
+
+pub(crate) extern "system" fn SciterRenderD2D(_hWndSciter: HWINDOW, _prt: *mut ID2D1RenderTarget) -> BOOL {
+	false as BOOL
+}
+
+pub(crate) extern "system" fn SciterD2DFactory(_ppf: *mut *mut ID2D1Factory) -> BOOL {
+	false as BOOL
+}
+
+pub(crate) extern "system" fn SciterDWFactory(_ppf: *mut *mut IDWriteFactory) -> BOOL {
+	false as BOOL
+}
+
+pub(crate) extern "system" fn SciterGraphicsCaps(pcaps: LPUINT) -> BOOL {
+	if !pcaps.is_null() {
+		unsafe { *pcaps = 0 };
+	}
+	true as BOOL
+}
+
+pub(crate) extern "system" fn SciterSetHomeURL(hWndSciter: HWINDOW, _baseUrl: LPCWSTR) -> BOOL {
+	window(hWndSciter).is_some() as BOOL
+}
+
+// Native windows are not supported: the platform code would call the OS on the returned handle,
+// use `fake::create_window` instead.
+
+pub(crate) extern "system" fn SciterCreateNSView(_frame: LPRECT) -> HWINDOW {
+	::std::ptr::null_mut()
+}
+
+pub(crate) extern "system" fn SciterCreateWidget(_frame: LPRECT) -> HWINDOW {
+	::std::ptr::null_mut()
+}
+
+pub(crate) extern "system" fn SciterCreateWindow(_creationFlags: UINT, _frame: LPCRECT, _delegate: *const SciterWindowDelegate, _delegateParam: LPVOID, _parent: HWINDOW) -> HWINDOW {
+	::std::ptr::null_mut()
+}
+
+pub(crate) extern "system" fn SciterSetupDebugOutput(_hwndOrNull: HWINDOW, _param: LPVOID, _pfOutput: DEBUG_OUTPUT_PROC) {
+}
+
+
+pub(crate) extern "system" fn SciterNodeAddRef(_hn: HNODE) -> SCDOM_RESULT {
+	SCDOM_RESULT::OPERATION_FAILED
+}
+
+pub(crate) extern "system" fn SciterNodeRelease(_hn: HNODE) -> SCDOM_RESULT {
+	SCDOM_RESULT::OPERATION_FAILED
+}
+
+pub(crate) extern "system" fn SciterNodeCastFromElement(_he: HELEMENT, _phn: *mut HNODE) -> SCDOM_RESULT {
+	SCDOM_RESULT::OPERATION_FAILED
+}
+
+pub(crate) extern "system" fn SciterNodeCastToElement(_hn: HNODE, _he: *mut HELEMENT) -> SCDOM_RESULT {
+	SCDOM_RESULT::OPERATION_FAILED
+}
+
+pub(crate) extern "system" fn SciterNodeFirstChild(_hn: HNODE, _phn: *mut HNODE) -> SCDOM_RESULT {
+	SCDOM_RESULT::OPERATION_FAILED
+}
+
+pub(crate) extern "system" fn SciterNodeLastChild(_hn: HNODE, _phn: *mut HNODE) -> SCDOM_RESULT {
+	SCDOM_RESULT::OPERATION_FAILED
+}
+
+pub(crate) extern "system" fn SciterNodeNextSibling(_hn: HNODE, _phn: *mut HNODE) -> SCDOM_RESULT {
+	SCDOM_RESULT::OPERATION_FAILED
+}
+
+pub(crate) extern "system" fn SciterNodePrevSibling(_hn: HNODE, _phn: *mut HNODE) -> SCDOM_RESULT {
+	SCDOM_RESULT::OPERATION_FAILED
+}
+
+pub(crate) extern "system" fn SciterNodeParent(_hnode: HNODE, _pheParent: *mut HELEMENT) -> SCDOM_RESULT {
+	SCDOM_RESULT::OPERATION_FAILED
+}
+
+pub(crate) extern "system" fn SciterNodeNthChild(_hnode: HNODE, _n: UINT, _phn: *mut HNODE) -> SCDOM_RESULT {
+	SCDOM_RESULT::OPERATION_FAILED
+}
+
+pub(crate) extern "system" fn SciterNodeChildrenCount(_hnode: HNODE, _pn: *mut UINT) -> SCDOM_RESULT {
+	SCDOM_RESULT::OPERATION_FAILED
+}
+
+pub(crate) extern "system" fn SciterNodeType(_hnode: HNODE, _pNodeType: *mut UINT) -> SCDOM_RESULT {
+	SCDOM_RESULT::OPERATION_FAILED
+}
+
+pub(crate) extern "system" fn SciterNodeGetText(_hnode: HNODE, _rcv: *mut LPCWSTR_RECEIVER, _rcv_param: LPVOID) -> SCDOM_RESULT {
+	SCDOM_RESULT::OPERATION_FAILED
+}
+
+pub(crate) extern "system" fn SciterNodeSetText(_hnode: HNODE, _text: LPCWSTR, _textLength: UINT) -> SCDOM_RESULT {
+	SCDOM_RESULT::OPERATION_FAILED
+}
+
+pub(crate) extern "system" fn SciterNodeInsert(_hnode: HNODE, _how: UINT, _what: HNODE) -> SCDOM_RESULT {
+	SCDOM_RESULT::OPERATION_FAILED
+}
+
+pub(crate) extern "system" fn SciterNodeRemove(_hnode: HNODE, _finalize: BOOL) -> SCDOM_RESULT {
+	SCDOM_RESULT::OPERATION_FAILED
+}
+
+pub(crate) extern "system" fn SciterCreateTextNode(_text: LPCWSTR, _textLength: UINT, _phnode: *mut HNODE) -> SCDOM_RESULT {
+	SCDOM_RESULT::OPERATION_FAILED
+}
+
+pub(crate) extern "system" fn SciterCreateCommentNode(_text: LPCWSTR, _textLength: UINT, _phnode: *mut HNODE) -> SCDOM_RESULT {
+	SCDOM_RESULT::OPERATION_FAILED
+}
+
+pub(crate) extern "system" fn SciterNodeUnwrap(_pval: *const VALUE, _ppElement: *mut HNODE) -> SCDOM_RESULT {
+	SCDOM_RESULT::OPERATION_FAILED
+}
+
+pub(crate) extern "system" fn SciterNodeWrap(_pval: *mut VALUE, _pElement: HNODE) -> SCDOM_RESULT {
+	SCDOM_RESULT::OPERATION_FAILED
+}
+
+
+pub(crate) extern "system" fn TIScriptAPI() -> *mut tiscript_native_interface {
+	::std::ptr::null_mut()
+}
+
+pub(crate) extern "system" fn SciterGetVM(_hwnd: HWINDOW) -> HVM {
+	::std::ptr::null_mut()
+}
+
+pub(crate) extern "system" fn Sciter_v2V(_vm: HVM, _script_value: tiscript_value, _value: *mut VALUE, _isolate: BOOL) -> BOOL {
+	false as BOOL
+}
+
+pub(crate) extern "system" fn Sciter_V2v(_vm: HVM, _valuev: *const VALUE, _script_value: *mut tiscript_value) -> BOOL {
+	false as BOOL
+}
+
+pub(crate) extern "system" fn SciterOpenArchive(_archiveData: LPCBYTE, _archiveDataLength: UINT) -> HSARCHIVE {
+	::std::ptr::null_mut()
+}
+
+pub(crate) extern "system" fn SciterGetArchiveItem(_harc: HSARCHIVE, _path: LPCWSTR, _pdata: *mut LPCBYTE, _pdataLength: *mut UINT) -> BOOL {
+	false as BOOL
+}
+
+pub(crate) extern "system" fn SciterCloseArchive(_harc: HSARCHIVE) -> BOOL {
+	false as BOOL
+}
+
+pub(crate) extern "system" fn SciterGetCallbackParam(hwnd: HWINDOW) -> LPVOID {
+	let id = id_of(hwnd);
+	with_dom(|dom| dom.windows.get(&id).and_then(|w| w.callback).map_or(0, |cb| cb.1)) as LPVOID
+}
+
+lazy_static! {
+	/// Callbacks posted from any thread: window id, `wparam` and `lparam`.
+	static ref POSTED_CALLBACKS: ::std::sync::Mutex<::std::collections::VecDeque<(usize, UINT_PTR, UINT_PTR)>> = Default::default();
+}
+
+pub(crate) extern "system" fn SciterPostCallback(hwnd: HWINDOW, wparam: UINT_PTR, lparam: UINT_PTR, _timeoutms: UINT) -> UINT_PTR {
+	let id = match window(hwnd) {
+		Some(id) => id,
+		None => return 0,
+	};
+	POSTED_CALLBACKS.lock().unwrap_or_else(|e| e.into_inner()).push_back((id, wparam, lparam));
+	1
+}
+
+/// Deliver the callbacks posted to the windows of the current thread as `SC_POSTED_NOTIFICATION`,
+/// returns the number of delivered ones.
+pub(crate) fn pump_callbacks() -> usize {
+	let current = Some(::std::thread::current().id());
+	let mut count = 0;
+	loop {
+		let next = {
+			let mut posted = POSTED_CALLBACKS.lock().unwrap_or_else(|e| e.into_inner());
+			let pos = posted.iter().position(|&(id, _, _)| with_dom(|dom| dom.windows.get(&id).is_none_or(|w| w.thread == current)));
+			pos.and_then(|pos| posted.remove(pos))
+		};
+		let (id, wparam, lparam) = match next {
+			Some(posted) => posted,
+			None => break,
+		};
+		let mut scnm = SCN_POSTED_NOTIFICATION {
+			code: SCITER_NOTIFICATION::SC_POSTED_NOTIFICATION as UINT,
+			hwnd: hwnd_of(id),
+			wparam,
+			lparam,
+			lreturn: 0,
+		};
+		if notify_host(id, &mut scnm).is_some() {
+			count += 1;
+		}
+	}
+	count
+}
+
+pub(crate) extern "system" fn GetSciterGraphicsAPI() -> *const SciterGraphicsAPI {
+	::std::ptr::null()
+}
+
+pub(crate) extern "system" fn GetSciterRequestAPI() -> *const SciterRequestAPI {
+	::std::ptr::null()
+}
+
+pub(crate) extern "system" fn SciterCreateOnDirectXWindow(_hwnd: HWINDOW, _pSwapChain: *mut IDXGISwapChain) -> BOOL {
+	false as BOOL
+}
+
+pub(crate) extern "system" fn SciterRenderOnDirectXWindow(_hwnd: HWINDOW, _elementToRenderOrNull: HELEMENT, _frontLayer: BOOL) -> BOOL {
+	false as BOOL
+}
+
+pub(crate) extern "system" fn SciterRenderOnDirectXTexture(_hwnd: HWINDOW, _elementToRenderOrNull: HELEMENT, _surface: *mut IDXGISurface) -> BOOL {
+	false as BOOL
+}
+
+pub(crate) extern "system" fn SciterProcX(hwnd: HWINDOW, msg: *const SCITER_X_MSG) -> BOOL {
+	if msg.is_null() {
+		return false as BOOL;
+	}
+	let id = id_of(hwnd);
+	match unsafe { &(*msg).msg } {
+		SCITER_X_MSG_CODE::SXM_CREATE => with_dom(|dom| dom.register_window(id)),
+		SCITER_X_MSG_CODE::SXM_DESTROY => super::destroy_window(hwnd),
+		_ => {},
+	}
+	true as BOOL
+}
+
+pub(crate) extern "system" fn SciterAtomValue(name: LPCSTR) -> som_atom_t {
+	let name = utf8(name);
+	with_dom(|dom| {
+		let n = match dom.atoms.iter().position(|a| *a == name) {
+			Some(n) => n,
+			None => {
+				dom.atoms.push(name);
+				dom.atoms.len() - 1
+			},
+		};
+		n as som_atom_t + 1
+	})
+}
+
+pub(crate) extern "system" fn SciterAtomNameCB(atomv: som_atom_t, rcv: LPCSTR_RECEIVER, rcv_param: LPVOID) -> BOOL {
+	let name = with_dom(|dom| (atomv as usize).checked_sub(1).and_then(|n| dom.atoms.get(n).cloned()));
+	match name {
+		Some(name) => {
+			let s = CString::new(name).unwrap_or_default();
+			rcv(s.as_ptr(), s.as_bytes().len() as UINT, rcv_param);
+			true as BOOL
+		},
+		None => false as BOOL,
+	}
+}
+
+pub(crate) extern "system" fn SciterSetGlobalAsset(pass: *mut som_asset_t) -> BOOL {
+	if pass.is_null() {
+		return false as BOOL;
+	}
+	// the engine owns global assets for the rest of the process life
+	true as BOOL
+}
+
+pub(crate) extern "system" fn SciterSetVariable(hwndOrNull: HWINDOW, path: LPCSTR, value: *const VALUE) -> SCDOM_RESULT {
+	let path = utf8(path);
+	if path.is_empty() || value.is_null() {
+		return SCDOM_RESULT::INVALID_PARAMETER;
+	}
+	let value = Slot::copy(unsafe { &*value });
+	let id = id_of(hwndOrNull);
+	with_dom(|dom| {
+		let vars = if hwndOrNull.is_null() {
+			&mut dom.globals
+		} else {
+			match dom.windows.get_mut(&id) {
+				Some(w) => &mut w.vars,
+				None => return SCDOM_RESULT::INVALID_HWND,
+			}
+		};
+		let prev = match vars.iter_mut().find(|v| v.0 == path) {
+			Some(v) => Some(::std::mem::replace(&mut v.1, value)),
+			None => {
+				vars.push((path, value));
+				None
+			},
+		};
+		dom.garbage.extend(prev);
+		SCDOM_RESULT::OK
+	})
+}
+
+pub(crate) extern "system" fn SciterGetVariable(hwndOrNull: HWINDOW, path: LPCSTR, value: *mut VALUE) -> SCDOM_RESULT {
+	let path = utf8(path);
+	let id = if hwndOrNull.is_null() { None } else {
+		match window(hwndOrNull) {
+			Some(id) => Some(id),
+			None => return SCDOM_RESULT::INVALID_HWND,
+		}
+	};
+	match with_dom(|dom| dom.variable(id, &path)) {
+		Some(v) => {
+			v.store(value);
+			SCDOM_RESULT::OK
+		},
+		None => SCDOM_RESULT::OK_NOT_HANDLED,
+	}
+}
@@ -0,0 +1,450 @@
+//! HTML fragments and CSS selectors of the fake engine.
+
+/// Parsed HTML node.
+#[derive(Debug)]
+pub(crate) enum Node {
+	Text(String),
+	Element(Tag),
+}
+
+/// Parsed HTML element.
+#[derive(Debug, Default)]
+pub(crate) struct Tag {
+	pub name: String,
+	pub attrs: Vec<(String, String)>,
+	pub children: Vec<Node>,
+}
+
+/// Elements without content.
+pub(crate) fn is_void(tag: &str) -> bool {
+	matches!(tag, "area" | "base" | "br" | "col" | "embed" | "hr" | "img" | "input" | "link" | "meta" | "param" | "source" | "track" | "wbr")
+}
+
+fn is_raw_text(tag: &str) -> bool {
+	matches!(tag, "script" | "style")
+}
+
+/// Parse an HTML fragment.
+///
+/// Unknown constructs are skipped, unbalanced closing tags are ignored
+/// and unclosed elements are closed at the end of input.
+pub(crate) fn parse(html: &str) -> Vec<Node> {
+	let mut stack: Vec<Tag> = vec![Tag::default()];
+	let mut rest = html;
+
+	fn append(stack: &mut [Tag], node: Node) {
+		stack.last_mut().unwrap().children.push(node);
+	}
+
+	fn close(stack: &mut Vec<Tag>) {
+		let tag = stack.pop().unwrap();
+		append(stack, Node::Element(tag));
+	}
+
+	while !rest.is_empty() {
+		if let Some(body) = rest.strip_prefix("<!--") {
+			rest = body.find("-->").map(|n| &body[n + 3..]).unwrap_or("");
+
+		} else if rest.starts_with("<!") || rest.starts_with("<?") {
+			rest = rest.find('>').map(|n| &rest[n + 1..]).unwrap_or("");
+
+		} else if let Some(body) = rest.strip_prefix("</") {
+			let end = body.find('>').unwrap_or(body.len());
+			let name = body[..end].trim().to_lowercase();
+			rest = body.get(end + 1..).unwrap_or("");
+			if let Some(n) = stack.iter().rposition(|t| t.name == name) {
+				if n > 0 {
+					while stack.len() > n {
+						close(&mut stack);
+					}
+				}
+			}
+
+		} else if rest.starts_with('<') && rest[1..].starts_with(|c: char| c.is_ascii_alphabetic()) {
+			let (tag, closed, tail) = parse_tag(&rest[1..]);
+			rest = tail;
+			let name = tag.name.clone();
+			if closed || is_void(&name) {
+				append(&mut stack, Node::Element(tag));
+			} else if is_raw_text(&name) {
+				let end = format!("</{}", name);
+				let n = rest.to_lowercase().find(&end).unwrap_or(rest.len());
+				let mut tag = tag;
+				if !rest[..n].trim().is_empty() {
+					tag.children.push(Node::Text(rest[..n].to_owned()));
+				}
+				rest = &rest[n..];
+				rest = rest.find('>').map(|n| &rest[n + 1..]).unwrap_or("");
+				append(&mut stack, Node::Element(tag));
+			} else {
+				stack.push(tag);
+			}
+
+		} else {
+			let n = rest[1..].find('<').map(|n| n + 1).unwrap_or(rest.len());
+			let text = decode(&rest[..n]);
+			rest = &rest[n..];
+			if !text.trim().is_empty() {
+				append(&mut stack, Node::Text(text));
+			}
+		}
+	}
+
+	while stack.len() > 1 {
+		close(&mut stack);
+	}
+	stack.pop().unwrap().children
+}
+
+/// Parse the `tag attr=value ...>` part, returns the tag, whether it is self-closed and the rest of input.
+fn parse_tag(s: &str) -> (Tag, bool, &str) {
+	let mut tag = Tag::default();
+	let end = s.find(|c: char| c.is_whitespace() || c == '>' || c == '/').unwrap_or(s.len());
+	tag.name = s[..end].to_lowercase();
+	let mut rest = &s[end..];
+	loop {
+		rest = rest.trim_start();
+		if let Some(tail) = rest.strip_prefix("/>") {
+			return (tag, true, tail);
+		}
+		if let Some(tail) = rest.strip_prefix('>') {
+			return (tag, false, tail);
+		}
+		if rest.is_empty() {
+			return (tag, false, rest);
+		}
+		if let Some(tail) = rest.strip_prefix('/') {
+			rest = tail;
+			continue;
+		}
+		let end = rest.find(|c: char| c.is_whitespace() || c == '=' || c == '>' || c == '/').unwrap_or(rest.len()).max(1);
+		let name = rest[..end].to_lowercase();
+		rest = rest[end..].trim_start();
+		let mut value = String::new();
+		if let Some(tail) = rest.strip_prefix('=') {
+			let tail = tail.trim_start();
+			if tail.starts_with('"') || tail.starts_with('\'') {
+				let quote = tail.chars().next().unwrap();
+				let end = tail[1..].find(quote).map(|n| n + 1).unwrap_or(tail.len());
+				value = decode(&tail[1..end]);
+				rest = tail.get(end + 1..).unwrap_or("");
+			} else {
+				let end = tail.find(|c: char| c.is_whitespace() || c == '>').unwrap_or(tail.len());
+				value = decode(&tail[..end]);
+				rest = &tail[end..];
+			}
+		}
+		if !tag.attrs.iter().any(|a| a.0 == name) {
+			tag.attrs.push((name, value));
+		}
+	}
+}
+
+/// Replace character references.
+fn decode(s: &str) -> String {
+	let mut out = String::with_capacity(s.len());
+	let mut rest = s;
+	while let Some(n) = rest.find('&') {
+		out.push_str(&rest[..n]);
+		rest = &rest[n..];
+		let end = rest.find(';').filter(|&e| e < 10);
+		let c = end.and_then(|end| {
+			let name = &rest[1..end];
+			match name {
+				"amp" => Some('&'),
+				"lt" => Some('<'),
+				"gt" => Some('>'),
+				"quot" => Some('"'),
+				"apos" => Some('\''),
+				"nbsp" => Some('\u{a0}'),
+				_ if name.starts_with("#x") || name.starts_with("#X") => u32::from_str_radix(&name[2..], 16).ok().and_then(::std::char::from_u32),
+				_ if name.starts_with('#') => name[1..].parse().ok().and_then(::std::char::from_u32),
+				_ => None,
+			}
+		});
+		match (c, end) {
+			(Some(c), Some(end)) => {
+				out.push(c);
+				rest = &rest[end + 1..];
+			},
+			_ => {
+				out.push('&');
+				rest = &rest[1..];
+			}
+		}
+	}
+	out.push_str(rest);
+	out
+}
+
+/// Escape text content.
+pub(crate) fn escape(s: &str, attribute: bool) -> String {
+	let mut out = String::with_capacity(s.len());
+	for c in s.chars() {
+		match c {
+			'&' => out.push_str("&amp;"),
+			'<' => out.push_str("&lt;"),
+			'>' => out.push_str("&gt;"),
+			'"' if attribute => out.push_str("&quot;"),
+			c => out.push(c),
+		}
+	}
+	out
+}
+
+
+/// Element tree as seen by selectors.
+pub(crate) trait Tree {
+	fn tag(&self, e: u32) -> &str;
+	fn attribute(&self, e: u32, name: &str) -> Option<&str>;
+	fn parent(&self, e: u32) -> Option<u32>;
+	/// Element children of the parent.
+	fn siblings(&self, e: u32) -> Vec<u32>;
+	fn is_empty(&self, e: u32) -> bool;
+	fn state(&self, e: u32) -> u32;
+}
+
+/// A comma separated list of complex selectors.
+#[derive(Debug)]
+pub(crate) struct Selector(Vec<Vec<(Combinator, Compound)>>);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Combinator {
+	/// The leftmost compound.
+	None,
+	Descendant,
+	Child,
+	Adjacent,
+	Sibling,
+}
+
+#[derive(Debug, Default)]
+struct Compound {
+	tag: Option<String>,
+	id: Option<String>,
+	classes: Vec<String>,
+	attrs: Vec<(String, Option<(char, String)>)>,
+	pseudo: Vec<String>,
+}
+
+/// State pseudo-classes, see `ELEMENT_STATE_BITS`.
+const STATES: &[(&str, u32)] = &[
+	("link", 0x01), ("hover", 0x02), ("active", 0x04), ("focus", 0x08), ("visited", 0x10),
+	("current", 0x20), ("checked", 0x40), ("disabled", 0x80), ("read-only", 0x100),
+	("expanded", 0x200), ("collapsed", 0x400), ("busy", 0x80000), ("ready", 0x4000_0000),
+];
+
+impl Selector {
+	/// Parse a selector, returns `None` if it is invalid or not supported.
+	pub fn parse(text: &str) -> Option<Selector> {
+		let mut list = Vec::new();
+		for part in text.split(',') {
+			let chars: Vec<char> = part.trim().chars().collect();
+			if chars.is_empty() {
+				return None;
+			}
+			let mut pos = 0;
+			let mut complex = Vec::new();
+			let mut combinator = Combinator::None;
+			while pos < chars.len() {
+				let compound = Selector::compound(&chars, &mut pos)?;
+				complex.push((combinator, compound));
+				// combinator
+				let mut ws = false;
+				while pos < chars.len() && chars[pos].is_whitespace() {
+					ws = true;
+					pos += 1;
+				}
+				if pos >= chars.len() {
+					break;
+				}
+				combinator = match chars[pos] {
+					'>' => Combinator::Child,
+					'+' => Combinator::Adjacent,
+					'~' => Combinator::Sibling,
+					_ if ws => Combinator::Descendant,
+					_ => return None,
+				};
+				if combinator != Combinator::Descendant {
+					pos += 1;
+					while pos < chars.len() && chars[pos].is_whitespace() {
+						pos += 1;
+					}
+				}
+			}
+			list.push(complex);
+		}
+		Some(Selector(list))
+	}
+
+	fn compound(s: &[char], pos: &mut usize) -> Option<Compound> {
+		fn name(s: &[char], pos: &mut usize) -> String {
+			let start = *pos;
+			while *pos < s.len() && (s[*pos].is_alphanumeric() || s[*pos] == '-' || s[*pos] == '_') {
+				*pos += 1;
+			}
+			s[start..*pos].iter().collect()
+		}
+
+		let mut c = Compound::default();
+		let start = *pos;
+		if *pos < s.len() && s[*pos] == '*' {
+			*pos += 1;
+		} else {
+			let tag = name(s, pos);
+			if !tag.is_empty() {
+				c.tag = Some(tag.to_lowercase());
+			}
+		}
+		while *pos < s.len() {
+			match s[*pos] {
+				'#' => {
+					*pos += 1;
+					c.id = Some(name(s, pos)).filter(|n| !n.is_empty());
+					c.id.as_ref()?;
+				},
+				'.' => {
+					*pos += 1;
+					let class = name(s, pos);
+					if class.is_empty() {
+						return None;
+					}
+					c.classes.push(class);
+				},
+				':' => {
+					*pos += 1;
+					let pseudo = name(s, pos);
+					let known = matches!(pseudo.as_str(), "root" | "first-child" | "last-child" | "only-child" | "empty")
+						|| STATES.iter().any(|x| x.0 == pseudo);
+					if !known {
+						return None;
+					}
+					c.pseudo.push(pseudo);
+				},
+				'[' => {
+					*pos += 1;
+					let end = s[*pos..].iter().position(|&c| c == ']')? + *pos;
+					let body: String = s[*pos..end].iter().collect();
+					*pos = end + 1;
+					let test = match body.find('=') {
+						Some(n) => {
+							let (head, value) = (&body[..n], &body[n + 1..]);
+							let (attr, op) = match head.chars().last() {
+								Some(op) if "~^$*|".contains(op) => (&head[..head.len() - 1], op),
+								_ => (head, '='),
+							};
+							let value = value.trim().trim_matches(|c| c == '"' || c == '\'').to_owned();
+							(attr.trim().to_lowercase(), Some((op, value)))
+						},
+						None => (body.trim().to_lowercase(), None),
+					};
+					if test.0.is_empty() {
+						return None;
+					}
+					c.attrs.push(test);
+				},
+				_ => break,
+			}
+		}
+		if *pos == start {
+			return None;
+		}
+		Some(c)
+	}
+
+	/// Test the element against this selector.
+	pub fn matches<T: Tree>(&self, tree: &T, e: u32) -> bool {
+		self.0.iter().any(|complex| Selector::matches_complex(tree, e, complex))
+	}
+
+	fn matches_complex<T: Tree>(tree: &T, e: u32, complex: &[(Combinator, Compound)]) -> bool {
+		let (last, head) = match complex.split_last() {
+			Some(x) => x,
+			None => return false,
+		};
+		if !Selector::matches_compound(tree, e, &last.1) {
+			return false;
+		}
+		match last.0 {
+			Combinator::None => true,
+			Combinator::Child => {
+				tree.parent(e).is_some_and(|p| Selector::matches_complex(tree, p, head))
+			},
+			Combinator::Descendant => {
+				let mut p = tree.parent(e);
+				while let Some(x) = p {
+					if Selector::matches_complex(tree, x, head) {
+						return true;
+					}
+					p = tree.parent(x);
+				}
+				false
+			},
+			Combinator::Adjacent | Combinator::Sibling => {
+				let siblings = tree.siblings(e);
+				let n = siblings.iter().position(|&x| x == e).unwrap_or(0);
+				let before = &siblings[..n];
+				if last.0 == Combinator::Adjacent {
+					before.last().is_some_and(|&x| Selector::matches_complex(tree, x, head))
+				} else {
+					before.iter().any(|&x| Selector::matches_complex(tree, x, head))
+				}
+			},
+		}
+	}
+
+	fn matches_compound<T: Tree>(tree: &T, e: u32, c: &Compound) -> bool {
+		if let Some(ref tag) = c.tag {
+			if tree.tag(e) != tag {
+				return false;
+			}
+		}
+		if let Some(ref id) = c.id {
+			if tree.attribute(e, "id") != Some(id.as_str()) {
+				return false;
+			}
+		}
+		for class in &c.classes {
+			let classes = tree.attribute(e, "class").unwrap_or("");
+			if !classes.split_whitespace().any(|x| x == class) {
+				return false;
+			}
+		}
+		for (name, test) in &c.attrs {
+			let value = match tree.attribute(e, name) {
+				Some(v) => v,
+				None => return false,
+			};
+			let ok = match *test {
+				None => true,
+				Some(('=', ref v)) => value == v,
+				Some(('~', ref v)) => value.split_whitespace().any(|x| x == v),
+				Some(('^', ref v)) => value.starts_with(v.as_str()),
+				Some(('$', ref v)) => value.ends_with(v.as_str()),
+				Some(('*', ref v)) => value.contains(v.as_str()),
+				Some(('|', ref v)) => value == v || value.starts_with(&format!("{}-", v)),
+				Some(_) => false,
+			};
+			if !ok {
+				return false;
+			}
+		}
+		for pseudo in &c.pseudo {
+			let ok = match pseudo.as_str() {
+				"root" => tree.parent(e).is_none(),
+				"first-child" => tree.parent(e).is_some() && tree.siblings(e).first() == Some(&e),
+				"last-child" => tree.parent(e).is_some() && tree.siblings(e).last() == Some(&e),
+				"only-child" => tree.parent(e).is_some() && tree.siblings(e).len() == 1,
+				"empty" => tree.is_empty(e),
+				state => {
+					let bit = STATES.iter().find(|x| x.0 == state).map(|x| x.1).unwrap_or(0);
+					tree.state(e) & bit != 0
+				},
+			};
+			if !ok {
+				return false;
+			}
+		}
+		true
+	}
+}
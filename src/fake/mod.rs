@@ -0,0 +1,292 @@
+/*! In-process fake Sciter engine.
+
+A pure Rust implementation of the Sciter API which allows to exercise
+[`Value`](../value/struct.Value.html), [`dom::Element`](../dom/struct.Element.html),
+[`Host`](../host/struct.Host.html) and [`EventHandler`](../dom/event/trait.EventHandler.html) code
+without the Sciter library, e.g. in unit tests on CI.
+Requires the `fake` feature, usually enabled only for tests:
+
+```toml
+[dev-dependencies]
+sciter-rs = { version = "0.5", features = ["fake"] }
+```
+
+It supports values, a minimal element tree, attributes, simple CSS selectors,
+event dispatching and element timers. There is no layout, rendering and no script engine:
+script evaluation understands only JSON literals, variables and calls of native functions,
+script objects can be made by [`script_object`](fn.script_object.html).
+
+Since native windows can not be created, use [`create_window`](fn.create_window.html)
+and attach a [`Host`](../host/struct.Host.html) to it:
+
+```rust
+sciter::fake::install();
+
+let host = sciter::Host::attach(sciter::fake::create_window());
+host.load_html(b"<html><body><p id=greeting>Hello</p></body></html>", None);
+
+let root = host.get_root().unwrap();
+let p = root.find_first("p").unwrap().unwrap();
+assert_eq!(p.get_text(), "Hello");
+```
+
+Posted events and timers are processed only by [`pump`](fn.pump.html) and
+[`advance`](fn.advance.html) calls on the same thread,
+callbacks posted to a window from any thread are processed by [`pump`](fn.pump.html) on the thread which created the window.
+
+*/
+
+use capi::scapi::ISciterAPI;
+use capi::sctypes::HWINDOW;
+
+mod dom;
+mod host;
+mod html;
+mod value;
+
+
+#[allow(deprecated)]
+static API: ISciterAPI = ISciterAPI {
+	version: if cfg!(feature = "windowless") { 0x0001_0009 } else { 0x0000_0009 },
+
+	SciterClassName: host::SciterClassName,
+	SciterVersion: host::SciterVersion,
+	SciterDataReady: host::SciterDataReady,
+	SciterDataReadyAsync: host::SciterDataReadyAsync,
+	SciterProc: host::SciterProc,
+	SciterProcND: host::SciterProcND,
+	SciterLoadFile: host::SciterLoadFile,
+	SciterLoadHtml: host::SciterLoadHtml,
+	SciterSetCallback: host::SciterSetCallback,
+	SciterSetMasterCSS: host::SciterSetMasterCSS,
+	SciterAppendMasterCSS: host::SciterAppendMasterCSS,
+	SciterSetCSS: host::SciterSetCSS,
+	SciterSetMediaType: host::SciterSetMediaType,
+	SciterSetMediaVars: host::SciterSetMediaVars,
+	SciterGetMinWidth: host::SciterGetMinWidth,
+	SciterGetMinHeight: host::SciterGetMinHeight,
+	SciterCall: host::SciterCall,
+	SciterEval: host::SciterEval,
+	SciterUpdateWindow: host::SciterUpdateWindow,
+	SciterTranslateMessage: host::SciterTranslateMessage,
+	SciterSetOption: host::SciterSetOption,
+	SciterGetPPI: host::SciterGetPPI,
+	SciterGetViewExpando: host::SciterGetViewExpando,
+	SciterRenderD2D: host::SciterRenderD2D,
+	SciterD2DFactory: host::SciterD2DFactory,
+	SciterDWFactory: host::SciterDWFactory,
+	SciterGraphicsCaps: host::SciterGraphicsCaps,
+	SciterSetHomeURL: host::SciterSetHomeURL,
+	SciterCreateNSView: host::SciterCreateNSView,
+	SciterCreateWidget: host::SciterCreateWidget,
+	SciterCreateWindow: host::SciterCreateWindow,
+	SciterSetupDebugOutput: host::SciterSetupDebugOutput,
+	Sciter_UseElement: dom::Sciter_UseElement,
+	Sciter_UnuseElement: dom::Sciter_UnuseElement,
+	SciterGetRootElement: dom::SciterGetRootElement,
+	SciterGetFocusElement: dom::SciterGetFocusElement,
+	SciterFindElement: dom::SciterFindElement,
+	SciterGetChildrenCount: dom::SciterGetChildrenCount,
+	SciterGetNthChild: dom::SciterGetNthChild,
+	SciterGetParentElement: dom::SciterGetParentElement,
+	SciterGetElementHtmlCB: dom::SciterGetElementHtmlCB,
+	SciterGetElementTextCB: dom::SciterGetElementTextCB,
+	SciterSetElementText: dom::SciterSetElementText,
+	SciterGetAttributeCount: dom::SciterGetAttributeCount,
+	SciterGetNthAttributeNameCB: dom::SciterGetNthAttributeNameCB,
+	SciterGetNthAttributeValueCB: dom::SciterGetNthAttributeValueCB,
+	SciterGetAttributeByNameCB: dom::SciterGetAttributeByNameCB,
+	SciterSetAttributeByName: dom::SciterSetAttributeByName,
+	SciterClearAttributes: dom::SciterClearAttributes,
+	SciterGetElementIndex: dom::SciterGetElementIndex,
+	SciterGetElementType: dom::SciterGetElementType,
+	SciterGetElementTypeCB: dom::SciterGetElementTypeCB,
+	SciterGetStyleAttributeCB: dom::SciterGetStyleAttributeCB,
+	SciterSetStyleAttribute: dom::SciterSetStyleAttribute,
+	SciterGetElementLocation: dom::SciterGetElementLocation,
+	SciterScrollToView: dom::SciterScrollToView,
+	SciterUpdateElement: dom::SciterUpdateElement,
+	SciterRefreshElementArea: dom::SciterRefreshElementArea,
+	SciterSetCapture: dom::SciterSetCapture,
+	SciterReleaseCapture: dom::SciterReleaseCapture,
+	SciterGetElementHwnd: dom::SciterGetElementHwnd,
+	SciterCombineURL: dom::SciterCombineURL,
+	SciterSelectElements: dom::SciterSelectElements,
+	SciterSelectElementsW: dom::SciterSelectElementsW,
+	SciterSelectParent: dom::SciterSelectParent,
+	SciterSelectParentW: dom::SciterSelectParentW,
+	SciterSetElementHtml: dom::SciterSetElementHtml,
+	SciterGetElementUID: dom::SciterGetElementUID,
+	SciterGetElementByUID: dom::SciterGetElementByUID,
+	SciterShowPopup: dom::SciterShowPopup,
+	SciterShowPopupAt: dom::SciterShowPopupAt,
+	SciterHidePopup: dom::SciterHidePopup,
+	SciterGetElementState: dom::SciterGetElementState,
+	SciterSetElementState: dom::SciterSetElementState,
+	SciterCreateElement: dom::SciterCreateElement,
+	SciterCloneElement: dom::SciterCloneElement,
+	SciterInsertElement: dom::SciterInsertElement,
+	SciterDetachElement: dom::SciterDetachElement,
+	SciterDeleteElement: dom::SciterDeleteElement,
+	SciterSetTimer: dom::SciterSetTimer,
+	SciterDetachEventHandler: dom::SciterDetachEventHandler,
+	SciterAttachEventHandler: dom::SciterAttachEventHandler,
+	SciterWindowAttachEventHandler: dom::SciterWindowAttachEventHandler,
+	SciterWindowDetachEventHandler: dom::SciterWindowDetachEventHandler,
+	SciterSendEvent: dom::SciterSendEvent,
+	SciterPostEvent: dom::SciterPostEvent,
+	SciterCallBehaviorMethod: dom::SciterCallBehaviorMethod,
+	SciterRequestElementData: dom::SciterRequestElementData,
+	SciterHttpRequest: dom::SciterHttpRequest,
+	SciterGetScrollInfo: dom::SciterGetScrollInfo,
+	SciterSetScrollPos: dom::SciterSetScrollPos,
+	SciterGetElementIntrinsicWidths: dom::SciterGetElementIntrinsicWidths,
+	SciterGetElementIntrinsicHeight: dom::SciterGetElementIntrinsicHeight,
+	SciterIsElementVisible: dom::SciterIsElementVisible,
+	SciterIsElementEnabled: dom::SciterIsElementEnabled,
+	SciterSortElements: dom::SciterSortElements,
+	SciterSwapElements: dom::SciterSwapElements,
+	SciterTraverseUIEvent: dom::SciterTraverseUIEvent,
+	SciterCallScriptingMethod: dom::SciterCallScriptingMethod,
+	SciterCallScriptingFunction: dom::SciterCallScriptingFunction,
+	SciterEvalElementScript: dom::SciterEvalElementScript,
+	SciterAttachHwndToElement: dom::SciterAttachHwndToElement,
+	SciterControlGetType: dom::SciterControlGetType,
+	SciterGetValue: dom::SciterGetValue,
+	SciterSetValue: dom::SciterSetValue,
+	SciterGetExpando: dom::SciterGetExpando,
+	SciterGetObject: dom::SciterGetObject,
+	SciterGetElementNamespace: dom::SciterGetElementNamespace,
+	SciterGetHighlightedElement: dom::SciterGetHighlightedElement,
+	SciterSetHighlightedElement: dom::SciterSetHighlightedElement,
+	SciterNodeAddRef: host::SciterNodeAddRef,
+	SciterNodeRelease: host::SciterNodeRelease,
+	SciterNodeCastFromElement: host::SciterNodeCastFromElement,
+	SciterNodeCastToElement: host::SciterNodeCastToElement,
+	SciterNodeFirstChild: host::SciterNodeFirstChild,
+	SciterNodeLastChild: host::SciterNodeLastChild,
+	SciterNodeNextSibling: host::SciterNodeNextSibling,
+	SciterNodePrevSibling: host::SciterNodePrevSibling,
+	SciterNodeParent: host::SciterNodeParent,
+	SciterNodeNthChild: host::SciterNodeNthChild,
+	SciterNodeChildrenCount: host::SciterNodeChildrenCount,
+	SciterNodeType: host::SciterNodeType,
+	SciterNodeGetText: host::SciterNodeGetText,
+	SciterNodeSetText: host::SciterNodeSetText,
+	SciterNodeInsert: host::SciterNodeInsert,
+	SciterNodeRemove: host::SciterNodeRemove,
+	SciterCreateTextNode: host::SciterCreateTextNode,
+	SciterCreateCommentNode: host::SciterCreateCommentNode,
+	ValueInit: value::ValueInit,
+	ValueClear: value::ValueClear,
+	ValueCompare: value::ValueCompare,
+	ValueCopy: value::ValueCopy,
+	ValueIsolate: value::ValueIsolate,
+	ValueType: value::ValueType,
+	ValueStringData: value::ValueStringData,
+	ValueStringDataSet: value::ValueStringDataSet,
+	ValueIntData: value::ValueIntData,
+	ValueIntDataSet: value::ValueIntDataSet,
+	ValueInt64Data: value::ValueInt64Data,
+	ValueInt64DataSet: value::ValueInt64DataSet,
+	ValueFloatData: value::ValueFloatData,
+	ValueFloatDataSet: value::ValueFloatDataSet,
+	ValueBinaryData: value::ValueBinaryData,
+	ValueBinaryDataSet: value::ValueBinaryDataSet,
+	ValueElementsCount: value::ValueElementsCount,
+	ValueNthElementValue: value::ValueNthElementValue,
+	ValueNthElementValueSet: value::ValueNthElementValueSet,
+	ValueNthElementKey: value::ValueNthElementKey,
+	ValueEnumElements: value::ValueEnumElements,
+	ValueSetValueToKey: value::ValueSetValueToKey,
+	ValueGetValueOfKey: value::ValueGetValueOfKey,
+	ValueToString: value::ValueToString,
+	ValueFromString: value::ValueFromString,
+	ValueInvoke: value::ValueInvoke,
+	ValueNativeFunctorSet: value::ValueNativeFunctorSet,
+	ValueIsNativeFunctor: value::ValueIsNativeFunctor,
+	TIScriptAPI: host::TIScriptAPI,
+	SciterGetVM: host::SciterGetVM,
+	Sciter_v2V: host::Sciter_v2V,
+	Sciter_V2v: host::Sciter_V2v,
+	SciterOpenArchive: host::SciterOpenArchive,
+	SciterGetArchiveItem: host::SciterGetArchiveItem,
+	SciterCloseArchive: host::SciterCloseArchive,
+	SciterFireEvent: dom::SciterFireEvent,
+	SciterGetCallbackParam: host::SciterGetCallbackParam,
+	SciterPostCallback: host::SciterPostCallback,
+	GetSciterGraphicsAPI: host::GetSciterGraphicsAPI,
+	GetSciterRequestAPI: host::GetSciterRequestAPI,
+	SciterCreateOnDirectXWindow: host::SciterCreateOnDirectXWindow,
+	SciterRenderOnDirectXWindow: host::SciterRenderOnDirectXWindow,
+	SciterRenderOnDirectXTexture: host::SciterRenderOnDirectXTexture,
+	SciterProcX: host::SciterProcX,
+	SciterAtomValue: host::SciterAtomValue,
+	SciterAtomNameCB: host::SciterAtomNameCB,
+	SciterSetGlobalAsset: host::SciterSetGlobalAsset,
+	SciterGetElementAsset: dom::SciterGetElementAsset,
+	SciterSetVariable: host::SciterSetVariable,
+	SciterGetVariable: host::SciterGetVariable,
+	SciterElementUnwrap: dom::SciterElementUnwrap,
+	SciterElementWrap: dom::SciterElementWrap,
+	SciterNodeUnwrap: host::SciterNodeUnwrap,
+	SciterNodeWrap: host::SciterNodeWrap,
+};
+
+/// The fake Sciter API.
+pub fn api() -> &'static ISciterAPI {
+	&API
+}
+
+/// Use the fake API instead of the Sciter library.
+///
+/// Note: Must be called first before any other function.
+pub fn install() {
+	use std::sync::Once;
+	static INSTALL: Once = Once::new();
+	INSTALL.call_once(|| ::set_host_api(api()));
+}
+
+/// Create a new engine instance, which can be used as a Sciter window.
+pub fn create_window() -> HWINDOW {
+	let id = dom::with_dom(|dom| dom.create_window());
+	dom::hwnd_of(id)
+}
+
+/// Destroy the engine instance created by [`create_window`](fn.create_window.html).
+pub fn destroy_window(hwnd: HWINDOW) {
+	dom::with_dom(|dom| dom.destroy_window(dom::id_of(hwnd)));
+}
+
+/// Create a script object of the `class` with own properties from the `props` map,
+/// as if it were created by script and passed to native code.
+///
+/// Its inherited `constructor.name` property is the class name.
+pub fn script_object(class: &str, props: &::value::Value) -> ::value::Value {
+	let props = props.items().iter()
+		.map(|(k, v)| (value::Slot::copy(unsafe { &*k.as_cptr() }), value::Slot::copy(unsafe { &*v.as_cptr() })))
+		.collect();
+	let object = value::Slot::object(class, props);
+	::value::Value::from(&object.0)
+}
+
+/// Create a script function, which unlike the native ones gets `this` of the call,
+/// as if it were defined by script and passed to native code.
+pub fn script_function<F>(f: F) -> ::value::Value
+	where F: Fn(&::value::Value, &[::value::Value]) -> ::value::Value + Send + Sync + 'static
+{
+	let function = value::Slot::function(::std::sync::Arc::new(f));
+	::value::Value::from(&function.0)
+}
+
+/// Move the timers clock of the current thread forward by `ms` milliseconds, firing due timers.
+pub fn advance(ms: u32) {
+	dom::advance(ms)
+}
+
+/// Dispatch events posted on the current thread and callbacks posted via `SciterPostCallback` to its windows.
+///
+/// Returns the number of dispatched events and callbacks.
+pub fn pump() -> usize {
+	dom::pump() + host::pump_callbacks()
+}
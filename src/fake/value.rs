@@ -0,0 +1,786 @@
+//! Value storage of the fake engine.
+//!
+//! Reference types (strings, bytes, arrays, maps, script objects and functions) live in
+//! reference counted heap nodes pointed by the `VALUE::d` field,
+//! DOM objects keep the element uid and assets keep the asset pointer.
+
+#![allow(non_snake_case)]
+
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use capi::sctypes::*;
+use capi::scvalue::*;
+use capi::scvalue::VALUE_TYPE::*;
+use capi::scdef::KeyValueCallback;
+use capi::scom::som_asset_t;
+
+use value::Value;
+
+use super::dom;
+
+
+/// Payload of a reference value.
+pub(crate) enum Data {
+	/// UTF-16 characters with a trailing zero.
+	Chars(Vec<u16>),
+	Bytes(Vec<u8>),
+	Array(Vec<Slot>),
+	Map(Vec<(Slot, Slot)>),
+	/// Script object: its class name and own properties.
+	Object {
+		class: String,
+		props: Vec<(Slot, Slot)>,
+	},
+	Functor {
+		invoke: NATIVE_FUNCTOR_INVOKE,
+		release: NATIVE_FUNCTOR_RELEASE,
+		tag: usize,
+	},
+	/// Script function, which gets `this` of the call.
+	Script(ScriptFunction),
+}
+
+pub(crate) type ScriptFunction = Arc<dyn Fn(&Value, &[Value]) -> Value + Send + Sync>;
+
+/// Heap node of a reference value.
+pub(crate) struct Node {
+	refs: AtomicUsize,
+	data: Mutex<Data>,
+}
+
+impl Drop for Node {
+	fn drop(&mut self) {
+		let data = self.data.get_mut().unwrap_or_else(|e| e.into_inner());
+		if let Data::Functor { release, tag, .. } = *data {
+			release(tag as LPVOID);
+		}
+	}
+}
+
+/// An owned `VALUE`: copying retains the referenced data, dropping releases it.
+pub(crate) struct Slot(pub VALUE);
+
+impl Slot {
+	/// `undefined`.
+	pub fn new() -> Slot {
+		Slot(VALUE::new())
+	}
+
+	/// Retain the given value.
+	pub fn copy(v: &VALUE) -> Slot {
+		retain(v);
+		Slot(v.clone())
+	}
+
+	/// Take the ownership of the given value, e.g. after `Value::pack_to`.
+	pub fn adopt(v: VALUE) -> Slot {
+		Slot(v)
+	}
+
+	/// Copy this value into the caller provided storage.
+	pub fn store(&self, dst: *mut VALUE) {
+		if !dst.is_null() {
+			assign(unsafe { &mut *dst }, self.clone());
+		}
+	}
+
+	pub fn int(n: i32) -> Slot {
+		Slot(VALUE { t: T_INT, u: 0, d: n as u32 as u64 })
+	}
+
+	pub fn string(s: &str, units: u32) -> Slot {
+		let mut chars: Vec<u16> = s.encode_utf16().collect();
+		chars.push(0);
+		Slot::with(T_STRING, units, Data::Chars(chars))
+	}
+
+	pub fn error(s: &str) -> Slot {
+		Slot::string(s, VALUE_UNIT_TYPE_STRING::ERROR as u32)
+	}
+
+	pub fn array(items: Vec<Slot>) -> Slot {
+		Slot::with(T_ARRAY, 0, Data::Array(items))
+	}
+
+	pub fn map(items: Vec<(Slot, Slot)>) -> Slot {
+		Slot::with(T_MAP, 0, Data::Map(items))
+	}
+
+	pub fn object(class: &str, props: Vec<(Slot, Slot)>) -> Slot {
+		Slot::with(T_OBJECT, VALUE_UNIT_TYPE_OBJECT::OBJECT as u32, Data::Object { class: class.to_owned(), props })
+	}
+
+	pub fn function(f: ScriptFunction) -> Slot {
+		Slot::with(T_FUNCTION, 0, Data::Script(f))
+	}
+
+	fn with(t: VALUE_TYPE, u: u32, data: Data) -> Slot {
+		let node = Box::new(Node { refs: AtomicUsize::new(1), data: Mutex::new(data) });
+		Slot(VALUE { t, u, d: Box::into_raw(node) as usize as u64 })
+	}
+
+	/// Text of a string value.
+	pub fn as_str(&self) -> Option<String> {
+		with_data(&self.0, |data| match data {
+			Data::Chars(chars) => Some(String::from_utf16_lossy(&chars[..chars.len() - 1])),
+			_ => None,
+		}).and_then(|s| s)
+	}
+
+	pub fn as_int(&self) -> Option<i32> {
+		match self.0.t {
+			T_INT | T_BOOL => Some(self.0.d as u32 as i32),
+			_ => None,
+		}
+	}
+}
+
+impl Clone for Slot {
+	fn clone(&self) -> Slot {
+		Slot::copy(&self.0)
+	}
+}
+
+impl Drop for Slot {
+	fn drop(&mut self) {
+		release(&self.0);
+	}
+}
+
+impl Default for Slot {
+	fn default() -> Slot {
+		Slot::new()
+	}
+}
+
+
+fn is_reference(t: VALUE_TYPE) -> bool {
+	matches!(t, T_STRING | T_BYTES | T_ARRAY | T_MAP | T_FUNCTION | T_OBJECT)
+}
+
+fn node(v: &VALUE) -> Option<&Node> {
+	if is_reference(v.t) && v.d != 0 {
+		Some(unsafe { &*(v.d as usize as *const Node) })
+	} else {
+		None
+	}
+}
+
+/// Access the payload of a reference value.
+pub(crate) fn with_data<R, F: FnOnce(&mut Data) -> R>(v: &VALUE, f: F) -> Option<R> {
+	node(v).map(|node| {
+		let mut data = node.data.lock().unwrap_or_else(|e| e.into_inner());
+		f(&mut data)
+	})
+}
+
+fn retain(v: &VALUE) {
+	if let Some(node) = node(v) {
+		node.refs.fetch_add(1, Ordering::SeqCst);
+	} else if v.t == T_DOM_OBJECT {
+		dom::use_uid(v.d as u32);
+	} else if v.t == T_ASSET && v.d != 0 {
+		let asset = v.d as usize as *mut som_asset_t;
+		unsafe { ((*asset).isa.add_ref)(asset) };
+	}
+}
+
+fn release(v: &VALUE) {
+	if let Some(node) = node(v) {
+		if node.refs.fetch_sub(1, Ordering::SeqCst) == 1 {
+			let ptr = v.d as usize as *mut Node;
+			drop(unsafe { Box::from_raw(ptr) });
+		}
+	} else if v.t == T_DOM_OBJECT {
+		dom::unuse_uid(v.d as u32);
+	} else if v.t == T_ASSET && v.d != 0 {
+		let asset = v.d as usize as *mut som_asset_t;
+		unsafe { ((*asset).isa.release)(asset) };
+	}
+}
+
+/// Replace the value in place, the previous content is released afterwards.
+pub(crate) fn assign(dst: &mut VALUE, src: Slot) {
+	let prev = Slot::adopt(::std::mem::replace(dst, VALUE::new()));
+	*dst = src.0.clone();
+	::std::mem::forget(src);
+	drop(prev);
+}
+
+fn value_type(t: UINT) -> Option<VALUE_TYPE> {
+	let all = [
+		T_UNDEFINED, T_NULL, T_BOOL, T_INT, T_FLOAT, T_STRING, T_DATE, T_CURRENCY, T_LENGTH,
+		T_ARRAY, T_MAP, T_FUNCTION, T_BYTES, T_OBJECT, T_DOM_OBJECT, T_RESOURCE, T_RANGE,
+		T_DURATION, T_ANGLE, T_COLOR, T_ENUM, T_ASSET,
+	];
+	all.iter().find(|&&x| x as UINT == t).cloned()
+}
+
+/// Keys are matched by text for strings and symbols and by value for everything else.
+fn same_key(a: &VALUE, b: &VALUE) -> bool {
+	if a.t == T_STRING && b.t == T_STRING {
+		// copies of the key share its node, which can not be locked twice
+		if a.d == b.d {
+			return true;
+		}
+		return with_data(a, |x| with_data(b, |y| match (&*x, &*y) {
+			(Data::Chars(x), Data::Chars(y)) => x == y,
+			_ => false,
+		})).and_then(|r| r).unwrap_or(false);
+	}
+	compare(a, b)
+}
+
+/// Structural equality.
+pub(crate) fn compare(a: &VALUE, b: &VALUE) -> bool {
+	if a.t != b.t || a.u != b.u {
+		return false;
+	}
+	if a.d == b.d {
+		return true;
+	}
+	// script objects are compared by identity
+	if node(a).is_none() || a.t == T_OBJECT {
+		return false;
+	}
+	// snapshot both sides in order not to hold two locks at once
+	let left = snapshot(a);
+	let right = snapshot(b);
+	match (left, right) {
+		(Some(Snapshot::Chars(x)), Some(Snapshot::Chars(y))) => x == y,
+		(Some(Snapshot::Bytes(x)), Some(Snapshot::Bytes(y))) => x == y,
+		(Some(Snapshot::Items(x)), Some(Snapshot::Items(y))) => {
+			// string and symbol keys of the same name are the same key
+			x.len() == y.len() && x.iter().zip(y.iter()).all(|(p, q)| same_key(&p.0 .0, &q.0 .0) && compare(&p.1 .0, &q.1 .0))
+		},
+		_ => false,
+	}
+}
+
+enum Snapshot {
+	Chars(Vec<u16>),
+	Bytes(Vec<u8>),
+	/// Array items (with index keys) or map pairs.
+	Items(Vec<(Slot, Slot)>),
+	Functor,
+}
+
+fn snapshot(v: &VALUE) -> Option<Snapshot> {
+	with_data(v, |data| match data {
+		Data::Chars(chars) => Snapshot::Chars(chars.clone()),
+		Data::Bytes(bytes) => Snapshot::Bytes(bytes.clone()),
+		Data::Array(items) => Snapshot::Items(items.iter().enumerate().map(|(i, v)| (Slot::int(i as i32), v.clone())).collect()),
+		Data::Map(items) | Data::Object { props: items, .. } => Snapshot::Items(items.clone()),
+		Data::Functor { .. } | Data::Script(_) => Snapshot::Functor,
+	})
+}
+
+/// Array items or map pairs of the value.
+pub(crate) fn items(v: &VALUE) -> Vec<(Slot, Slot)> {
+	match snapshot(v) {
+		Some(Snapshot::Items(items)) => items,
+		_ => Vec::new(),
+	}
+}
+
+/// Deep copy of containers.
+fn isolated(v: &VALUE) -> Slot {
+	match v.t {
+		T_ARRAY => {
+			let items = items(v).into_iter().map(|(_, v)| isolated(&v.0)).collect();
+			Slot::array(items)
+		},
+		T_MAP | T_OBJECT => {
+			let items = items(v).into_iter().map(|(k, v)| (k, isolated(&v.0))).collect();
+			Slot::map(items)
+		},
+		_ => Slot::copy(v),
+	}
+}
+
+
+macro_rules! deref {
+	($p:ident) => {
+		if $p.is_null() {
+			return VALUE_RESULT::BAD_PARAMETER;
+		} else {
+			unsafe { &*$p }
+		}
+	};
+	(mut $p:ident) => {
+		if $p.is_null() {
+			return VALUE_RESULT::BAD_PARAMETER;
+		} else {
+			unsafe { &mut *$p }
+		}
+	};
+}
+
+pub(crate) extern "system" fn ValueInit(pval: *mut VALUE) -> VALUE_RESULT {
+	let v = deref!(mut pval);
+	*v = VALUE::new();
+	VALUE_RESULT::OK
+}
+
+pub(crate) extern "system" fn ValueClear(pval: *mut VALUE) -> VALUE_RESULT {
+	let v = deref!(mut pval);
+	assign(v, Slot::new());
+	VALUE_RESULT::OK
+}
+
+pub(crate) extern "system" fn ValueCompare(pval1: *const VALUE, pval2: *const VALUE) -> VALUE_RESULT {
+	let a = deref!(pval1);
+	let b = deref!(pval2);
+	if compare(a, b) { VALUE_RESULT::OK_TRUE } else { VALUE_RESULT::OK }
+}
+
+pub(crate) extern "system" fn ValueCopy(pdst: *mut VALUE, psrc: *const VALUE) -> VALUE_RESULT {
+	let src = deref!(psrc);
+	let copy = Slot::copy(src);
+	let dst = deref!(mut pdst);
+	assign(dst, copy);
+	VALUE_RESULT::OK
+}
+
+pub(crate) extern "system" fn ValueIsolate(pdst: *mut VALUE) -> VALUE_RESULT {
+	let v = deref!(mut pdst);
+	let copy = isolated(v);
+	assign(v, copy);
+	VALUE_RESULT::OK
+}
+
+pub(crate) extern "system" fn ValueType(pval: *const VALUE, pType: *mut UINT, pUnits: *mut UINT) -> VALUE_RESULT {
+	let v = deref!(pval);
+	if !pType.is_null() {
+		unsafe { *pType = v.t as UINT };
+	}
+	if !pUnits.is_null() {
+		unsafe { *pUnits = v.u };
+	}
+	VALUE_RESULT::OK
+}
+
+pub(crate) extern "system" fn ValueStringData(pval: *const VALUE, pChars: *mut LPCWSTR, pNumChars: *mut UINT) -> VALUE_RESULT {
+	let v = deref!(pval);
+	if v.t != T_STRING {
+		return VALUE_RESULT::INCOMPATIBLE_TYPE;
+	}
+	// strings are immutable, so the pointer stays valid while the value is alive
+	let r = with_data(v, |data| match data {
+		Data::Chars(chars) => Some((chars.as_ptr(), chars.len() as UINT - 1)),
+		_ => None,
+	}).and_then(|r| r);
+	match r {
+		Some((p, n)) => {
+			if !pChars.is_null() {
+				unsafe { *pChars = p };
+			}
+			if !pNumChars.is_null() {
+				unsafe { *pNumChars = n };
+			}
+			VALUE_RESULT::OK
+		},
+		None => VALUE_RESULT::INCOMPATIBLE_TYPE,
+	}
+}
+
+pub(crate) extern "system" fn ValueStringDataSet(pval: *mut VALUE, chars: LPCWSTR, numChars: UINT, units: UINT) -> VALUE_RESULT {
+	let v = deref!(mut pval);
+	let mut text = if chars.is_null() {
+		Vec::new()
+	} else {
+		unsafe { ::std::slice::from_raw_parts(chars, numChars as usize) }.to_vec()
+	};
+	text.push(0);
+	assign(v, Slot::with(T_STRING, units, Data::Chars(text)));
+	VALUE_RESULT::OK
+}
+
+pub(crate) extern "system" fn ValueIntData(pval: *const VALUE, pData: *mut INT) -> VALUE_RESULT {
+	let v = deref!(pval);
+	match v.t {
+		T_INT | T_BOOL | T_COLOR | T_ENUM => {
+			if !pData.is_null() {
+				unsafe { *pData = v.d as u32 as INT };
+			}
+			VALUE_RESULT::OK
+		},
+		_ => VALUE_RESULT::INCOMPATIBLE_TYPE,
+	}
+}
+
+pub(crate) extern "system" fn ValueIntDataSet(pval: *mut VALUE, data: INT, vtype: UINT, units: UINT) -> VALUE_RESULT {
+	let v = deref!(mut pval);
+	let slot = match value_type(vtype) {
+		Some(T_ARRAY) => {
+			let n = if data > 0 { data as usize } else { 0 };
+			Slot::array((0..n).map(|_| Slot::new()).collect())
+		},
+		Some(T_MAP) => {
+			Slot::map(Vec::new())
+		},
+		Some(t @ T_FLOAT) | Some(t @ T_LENGTH) | Some(t @ T_DURATION) | Some(t @ T_ANGLE) => {
+			Slot(VALUE { t, u: units, d: (data as f64).to_bits() })
+		},
+		Some(t) if !is_reference(t) && t != T_DOM_OBJECT && t != T_ASSET => {
+			Slot(VALUE { t, u: units, d: data as u32 as u64 })
+		},
+		_ => return VALUE_RESULT::BAD_PARAMETER,
+	};
+	assign(v, slot);
+	VALUE_RESULT::OK
+}
+
+pub(crate) extern "system" fn ValueInt64Data(pval: *const VALUE, pData: *mut INT64) -> VALUE_RESULT {
+	let v = deref!(pval);
+	match v.t {
+		T_DATE | T_CURRENCY | T_ASSET => {
+			if !pData.is_null() {
+				unsafe { *pData = v.d as INT64 };
+			}
+			VALUE_RESULT::OK
+		},
+		_ => VALUE_RESULT::INCOMPATIBLE_TYPE,
+	}
+}
+
+pub(crate) extern "system" fn ValueInt64DataSet(pval: *mut VALUE, data: INT64, vtype: UINT, units: UINT) -> VALUE_RESULT {
+	let v = deref!(mut pval);
+	let slot = match value_type(vtype) {
+		Some(T_ASSET) => {
+			if data == 0 {
+				return VALUE_RESULT::BAD_PARAMETER;
+			}
+			Slot::copy(&VALUE { t: T_ASSET, u: 0, d: data as u64 })
+		},
+		Some(t) if !is_reference(t) && t != T_DOM_OBJECT => {
+			Slot(VALUE { t, u: units, d: data as u64 })
+		},
+		_ => return VALUE_RESULT::BAD_PARAMETER,
+	};
+	assign(v, slot);
+	VALUE_RESULT::OK
+}
+
+pub(crate) extern "system" fn ValueFloatData(pval: *const VALUE, pData: *mut FLOAT_VALUE) -> VALUE_RESULT {
+	let v = deref!(pval);
+	let f = match v.t {
+		T_FLOAT | T_LENGTH | T_DURATION | T_ANGLE => f64::from_bits(v.d),
+		T_INT => v.d as u32 as i32 as f64,
+		_ => return VALUE_RESULT::INCOMPATIBLE_TYPE,
+	};
+	if !pData.is_null() {
+		unsafe { *pData = f };
+	}
+	VALUE_RESULT::OK
+}
+
+pub(crate) extern "system" fn ValueFloatDataSet(pval: *mut VALUE, data: FLOAT_VALUE, vtype: UINT, units: UINT) -> VALUE_RESULT {
+	let v = deref!(mut pval);
+	match value_type(vtype) {
+		Some(t @ T_FLOAT) | Some(t @ T_LENGTH) | Some(t @ T_DURATION) | Some(t @ T_ANGLE) => {
+			assign(v, Slot(VALUE { t, u: units, d: data.to_bits() }));
+			VALUE_RESULT::OK
+		},
+		_ => VALUE_RESULT::BAD_PARAMETER,
+	}
+}
+
+pub(crate) extern "system" fn ValueBinaryData(pval: *const VALUE, pBytes: *mut LPCBYTE, pnBytes: *mut UINT) -> VALUE_RESULT {
+	let v = deref!(pval);
+	if v.t != T_BYTES {
+		return VALUE_RESULT::INCOMPATIBLE_TYPE;
+	}
+	// bytes are immutable as well
+	let r = with_data(v, |data| match data {
+		Data::Bytes(bytes) => Some((bytes.as_ptr(), bytes.len() as UINT)),
+		_ => None,
+	}).and_then(|r| r);
+	match r {
+		Some((p, n)) => {
+			if !pBytes.is_null() {
+				unsafe { *pBytes = p };
+			}
+			if !pnBytes.is_null() {
+				unsafe { *pnBytes = n };
+			}
+			VALUE_RESULT::OK
+		},
+		None => VALUE_RESULT::INCOMPATIBLE_TYPE,
+	}
+}
+
+pub(crate) extern "system" fn ValueBinaryDataSet(pval: *mut VALUE, pBytes: LPCBYTE, nBytes: UINT, vtype: UINT, units: UINT) -> VALUE_RESULT {
+	let v = deref!(mut pval);
+	if value_type(vtype) != Some(T_BYTES) {
+		return VALUE_RESULT::BAD_PARAMETER;
+	}
+	let bytes = if pBytes.is_null() {
+		Vec::new()
+	} else {
+		unsafe { ::std::slice::from_raw_parts(pBytes, nBytes as usize) }.to_vec()
+	};
+	assign(v, Slot::with(T_BYTES, units, Data::Bytes(bytes)));
+	VALUE_RESULT::OK
+}
+
+pub(crate) extern "system" fn ValueElementsCount(pval: *const VALUE, pn: *mut INT) -> VALUE_RESULT {
+	let v = deref!(pval);
+	let n = with_data(v, |data| match data {
+		Data::Array(items) => items.len(),
+		Data::Map(items) | Data::Object { props: items, .. } => items.len(),
+		_ => 0,
+	}).unwrap_or(0);
+	if !pn.is_null() {
+		unsafe { *pn = n as INT };
+	}
+	VALUE_RESULT::OK
+}
+
+pub(crate) extern "system" fn ValueNthElementValue(pval: *const VALUE, n: INT, pretval: *mut VALUE) -> VALUE_RESULT {
+	let v = deref!(pval);
+	let item = with_data(v, |data| {
+		let n = n as usize;
+		match data {
+			Data::Array(items) => items.get(n).cloned(),
+			Data::Map(items) | Data::Object { props: items, .. } => items.get(n).map(|pair| pair.1.clone()),
+			_ => None,
+		}
+	}).and_then(|r| r);
+	let ok = if item.is_some() { VALUE_RESULT::OK } else { VALUE_RESULT::BAD_PARAMETER };
+	item.unwrap_or_default().store(pretval);
+	ok
+}
+
+pub(crate) extern "system" fn ValueNthElementValueSet(pval: *mut VALUE, n: INT, pval_to_set: *const VALUE) -> VALUE_RESULT {
+	let item = Slot::copy(deref!(pval_to_set));
+	let v = deref!(mut pval);
+	if n < 0 {
+		return VALUE_RESULT::BAD_PARAMETER;
+	}
+	if v.t == T_UNDEFINED || v.t == T_NULL {
+		assign(v, Slot::array(Vec::new()));
+	}
+	let n = n as usize;
+	let prev = with_data(v, |data| match data {
+		Data::Array(items) => {
+			if n >= items.len() {
+				items.resize_with(n + 1, Slot::new);
+			}
+			Ok(::std::mem::replace(&mut items[n], item))
+		},
+		Data::Map(items) | Data::Object { props: items, .. } => {
+			if n < items.len() {
+				Ok(::std::mem::replace(&mut items[n].1, item))
+			} else {
+				Err(VALUE_RESULT::BAD_PARAMETER)
+			}
+		},
+		_ => Err(VALUE_RESULT::INCOMPATIBLE_TYPE),
+	}).unwrap_or(Err(VALUE_RESULT::INCOMPATIBLE_TYPE));
+	// the replaced item is released after unlocking
+	match prev {
+		Ok(_) => VALUE_RESULT::OK,
+		Err(e) => e,
+	}
+}
+
+pub(crate) extern "system" fn ValueNthElementKey(pval: *const VALUE, n: INT, pretval: *mut VALUE) -> VALUE_RESULT {
+	let v = deref!(pval);
+	let key = with_data(v, |data| match data {
+		Data::Map(items) | Data::Object { props: items, .. } => items.get(n as usize).map(|pair| pair.0.clone()),
+		_ => None,
+	}).and_then(|r| r);
+	let ok = if key.is_some() { VALUE_RESULT::OK } else { VALUE_RESULT::BAD_PARAMETER };
+	key.unwrap_or_default().store(pretval);
+	ok
+}
+
+pub(crate) extern "system" fn ValueEnumElements(pval: *const VALUE, penum: KeyValueCallback, param: LPVOID) -> VALUE_RESULT {
+	let v = deref!(pval);
+	if v.t != T_ARRAY && v.t != T_MAP && v.t != T_OBJECT {
+		return VALUE_RESULT::INCOMPATIBLE_TYPE;
+	}
+	for (key, val) in items(v) {
+		if penum(param, &key.0, &val.0) == 0 {
+			break;
+		}
+	}
+	VALUE_RESULT::OK
+}
+
+pub(crate) extern "system" fn ValueSetValueToKey(pval: *mut VALUE, pkey: *const VALUE, pval_to_set: *const VALUE) -> VALUE_RESULT {
+	let key = Slot::copy(deref!(pkey));
+	let item = Slot::copy(deref!(pval_to_set));
+	let v = deref!(mut pval);
+	if v.t == T_ARRAY {
+		if let Some(n) = key.as_int().filter(|&n| n >= 0) {
+			return ValueNthElementValueSet(v, n, &item.0);
+		}
+		return VALUE_RESULT::INCOMPATIBLE_TYPE;
+	}
+	if v.t == T_UNDEFINED || v.t == T_NULL {
+		assign(v, Slot::map(Vec::new()));
+	}
+	let prev = with_data(v, |data| match data {
+		Data::Map(items) | Data::Object { props: items, .. } => {
+			if let Some(pair) = items.iter_mut().find(|pair| same_key(&pair.0 .0, &key.0)) {
+				Some(::std::mem::replace(&mut pair.1, item))
+			} else {
+				items.push((key, item));
+				Some(Slot::new())
+			}
+		},
+		_ => None,
+	}).and_then(|r| r);
+	if prev.is_some() { VALUE_RESULT::OK } else { VALUE_RESULT::INCOMPATIBLE_TYPE }
+}
+
+/// Inherited `constructor` property of script objects, which holds the class `name`.
+fn constructor(class: &str, key: &VALUE) -> Slot {
+	let is_constructor = key.t == T_STRING && Slot::copy(key).as_str().as_deref() == Some("constructor");
+	if is_constructor {
+		Slot::map(vec![(Slot::string("name", 0), Slot::string(class, 0))])
+	} else {
+		Slot::new()
+	}
+}
+
+pub(crate) extern "system" fn ValueGetValueOfKey(pval: *const VALUE, pkey: *const VALUE, pretval: *mut VALUE) -> VALUE_RESULT {
+	let v = deref!(pval);
+	let key = deref!(pkey);
+	let item = if v.t == T_ARRAY {
+		let n = if key.t == T_INT { key.d as u32 as i32 } else { -1 };
+		with_data(v, |data| match data {
+			Data::Array(items) if n >= 0 => items.get(n as usize).cloned(),
+			_ => None,
+		}).and_then(|r| r)
+	} else {
+		with_data(v, |data| match data {
+			Data::Map(items) => items.iter().find(|pair| same_key(&pair.0 .0, key)).map(|pair| pair.1.clone()),
+			Data::Object { class, props } => props.iter().find(|pair| same_key(&pair.0 .0, key)).map(|pair| pair.1.clone())
+				.or_else(|| Some(constructor(class, key))),
+			_ => None,
+		}).and_then(|r| r)
+	};
+	item.unwrap_or_default().store(pretval);
+	VALUE_RESULT::OK
+}
+
+pub(crate) extern "system" fn ValueToString(pval: *mut VALUE, how: VALUE_STRING_CVT_TYPE) -> VALUE_RESULT {
+	let v = deref!(mut pval);
+	if how == VALUE_STRING_CVT_TYPE::SIMPLE && v.t == T_STRING {
+		return VALUE_RESULT::OK;
+	}
+	let mut text = ::json::to_string(&Value::from(&*v));
+	if how == VALUE_STRING_CVT_TYPE::JSON_MAP && v.t == T_MAP {
+		text = text[1..text.len() - 1].to_owned();
+	}
+	assign(v, Slot::string(&text, 0));
+	VALUE_RESULT::OK
+}
+
+pub(crate) extern "system" fn ValueFromString(pval: *mut VALUE, str: LPCWSTR, strLength: UINT, how: VALUE_STRING_CVT_TYPE) -> UINT {
+	if pval.is_null() {
+		return strLength.max(1);
+	}
+	let text = if str.is_null() {
+		String::new()
+	} else {
+		let chars = unsafe { ::std::slice::from_raw_parts(str, strLength as usize) };
+		String::from_utf16_lossy(chars)
+	};
+	let v = unsafe { &mut *pval };
+	if how == VALUE_STRING_CVT_TYPE::SIMPLE {
+		assign(v, Slot::string(&text, 0));
+		return 0;
+	}
+	if text.trim().is_empty() {
+		assign(v, Slot::new());
+		return 0;
+	}
+
+	// `key: value, ...` without braces, which the literals can be as well
+	let (parsed, shift) = if how == VALUE_STRING_CVT_TYPE::JSON_MAP {
+		(parse(&format!("{{{}}}", text)), 1)
+	} else {
+		match (parse(&text), parse(&format!("{{{}}}", text))) {
+			(Err(_), Ok(map)) => (Ok(map), 1),
+			(parsed, _) => (parsed, 0),
+		}
+	};
+	match parsed {
+		Ok(slot) => {
+			assign(v, slot);
+			0
+		},
+		Err(e) => {
+			assign(v, Slot::new());
+			let offset = e.offset().saturating_sub(shift).min(text.len());
+			text[offset..].encode_utf16().count().max(1) as UINT
+		},
+	}
+}
+
+pub(crate) extern "system" fn ValueInvoke(pval: *const VALUE, pthis: *mut VALUE, argc: UINT, argv: *const VALUE, pretval: *mut VALUE, url: LPCWSTR) -> VALUE_RESULT {
+	let _ = url;
+	// keep the function alive during the call
+	let func = Slot::copy(deref!(pval));
+	let script = with_data(&func.0, |data| match *data {
+		Data::Script(ref f) => Some(f.clone()),
+		_ => None,
+	}).and_then(|r| r);
+	if let Some(f) = script {
+		let this = if pthis.is_null() { Value::new() } else { Value::from(unsafe { &*pthis }) };
+		let args = unsafe { Value::unpack_from(argv, argc) };
+		let rv = f(&this, &args);
+		Slot::copy(unsafe { &*rv.as_cptr() }).store(pretval);
+		return VALUE_RESULT::OK;
+	}
+	let functor = with_data(&func.0, |data| match *data {
+		Data::Functor { invoke, tag, .. } => Some((invoke, tag)),
+		_ => None,
+	}).and_then(|r| r);
+	match functor {
+		Some((invoke, tag)) => {
+			let mut rv = VALUE::new();
+			invoke(tag as LPVOID, argc, argv, &mut rv);
+			Slot::adopt(rv).store(pretval);
+			VALUE_RESULT::OK
+		},
+		None => VALUE_RESULT::INCOMPATIBLE_TYPE,
+	}
+}
+
+pub(crate) extern "system" fn ValueNativeFunctorSet(pval: *mut VALUE, pinvoke: NATIVE_FUNCTOR_INVOKE, prelease: NATIVE_FUNCTOR_RELEASE, tag: LPVOID) -> VALUE_RESULT {
+	let v = deref!(mut pval);
+	let functor = Data::Functor { invoke: pinvoke, release: prelease, tag: tag as usize };
+	assign(v, Slot::with(T_FUNCTION, 0, functor));
+	VALUE_RESULT::OK
+}
+
+pub(crate) extern "system" fn ValueIsNativeFunctor(pval: *const VALUE) -> BOOL {
+	if pval.is_null() {
+		return false as BOOL;
+	}
+	let v = unsafe { &*pval };
+	let yes = v.t == T_FUNCTION && with_data(v, |data| matches!(*data, Data::Functor { .. })).unwrap_or(false);
+	yes as BOOL
+}
+
+/// Invoke a native function stored in the value.
+/// Parse a JSON+ literal.
+pub(crate) fn parse(text: &str) -> Result<Slot, ::json::Error> {
+	::json::parse(text).map(|v| Slot::copy(unsafe { &*v.as_cptr() }))
+}
+
+pub(crate) fn invoke(func: &Slot, args: &[Slot]) -> Option<Slot> {
+	if ValueIsNativeFunctor(&func.0) == 0 {
+		return None;
+	}
+	let argv: Vec<VALUE> = args.iter().map(|a| a.0.clone()).collect();
+	let mut rv = VALUE::new();
+	ValueInvoke(&func.0, ::std::ptr::null_mut(), argv.len() as UINT, argv.as_ptr(), &mut rv, ::std::ptr::null());
+	Some(Slot::adopt(rv))
+}
@@ -8,28 +8,21 @@ use capi::schandler::NativeHandler;
 use dom::{self, event::EventHandler};
 use eventhandler::*;
 use value::{Value};
+use error::{Error, ScriptError};
 
 pub use capi::scdef::{LOAD_RESULT, OUTPUT_SUBSYTEMS, OUTPUT_SEVERITY};
-pub use capi::scdef::{SCN_LOAD_DATA, SCN_DATA_LOADED, SCN_ATTACH_BEHAVIOR, SCN_INVALIDATE_RECT};
+pub use capi::scdef::{SCN_LOAD_DATA, SCN_DATA_LOADED, SCN_ATTACH_BEHAVIOR, SCN_INVALIDATE_RECT, SCN_POSTED_NOTIFICATION};
 
 
 /// A specialized `Result` type for Sciter host operations.
-pub type Result<T> = ::std::result::Result<T, ()>;
+pub type Result<T> = ::std::result::Result<T, Error>;
 
 macro_rules! ok_or {
-	($ok:ident) => {
+	($ok:ident, $call:expr) => {
 		if $ok != 0 {
 			Ok(())
 		} else {
-			Err(())
-		}
-	};
-
-	($ok:ident, $rv:expr) => {
-		if $ok != 0 {
-			Ok($rv)
-		} else {
-			Err(())
+			Err(Error::from(()).with_call($call))
 		}
 	};
 
@@ -91,6 +84,10 @@ pub trait HostHandler {
 	/// This notification is sent when the engine needs some area to be redrawn.
 	fn on_invalidate(&mut self, pnm: &SCN_INVALIDATE_RECT) {}
 
+	/// This notification is sent on the UI thread in response to `SciterPostCallback`
+	/// which was not issued by a [`Dispatcher`](../dispatch/struct.Dispatcher.html).
+	fn on_posted_notification(&mut self, pnm: &mut SCN_POSTED_NOTIFICATION) {}
+
 	/// This output function will be used for reporting problems found while loading html and css documents.
 	fn on_debug_output(&mut self, subsystem: OUTPUT_SUBSYTEMS, severity: OUTPUT_SEVERITY, message: &str) {
 		if !message.is_empty() {
@@ -263,6 +260,11 @@ impl Host {
 		self.hwnd
 	}
 
+	/// Get a [`Dispatcher`](../dispatch/struct.Dispatcher.html) to run code on the UI thread of the window from other threads.
+	pub fn dispatcher(&self) -> ::dispatch::Dispatcher {
+		::dispatch::Dispatcher::new(self.hwnd)
+	}
+
 	/// Get window root DOM element.
 	pub fn get_root(&self) -> Option<dom::Element> {
 		dom::Element::from_window(self.hwnd).ok()
@@ -309,26 +311,38 @@ impl Host {
 
 	/// Evaluate the given script in context of the current document.
 	///
-	/// This function returns `Result<Value,Value>` with script function result value or with Sciter script error.
-	pub fn eval_script(&self, script: &str) -> ::std::result::Result<Value, Value> {
+	/// This function returns `Result<Value,ScriptError>` with script function result value or with Sciter script error.
+	pub fn eval_script(&self, script: &str) -> ::std::result::Result<Value, ScriptError> {
+		self.eval_impl(script, None)
+	}
+
+	/// Evaluate the given script named by the `url` in context of the current document.
+	///
+	/// The engine doesn't know about the `url`, it is attached to the failure
+	/// as the [`ScriptError::source_url`](../error/struct.ScriptError.html#method.source_url) context.
+	pub fn eval_script_with_url(&self, script: &str, url: &str) -> ::std::result::Result<Value, ScriptError> {
+		self.eval_impl(script, Some(url))
+	}
+
+	fn eval_impl(&self, script: &str, url: Option<&str>) -> ::std::result::Result<Value, ScriptError> {
 		let (s,n) = s2wn!(script);
 		let mut rv = Value::new();
 		let ok = (_API.SciterEval)(self.hwnd, s.as_ptr(), n, rv.as_ptr());
-		ok_or!(ok, rv, rv)
+		ok_or!(ok, rv, ScriptError::from(rv).with_source_url(url))
 	}
 
 	/// Call a script function defined in the global namespace.
 	///
-	/// This function returns `Result<Value,Value>` with script function result value or with Sciter script error.
+	/// This function returns `Result<Value,ScriptError>` with script function result value or with Sciter script error.
 	///
 	/// You can use the [`&make_args!(args...)`](../macro.make_args.html) macro which helps you
 	/// to construct script arguments from Rust types.
-	pub fn call_function(&self, name: &str, args: &[Value]) -> ::std::result::Result<Value, Value> {
+	pub fn call_function(&self, name: &str, args: &[Value]) -> ::std::result::Result<Value, ScriptError> {
 		let mut rv = Value::new();
 		let s = s2u!(name);
 		let argv = Value::pack_args(args);
 		let ok = (_API.SciterCall)(self.hwnd, s.as_ptr(), argv.len() as UINT, argv.as_ptr(), rv.as_ptr());
-		ok_or!(ok, rv, rv)
+		ok_or!(ok, rv, ScriptError::from(rv))
 	}
 
 	/// Set home url for Sciter resources.
@@ -342,7 +356,7 @@ impl Host {
 	pub fn set_home_url(&self, url: &str) -> Result<()> {
 		let s = s2w!(url);
 		let ok = (_API.SciterSetHomeURL)(self.hwnd, s.as_ptr());
-		ok_or!(ok)
+		ok_or!(ok, "SciterSetHomeURL").map_err(|e| e.with_url(url))
 	}
 
 	/// Set media type of this Sciter instance.
@@ -356,7 +370,7 @@ impl Host {
 	pub fn set_media_type(&self, media_type: &str) -> Result<()> {
 		let s = s2w!(media_type);
 		let ok = (_API.SciterSetMediaType)(self.hwnd, s.as_ptr());
-		ok_or!(ok)
+		ok_or!(ok, "SciterSetMediaType")
 	}
 
 	/// Set media variables (dictionary) for this Sciter instance.
@@ -377,7 +391,7 @@ impl Host {
 	/// ```
 	pub fn set_media_vars(&self, media: &Value) -> Result<()> {
 		let ok = (_API.SciterSetMediaVars)(self.hwnd, media.as_cptr());
-		ok_or!(ok)
+		ok_or!(ok, "SciterSetMediaVars")
 	}
 
 	/// Set or append the [master](https://sciter.com/css-extensions-in-h-smile-engine-part-i-style-sets/)
@@ -386,12 +400,12 @@ impl Host {
 		let s = s2u!(css);
 		let b = s.as_bytes();
 		let n = b.len() as UINT;
-		let ok = if append {
-			(_API.SciterAppendMasterCSS)(b.as_ptr(), n)
+		let (ok, call) = if append {
+			((_API.SciterAppendMasterCSS)(b.as_ptr(), n), "SciterAppendMasterCSS")
 		} else {
-			(_API.SciterSetMasterCSS)(b.as_ptr(), n)
+			((_API.SciterSetMasterCSS)(b.as_ptr(), n), "SciterSetMasterCSS")
 		};
-		ok_or!(ok)
+		ok_or!(ok, call)
 	}
 
 	/// Set (reset) style sheet of the **current** document.
@@ -404,7 +418,7 @@ impl Host {
 		let b = s.as_bytes();
 		let n = b.len() as UINT;
 		let ok = (_API.SciterSetCSS)(self.hwnd, b.as_ptr(), n, url.as_ptr(), media.as_ptr());
-		ok_or!(ok)
+		ok_or!(ok, "SciterSetCSS").map_err(|e| e.with_url(base_url))
 	}
 
 }
@@ -492,6 +506,15 @@ extern "system" fn _on_handle_notification<T: HostHandler>(pnm: *mut ::capi::scd
 			0
 		}
 
+		SCITER_NOTIFICATION::SC_POSTED_NOTIFICATION => {
+			let scnm = pnm as *mut SCN_POSTED_NOTIFICATION;
+			let scnm = unsafe { &mut *scnm };
+			if !::dispatch::run_posted(scnm.wparam, scnm.lparam) {
+				me.on_posted_notification(scnm);
+			}
+			0
+		}
+
 		_ => 0,
 	};
 
@@ -553,7 +576,7 @@ impl Archive {
     if !p.is_null() {
       Ok(Archive(p))
     } else {
-      Err(())
+      Err(Error::from(()).with_call("SciterOpenArchive"))
     }
   }
 
@@ -0,0 +1,817 @@
+/*! Native reader and writer of the Sciter extended JSON.
+
+Unlike [`Value::parse`](../value/struct.Value.html#method.parse) and [`Value::into_string`](../value/struct.Value.html#method.into_string),
+which delegate the job to the engine, this module does the text processing in Rust,
+so it can be used by offline tooling and reports the exact location of a syntax error.
+
+Besides the plain JSON it understands:
+
+* unquoted keys and symbols: `{ name: value }`,
+* single quoted strings, comments and trailing commas,
+* colors: `#fff`, `#ffffff80`,
+* lengths: `10px`, `1.5em`, `50%`,
+* durations: `1s`, `250ms`,
+* angles: `90deg`, `1rad`, `100grad`, `0.5turn`,
+* dates: `2020-12-31`, `2020-12-31T23:59:59.5Z`, `2020-12-31T23:59:59+02:00`
+  and local `2020-12-31T23:59`,
+* big integers: `9007199254740993n`,
+* `undefined`, `nothing`, `NaN` and `Infinity`.
+
+```
+# sciter::fake::install();
+use sciter::{json, Value};
+
+let v = json::parse("{ color: #f00, width: 10px, delay: 250ms }").unwrap();
+assert_eq!(v.get_item("color"), Value::color(0xFF0000FF));
+assert_eq!(v.get_item("delay"), Value::duration(0.25));
+assert_eq!(json::to_string(&v), r#"{"color":#ff0000,"width":10px,"delay":0.25s}"#);
+
+let e = json::parse("[1,\n 2 3]").unwrap_err();
+assert_eq!((e.line(), e.column()), (2, 4));
+```
+
+Colors are stored in the `0xAABBGGRR` form (see [`Value::color`](../value/struct.Value.html#method.color)),
+dates as 100ns intervals since the Unix epoch (see [`Value::date`](../value/struct.Value.html#method.date)):
+in UTC with the `DT_UTC` flag, or the local wall clock time for the ones without an offset.
+
+With the `serde_json` feature enabled, `Value` also converts directly from and to `serde_json::Value`
+without going through a text, see `TryFrom<&Value> for serde_json::Value` for the mapping of the Sciter-only types.
+
+*/
+
+use ::{_API};
+
+use capi::scvalue::VALUE_UNIT_TYPE_DATE;
+use value::{Length, LengthUnit, Value};
+#[cfg(feature = "serde_json")]
+use value::{ConversionError, FromValue};
+
+
+/// Syntax error with its location in the source text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Error {
+	line: usize,
+	column: usize,
+	offset: usize,
+	message: String,
+}
+
+impl Error {
+	/// Line number of the error, starting from 1.
+	pub fn line(&self) -> usize {
+		self.line
+	}
+
+	/// Column number (in characters) of the error, starting from 1.
+	pub fn column(&self) -> usize {
+		self.column
+	}
+
+	/// Byte offset of the error in the source text.
+	pub fn offset(&self) -> usize {
+		self.offset
+	}
+
+	/// Description of the error.
+	pub fn message(&self) -> &str {
+		&self.message
+	}
+}
+
+impl ::std::fmt::Display for Error {
+	fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+		write!(f, "{} at line {} column {}", self.message, self.line, self.column)
+	}
+}
+
+impl ::std::error::Error for Error {}
+
+/// A specialized `Result` type for the JSON parsing.
+pub type Result<T> = ::std::result::Result<T, Error>;
+
+
+/// Parse a JSON+ text into value.
+///
+/// Arrays and maps may be nested up to 128 levels deep, deeper input is an error.
+pub fn parse(text: &str) -> Result<Value> {
+	let mut p = Parser { text, pos: 0, depth: 0 };
+	p.skip()?;
+	let value = p.value()?;
+	p.skip()?;
+	if p.pos < text.len() {
+		return Err(p.error("unexpected characters after the value"));
+	}
+	return Ok(value);
+}
+
+/// Print value as a compact JSON+ text.
+///
+/// Values that have no literal form (functions, elements, objects and so on) are written as `null`.
+pub fn to_string(value: &Value) -> String {
+	let mut out = String::new();
+	write(&mut out, value);
+	return out;
+}
+
+
+/// Convert a `serde_json` value.
+///
+/// Integers are stored as numbers while they fit into the script number range and rounded to floats beyond it,
+/// see [`Value::from(i64)`](../value/struct.Value.html#impl-From%3Ci64%3E-for-Value).
+#[cfg(feature = "serde_json")]
+impl<'a> From<&'a serde_json::Value> for Value {
+	fn from(json: &'a serde_json::Value) -> Self {
+		use serde_json::Value as Json;
+		match *json {
+			Json::Null => Value::null(),
+			Json::Bool(b) => Value::from(b),
+			Json::Number(ref n) => {
+				if let Some(i) = n.as_i64() {
+					Value::from(i)
+				} else if let Some(u) = n.as_u64() {
+					Value::from(u)
+				} else {
+					Value::from(n.as_f64().unwrap_or(f64::NAN))
+				}
+			},
+			Json::String(ref s) => Value::from(s.as_str()),
+			Json::Array(ref items) => items.iter().map(Value::from).collect(),
+			Json::Object(ref items) => {
+				let mut map = Value::map();
+				for (key, item) in items {
+					map.set_item(key.as_str(), Value::from(item));
+				}
+				map
+			},
+		}
+	}
+}
+
+/// Convert a `serde_json` value, see `From<&serde_json::Value>`.
+#[cfg(feature = "serde_json")]
+impl From<serde_json::Value> for Value {
+	fn from(json: serde_json::Value) -> Self {
+		Value::from(&json)
+	}
+}
+
+/// Convert to a `serde_json` value.
+///
+/// Values without a JSON counterpart are stored as follows:
+///
+/// * `undefined` and `nothing` as `null`, non-finite floats as `null` as well;
+/// * currency (and Sciter.JS `BigInt`) as its raw 64-bit integer, see [`Value::to_big_int`](../value/struct.Value.html#method.to_big_int);
+/// * symbols and error strings as strings;
+/// * bytes as a base64 string;
+/// * dates as ISO 8601 strings, e.g. `"2020-12-31T23:59:59Z"`;
+/// * lengths, durations, angles and colors as their JSON+ literals, e.g. `"10px"`, `"0.25s"`, `"1rad"`, `"#ff0000"`;
+/// * script arrays and objects as arrays and objects, non-string map keys as their JSON+ text.
+///
+/// Functions, DOM elements, assets and other engine objects are rejected with the path to the offending element.
+#[cfg(feature = "serde_json")]
+impl<'a> ::std::convert::TryFrom<&'a Value> for serde_json::Value {
+	type Error = ConversionError;
+
+	fn try_from(value: &'a Value) -> ::std::result::Result<Self, ConversionError> {
+		use serde_json::Value as Json;
+		let json = if value.is_undefined() || value.is_nothing() || value.is_null() {
+			Json::Null
+		} else if value.is_bool() {
+			Json::Bool(value.to_bool() == Some(true))
+		} else if value.is_int() {
+			Json::from(value.to_int().unwrap_or_default())
+		} else if value.is_float() {
+			Json::from(value.to_float().unwrap_or_default())
+		} else if value.is_currency() {
+			Json::from(value.to_big_int().unwrap_or_default())
+		} else if value.is_string() {
+			Json::String(value.as_string().unwrap_or_default())
+		} else if value.is_bytes() {
+			Json::String(base64(value.as_bytes().unwrap_or_default()))
+		} else if value.is_date() {
+			let mut out = String::new();
+			write_date(&mut out, value.to_date().unwrap_or_default(), value.has_date_flag(VALUE_UNIT_TYPE_DATE::DT_UTC));
+			Json::String(out)
+		} else if value.is_length() || value.is_duration() || value.is_angle() || value.is_color() {
+			Json::String(to_string(value))
+		} else if value.is_array() || value.is_object_array() {
+			let mut items = Vec::with_capacity(value.len());
+			for (i, item) in value.values().enumerate() {
+				items.push(Json::try_from(&item).map_err(|e| e.at_index(i))?);
+			}
+			Json::Array(items)
+		} else if value.is_map() || value.is_object_map() {
+			let mut items = serde_json::Map::new();
+			for (key, item) in value.items() {
+				let item = Json::try_from(&item).map_err(|e| e.at_key(&key))?;
+				let key = if key.is_string() { key.as_string().unwrap_or_default() } else { to_string(&key) };
+				items.insert(key, item);
+			}
+			Json::Object(items)
+		} else {
+			return Err(ConversionError::new("JSON value", value));
+		};
+		Ok(json)
+	}
+}
+
+/// Convert to a `serde_json` value, see `TryFrom<&Value>`.
+#[cfg(feature = "serde_json")]
+impl FromValue for serde_json::Value {
+	fn from_value(v: &Value) -> Option<Self> {
+		Self::try_from_value(v).ok()
+	}
+
+	fn try_from_value(v: &Value) -> ::std::result::Result<Self, ConversionError> {
+		use std::convert::TryFrom;
+		serde_json::Value::try_from(v)
+	}
+}
+
+
+/// 100ns intervals per second.
+const TICKS: i64 = 10_000_000;
+
+/// Maximum nesting of arrays and maps.
+const MAX_DEPTH: usize = 128;
+
+struct Parser<'a> {
+	text: &'a str,
+	pos: usize,
+	depth: usize,
+}
+
+impl<'a> Parser<'a> {
+	fn error(&self, message: &str) -> Error {
+		self.error_at(self.pos, message)
+	}
+
+	fn error_at(&self, offset: usize, message: &str) -> Error {
+		let offset = offset.min(self.text.len());
+		let before = &self.text[..offset];
+		let line_start = before.rfind('\n').map_or(0, |n| n + 1);
+		Error {
+			line: before.matches('\n').count() + 1,
+			column: before[line_start..].chars().count() + 1,
+			offset,
+			message: message.to_owned(),
+		}
+	}
+
+	fn rest(&self) -> &'a str {
+		&self.text[self.pos..]
+	}
+
+	fn peek(&self) -> Option<char> {
+		self.rest().chars().next()
+	}
+
+	fn next(&mut self) -> Option<char> {
+		let c = self.peek();
+		if let Some(c) = c {
+			self.pos += c.len_utf8();
+		}
+		return c;
+	}
+
+	fn eat(&mut self, s: &str) -> bool {
+		if self.rest().starts_with(s) {
+			self.pos += s.len();
+			true
+		} else {
+			false
+		}
+	}
+
+	/// Skip whitespace and comments.
+	fn skip(&mut self) -> Result<()> {
+		loop {
+			let rest = self.rest();
+			let trimmed = rest.trim_start();
+			self.pos += rest.len() - trimmed.len();
+			if self.eat("//") {
+				self.pos = self.rest().find('\n').map_or(self.text.len(), |n| self.pos + n);
+			} else if self.rest().starts_with("/*") {
+				match self.rest().find("*/") {
+					Some(n) => self.pos += n + 2,
+					None => return Err(self.error("unterminated comment")),
+				}
+			} else {
+				return Ok(());
+			}
+		}
+	}
+
+	fn value(&mut self) -> Result<Value> {
+		match self.peek() {
+			None => Err(self.error("unexpected end of input")),
+			Some('{') => self.nested(Parser::map),
+			Some('[') => self.nested(Parser::array),
+			Some('"') | Some('\'') => Ok(Value::from(self.string()?)),
+			Some('#') => self.color(),
+			Some(c) if c == '-' || c == '+' || c == '.' || c.is_ascii_digit() => self.number(),
+			Some(c) if is_ident_start(c) => {
+				let name = self.ident();
+				Ok(match name {
+					"true" => Value::from(true),
+					"false" => Value::from(false),
+					"null" => Value::null(),
+					"undefined" => Value::new(),
+					"nothing" => Value::nothing(),
+					"NaN" => Value::from(f64::NAN),
+					"Infinity" => Value::from(f64::INFINITY),
+					_ => Value::symbol(name),
+				})
+			},
+			Some(c) => Err(self.error(&format!("unexpected character `{}`", c))),
+		}
+	}
+
+	/// Parse an array or a map, limiting the nesting depth.
+	fn nested(&mut self, parse: fn(&mut Self) -> Result<Value>) -> Result<Value> {
+		if self.depth == MAX_DEPTH {
+			return Err(self.error("nesting is too deep"));
+		}
+		self.depth += 1;
+		let value = parse(self);
+		self.depth -= 1;
+		value
+	}
+
+	fn array(&mut self) -> Result<Value> {
+		self.pos += 1;
+		let mut list = Value::array(0);
+		loop {
+			self.skip()?;
+			if self.eat("]") {
+				return Ok(list);
+			}
+			list.push(self.value()?);
+			self.skip()?;
+			if !self.eat(",") && !self.rest().starts_with(']') {
+				return Err(self.error("expected `,` or `]`"));
+			}
+		}
+	}
+
+	fn map(&mut self) -> Result<Value> {
+		self.pos += 1;
+		let mut map = Value::map();
+		loop {
+			self.skip()?;
+			if self.eat("}") {
+				return Ok(map);
+			}
+			let key = self.key()?;
+			self.skip()?;
+			if !self.eat(":") {
+				return Err(self.error("expected `:`"));
+			}
+			self.skip()?;
+			let value = self.value()?;
+			map.set_item(key, value);
+			self.skip()?;
+			if !self.eat(",") && !self.rest().starts_with('}') {
+				return Err(self.error("expected `,` or `}`"));
+			}
+		}
+	}
+
+	/// Quoted keys are strings and unquoted ones are symbols, like the engine makes them.
+	fn key(&mut self) -> Result<Value> {
+		match self.peek() {
+			Some('"') | Some('\'') => Ok(Value::from(self.string()?)),
+			Some(c) if is_ident_start(c) => Ok(Value::symbol(self.ident())),
+			Some(c) if c.is_ascii_digit() => Ok(Value::from(self.ident())),
+			_ => Err(self.error("expected a key")),
+		}
+	}
+
+	fn ident(&mut self) -> &'a str {
+		let rest = self.rest();
+		let n = rest.find(|c: char| !(is_ident_start(c) || c.is_ascii_digit() || c == '-')).unwrap_or(rest.len());
+		self.pos += n;
+		return &rest[..n];
+	}
+
+	fn string(&mut self) -> Result<String> {
+		let start = self.pos;
+		let quote = self.next();
+		let mut s = String::new();
+		loop {
+			let at = self.pos;
+			match self.next() {
+				None => return Err(self.error_at(start, "unterminated string")),
+				Some(c) if Some(c) == quote => return Ok(s),
+				Some('\\') => {
+					let c = match self.next() {
+						Some('n') => '\n',
+						Some('r') => '\r',
+						Some('t') => '\t',
+						Some('b') => '\u{8}',
+						Some('f') => '\u{c}',
+						Some('v') => '\u{b}',
+						Some('0') => '\0',
+						Some('u') => self.escape(at)?,
+						Some(c) => c,
+						None => return Err(self.error_at(start, "unterminated string")),
+					};
+					s.push(c);
+				},
+				Some(c) => s.push(c),
+			}
+		}
+	}
+
+	/// `\uXXXX` escape including the surrogate pairs.
+	fn escape(&mut self, at: usize) -> Result<char> {
+		let hi = self.hex4().ok_or_else(|| self.error_at(at, "invalid unicode escape"))?;
+		if (0xD800..0xDC00).contains(&hi) && self.rest().starts_with("\\u") {
+			let save = self.pos;
+			self.pos += 2;
+			match self.hex4() {
+				Some(lo) if (0xDC00..0xE000).contains(&lo) => {
+					let c = 0x10000 + ((hi - 0xD800) << 10) + (lo - 0xDC00);
+					return Ok(::std::char::from_u32(c).unwrap_or('\u{fffd}'));
+				},
+				_ => self.pos = save,
+			}
+		}
+		return Ok(::std::char::from_u32(hi).unwrap_or('\u{fffd}'));
+	}
+
+	fn hex4(&mut self) -> Option<u32> {
+		let digits = self.rest().get(..4)?;
+		let n = u32::from_str_radix(digits, 16).ok()?;
+		self.pos += 4;
+		return Some(n);
+	}
+
+	fn color(&mut self) -> Result<Value> {
+		let start = self.pos;
+		self.pos += 1;
+		let rest = self.rest();
+		let n = rest.find(|c: char| !c.is_ascii_alphanumeric()).unwrap_or(rest.len());
+		let digits = &rest[..n];
+		let rgba = match u32::from_str_radix(digits, 16) {
+			Ok(x) if n == 3 || n == 4 => {
+				// expand `#rgb[a]` to `#rrggbb[aa]`
+				let x = (0..n).fold(0, |acc, i| {
+					let d = (x >> (4 * (n - 1 - i))) & 0xF;
+					(acc << 8) | (d << 4) | d
+				});
+				if n == 3 { (x << 8) | 0xFF } else { x }
+			},
+			Ok(x) if n == 6 => (x << 8) | 0xFF,
+			Ok(x) if n == 8 => x,
+			_ => return Err(self.error_at(start, "invalid color")),
+		};
+		self.pos += n;
+		let (r, g, b, a) = (rgba >> 24, (rgba >> 16) & 0xFF, (rgba >> 8) & 0xFF, rgba & 0xFF);
+		return Ok(Value::color((a << 24) | (b << 16) | (g << 8) | r));
+	}
+
+	fn number(&mut self) -> Result<Value> {
+		let start = self.pos;
+		if is_date(self.rest()) {
+			return self.date();
+		}
+
+		let sign = if self.eat("-") { -1.0 } else { self.eat("+"); 1.0 };
+		if self.eat("Infinity") {
+			return Ok(Value::from(sign * f64::INFINITY));
+		}
+
+		let rest = self.rest();
+		let mut n = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+		let mut is_float = false;
+		if rest[n..].starts_with('.') {
+			is_float = true;
+			n += 1;
+			n += rest[n..].find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len() - n);
+		}
+		let exp = &rest[n..];
+		if exp.starts_with('e') || exp.starts_with('E') {
+			let signed = exp[1..].starts_with('-') || exp[1..].starts_with('+');
+			let digits = &exp[1 + signed as usize..];
+			let m = digits.find(|c: char| !c.is_ascii_digit()).unwrap_or(digits.len());
+			if m > 0 {
+				is_float = true;
+				n += 1 + signed as usize + m;
+			}
+		}
+		let digits = &rest[..n];
+		if digits.is_empty() || digits == "." {
+			return Err(self.error_at(start, "invalid number"));
+		}
+		self.pos += n;
+
+		let text = &self.text[start..self.pos];
+		let x = match digits.parse::<f64>() {
+			Ok(x) => sign * x,
+			Err(_) => return Err(self.error_at(start, "invalid number")),
+		};
+
+		let unit_start = self.pos;
+		let rest = self.rest();
+		let unit = &rest[..rest.find(|c: char| !(c.is_ascii_alphabetic() || c == '%')).unwrap_or(rest.len())];
+		if unit == "n" && !is_float {
+			self.pos += 1;
+			return match text.parse::<i64>() {
+				Ok(i) => Ok(Value::big_int(i)),
+				Err(_) => Err(self.error_at(start, "big integer is out of range")),
+			};
+		}
+		if unit.is_empty() {
+			if !is_float {
+				if let Ok(i) = text.parse::<i32>() {
+					return Ok(Value::from(i));
+				}
+			}
+			return Ok(Value::from(x));
+		}
+		self.pos += unit.len();
+
+		let value = match unit {
+			"s" => Value::duration(x),
+			"ms" => Value::duration(x / 1000.0),
+			"rad" => Value::angle(x),
+			"deg" => Value::angle(x.to_radians()),
+			"grad" => Value::angle(x * ::std::f64::consts::PI / 200.0),
+			"turn" => Value::angle(x * 2.0 * ::std::f64::consts::PI),
+			_ => match LengthUnit::from_suffix(unit) {
+				Some(u) => Value::length(Length::new(x, u)),
+				None => return Err(self.error_at(unit_start, &format!("unknown unit `{}`", unit))),
+			},
+		};
+		return Ok(value);
+	}
+
+	/// `YYYY-MM-DD[Thh:mm[:ss[.fff]][Z|(+|-)hh:mm]]`
+	///
+	/// Like in script, a date alone is UTC and a time without the offset is local.
+	fn date(&mut self) -> Result<Value> {
+		let start = self.pos;
+		let invalid = |p: &Parser| p.error_at(start, "invalid date");
+
+		let year = self.digits(4).ok_or_else(|| invalid(self))?;
+		self.eat("-");
+		let month = self.digits(2).filter(|m| (1..=12).contains(m)).ok_or_else(|| invalid(self))?;
+		if !self.eat("-") {
+			return Err(invalid(self));
+		}
+		let day = self.digits(2).filter(|d| *d >= 1 && *d <= days_in_month(year, month)).ok_or_else(|| invalid(self))?;
+		let mut ticks = days_from_civil(year, month, day) * 86400 * TICKS;
+		let mut utc = true;
+
+		if self.eat("T") {
+			let hours = self.digits(2).filter(|h| *h < 24).ok_or_else(|| invalid(self))?;
+			if !self.eat(":") {
+				return Err(invalid(self));
+			}
+			let minutes = self.digits(2).filter(|m| *m < 60).ok_or_else(|| invalid(self))?;
+			let mut seconds = 0;
+			if self.eat(":") {
+				seconds = self.digits(2).filter(|s| *s < 60).ok_or_else(|| invalid(self))?;
+			}
+			ticks += (hours * 3600 + minutes * 60 + seconds) * TICKS;
+
+			if self.eat(".") {
+				let rest = self.rest();
+				let n = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+				if n == 0 {
+					return Err(invalid(self));
+				}
+				// 100ns precision at most
+				let fraction = format!("{:0<7}", &rest[..n.min(7)]);
+				ticks += fraction.parse::<i64>().unwrap_or(0);
+				self.pos += n;
+			}
+
+			if !self.eat("Z") {
+				let sign = if self.eat("+") { 1 } else if self.eat("-") { -1 } else { 0 };
+				utc = sign != 0;
+				if sign != 0 {
+					let hours = self.digits(2).filter(|h| *h < 24).ok_or_else(|| invalid(self))?;
+					self.eat(":");
+					let minutes = self.digits(2).filter(|m| *m < 60).ok_or_else(|| invalid(self))?;
+					ticks -= sign * (hours * 3600 + minutes * 60) * TICKS;
+				}
+			}
+		}
+
+		if self.peek().is_some_and(|c| c.is_ascii_alphanumeric()) {
+			return Err(invalid(self));
+		}
+
+		return Ok(Value::date(ticks, if utc { VALUE_UNIT_TYPE_DATE::DT_UTC as u32 } else { 0 }));
+	}
+
+	fn digits(&mut self, n: usize) -> Option<i64> {
+		let s = self.rest().get(..n)?;
+		if !s.bytes().all(|c| c.is_ascii_digit()) {
+			return None;
+		}
+		self.pos += n;
+		return s.parse().ok();
+	}
+}
+
+fn is_ident_start(c: char) -> bool {
+	c.is_alphabetic() || c == '_' || c == '$'
+}
+
+/// `NNNN-NN-` starts a date rather than a number.
+fn is_date(s: &str) -> bool {
+	let b = s.as_bytes();
+	b.len() >= 8 && b[..4].iter().all(u8::is_ascii_digit) && b[4] == b'-'
+		&& b[5..7].iter().all(u8::is_ascii_digit) && b[7] == b'-'
+}
+
+fn is_leap(year: i64) -> bool {
+	year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+	match month {
+		2 if is_leap(year) => 29,
+		2 => 28,
+		4 | 6 | 9 | 11 => 30,
+		_ => 31,
+	}
+}
+
+/// Days since 1970-01-01 of the proleptic Gregorian date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+	let y = if month <= 2 { year - 1 } else { year };
+	let era = y.div_euclid(400);
+	let yoe = y - era * 400;
+	let mp = (month + 9) % 12;
+	let doy = (153 * mp + 2) / 5 + day - 1;
+	let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+	return era * 146_097 + doe - 719_468;
+}
+
+/// Date of the given day since 1970-01-01.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+	let z = days + 719_468;
+	let era = z.div_euclid(146_097);
+	let doe = z - era * 146_097;
+	let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+	let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+	let mp = (5 * doy + 2) / 153;
+	let day = doy - (153 * mp + 2) / 5 + 1;
+	let month = if mp < 10 { mp + 3 } else { mp - 9 };
+	let year = yoe + era * 400 + (month <= 2) as i64;
+	return (year, month, day);
+}
+
+
+fn write(out: &mut String, value: &Value) {
+	let float = || value.to_float().unwrap_or_default();
+
+	if value.is_undefined() {
+		out.push_str("undefined");
+	} else if value.is_nothing() {
+		out.push_str("nothing");
+	} else if value.is_null() {
+		out.push_str("null");
+	} else if value.is_bool() {
+		out.push_str(if value.to_bool() == Some(true) { "true" } else { "false" });
+	} else if value.is_int() {
+		out.push_str(&value.to_int().unwrap_or_default().to_string());
+	} else if value.is_float() {
+		write_float(out, float());
+	} else if value.is_length() {
+		let length = value.to_length().unwrap_or_else(|| Length::px(float()));
+		write_number(out, length.value);
+		out.push_str(length.unit.suffix());
+	} else if value.is_duration() {
+		write_number(out, float());
+		out.push('s');
+	} else if value.is_angle() {
+		write_number(out, float());
+		out.push_str("rad");
+	} else if value.is_color() {
+		let c = value.to_color().unwrap_or_default();
+		let (r, g, b, a) = (c & 0xFF, (c >> 8) & 0xFF, (c >> 16) & 0xFF, c >> 24);
+		out.push_str(&format!("#{:02x}{:02x}{:02x}", r, g, b));
+		if a != 0xFF {
+			out.push_str(&format!("{:02x}", a));
+		}
+	} else if value.is_date() || value.is_currency() {
+		let mut n = 0;
+		(_API.ValueInt64Data)(value.as_cptr(), &mut n);
+		if value.is_date() {
+			write_date(out, n, value.has_date_flag(VALUE_UNIT_TYPE_DATE::DT_UTC));
+		} else {
+			out.push_str(&n.to_string());
+			out.push('n');
+		}
+	} else if value.is_string() {
+		let s = value.as_string().unwrap_or_default();
+		let bare = s.chars().next().is_some_and(is_ident_start)
+			&& s.chars().all(|c| is_ident_start(c) || c.is_ascii_digit() || c == '-');
+		if value.is_symbol() && bare {
+			out.push_str(&s);
+		} else {
+			quote(out, &s);
+		}
+	} else if value.is_array() || value.is_object_array() {
+		out.push('[');
+		for (i, item) in value.values().enumerate() {
+			if i > 0 {
+				out.push(',');
+			}
+			write(out, &item);
+		}
+		out.push(']');
+	} else if value.is_map() || value.is_object_map() {
+		out.push('{');
+		for (i, (key, item)) in value.items().iter().enumerate() {
+			if i > 0 {
+				out.push(',');
+			}
+			match key.as_string() {
+				Some(s) => quote(out, &s),
+				None => write(out, key),
+			}
+			out.push(':');
+			write(out, item);
+		}
+		out.push('}');
+	} else {
+		out.push_str("null");
+	}
+}
+
+/// Float which is read back as float.
+fn write_float(out: &mut String, x: f64) {
+	if x.is_nan() {
+		out.push_str("NaN");
+	} else if x.is_infinite() {
+		out.push_str(if x > 0.0 { "Infinity" } else { "-Infinity" });
+	} else {
+		out.push_str(&format!("{:?}", x));
+	}
+}
+
+/// Number followed by a unit.
+fn write_number(out: &mut String, x: f64) {
+	if x.is_finite() {
+		out.push_str(&x.to_string());
+	} else {
+		out.push('0');
+	}
+}
+
+fn write_date(out: &mut String, ticks: i64, utc: bool) {
+	let days = ticks.div_euclid(86400 * TICKS);
+	let time = ticks.rem_euclid(86400 * TICKS);
+	let (year, month, day) = civil_from_days(days);
+	let seconds = time / TICKS;
+	out.push_str(&format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}", year, month, day, seconds / 3600, seconds / 60 % 60, seconds % 60));
+	let fraction = time % TICKS;
+	if fraction != 0 {
+		let digits = format!("{:07}", fraction);
+		out.push('.');
+		out.push_str(digits.trim_end_matches('0'));
+	}
+	if utc {
+		out.push('Z');
+	}
+}
+
+fn quote(out: &mut String, s: &str) {
+	out.push('"');
+	for c in s.chars() {
+		match c {
+			'"' => out.push_str("\\\""),
+			'\\' => out.push_str("\\\\"),
+			'\n' => out.push_str("\\n"),
+			'\r' => out.push_str("\\r"),
+			'\t' => out.push_str("\\t"),
+			c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+			c => out.push(c),
+		}
+	}
+	out.push('"');
+}
+
+/// Standard base64 with padding.
+#[cfg(feature = "serde_json")]
+fn base64(bytes: &[u8]) -> String {
+	const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+	let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+	for chunk in bytes.chunks(3) {
+		let n = chunk.iter().enumerate().fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+		for i in 0..4 {
+			if i <= chunk.len() {
+				out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3F) as usize] as char);
+			} else {
+				out.push('=');
+			}
+		}
+	}
+	return out;
+}
@@ -77,6 +77,10 @@ folder for more complex usage and module-level sections for the guides about:
 #[cfg(target_os = "macos")]
 #[macro_use] extern crate objc;
 #[macro_use] extern crate lazy_static;
+#[cfg(feature = "derive")]
+extern crate sciter_derive;
+#[cfg(feature = "serde_json")]
+extern crate serde_json;
 
 
 #[macro_use] pub mod macros;
@@ -91,11 +95,18 @@ pub use capi::scdef::{GFX_LAYER, SCRIPT_RUNTIME_FEATURES};
 mod platform;
 mod eventhandler;
 
+pub mod dispatch;
 pub mod dom;
+pub mod error;
+#[cfg(feature = "fake")]
+pub mod fake;
 pub mod graphics;
 pub mod host;
+pub mod json;
 pub mod om;
+pub mod promise;
 pub mod request;
+pub mod trace;
 pub mod types;
 pub mod utf;
 pub mod value;
@@ -104,11 +115,16 @@ pub mod window;
 pub mod windowless;
 
 pub use dom::Element;
+pub use error::{Error, Result, ScriptError};
 pub use dom::event::EventHandler;
 pub use host::{Archive, Host, HostHandler};
 pub use value::{Value, FromValue};
 pub use window::Window;
 
+/// `#[derive(ToValue, FromValue)]` and `#[script_api]` macros, see [`sciter-derive`](https://docs.rs/sciter-derive).
+#[cfg(feature = "derive")]
+pub use sciter_derive::{FromValue, ToValue, script_api};
+
 
 /// Builder pattern for window creation. See [`window::Builder`](window/struct.Builder.html) documentation.
 ///
@@ -393,12 +409,16 @@ pub fn SciterAPI<'a>() -> &'a ISciterAPI {
       // but how on earth it builds without `cfg(test)`?
       //
 			if cfg!(test) {
-				&*ext::SciterAPI()
+				// unit tests may install the `sciter::fake` API first
+				EXT_API.unwrap_or_else(|| &*ext::SciterAPI())
 			} else {
 				EXT_API
 					//.or_else(|| Some(&*ext::SciterAPI()))
 					.expect("Sciter API is not available yet, call `sciter::set_api()` first.")
 			}
+		} else if let Some(api) = EXT_API {
+			// e.g. the `sciter::fake` API installed via `set_host_api`
+			api
 		} else {
 			&*ext::SciterAPI()
 		}
@@ -425,6 +445,8 @@ pub fn SciterAPI_unchecked<'a>() -> &'a ISciterAPI {
 	let ap = unsafe {
 		if cfg!(feature="extension") {
 			EXT_API.expect("Sciter API is not available yet, call `sciter::set_api()` first.")
+		} else if let Some(api) = EXT_API {
+			api
 		} else {
 			&*ext::SciterAPI()
 		}
@@ -435,14 +457,20 @@ pub fn SciterAPI_unchecked<'a>() -> &'a ISciterAPI {
 
 
 lazy_static! {
-	static ref _API: &'static ISciterAPI = SciterAPI();
+	static ref _API: &'static ISciterAPI = trace::wrap(SciterAPI());
 	static ref _GAPI: &'static SciterGraphicsAPI = {
 		if version_num() < 0x0401_0A00 {
 			panic!("Graphics API is incompatible since 4.1.10 (your version is {})", version());
 		}
-		unsafe { &*(SciterAPI().GetSciterGraphicsAPI)() }
+		let ptr = (_API.GetSciterGraphicsAPI)();
+		assert!(!ptr.is_null(), "Graphics API is not available");
+		unsafe { &*ptr }
+	};
+	static ref _RAPI: &'static SciterRequestAPI = {
+		let ptr = (_API.GetSciterRequestAPI)();
+		assert!(!ptr.is_null(), "Request API is not available");
+		unsafe { &*ptr }
 	};
-	static ref _RAPI: &'static SciterRequestAPI = unsafe { &*(SciterAPI().GetSciterRequestAPI)() };
 }
 
 /// Set a custom path to the Sciter dynamic library.
@@ -458,7 +486,7 @@ lazy_static! {
 ///   println!("loaded Sciter version {}", sciter::version());
 /// }
 /// ```
-pub fn set_library(custom_path: &str) -> ::std::result::Result<(), String> {
+pub fn set_library(custom_path: &str) -> Result<()> {
   #[cfg(not(feature = "dynamic"))]
   fn set_impl(_: &str) -> ::std::result::Result<(), String> {
     Err("Don't use `sciter::set_library()` in static builds.\n  Build with the feature \"dynamic\" instead.".to_owned())
@@ -472,19 +500,20 @@ pub fn set_library(custom_path: &str) -> ::std::result::Result<(), String> {
     ext::try_load_library(false).map(|_| ())
   }
 
-  set_impl(custom_path)
+  set_impl(custom_path).map_err(|msg| Error::new(error::Code::Library).with_path(custom_path).with_message(msg))
 }
 
 static mut EXT_API: Option<&'static ISciterAPI> = None;
 
 /// Set the Sciter API coming from `SciterLibraryInit`.
 ///
+/// In regular builds it replaces the API of the Sciter library,
+/// e.g. with the in-process [`sciter::fake`](fake/index.html) one.
+///
 /// Note: Must be called first before any other function.
 pub fn set_host_api(api: &'static ISciterAPI) {
-	if cfg!(feature="extension") {
-		unsafe {
-			EXT_API.replace(api);
-		}
+	unsafe {
+		*std::ptr::addr_of_mut!(EXT_API) = Some(api);
 	}
 }
 
@@ -575,6 +604,10 @@ pub enum RuntimeOptions<'a> {
 	///
 	/// since [4.4.5.0](https://rawgit.com/c-smile/sciter-sdk/aafb625bb0bc317d79c0a14d02b5730f6a02b48a/logfile.htm).
 	LogicalPixel(bool),
+	/// global; value: where to record the Sciter API calls, see [`sciter::trace`](trace/index.html).
+	///
+	/// Must be called before any other Sciter function, after that tracing can be switched on and off at any time.
+	TraceApi(trace::Target<'a>),
 }
 
 /// Set various global Sciter engine options, see the [`RuntimeOptions`](enum.RuntimeOptions.html).
@@ -596,6 +629,9 @@ pub fn set_options(options: RuntimeOptions) -> std::result::Result<(), ()> {
     LibraryPath(path) => {
       return set_library(path).map_err(|_|());
     }
+		TraceApi(target) => {
+			return trace::set_target(target).map_err(|_|());
+		}
 	};
 	let ok = (_API.SciterSetOption)(std::ptr::null_mut(), option, value);
 	if ok != 0 {
@@ -620,7 +656,7 @@ pub fn set_variable(path: &str, value: Value) -> dom::Result<()> {
 	if ok == dom::SCDOM_RESULT::OK {
 		Ok(())
 	} else {
-		Err(ok)
+		Err(Error::from(ok).with_call("SciterSetVariable").with_path(path))
 	}
 }
 
@@ -635,6 +671,6 @@ pub fn get_variable(path: &str) -> dom::Result<Value> {
 	if ok == dom::SCDOM_RESULT::OK {
 		Ok(value)
 	} else {
-		Err(ok)
+		Err(Error::from(ok).with_call("SciterGetVariable").with_path(path))
 	}
 }
@@ -139,12 +139,13 @@ macro_rules! MAKE_HANDLE {
 /// with arguments unpacking and type checking.
 ///
 /// Note: unstable, will be improved.
+/// See also the `#[script_api]` attribute of the `derive` feature which supports optional arguments and `Result` returns.
 #[macro_export]
 macro_rules! dispatch_script_call {
 
 	(
 		$(
-			fn $name:ident ( $( $argt:ident ),* );
+			fn $name:ident ( $( $argt:ty ),* );
 		 )*
 	) => {
 
@@ -171,12 +172,12 @@ macro_rules! dispatch_script_call {
 						let rv = self.$name(
 							$(
 								{
-									match $crate::FromValue::from_value(&argv[_i]) {
-										Some(arg) => { _i += 1; arg },
-										None => {
+									match $crate::FromValue::try_from_value(&argv[_i]) {
+										Ok(arg) => { _i += 1; arg },
+										Err(e) => {
 											// invalid type
-											return Some($crate::Value::error(&format!("{} error: invalid type of {} argument ({} expected, {:?} provided).",
-												stringify!($name), _i, stringify!($argt), argv[_i])));
+											return Some($crate::Value::error(&format!("{} error: invalid type of {} argument ({} expected): {}.",
+												stringify!($name), _i, stringify!($argt), e)));
 										},
 									}
 								}
@@ -205,6 +206,7 @@ macro_rules! dispatch_script_call {
 /// ```rust
 /// # #[macro_use] extern crate sciter;
 /// # fn main() {
+/// # sciter::fake::install();
 /// let v: sciter::Value = vmap! {
 ///   "one" => 1,
 ///   "two" => 2.0,
@@ -235,6 +237,7 @@ macro_rules! vmap {
 /// ```rust
 /// # #[macro_use] extern crate sciter;
 /// # fn main() {
+/// # sciter::fake::install();
 /// let v: sciter::Value = varray![1, 2.0, "three"];
 /// assert!(v.is_array());
 /// assert_eq!(v.len(), 3);
@@ -0,0 +1,289 @@
+/*! Script promises settled from native code.
+
+A native function can return a [`Promise`](struct.Promise.html) to script
+and settle it later from any thread via its [`Resolver`](struct.Resolver.html).
+The settlement is marshalled to the UI thread of the window the promise belongs to
+via a [`Dispatcher`](../dispatch/struct.Dispatcher.html),
+so script can simply `await` the result.
+Since a `Value` must not be created on other threads, the result is an
+[`OwnedValue`](../value/enum.OwnedValue.html) which becomes a `Value` on the UI thread:
+
+```rust,no_run
+# #[macro_use] extern crate sciter;
+use sciter::types::HWINDOW;
+use sciter::promise::Promise;
+
+struct Handler {
+  hwnd: HWINDOW,
+}
+
+impl Handler {
+  #[allow(non_snake_case)]
+  fn loadReport(&self, id: i32) -> Promise {
+    let promise = Promise::new(self.hwnd);
+    let resolver = promise.resolver();
+    std::thread::spawn(move || {
+      // a long operation
+      let report = format!("report #{}", id);
+      resolver.resolve(report);
+    });
+    promise
+  }
+}
+
+impl sciter::EventHandler for Handler {
+  dispatch_script_call! {
+    fn loadReport(i32);
+  }
+}
+
+# fn main() {
+let mut frame = sciter::Window::new();
+frame.event_handler(Handler { hwnd: frame.get_hwnd() });
+# }
+```
+
+```js
+const report = await view.loadReport(1);
+```
+
+The script side of the promise is a _thenable_ object:
+it has a single `then(onFulfilled, onRejected)` method, which is enough for `await`
+and `Promise.resolve()`.
+
+The promise state holds script values, so it is released on the UI thread only.
+If the window is destroyed before a settlement from another thread reaches it,
+the state is leaked instead.
+
+*/
+
+use std::fmt;
+use std::mem;
+use std::ops::Deref;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use capi::sctypes::HWINDOW;
+use dispatch::Dispatcher;
+use dom::Element;
+use value::{OwnedValue, Value};
+
+
+/// A script promise settled by native code.
+///
+/// Convert it to `Value` to return it to script.
+pub struct Promise {
+	shared: Arc<Shared>,
+	resolver: Resolver,
+}
+
+/// Settles its [`Promise`](struct.Promise.html) from any thread.
+///
+/// The first settlement wins, the following ones are ignored.
+/// If all resolvers of a pending promise are dropped, the promise is rejected.
+#[derive(Clone)]
+pub struct Resolver {
+	settler: Arc<Settler>,
+}
+
+impl Promise {
+	/// Create a pending promise which is settled on the UI thread of the given window.
+	pub fn new(hwnd: HWINDOW) -> Promise {
+		let shared = Arc::new(Shared {
+			ui: Dispatcher::new(hwnd),
+			state: Mutex::new(State::Pending(Vec::new())),
+		});
+		let settler = Settler {
+			shared: Mutex::new(Some(UiShared(Some(shared.clone())))),
+			settled: AtomicBool::new(false),
+		};
+		Promise {
+			shared: shared,
+			resolver: Resolver { settler: Arc::new(settler) },
+		}
+	}
+
+	/// Create a pending promise which is settled on the UI thread of the element's window.
+	pub fn for_element(element: &Element) -> Promise {
+		Promise::new(element.get_hwnd(true))
+	}
+
+	/// Get a resolver of this promise.
+	pub fn resolver(&self) -> Resolver {
+		self.resolver.clone()
+	}
+}
+
+impl fmt::Debug for Promise {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let state = match *self.shared.lock() {
+			State::Pending(_) => "pending",
+			State::Fulfilled(_) => "fulfilled",
+			State::Rejected(_) => "rejected",
+		};
+		write!(f, "Promise {{ {} }}", state)
+	}
+}
+
+impl From<Promise> for Value {
+	/// Script object with the `then(onFulfilled, onRejected)` method.
+	fn from(promise: Promise) -> Value {
+		// the promise's own resolver is dropped here,
+		// so the promise is rejected if nobody else can settle it.
+		let shared = promise.shared;
+		let then = move |args: &[Value]| {
+			let callback = |n: usize| args.get(n).cloned().unwrap_or_default();
+			shared.then(callback(0), callback(1));
+			Value::null()
+		};
+		let mut object = Value::map();
+		object.set_item("then", then);
+		object
+	}
+}
+
+impl Resolver {
+	/// Fulfill the promise with the value, converted to `Value` on the UI thread.
+	///
+	/// Returns `false` if the promise has been settled already.
+	pub fn resolve<T: Into<OwnedValue>>(&self, value: T) -> bool {
+		self.settler.settle(Ok(value.into()))
+	}
+
+	/// Reject the promise with the reason, usually an error value.
+	///
+	/// Returns `false` if the promise has been settled already.
+	pub fn reject<T: Into<OwnedValue>>(&self, reason: T) -> bool {
+		self.settler.settle(Err(reason.into()))
+	}
+
+	/// Settle the promise with the result, an `Err` rejects it with an error value.
+	///
+	/// Returns `false` if the promise has been settled already.
+	pub fn settle<T: Into<OwnedValue>, E: fmt::Display>(&self, result: Result<T, E>) -> bool {
+		match result {
+			Ok(value) => self.resolve(value),
+			Err(e) => self.reject(OwnedValue::Error(e.to_string())),
+		}
+	}
+
+	/// Whether the promise has been settled by any of its resolvers.
+	pub fn is_settled(&self) -> bool {
+		self.settler.settled.load(Ordering::SeqCst)
+	}
+}
+
+impl fmt::Debug for Resolver {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("Resolver").field("settled", &self.is_settled()).finish()
+	}
+}
+
+
+/// Script callbacks of `then()`: `onFulfilled` and `onRejected`.
+type Callbacks = (Value, Value);
+
+enum State {
+	Pending(Vec<Callbacks>),
+	Fulfilled(Value),
+	Rejected(Value),
+}
+
+/// State of the promise, accessed on the UI thread only.
+struct Shared {
+	ui: Dispatcher,
+	state: Mutex<State>,
+}
+
+impl Shared {
+	fn lock(&self) -> ::std::sync::MutexGuard<'_, State> {
+		self.state.lock().unwrap_or_else(|e| e.into_inner())
+	}
+
+	fn then(&self, on_fulfilled: Value, on_rejected: Value) {
+		let settled = match *self.lock() {
+			State::Pending(ref mut callbacks) => {
+				callbacks.push((on_fulfilled, on_rejected));
+				return;
+			},
+			State::Fulfilled(ref value) => Ok(value.clone()),
+			State::Rejected(ref reason) => Err(reason.clone()),
+		};
+		Shared::notify((on_fulfilled, on_rejected), &settled);
+	}
+
+	fn complete(&self, result: Result<Value, Value>) {
+		let state = match result {
+			Ok(ref value) => State::Fulfilled(value.clone()),
+			Err(ref reason) => State::Rejected(reason.clone()),
+		};
+		let callbacks = match ::std::mem::replace(&mut *self.lock(), state) {
+			State::Pending(callbacks) => callbacks,
+			_ => unreachable!("promise is settled twice"),
+		};
+		for pair in callbacks {
+			Shared::notify(pair, &result);
+		}
+	}
+
+	fn notify(callbacks: Callbacks, result: &Result<Value, Value>) {
+		let (callback, arg) = match *result {
+			Ok(ref value) => (callbacks.0, value),
+			Err(ref reason) => (callbacks.1, reason),
+		};
+		if callback.is_vfunction() {
+			// errors are reported by the engine itself
+			let _ = callback.call(None, ::std::slice::from_ref(arg), Some("Promise"));
+		}
+	}
+}
+
+/// Reference to the state which is released on the UI thread only,
+/// on other threads it is leaked: the script values it holds can not be released there.
+struct UiShared(Option<Arc<Shared>>);
+
+impl Deref for UiShared {
+	type Target = Shared;
+	fn deref(&self) -> &Shared {
+		self.0.as_ref().expect("promise state")
+	}
+}
+
+impl Drop for UiShared {
+	fn drop(&mut self) {
+		if let Some(shared) = self.0.take() {
+			if !shared.ui.is_ui_thread() {
+				mem::forget(shared);
+			}
+		}
+	}
+}
+
+/// Shared by the clones of a resolver.
+struct Settler {
+	/// Moved to the UI thread by the settlement.
+	shared: Mutex<Option<UiShared>>,
+	settled: AtomicBool,
+}
+
+impl Settler {
+	fn settle(&self, result: Result<OwnedValue, OwnedValue>) -> bool {
+		if self.settled.swap(true, Ordering::SeqCst) {
+			return false;
+		}
+		let shared = match self.shared.lock().unwrap_or_else(|e| e.into_inner()).take() {
+			Some(shared) => shared,
+			None => return false,
+		};
+		let ui = shared.ui.clone();
+		// if the post fails, the task is dropped right here and `UiShared` takes care of the state
+		ui.post(move || shared.complete(result.map(Value::from).map_err(Value::from))).ok();
+		true
+	}
+}
+
+impl Drop for Settler {
+	fn drop(&mut self) {
+		self.settle(Err(OwnedValue::Error("promise is dropped without settlement".to_owned())));
+	}
+}
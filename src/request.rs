@@ -13,27 +13,32 @@ use capi::sctypes::{LPVOID, UINT};
 use capi::scdef::{LPCWSTR_RECEIVER};
 
 use utf::{store_astr, store_wstr, store_bstr};
+use error::Error;
 
 use _RAPI;
 
 
 
 macro_rules! ok_or {
-	($ok:ident) => {
-		ok_or!((), $ok)
-	};
+  ($rv:expr, $ok:ident, $call:expr) => {
+    if $ok == REQUEST_RESULT::OK {
+      Ok($rv)
+    } else {
+      Err(Error::from($ok).with_call($call))
+    }
+  };
 
-  ($rv:expr, $ok:ident) => {
+  ($rv:expr, $ok:ident, $call:expr, $rq:expr) => {
     if $ok == REQUEST_RESULT::OK {
       Ok($rv)
     } else {
-      Err($ok)
+      Err($rq.with_url(Error::from($ok).with_call($call)))
     }
   };
 }
 
 /// A specialized `Result` type for request operations.
-pub type Result<T> = ::std::result::Result<T, REQUEST_RESULT>;
+pub type Result<T> = ::std::result::Result<T, Error>;
 
 type GetCountFn = extern "system" fn (rq: HREQUEST, pNumber: &mut UINT) -> REQUEST_RESULT;
 type GetNameFn = extern "system" fn (rq: HREQUEST, n: UINT, rcv: LPCWSTR_RECEIVER, rcv_param: LPVOID) -> REQUEST_RESULT;
@@ -90,7 +95,7 @@ impl Request {
 			(std::ptr::null(), 0_u32)
 		};
 		let ok = (_RAPI.RequestSetSucceeded)(self.0, status, ptr, size);
-		ok_or!(ok)
+		ok_or!((), ok, "RequestSetSucceeded", self)
 	}
 
 	/// Mark the request as complete with failure.
@@ -101,92 +106,105 @@ impl Request {
 			(std::ptr::null(), 0_u32)
 		};
 		let ok = (_RAPI.RequestSetSucceeded)(self.0, status, ptr, size);
-		ok_or!(ok)
+		ok_or!((), ok, "RequestSetSucceeded", self)
 	}
 
 	/// Append a data chunk to the received data.
 	pub fn append_received_data(&mut self, data: &[u8]) -> Result<()> {
 		let (ptr, size) = (data.as_ptr(), data.len() as u32);
 		let ok = (_RAPI.RequestAppendDataChunk)(self.0, ptr, size);
-		ok_or!(ok)
+		ok_or!((), ok, "RequestAppendDataChunk", self)
 	}
 
 	/// Get received (so far) data.
 	pub fn get_received_data(&self) -> Result<Vec<u8>> {
 		let mut data = Vec::new();
 		let ok = (_RAPI.RequestGetData)(self.0, store_bstr, &mut data as *mut _ as LPVOID);
-		ok_or!(data, ok)
+		ok_or!(data, ok, "RequestGetData", self)
 	}
 
 	/// Get the URL of the request.
 	pub fn url(&self) -> Result<String> {
 		let mut s = String::new();
 		let ok = (_RAPI.RequestUrl)(self.0, store_astr, &mut s as *mut _ as LPVOID);
-		ok_or!(s, ok)
+		ok_or!(s, ok, "RequestUrl")
 	}
 
 	/// Get a real URL of the content (e.g., after possible redirection).
 	pub fn content_url(&self) -> Result<String> {
 		let mut s = String::new();
 		let ok = (_RAPI.RequestContentUrl)(self.0, store_astr, &mut s as *mut _ as LPVOID);
-		ok_or!(s, ok)
+		ok_or!(s, ok, "RequestContentUrl", self)
 	}
 
 	/// Get the data type of the request.
 	pub fn method(&self) -> Result<REQUEST_METHOD> {
 		let mut t = REQUEST_METHOD::GET;
 		let ok = (_RAPI.RequestGetRequestType)(self.0, &mut t);
-		ok_or!(t, ok)
+		ok_or!(t, ok, "RequestGetRequestType", self)
 	}
 
 	/// Get the resource data type of the request.
 	pub fn request_type(&self) -> Result<RESOURCE_TYPE> {
 		let mut t = RESOURCE_TYPE::RAW;
 		let ok = (_RAPI.RequestGetRequestedDataType)(self.0, &mut t);
-		ok_or!(t, ok)
+		ok_or!(t, ok, "RequestGetRequestedDataType", self)
 	}
 
 	/// Get the MIME type of the received data.
 	pub fn response_type(&self) -> Result<String> {
 		let mut s = String::new();
 		let ok = (_RAPI.RequestGetReceivedDataType)(self.0, store_astr, &mut s as *mut _ as LPVOID);
-		ok_or!(s, ok)
+		ok_or!(s, ok, "RequestGetReceivedDataType", self)
 	}
 
 	/// Set the MIME type of the received data.
 	pub fn set_response_type(&mut self, mime_type: &str) -> Result<()> {
 		let text = s2u!(mime_type);
 		let ok = (_RAPI.RequestSetReceivedDataType)(self.0, text.as_ptr());
-		ok_or!(ok)
+		ok_or!((), ok, "RequestSetReceivedDataType", self)
 	}
 
 	/// Set the data encoding for the received data.
 	pub fn set_response_encoding(&mut self, encoding_type: &str) -> Result<()> {
 		let text = s2u!(encoding_type);
 		let ok = (_RAPI.RequestSetReceivedDataEncoding)(self.0, text.as_ptr());
-		ok_or!(ok)
+		ok_or!((), ok, "RequestSetReceivedDataEncoding", self)
+	}
+
+	/// Attach the request URL to the error, if it is available.
+	fn with_url(&self, error: Error) -> Error {
+		let mut url = String::new();
+		let ok = (_RAPI.RequestUrl)(self.0, store_astr, &mut url as *mut _ as LPVOID);
+		if ok == REQUEST_RESULT::OK {
+			error.with_url(&url)
+		} else {
+			error
+		}
 	}
 
-	fn get_collection_impl(&self, get_count: GetCountFn, get_name: GetNameFn, get_value: GetValueFn) -> Result<std::collections::HashMap<String, String>>	{
+	fn get_collection_impl(&self, get_count: GetCountFn, get_name: GetNameFn, get_value: GetValueFn, names: (&'static str, &'static str, &'static str)) -> Result<std::collections::HashMap<String, String>>	{
 		let mut count = 0;
 		let ok = get_count(self.0, &mut count);
 		if ok != REQUEST_RESULT::OK {
-			return Err(ok);
+			return Err(self.with_url(Error::from(ok).with_call(names.0)));
 		}
 
 		let mut args = std::collections::HashMap::with_capacity(count as usize);
 		for i in 0..count {
 			let mut name = String::new();
+			let mut call = names.1;
 			let mut ok = get_name(self.0, i, store_wstr, &mut name as *mut _ as LPVOID);
 			if ok == REQUEST_RESULT::OK {
 				let mut value = String::new();
+				call = names.2;
 				ok = get_value(self.0, i, store_wstr, &mut value as *mut _ as LPVOID);
 				if ok == REQUEST_RESULT::OK {
 					args.insert(name, value);
 				}
 			}
 			if ok != REQUEST_RESULT::OK {
-				return Err(ok);
+				return Err(self.with_url(Error::from(ok).with_call(call)));
 			}
 		}
 
@@ -195,12 +213,14 @@ impl Request {
 
 	/// Get the parameters of the request.
 	pub fn parameters(&self) -> Result<std::collections::HashMap<String, String>> {
-		self.get_collection_impl(_RAPI.RequestGetNumberOfParameters, _RAPI.RequestGetNthParameterName, _RAPI.RequestGetNthParameterValue)
+		self.get_collection_impl(_RAPI.RequestGetNumberOfParameters, _RAPI.RequestGetNthParameterName, _RAPI.RequestGetNthParameterValue,
+			("RequestGetNumberOfParameters", "RequestGetNthParameterName", "RequestGetNthParameterValue"))
 	}
 
 	/// Get the headers of the request.
 	pub fn request_headers(&self) -> Result<std::collections::HashMap<String, String>> {
-		self.get_collection_impl(_RAPI.RequestGetNumberOfRqHeaders, _RAPI.RequestGetNthRqHeaderName, _RAPI.RequestGetNthRqHeaderValue)
+		self.get_collection_impl(_RAPI.RequestGetNumberOfRqHeaders, _RAPI.RequestGetNthRqHeaderName, _RAPI.RequestGetNthRqHeaderValue,
+			("RequestGetNumberOfRqHeaders", "RequestGetNthRqHeaderName", "RequestGetNthRqHeaderValue"))
 	}
 
 	/// Set request header (a single item).
@@ -208,12 +228,13 @@ impl Request {
 		let wname = s2w!(name);
 		let wtext = s2w!(value);
 		let ok = (_RAPI.RequestSetRqHeader)(self.0, wname.as_ptr(), wtext.as_ptr());
-		ok_or!(ok)
+		ok_or!((), ok, "RequestSetRqHeader", self)
 	}
 
 	/// Get the headers of the response.
 	pub fn response_headers(&self) -> Result<std::collections::HashMap<String, String>> {
-		self.get_collection_impl(_RAPI.RequestGetNumberOfRspHeaders, _RAPI.RequestGetNthRspHeaderName, _RAPI.RequestGetNthRspHeaderValue)
+		self.get_collection_impl(_RAPI.RequestGetNumberOfRspHeaders, _RAPI.RequestGetNthRspHeaderName, _RAPI.RequestGetNthRspHeaderValue,
+			("RequestGetNumberOfRspHeaders", "RequestGetNthRspHeaderName", "RequestGetNthRspHeaderValue"))
 	}
 
 	/// Set respone header (a single item).
@@ -221,7 +242,7 @@ impl Request {
 		let wname = s2w!(name);
 		let wtext = s2w!(value);
 		let ok = (_RAPI.RequestSetRspHeader)(self.0, wname.as_ptr(), wtext.as_ptr());
-		ok_or!(ok)
+		ok_or!((), ok, "RequestSetRspHeader", self)
 	}
 
 	/// Get proxy host and port (if any).
@@ -234,8 +255,9 @@ impl Request {
 			if ok == REQUEST_RESULT::OK {
 				return Ok((s, n as u16));
 			}
+			return Err(self.with_url(Error::from(ok).with_call("RequestGetProxyPort")));
 		}
-		Err(ok)
+		Err(self.with_url(Error::from(ok).with_call("RequestGetProxyHost")))
 	}
 
 	/// Get the current completion status of the request.
@@ -245,7 +267,7 @@ impl Request {
 		let mut state = REQUEST_STATE::SUCCESS;
 		let mut code = 0_u32;
 		let ok = (_RAPI.RequestGetCompletionStatus)(self.0, &mut state, &mut code);
-		ok_or!((state, code), ok)
+		ok_or!((state, code), ok, "RequestGetCompletionStatus", self)
 	}
 
 	/// Get the execution duratiom of the request.
@@ -257,7 +279,7 @@ impl Request {
 			let d = std::time::Duration::from_millis(ended as u64 - started as u64);
 			Ok(d)
 		} else {
-			Err(ok)
+			Err(self.with_url(Error::from(ok).with_call("RequestGetTimes")))
 		}
 	}
 
@@ -272,7 +294,7 @@ impl Request {
 			let e = Duration::from_millis(ended as u64);
 			Ok((s, e))
 		} else {
-			Err(ok)
+			Err(self.with_url(Error::from(ok).with_call("RequestGetTimes")))
 		}
 	}
 
@@ -0,0 +1,626 @@
+/*! Tracing of the Sciter API calls.
+
+When something misbehaves inside the engine, it helps to see what exactly was passed across FFI.
+The tracing layer sits between this crate and the [`ISciterAPI`](../struct.ISciterAPI.html)
+(including the Graphics and Request APIs) and records every call:
+its name, decoded arguments (strings, element UIDs, value types), result and duration, e.g.
+
+```text
+SciterCreateElement(tagname="div", textOrNull="hello") -> OK phe=#12 [2.1µs]
+ValueStringDataSet(pval=undefined, chars="text", numChars=4, units=0) -> OK [450ns]
+```
+
+Tracing must be requested before the first Sciter call, either by the `SCITER_TRACE` environment variable
+(`stderr`, `log` or a file name) or by [`sciter::set_options`](../fn.set_options.html):
+
+```rust,no_run
+use sciter::trace::Target;
+
+sciter::set_options(sciter::RuntimeOptions::TraceApi(Target::File("sciter-api.log"))).unwrap();
+// ...
+sciter::set_options(sciter::RuntimeOptions::TraceApi(Target::Off)).unwrap();
+```
+
+After that it can be switched on and off at any time.
+Otherwise the calls go directly to the engine and there is no overhead at all.
+
+The `Log` target requires the `log` feature and emits the records with the `sciter::api` target at the `trace` level.
+
+*/
+
+#![allow(non_snake_case, deprecated)]
+
+use std::ffi::CStr;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{LineWriter, Write};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicPtr, Ordering};
+use std::time::{Duration, Instant};
+
+use capi::sctypes::*;
+use capi::scdef::*;
+use capi::scdom::*;
+use capi::scvalue::*;
+use capi::sctiscript::{HVM, tiscript_value, tiscript_native_interface};
+use capi::scbehavior::*;
+use capi::scgraphics::*;
+use capi::screquest::*;
+use capi::scmsg::SCITER_X_MSG;
+use capi::scom::{som_asset_t, som_atom_t};
+use capi::scapi::ISciterAPI;
+
+
+/// Where the API calls are recorded.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Target<'a> {
+	/// Stop tracing.
+	Off,
+	/// Standard error output.
+	Stderr,
+	/// Record to a file, its previous content is truncated.
+	File(&'a str),
+	/// Emit the records via the [`log`](https://docs.rs/log) crate.
+	#[cfg(feature = "log")]
+	Log,
+}
+
+/// Start or stop tracing, see the [module-level](index.html) documentation.
+///
+/// Returns an error if the file can not be created or
+/// if the Sciter API was already in use when tracing had not been requested yet.
+pub fn set_target(target: Target) -> Result<(), String> {
+	let sink = match target {
+		Target::Off => None,
+		Target::Stderr => Some(Sink::Stderr),
+		Target::File(path) => {
+			let file = File::create(path).map_err(|e| format!("can't create {:?}: {}", path, e))?;
+			Some(Sink::File(LineWriter::new(file)))
+		},
+		#[cfg(feature = "log")]
+		Target::Log => Some(Sink::Log),
+	};
+
+	let mut state = STATE.lock().unwrap_or_else(|e| e.into_inner());
+	if state.started && !state.installed && sink.is_some() {
+		return Err("Sciter API tracing must be requested before any other Sciter function.".to_owned());
+	}
+	ENABLED.store(sink.is_some(), Ordering::SeqCst);
+	state.sink = sink;
+	Ok(())
+}
+
+enum Sink {
+	Stderr,
+	File(LineWriter<File>),
+	#[cfg(feature = "log")]
+	Log,
+}
+
+struct State {
+	/// The API is in use.
+	started: bool,
+	/// The API is in use via the tracing proxy.
+	installed: bool,
+	sink: Option<Sink>,
+}
+
+static STATE: Mutex<State> = Mutex::new(State { started: false, installed: false, sink: None });
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+static SCITER: AtomicPtr<ISciterAPI> = AtomicPtr::new(::std::ptr::null_mut());
+static GRAPHICS: AtomicPtr<SciterGraphicsAPI> = AtomicPtr::new(::std::ptr::null_mut());
+static REQUEST: AtomicPtr<SciterRequestAPI> = AtomicPtr::new(::std::ptr::null_mut());
+
+/// Wrap the API into the tracing proxy if tracing was requested.
+pub(crate) fn wrap(api: &'static ISciterAPI) -> &'static ISciterAPI {
+	if let Ok(target) = ::std::env::var("SCITER_TRACE") {
+		let target = match target.as_str() {
+			"" => Target::Off,
+			"stderr" => Target::Stderr,
+			#[cfg(feature = "log")]
+			"log" => Target::Log,
+			path => Target::File(path),
+		};
+		if let Err(e) = set_target(target) {
+			eprintln!("SCITER_TRACE: {}", e);
+		}
+	}
+
+	let mut state = STATE.lock().unwrap_or_else(|e| e.into_inner());
+	state.started = true;
+	if state.sink.is_none() {
+		return api;
+	}
+	state.installed = true;
+	return Box::leak(Box::new(sciter_api(api)));
+}
+
+fn record(name: &str, args: &str, result: &str, elapsed: Duration) {
+	let mut line = format!("{}({})", name, args);
+	if !result.is_empty() {
+		line.push_str(" -> ");
+		line.push_str(result);
+	}
+	let _ = write!(line, " [{:?}]", elapsed);
+
+	let mut state = STATE.lock().unwrap_or_else(|e| e.into_inner());
+	match state.sink {
+		Some(Sink::Stderr) => eprintln!("{}", line),
+		Some(Sink::File(ref mut file)) => { let _ = writeln!(file, "{}", line); },
+		#[cfg(feature = "log")]
+		Some(Sink::Log) => log::trace!(target: "sciter::api", "{}", line),
+		None => {},
+	}
+}
+
+
+/// Longest string argument to print.
+const MAX_CHARS: usize = 100;
+
+fn quoted(out: &mut String, s: &str) {
+	if s.chars().count() > MAX_CHARS {
+		let s: String = s.chars().take(MAX_CHARS).collect();
+		let _ = write!(out, "{:?}...", s);
+	} else {
+		let _ = write!(out, "{:?}", s);
+	}
+}
+
+fn wstr(out: &mut String, s: LPCWSTR, len: Option<usize>) {
+	if s.is_null() {
+		out.push_str("null");
+		return;
+	}
+	let len = len.unwrap_or_else(|| (0..).take_while(|&i| unsafe { *s.add(i) } != 0).count());
+	let chars = unsafe { ::std::slice::from_raw_parts(s, len) };
+	quoted(out, &String::from_utf16_lossy(chars));
+}
+
+fn astr(out: &mut String, s: LPCSTR) {
+	if s.is_null() {
+		out.push_str("null");
+	} else {
+		quoted(out, &unsafe { CStr::from_ptr(s) }.to_string_lossy());
+	}
+}
+
+fn element(out: &mut String, he: HELEMENT) {
+	let mut uid = 0;
+	if he.is_null() {
+		out.push_str("null");
+	} else if (sciter().SciterGetElementUID)(he, &mut uid) == SCDOM_RESULT::OK {
+		let _ = write!(out, "#{}", uid);
+	} else {
+		let _ = write!(out, "{:p}", he);
+	}
+}
+
+fn value(out: &mut String, pv: *const VALUE, count: Option<usize>) {
+	if pv.is_null() {
+		out.push_str("null");
+		return;
+	}
+	let one = |out: &mut String, v: &VALUE| {
+		let name = format!("{:?}", v.t);
+		out.push_str(&name.trim_start_matches("T_").to_lowercase());
+	};
+	match count {
+		None => one(out, unsafe { &*pv }),
+		Some(n) => {
+			out.push('[');
+			for (i, v) in unsafe { ::std::slice::from_raw_parts(pv, n) }.iter().enumerate() {
+				if i > 0 {
+					out.push_str(", ");
+				}
+				one(out, v);
+			}
+			out.push(']');
+		},
+	}
+}
+
+// The element is written only on success, otherwise it can be garbage and the error code is enough.
+fn out_element(out: &mut String, rv: SCDOM_RESULT, name: &str, phe: *mut HELEMENT) {
+	if rv == SCDOM_RESULT::OK && !phe.is_null() {
+		out.push(' ');
+		out.push_str(name);
+		out.push('=');
+		element(out, unsafe { *phe });
+	}
+}
+
+
+/// Input argument.
+macro_rules! trace_in {
+	($out:ident, out_element, $arg:ident) => {};
+	($out:ident, $kind:ident, $arg:ident $(, $len:ident)*) => {
+		if !$out.is_empty() {
+			$out.push_str(", ");
+		}
+		$out.push_str(stringify!($arg));
+		$out.push('=');
+		trace_arg!($out, $kind, $arg $(, $len)*);
+	};
+}
+
+/// Output argument, printed after a successful call.
+macro_rules! trace_out {
+	($out:ident, $rv:ident, out_element, $arg:ident) => {
+		out_element(&mut $out, $rv, stringify!($arg), $arg);
+	};
+	($out:ident, $rv:ident, $kind:ident, $arg:ident $(, $len:ident)*) => {};
+}
+
+macro_rules! trace_arg {
+	($out:ident, wstr, $arg:ident) => { wstr(&mut $out, $arg, None) };
+	($out:ident, wstr, $arg:ident, $len:ident) => { wstr(&mut $out, $arg, Some($len as usize)) };
+	($out:ident, astr, $arg:ident) => { astr(&mut $out, $arg) };
+	($out:ident, element, $arg:ident) => { element(&mut $out, $arg) };
+	($out:ident, value, $arg:ident) => { value(&mut $out, $arg, None) };
+	($out:ident, value, $arg:ident, $len:ident) => { value(&mut $out, $arg, Some($len as usize)) };
+	($out:ident, num, $arg:ident) => { let _ = write!($out, "{}", $arg); };
+	($out:ident, debug, $arg:ident) => { let _ = write!($out, "{:?}", $arg); };
+	($out:ident, ptr, $arg:ident) => { let _ = write!($out, "{:p}", $arg); };
+	($out:ident, void, $arg:ident) => {};
+}
+
+/// Generate the tracing wrappers for each API function
+/// and a function which makes the API table with them.
+macro_rules! proxy {
+	(
+		fn $make:ident($api:ident: &$T:ident) via $real:ident { $($field:ident: $value:expr),* }
+		$( fn $name:ident($($arg:ident: $ty:ty => $kind:ident $([$len:ident])*),*) -> $rt:ty => $rkind:ident; )*
+	) => {
+		fn $make($api: &'static $T) -> $T {
+			$real.store($api as *const $T as *mut $T, Ordering::SeqCst);
+			$T {
+				$($field: $value,)*
+				$($name: $name,)*
+			}
+		}
+
+		$(
+			#[allow(unused_mut)]
+			extern "system" fn $name($($arg: $ty),*) -> $rt {
+				let real = unsafe { &*$real.load(Ordering::Relaxed) };
+				if !ENABLED.load(Ordering::Relaxed) {
+					return (real.$name)($($arg),*);
+				}
+
+				let mut args = String::new();
+				$( trace_in!(args, $kind, $arg $(, $len)*); )*
+
+				let start = Instant::now();
+				let rv = (real.$name)($($arg),*);
+				let elapsed = start.elapsed();
+
+				let mut result = String::new();
+				trace_arg!(result, $rkind, rv);
+				$( trace_out!(result, rv, $kind, $arg); )*
+				record(stringify!($name), &args, &result, elapsed);
+				return rv;
+			}
+		)*
+	};
+}
+
+fn sciter() -> &'static ISciterAPI {
+	unsafe { &*SCITER.load(Ordering::Relaxed) }
+}
+
+lazy_static! {
+	static ref GRAPHICS_API: Option<&'static SciterGraphicsAPI> = {
+		let api = unsafe { (sciter().GetSciterGraphicsAPI)().as_ref() };
+		api.map(|api| &*Box::leak(Box::new(graphics_api(api))))
+	};
+	static ref REQUEST_API: Option<&'static SciterRequestAPI> = {
+		let api = unsafe { (sciter().GetSciterRequestAPI)().as_ref() };
+		api.map(|api| &*Box::leak(Box::new(request_api(api))))
+	};
+}
+
+extern "system" fn GetSciterGraphicsAPI() -> *const SciterGraphicsAPI {
+	GRAPHICS_API.map_or(::std::ptr::null(), |api| api)
+}
+
+extern "system" fn GetSciterRequestAPI() -> *const SciterRequestAPI {
+	REQUEST_API.map_or(::std::ptr::null(), |api| api)
+}
+
+proxy! {
+	fn sciter_api(api: &ISciterAPI) via SCITER {
+		version: api.version,
+		GetSciterGraphicsAPI: GetSciterGraphicsAPI,
+		GetSciterRequestAPI: GetSciterRequestAPI
+	}
+
+	fn SciterClassName() -> LPCWSTR => wstr;
+	fn SciterVersion(major: BOOL => num) -> UINT => num;
+	fn SciterDataReady(hwnd: HWINDOW => ptr, uri: LPCWSTR => wstr, data: LPCBYTE => ptr, dataLength: UINT => num) -> BOOL => num;
+	fn SciterDataReadyAsync(hwnd: HWINDOW => ptr, uri: LPCWSTR => wstr, data: LPCBYTE => ptr, dataLength: UINT => num, requestId: HREQUEST => ptr) -> BOOL => num;
+	fn SciterProc(hwnd: HWINDOW => ptr, msg: UINT => num, wParam: WPARAM => num, lParam: LPARAM => num) -> LRESULT => num;
+	fn SciterProcND(hwnd: HWINDOW => ptr, msg: UINT => num, wParam: WPARAM => num, lParam: LPARAM => num, pbHandled: *mut BOOL => ptr) -> LRESULT => num;
+	fn SciterLoadFile(hWndSciter: HWINDOW => ptr, filename: LPCWSTR => wstr) -> BOOL => num;
+	fn SciterLoadHtml(hWndSciter: HWINDOW => ptr, html: LPCBYTE => ptr, htmlSize: UINT => num, baseUrl: LPCWSTR => wstr) -> BOOL => num;
+	fn SciterSetCallback(hWndSciter: HWINDOW => ptr, cb: SciterHostCallback => ptr, cbParam: LPVOID => ptr) -> VOID => void;
+	fn SciterSetMasterCSS(utf8: LPCBYTE => ptr, numBytes: UINT => num) -> BOOL => num;
+	fn SciterAppendMasterCSS(utf8: LPCBYTE => ptr, numBytes: UINT => num) -> BOOL => num;
+	fn SciterSetCSS(hWndSciter: HWINDOW => ptr, utf8: LPCBYTE => ptr, numBytes: UINT => num, baseUrl: LPCWSTR => wstr, mediaType: LPCWSTR => wstr) -> BOOL => num;
+	fn SciterSetMediaType(hWndSciter: HWINDOW => ptr, mediaType: LPCWSTR => wstr) -> BOOL => num;
+	fn SciterSetMediaVars(hWndSciter: HWINDOW => ptr, mediaVars: *const VALUE => value) -> BOOL => num;
+	fn SciterGetMinWidth(hWndSciter: HWINDOW => ptr) -> UINT => num;
+	fn SciterGetMinHeight(hWndSciter: HWINDOW => ptr, width: UINT => num) -> UINT => num;
+	fn SciterCall(hWnd: HWINDOW => ptr, functionName: LPCSTR => astr, argc: UINT => num, argv: *const VALUE => value[argc], retval: *mut VALUE => value) -> BOOL => num;
+	fn SciterEval(hwnd: HWINDOW => ptr, script: LPCWSTR => wstr[scriptLength], scriptLength: UINT => num, pretval: *mut VALUE => value) -> BOOL => num;
+	fn SciterUpdateWindow(hwnd: HWINDOW => ptr) -> VOID => void;
+	fn SciterTranslateMessage(lpMsg: LPMSG => ptr) -> BOOL => num;
+	fn SciterSetOption(hWnd: HWINDOW => ptr, option: SCITER_RT_OPTIONS => debug, value: UINT_PTR => num) -> BOOL => num;
+	fn SciterGetPPI(hWndSciter: HWINDOW => ptr, px: *mut UINT => ptr, py: *mut UINT => ptr) -> VOID => void;
+	fn SciterGetViewExpando(hwnd: HWINDOW => ptr, pval: *mut VALUE => value) -> BOOL => num;
+	fn SciterRenderD2D(hWndSciter: HWINDOW => ptr, prt: *mut ID2D1RenderTarget => ptr) -> BOOL => num;
+	fn SciterD2DFactory(ppf: *mut *mut ID2D1Factory => ptr) -> BOOL => num;
+	fn SciterDWFactory(ppf: *mut *mut IDWriteFactory => ptr) -> BOOL => num;
+	fn SciterGraphicsCaps(pcaps: LPUINT => ptr) -> BOOL => num;
+	fn SciterSetHomeURL(hWndSciter: HWINDOW => ptr, baseUrl: LPCWSTR => wstr) -> BOOL => num;
+	fn SciterCreateNSView(frame: LPRECT => ptr) -> HWINDOW => ptr;
+	fn SciterCreateWidget(frame: LPRECT => ptr) -> HWINDOW => ptr;
+	fn SciterCreateWindow(creationFlags: UINT => num, frame: LPCRECT => ptr, delegate: *const SciterWindowDelegate => ptr, delegateParam: LPVOID => ptr, parent: HWINDOW => ptr) -> HWINDOW => ptr;
+	fn SciterSetupDebugOutput(hwndOrNull: HWINDOW => ptr, param: LPVOID => ptr, pfOutput: DEBUG_OUTPUT_PROC => ptr) -> () => void;
+	fn Sciter_UseElement(he: HELEMENT => element) -> SCDOM_RESULT => debug;
+	fn Sciter_UnuseElement(he: HELEMENT => element) -> SCDOM_RESULT => debug;
+	fn SciterGetRootElement(hwnd: HWINDOW => ptr, phe: *mut HELEMENT => out_element) -> SCDOM_RESULT => debug;
+	fn SciterGetFocusElement(hwnd: HWINDOW => ptr, phe: *mut HELEMENT => out_element) -> SCDOM_RESULT => debug;
+	fn SciterFindElement(hwnd: HWINDOW => ptr, pt: POINT => debug, phe: *mut HELEMENT => out_element) -> SCDOM_RESULT => debug;
+	fn SciterGetChildrenCount(he: HELEMENT => element, count: *mut UINT => ptr) -> SCDOM_RESULT => debug;
+	fn SciterGetNthChild(he: HELEMENT => element, n: UINT => num, phe: *mut HELEMENT => out_element) -> SCDOM_RESULT => debug;
+	fn SciterGetParentElement(he: HELEMENT => element, p_parent_he: *mut HELEMENT => out_element) -> SCDOM_RESULT => debug;
+	fn SciterGetElementHtmlCB(he: HELEMENT => element, outer: BOOL => num, rcv: LPCBYTE_RECEIVER => ptr, rcv_param: LPVOID => ptr) -> SCDOM_RESULT => debug;
+	fn SciterGetElementTextCB(he: HELEMENT => element, rcv: LPCWSTR_RECEIVER => ptr, rcv_param: LPVOID => ptr) -> SCDOM_RESULT => debug;
+	fn SciterSetElementText(he: HELEMENT => element, utf16: LPCWSTR => wstr[length], length: UINT => num) -> SCDOM_RESULT => debug;
+	fn SciterGetAttributeCount(he: HELEMENT => element, p_count: LPUINT => ptr) -> SCDOM_RESULT => debug;
+	fn SciterGetNthAttributeNameCB(he: HELEMENT => element, n: UINT => num, rcv: LPCSTR_RECEIVER => ptr, rcv_param: LPVOID => ptr) -> SCDOM_RESULT => debug;
+	fn SciterGetNthAttributeValueCB(he: HELEMENT => element, n: UINT => num, rcv: LPCWSTR_RECEIVER => ptr, rcv_param: LPVOID => ptr) -> SCDOM_RESULT => debug;
+	fn SciterGetAttributeByNameCB(he: HELEMENT => element, name: LPCSTR => astr, rcv: LPCWSTR_RECEIVER => ptr, rcv_param: LPVOID => ptr) -> SCDOM_RESULT => debug;
+	fn SciterSetAttributeByName(he: HELEMENT => element, name: LPCSTR => astr, value: LPCWSTR => wstr) -> SCDOM_RESULT => debug;
+	fn SciterClearAttributes(he: HELEMENT => element) -> SCDOM_RESULT => debug;
+	fn SciterGetElementIndex(he: HELEMENT => element, p_index: LPUINT => ptr) -> SCDOM_RESULT => debug;
+	fn SciterGetElementType(he: HELEMENT => element, p_type: *mut LPCSTR => ptr) -> SCDOM_RESULT => debug;
+	fn SciterGetElementTypeCB(he: HELEMENT => element, rcv: LPCSTR_RECEIVER => ptr, rcv_param: LPVOID => ptr) -> SCDOM_RESULT => debug;
+	fn SciterGetStyleAttributeCB(he: HELEMENT => element, name: LPCSTR => astr, rcv: LPCWSTR_RECEIVER => ptr, rcv_param: LPVOID => ptr) -> SCDOM_RESULT => debug;
+	fn SciterSetStyleAttribute(he: HELEMENT => element, name: LPCSTR => astr, value: LPCWSTR => wstr) -> SCDOM_RESULT => debug;
+	fn SciterGetElementLocation(he: HELEMENT => element, p_location: LPRECT => ptr, areas: UINT => num) -> SCDOM_RESULT => debug;
+	fn SciterScrollToView(he: HELEMENT => element, SciterScrollFlags: UINT => num) -> SCDOM_RESULT => debug;
+	fn SciterUpdateElement(he: HELEMENT => element, andForceRender: BOOL => num) -> SCDOM_RESULT => debug;
+	fn SciterRefreshElementArea(he: HELEMENT => element, rc: RECT => debug) -> SCDOM_RESULT => debug;
+	fn SciterSetCapture(he: HELEMENT => element) -> SCDOM_RESULT => debug;
+	fn SciterReleaseCapture(he: HELEMENT => element) -> SCDOM_RESULT => debug;
+	fn SciterGetElementHwnd(he: HELEMENT => element, p_hwnd: *mut HWINDOW => ptr, rootWindow: BOOL => num) -> SCDOM_RESULT => debug;
+	fn SciterCombineURL(he: HELEMENT => element, szUrlBuffer: LPWSTR => ptr, UrlBufferSize: UINT => num) -> SCDOM_RESULT => debug;
+	fn SciterSelectElements(he: HELEMENT => element, CSS_selectors: LPCSTR => astr, callback: SciterElementCallback => ptr, param: LPVOID => ptr) -> SCDOM_RESULT => debug;
+	fn SciterSelectElementsW(he: HELEMENT => element, CSS_selectors: LPCWSTR => wstr, callback: SciterElementCallback => ptr, param: LPVOID => ptr) -> SCDOM_RESULT => debug;
+	fn SciterSelectParent(he: HELEMENT => element, selector: LPCSTR => astr, depth: UINT => num, heFound: *mut HELEMENT => out_element) -> SCDOM_RESULT => debug;
+	fn SciterSelectParentW(he: HELEMENT => element, selector: LPCWSTR => wstr, depth: UINT => num, heFound: *mut HELEMENT => out_element) -> SCDOM_RESULT => debug;
+	fn SciterSetElementHtml(he: HELEMENT => element, html: *const BYTE => ptr, htmlLength: UINT => num, how: UINT => num) -> SCDOM_RESULT => debug;
+	fn SciterGetElementUID(he: HELEMENT => element, puid: *mut UINT => ptr) -> SCDOM_RESULT => debug;
+	fn SciterGetElementByUID(hwnd: HWINDOW => ptr, uid: UINT => num, phe: *mut HELEMENT => out_element) -> SCDOM_RESULT => debug;
+	fn SciterShowPopup(hePopup: HELEMENT => element, heAnchor: HELEMENT => element, placement: UINT => num) -> SCDOM_RESULT => debug;
+	fn SciterShowPopupAt(hePopup: HELEMENT => element, pos: POINT => debug, placement: UINT => num) -> SCDOM_RESULT => debug;
+	fn SciterHidePopup(he: HELEMENT => element) -> SCDOM_RESULT => debug;
+	fn SciterGetElementState(he: HELEMENT => element, pstateBits: *mut UINT => ptr) -> SCDOM_RESULT => debug;
+	fn SciterSetElementState(he: HELEMENT => element, stateBitsToSet: UINT => num, stateBitsToClear: UINT => num, updateView: BOOL => num) -> SCDOM_RESULT => debug;
+	fn SciterCreateElement(tagname: LPCSTR => astr, textOrNull: LPCWSTR => wstr, phe: *mut HELEMENT => out_element) -> SCDOM_RESULT => debug;
+	fn SciterCloneElement(he: HELEMENT => element, phe: *mut HELEMENT => out_element) -> SCDOM_RESULT => debug;
+	fn SciterInsertElement(he: HELEMENT => element, hparent: HELEMENT => element, index: UINT => num) -> SCDOM_RESULT => debug;
+	fn SciterDetachElement(he: HELEMENT => element) -> SCDOM_RESULT => debug;
+	fn SciterDeleteElement(he: HELEMENT => element) -> SCDOM_RESULT => debug;
+	fn SciterSetTimer(he: HELEMENT => element, milliseconds: UINT => num, timer_id: UINT_PTR => num) -> SCDOM_RESULT => debug;
+	fn SciterDetachEventHandler(he: HELEMENT => element, pep: ElementEventProc => ptr, tag: LPVOID => ptr) -> SCDOM_RESULT => debug;
+	fn SciterAttachEventHandler(he: HELEMENT => element, pep: ElementEventProc => ptr, tag: LPVOID => ptr) -> SCDOM_RESULT => debug;
+	fn SciterWindowAttachEventHandler(hwndLayout: HWINDOW => ptr, pep: ElementEventProc => ptr, tag: LPVOID => ptr, subscription: UINT => num) -> SCDOM_RESULT => debug;
+	fn SciterWindowDetachEventHandler(hwndLayout: HWINDOW => ptr, pep: ElementEventProc => ptr, tag: LPVOID => ptr) -> SCDOM_RESULT => debug;
+	fn SciterSendEvent(he: HELEMENT => element, appEventCode: UINT => num, heSource: HELEMENT => element, reason: UINT_PTR => num, handled: *mut BOOL => ptr) -> SCDOM_RESULT => debug;
+	fn SciterPostEvent(he: HELEMENT => element, appEventCode: UINT => num, heSource: HELEMENT => element, reason: UINT_PTR => num) -> SCDOM_RESULT => debug;
+	fn SciterCallBehaviorMethod(he: HELEMENT => element, params: *const METHOD_PARAMS => ptr) -> SCDOM_RESULT => debug;
+	fn SciterRequestElementData(he: HELEMENT => element, url: LPCWSTR => wstr, dataType: UINT => num, initiator: HELEMENT => element) -> SCDOM_RESULT => debug;
+	fn SciterHttpRequest(he: HELEMENT => element, url: LPCWSTR => wstr, dataType: UINT => num, requestType: UINT => num, requestParams: *const REQUEST_PARAM => ptr, nParams: UINT => num) -> SCDOM_RESULT => debug;
+	fn SciterGetScrollInfo(he: HELEMENT => element, scrollPos: LPPOINT => ptr, viewRect: LPRECT => ptr, contentSize: LPSIZE => ptr) -> SCDOM_RESULT => debug;
+	fn SciterSetScrollPos(he: HELEMENT => element, scrollPos: POINT => debug, smooth: BOOL => num) -> SCDOM_RESULT => debug;
+	fn SciterGetElementIntrinsicWidths(he: HELEMENT => element, pMinWidth: *mut INT => ptr, pMaxWidth: *mut INT => ptr) -> SCDOM_RESULT => debug;
+	fn SciterGetElementIntrinsicHeight(he: HELEMENT => element, forWidth: INT => num, pHeight: *mut INT => ptr) -> SCDOM_RESULT => debug;
+	fn SciterIsElementVisible(he: HELEMENT => element, pVisible: *mut BOOL => ptr) -> SCDOM_RESULT => debug;
+	fn SciterIsElementEnabled(he: HELEMENT => element, pEnabled: *mut BOOL => ptr) -> SCDOM_RESULT => debug;
+	fn SciterSortElements(he: HELEMENT => element, firstIndex: UINT => num, lastIndex: UINT => num, cmpFunc: *mut ELEMENT_COMPARATOR => ptr, cmpFuncParam: LPVOID => ptr) -> SCDOM_RESULT => debug;
+	fn SciterSwapElements(he1: HELEMENT => element, he2: HELEMENT => element) -> SCDOM_RESULT => debug;
+	fn SciterTraverseUIEvent(evt: UINT => num, eventCtlStruct: LPVOID => ptr, bOutProcessed: *mut BOOL => ptr) -> SCDOM_RESULT => debug;
+	fn SciterCallScriptingMethod(he: HELEMENT => element, name: LPCSTR => astr, argv: *const VALUE => value[argc], argc: UINT => num, retval: *mut VALUE => value) -> SCDOM_RESULT => debug;
+	fn SciterCallScriptingFunction(he: HELEMENT => element, name: LPCSTR => astr, argv: *const VALUE => value[argc], argc: UINT => num, retval: *mut VALUE => value) -> SCDOM_RESULT => debug;
+	fn SciterEvalElementScript(he: HELEMENT => element, script: LPCWSTR => wstr[scriptLength], scriptLength: UINT => num, retval: *mut VALUE => value) -> SCDOM_RESULT => debug;
+	fn SciterAttachHwndToElement(he: HELEMENT => element, hwnd: HWINDOW => ptr) -> SCDOM_RESULT => debug;
+	fn SciterControlGetType(he: HELEMENT => element, pType: *mut UINT => ptr) -> SCDOM_RESULT => debug;
+	fn SciterGetValue(he: HELEMENT => element, pval: *mut VALUE => value) -> SCDOM_RESULT => debug;
+	fn SciterSetValue(he: HELEMENT => element, pval: *const VALUE => value) -> SCDOM_RESULT => debug;
+	fn SciterGetExpando(he: HELEMENT => element, pval: *mut VALUE => value, forceCreation: BOOL => num) -> SCDOM_RESULT => debug;
+	fn SciterGetObject(he: HELEMENT => element, pval: *mut tiscript_value => ptr, forceCreation: BOOL => num) -> SCDOM_RESULT => debug;
+	fn SciterGetElementNamespace(he: HELEMENT => element, pval: *mut tiscript_value => ptr) -> SCDOM_RESULT => debug;
+	fn SciterGetHighlightedElement(hwnd: HWINDOW => ptr, phe: *mut HELEMENT => out_element) -> SCDOM_RESULT => debug;
+	fn SciterSetHighlightedElement(hwnd: HWINDOW => ptr, he: HELEMENT => element) -> SCDOM_RESULT => debug;
+	fn SciterNodeAddRef(hn: HNODE => ptr) -> SCDOM_RESULT => debug;
+	fn SciterNodeRelease(hn: HNODE => ptr) -> SCDOM_RESULT => debug;
+	fn SciterNodeCastFromElement(he: HELEMENT => element, phn: *mut HNODE => ptr) -> SCDOM_RESULT => debug;
+	fn SciterNodeCastToElement(hn: HNODE => ptr, he: *mut HELEMENT => out_element) -> SCDOM_RESULT => debug;
+	fn SciterNodeFirstChild(hn: HNODE => ptr, phn: *mut HNODE => ptr) -> SCDOM_RESULT => debug;
+	fn SciterNodeLastChild(hn: HNODE => ptr, phn: *mut HNODE => ptr) -> SCDOM_RESULT => debug;
+	fn SciterNodeNextSibling(hn: HNODE => ptr, phn: *mut HNODE => ptr) -> SCDOM_RESULT => debug;
+	fn SciterNodePrevSibling(hn: HNODE => ptr, phn: *mut HNODE => ptr) -> SCDOM_RESULT => debug;
+	fn SciterNodeParent(hnode: HNODE => ptr, pheParent: *mut HELEMENT => out_element) -> SCDOM_RESULT => debug;
+	fn SciterNodeNthChild(hnode: HNODE => ptr, n: UINT => num, phn: *mut HNODE => ptr) -> SCDOM_RESULT => debug;
+	fn SciterNodeChildrenCount(hnode: HNODE => ptr, pn: *mut UINT => ptr) -> SCDOM_RESULT => debug;
+	fn SciterNodeType(hnode: HNODE => ptr, pNodeType: *mut UINT => ptr) -> SCDOM_RESULT => debug;
+	fn SciterNodeGetText(hnode: HNODE => ptr, rcv: *mut LPCWSTR_RECEIVER => ptr, rcv_param: LPVOID => ptr) -> SCDOM_RESULT => debug;
+	fn SciterNodeSetText(hnode: HNODE => ptr, text: LPCWSTR => wstr[textLength], textLength: UINT => num) -> SCDOM_RESULT => debug;
+	fn SciterNodeInsert(hnode: HNODE => ptr, how: UINT => num, what: HNODE => ptr) -> SCDOM_RESULT => debug;
+	fn SciterNodeRemove(hnode: HNODE => ptr, finalize: BOOL => num) -> SCDOM_RESULT => debug;
+	fn SciterCreateTextNode(text: LPCWSTR => wstr[textLength], textLength: UINT => num, phnode: *mut HNODE => ptr) -> SCDOM_RESULT => debug;
+	fn SciterCreateCommentNode(text: LPCWSTR => wstr[textLength], textLength: UINT => num, phnode: *mut HNODE => ptr) -> SCDOM_RESULT => debug;
+	fn ValueInit(pval: *mut VALUE => value) -> VALUE_RESULT => debug;
+	fn ValueClear(pval: *mut VALUE => value) -> VALUE_RESULT => debug;
+	fn ValueCompare(pval1: *const VALUE => value, pval2: *const VALUE => value) -> VALUE_RESULT => debug;
+	fn ValueCopy(pdst: *mut VALUE => value, psrc: *const VALUE => value) -> VALUE_RESULT => debug;
+	fn ValueIsolate(pdst: *mut VALUE => value) -> VALUE_RESULT => debug;
+	fn ValueType(pval: *const VALUE => value, pType: *mut UINT => ptr, pUnits: *mut UINT => ptr) -> VALUE_RESULT => debug;
+	fn ValueStringData(pval: *const VALUE => value, pChars: *mut LPCWSTR => ptr, pNumChars: *mut UINT => ptr) -> VALUE_RESULT => debug;
+	fn ValueStringDataSet(pval: *mut VALUE => value, chars: LPCWSTR => wstr[numChars], numChars: UINT => num, units: UINT => num) -> VALUE_RESULT => debug;
+	fn ValueIntData(pval: *const VALUE => value, pData: *mut INT => ptr) -> VALUE_RESULT => debug;
+	fn ValueIntDataSet(pval: *mut VALUE => value, data: INT => num, vtype: UINT => num, units: UINT => num) -> VALUE_RESULT => debug;
+	fn ValueInt64Data(pval: *const VALUE => value, pData: *mut INT64 => ptr) -> VALUE_RESULT => debug;
+	fn ValueInt64DataSet(pval: *mut VALUE => value, data: INT64 => num, vtype: UINT => num, units: UINT => num) -> VALUE_RESULT => debug;
+	fn ValueFloatData(pval: *const VALUE => value, pData: *mut FLOAT_VALUE => ptr) -> VALUE_RESULT => debug;
+	fn ValueFloatDataSet(pval: *mut VALUE => value, data: FLOAT_VALUE => num, vtype: UINT => num, units: UINT => num) -> VALUE_RESULT => debug;
+	fn ValueBinaryData(pval: *const VALUE => value, pBytes: *mut LPCBYTE => ptr, pnBytes: *mut UINT => ptr) -> VALUE_RESULT => debug;
+	fn ValueBinaryDataSet(pval: *mut VALUE => value, pBytes: LPCBYTE => ptr, nBytes: UINT => num, vtype: UINT => num, units: UINT => num) -> VALUE_RESULT => debug;
+	fn ValueElementsCount(pval: *const VALUE => value, pn: *mut INT => ptr) -> VALUE_RESULT => debug;
+	fn ValueNthElementValue(pval: *const VALUE => value, n: INT => num, pretval: *mut VALUE => value) -> VALUE_RESULT => debug;
+	fn ValueNthElementValueSet(pval: *mut VALUE => value, n: INT => num, pval_to_set: *const VALUE => value) -> VALUE_RESULT => debug;
+	fn ValueNthElementKey(pval: *const VALUE => value, n: INT => num, pretval: *mut VALUE => value) -> VALUE_RESULT => debug;
+	fn ValueEnumElements(pval: *const VALUE => value, penum: KeyValueCallback => ptr, param: LPVOID => ptr) -> VALUE_RESULT => debug;
+	fn ValueSetValueToKey(pval: *mut VALUE => value, pkey: *const VALUE => value, pval_to_set: *const VALUE => value) -> VALUE_RESULT => debug;
+	fn ValueGetValueOfKey(pval: *const VALUE => value, pkey: *const VALUE => value, pretval: *mut VALUE => value) -> VALUE_RESULT => debug;
+	fn ValueToString(pval: *mut VALUE => value, how: VALUE_STRING_CVT_TYPE => debug) -> VALUE_RESULT => debug;
+	fn ValueFromString(pval: *mut VALUE => value, str: LPCWSTR => wstr[strLength], strLength: UINT => num, how: VALUE_STRING_CVT_TYPE => debug) -> UINT => num;
+	fn ValueInvoke(pval: *const VALUE => value, pthis: *mut VALUE => value, argc: UINT => num, argv: *const VALUE => value[argc], pretval: *mut VALUE => value, url: LPCWSTR => wstr) -> VALUE_RESULT => debug;
+	fn ValueNativeFunctorSet(pval: *mut VALUE => value, pinvoke: NATIVE_FUNCTOR_INVOKE => ptr, prelease: NATIVE_FUNCTOR_RELEASE => ptr, tag: LPVOID => ptr) -> VALUE_RESULT => debug;
+	fn ValueIsNativeFunctor(pval: *const VALUE => value) -> BOOL => num;
+	fn TIScriptAPI() -> * mut tiscript_native_interface => ptr;
+	fn SciterGetVM(hwnd: HWINDOW => ptr) -> HVM => ptr;
+	fn Sciter_v2V(vm: HVM => ptr, script_value: tiscript_value => num, value: *mut VALUE => value, isolate: BOOL => num) -> BOOL => num;
+	fn Sciter_V2v(vm: HVM => ptr, valuev: *const VALUE => value, script_value: *mut tiscript_value => ptr) -> BOOL => num;
+	fn SciterOpenArchive(archiveData: LPCBYTE => ptr, archiveDataLength: UINT => num) -> HSARCHIVE => ptr;
+	fn SciterGetArchiveItem(harc: HSARCHIVE => ptr, path: LPCWSTR => wstr, pdata: *mut LPCBYTE => ptr, pdataLength: *mut UINT => ptr) -> BOOL => num;
+	fn SciterCloseArchive(harc: HSARCHIVE => ptr) -> BOOL => num;
+	fn SciterFireEvent(evt: *const BEHAVIOR_EVENT_PARAMS => ptr, post: BOOL => num, handled: *mut BOOL => ptr) -> SCDOM_RESULT => debug;
+	fn SciterGetCallbackParam(hwnd: HWINDOW => ptr) -> LPVOID => ptr;
+	fn SciterPostCallback(hwnd: HWINDOW => ptr, wparam: UINT_PTR => num, lparam: UINT_PTR => num, timeoutms: UINT => num) -> UINT_PTR => num;
+	fn SciterCreateOnDirectXWindow(hwnd: HWINDOW => ptr, pSwapChain: *mut IDXGISwapChain => ptr) -> BOOL => num;
+	fn SciterRenderOnDirectXWindow(hwnd: HWINDOW => ptr, elementToRenderOrNull: HELEMENT => element, frontLayer: BOOL => num) -> BOOL => num;
+	fn SciterRenderOnDirectXTexture(hwnd: HWINDOW => ptr, elementToRenderOrNull: HELEMENT => element, surface: *mut IDXGISurface => ptr) -> BOOL => num;
+	fn SciterProcX(hwnd: HWINDOW => ptr, msg: *const SCITER_X_MSG => ptr) -> BOOL => num;
+	fn SciterAtomValue(name: LPCSTR => astr) -> som_atom_t => num;
+	fn SciterAtomNameCB(atomv: som_atom_t => num, rcv: LPCSTR_RECEIVER => ptr, rcv_param: LPVOID => ptr) -> BOOL => num;
+	fn SciterSetGlobalAsset(pass: *mut som_asset_t => ptr) -> BOOL => num;
+	fn SciterGetElementAsset(el: HELEMENT => element, atomv: som_atom_t => num, pass: *mut *mut som_asset_t => ptr) -> SCDOM_RESULT => debug;
+	fn SciterSetVariable(hwndOrNull: HWINDOW => ptr, path: LPCSTR => astr, value: *const VALUE => value) -> SCDOM_RESULT => debug;
+	fn SciterGetVariable(hwndOrNull: HWINDOW => ptr, path: LPCSTR => astr, value: *mut VALUE => value) -> SCDOM_RESULT => debug;
+	fn SciterElementUnwrap(pval: *const VALUE => value, ppElement: *mut HELEMENT => out_element) -> SCDOM_RESULT => debug;
+	fn SciterElementWrap(pval: *mut VALUE => value, pElement: HELEMENT => element) -> SCDOM_RESULT => debug;
+	fn SciterNodeUnwrap(pval: *const VALUE => value, ppElement: *mut HNODE => ptr) -> SCDOM_RESULT => debug;
+	fn SciterNodeWrap(pval: *mut VALUE => value, pElement: HNODE => ptr) -> SCDOM_RESULT => debug;
+}
+
+proxy! {
+	fn graphics_api(api: &SciterGraphicsAPI) via GRAPHICS {}
+
+	fn imageCreate(poutImg: &mut HIMG => ptr, width: UINT => num, height: UINT => num, withAlpha: BOOL => num) -> GRAPHIN_RESULT => debug;
+	fn imageCreateFromPixmap(poutImg: &mut HIMG => ptr, pixmapWidth: UINT => num, pixmapHeight: UINT => num, withAlpha: BOOL => num, pixmap: LPCBYTE => ptr) -> GRAPHIN_RESULT => debug;
+	fn imageAddRef(himg: HIMG => ptr) -> GRAPHIN_RESULT => debug;
+	fn imageRelease(himg: HIMG => ptr) -> GRAPHIN_RESULT => debug;
+	fn imageGetInfo(himg: HIMG => ptr, width: &mut UINT => ptr, height: &mut UINT => ptr, usesAlpha: &mut BOOL => ptr) -> GRAPHIN_RESULT => debug;
+	fn imageClear(himg: HIMG => ptr, byColor: SC_COLOR => num) -> GRAPHIN_RESULT => debug;
+	fn imageLoad(bytes: LPCBYTE => ptr, num_bytes: UINT => num, pout_img: &mut HIMG => ptr) -> GRAPHIN_RESULT => debug;
+	fn imageSave(himg: HIMG => ptr, pfn: ImageWriteFunction => ptr, prm: LPVOID => ptr, encoding: IMAGE_ENCODING => debug, quality: UINT => num) -> GRAPHIN_RESULT => debug;
+	fn RGBA(red: UINT => num, green: UINT => num, blue: UINT => num, alpha: UINT => num) -> SC_COLOR => num;
+	fn gCreate(img: HIMG => ptr, pout_gfx: &mut HGFX => ptr) -> GRAPHIN_RESULT => debug;
+	fn gAddRef(gfx: HGFX => ptr) -> GRAPHIN_RESULT => debug;
+	fn gRelease(gfx: HGFX => ptr) -> GRAPHIN_RESULT => debug;
+	fn gLine(hgfx: HGFX => ptr, x1: SC_POS => num, y1: SC_POS => num, x2: SC_POS => num, y2: SC_POS => num) -> GRAPHIN_RESULT => debug;
+	fn gRectangle(hgfx: HGFX => ptr, x1: SC_POS => num, y1: SC_POS => num, x2: SC_POS => num, y2: SC_POS => num) -> GRAPHIN_RESULT => debug;
+	fn gRoundedRectangle(hgfx: HGFX => ptr, x1: SC_POS => num, y1: SC_POS => num, x2: SC_POS => num, y2: SC_POS => num, radii8: *const SC_DIM => ptr) -> GRAPHIN_RESULT => debug;
+	fn gEllipse(hgfx: HGFX => ptr, x: SC_POS => num, y: SC_POS => num, rx: SC_DIM => num, ry: SC_DIM => num) -> GRAPHIN_RESULT => debug;
+	fn gArc(hgfx: HGFX => ptr, x: SC_POS => num, y: SC_POS => num, rx: SC_POS => num, ry: SC_POS => num, start: SC_ANGLE => num, sweep: SC_ANGLE => num) -> GRAPHIN_RESULT => debug;
+	fn gStar(hgfx: HGFX => ptr, x: SC_POS => num, y: SC_POS => num, r1: SC_DIM => num, r2: SC_DIM => num, start: SC_ANGLE => num, rays: UINT => num) -> GRAPHIN_RESULT => debug;
+	fn gPolygon(hgfx: HGFX => ptr, xy: *const SC_POS => ptr, num_points: UINT => num) -> GRAPHIN_RESULT => debug;
+	fn gPolyline(hgfx: HGFX => ptr, xy: *const SC_POS => ptr, num_points: UINT => num) -> GRAPHIN_RESULT => debug;
+	fn pathCreate(path: &mut HPATH => ptr) -> GRAPHIN_RESULT => debug;
+	fn pathAddRef(path: HPATH => ptr) -> GRAPHIN_RESULT => debug;
+	fn pathRelease(path: HPATH => ptr) -> GRAPHIN_RESULT => debug;
+	fn pathMoveTo(path: HPATH => ptr, x: SC_POS => num, y: SC_POS => num, relative: BOOL => num) -> GRAPHIN_RESULT => debug;
+	fn pathLineTo(path: HPATH => ptr, x: SC_POS => num, y: SC_POS => num, relative: BOOL => num) -> GRAPHIN_RESULT => debug;
+	fn pathArcTo(path: HPATH => ptr, x: SC_POS => num, y: SC_POS => num, angle: SC_ANGLE => num, rx: SC_DIM => num, ry: SC_DIM => num, is_large_arc: BOOL => num, clockwise: BOOL => num, relative: BOOL => num) -> GRAPHIN_RESULT => debug;
+	fn pathQuadraticCurveTo(path: HPATH => ptr, xc: SC_POS => num, yc: SC_POS => num, x: SC_POS => num, y: SC_POS => num, relative: BOOL => num) -> GRAPHIN_RESULT => debug;
+	fn pathBezierCurveTo(path: HPATH => ptr, xc1: SC_POS => num, yc1: SC_POS => num, xc2: SC_POS => num, yc2: SC_POS => num, x: SC_POS => num, y: SC_POS => num, relative: BOOL => num) -> GRAPHIN_RESULT => debug;
+	fn pathClosePath(path: HPATH => ptr) -> GRAPHIN_RESULT => debug;
+	fn gDrawPath(hgfx: HGFX => ptr, path: HPATH => ptr, dpm: DRAW_PATH => debug) -> GRAPHIN_RESULT => debug;
+	fn gRotate(hgfx: HGFX => ptr, radians: SC_ANGLE => num, cx: Option<&SC_POS> => debug, cy: Option<&SC_POS> => debug) -> GRAPHIN_RESULT => debug;
+	fn gTranslate(hgfx: HGFX => ptr, cx: SC_POS => num, cy: SC_POS => num) -> GRAPHIN_RESULT => debug;
+	fn gScale(hgfx: HGFX => ptr, x: SC_DIM => num, y: SC_DIM => num) -> GRAPHIN_RESULT => debug;
+	fn gSkew(hgfx: HGFX => ptr, dx: SC_DIM => num, dy: SC_DIM => num) -> GRAPHIN_RESULT => debug;
+	fn gTransform(hgfx: HGFX => ptr, m11: SC_POS => num, m12: SC_POS => num, m21: SC_POS => num, m22: SC_POS => num, dx: SC_POS => num, dy: SC_POS => num) -> GRAPHIN_RESULT => debug;
+	fn gStateSave(hgfx: HGFX => ptr) -> GRAPHIN_RESULT => debug;
+	fn gStateRestore(hgfx: HGFX => ptr) -> GRAPHIN_RESULT => debug;
+	fn gLineWidth(hgfx: HGFX => ptr, width: SC_DIM => num) -> GRAPHIN_RESULT => debug;
+	fn gLineJoin(hgfx: HGFX => ptr, join_type: LINE_JOIN => debug) -> GRAPHIN_RESULT => debug;
+	fn gLineCap(hgfx: HGFX => ptr, cap_type: LINE_CAP => debug) -> GRAPHIN_RESULT => debug;
+	fn gLineColor(hgfx: HGFX => ptr, color: SC_COLOR => num) -> GRAPHIN_RESULT => debug;
+	fn gFillColor(hgfx: HGFX => ptr, color: SC_COLOR => num) -> GRAPHIN_RESULT => debug;
+	fn gLineGradientLinear(hgfx: HGFX => ptr, x1: SC_POS => num, y1: SC_POS => num, x2: SC_POS => num, y2: SC_POS => num, stops: *const SC_COLOR_STOP => ptr, nstops: UINT => num) -> GRAPHIN_RESULT => debug;
+	fn gFillGradientLinear(hgfx: HGFX => ptr, x1: SC_POS => num, y1: SC_POS => num, x2: SC_POS => num, y2: SC_POS => num, stops: *const SC_COLOR_STOP => ptr, nstops: UINT => num) -> GRAPHIN_RESULT => debug;
+	fn gLineGradientRadial(hgfx: HGFX => ptr, x: SC_POS => num, y: SC_POS => num, rx: SC_DIM => num, ry: SC_DIM => num, stops: *const SC_COLOR_STOP => ptr, nstops: UINT => num) -> GRAPHIN_RESULT => debug;
+	fn gFillGradientRadial(hgfx: HGFX => ptr, x: SC_POS => num, y: SC_POS => num, rx: SC_DIM => num, ry: SC_DIM => num, stops: *const SC_COLOR_STOP => ptr, nstops: UINT => num) -> GRAPHIN_RESULT => debug;
+	fn gFillMode(hgfx: HGFX => ptr, even_odd: BOOL => num) -> GRAPHIN_RESULT => debug;
+	fn textCreateForElement(ptext: &mut HTEXT => ptr, text: LPCWSTR => wstr[textLength], textLength: UINT => num, he: HELEMENT => element, classNameOrNull: LPCWSTR => wstr) -> GRAPHIN_RESULT => debug;
+	fn textCreateForElementAndStyle(ptext: &mut HTEXT => ptr, text: LPCWSTR => wstr[textLength], textLength: UINT => num, he: HELEMENT => element, style: LPCWSTR => wstr[styleLength], styleLength: UINT => num) -> GRAPHIN_RESULT => debug;
+	fn textAddRef(text: HTEXT => ptr) -> GRAPHIN_RESULT => debug;
+	fn textRelease(text: HTEXT => ptr) -> GRAPHIN_RESULT => debug;
+	fn textGetMetrics(text: HTEXT => ptr, minWidth: &mut SC_DIM => ptr, maxWidth: &mut SC_DIM => ptr, height: &mut SC_DIM => ptr, ascent: &mut SC_DIM => ptr, descent: &mut SC_DIM => ptr, nLines: &mut UINT => ptr) -> GRAPHIN_RESULT => debug;
+	fn textSetBox(text: HTEXT => ptr, width: SC_DIM => num, height: SC_DIM => num) -> GRAPHIN_RESULT => debug;
+	fn gDrawText(hgfx: HGFX => ptr, text: HTEXT => ptr, px: SC_POS => num, py: SC_POS => num, position: UINT => num) -> GRAPHIN_RESULT => debug;
+	fn gDrawImage(hgfx: HGFX => ptr, himg: HIMG => ptr, x: SC_POS => num, y: SC_POS => num, w: Option<&SC_DIM> => debug, h: Option<&SC_DIM> => debug, ix: Option<&UINT> => debug, iy: Option<&UINT> => debug, iw: Option<&UINT> => debug, ih: Option<&UINT> => debug, opacity: Option<&f32> => debug) -> GRAPHIN_RESULT => debug;
+	fn gWorldToScreen(hgfx: HGFX => ptr, inout_x: &mut SC_POS => ptr, inout_y: &mut SC_POS => ptr) -> GRAPHIN_RESULT => debug;
+	fn gScreenToWorld(hgfx: HGFX => ptr, inout_x: &mut SC_POS => ptr, inout_y: &mut SC_POS => ptr) -> GRAPHIN_RESULT => debug;
+	fn gPushClipBox(hgfx: HGFX => ptr, x1: SC_POS => num, y1: SC_POS => num, x2: SC_POS => num, y2: SC_POS => num, opacity: f32 => num) -> GRAPHIN_RESULT => debug;
+	fn gPushClipPath(hgfx: HGFX => ptr, hpath: HPATH => ptr, opacity: f32 => num) -> GRAPHIN_RESULT => debug;
+	fn gPopClip(hgfx: HGFX => ptr) -> GRAPHIN_RESULT => debug;
+	fn imagePaint(himg: HIMG => ptr, pPainter: ImagePaintFunction => ptr, prm: LPVOID => ptr) -> GRAPHIN_RESULT => debug;
+	fn vWrapGfx(hgfx: HGFX => ptr, toValue: *mut VALUE => value) -> GRAPHIN_RESULT => debug;
+	fn vWrapImage(himg: HIMG => ptr, toValue: *mut VALUE => value) -> GRAPHIN_RESULT => debug;
+	fn vWrapPath(hpath: HPATH => ptr, toValue: *mut VALUE => value) -> GRAPHIN_RESULT => debug;
+	fn vWrapText(htext: HTEXT => ptr, toValue: *mut VALUE => value) -> GRAPHIN_RESULT => debug;
+	fn vUnWrapGfx(fromValue: *const VALUE => value, phgfx: &mut HGFX => ptr) -> GRAPHIN_RESULT => debug;
+	fn vUnWrapImage(fromValue: *const VALUE => value, phimg: &mut HIMG => ptr) -> GRAPHIN_RESULT => debug;
+	fn vUnWrapPath(fromValue: *const VALUE => value, phpath: &mut HPATH => ptr) -> GRAPHIN_RESULT => debug;
+	fn vUnWrapText(fromValue: *const VALUE => value, phtext: &mut HTEXT => ptr) -> GRAPHIN_RESULT => debug;
+	fn gFlush(hgfx: HGFX => ptr) -> GRAPHIN_RESULT => debug;
+}
+
+proxy! {
+	fn request_api(api: &SciterRequestAPI) via REQUEST {}
+
+	fn RequestUse(rq: HREQUEST => ptr) -> REQUEST_RESULT => debug;
+	fn RequestUnUse(rq: HREQUEST => ptr) -> REQUEST_RESULT => debug;
+	fn RequestUrl(rq: HREQUEST => ptr, rcv: LPCSTR_RECEIVER => ptr, rcv_param: LPVOID => ptr) -> REQUEST_RESULT => debug;
+	fn RequestContentUrl(rq: HREQUEST => ptr, rcv: LPCSTR_RECEIVER => ptr, rcv_param: LPVOID => ptr) -> REQUEST_RESULT => debug;
+	fn RequestGetRequestType(rq: HREQUEST => ptr, pType: &mut REQUEST_METHOD => ptr) -> REQUEST_RESULT => debug;
+	fn RequestGetRequestedDataType(rq: HREQUEST => ptr, pData: &mut RESOURCE_TYPE => ptr) -> REQUEST_RESULT => debug;
+	fn RequestGetReceivedDataType(rq: HREQUEST => ptr, rcv: LPCSTR_RECEIVER => ptr, rcv_param: LPVOID => ptr) -> REQUEST_RESULT => debug;
+	fn RequestGetNumberOfParameters(rq: HREQUEST => ptr, pNumber: &mut UINT => ptr) -> REQUEST_RESULT => debug;
+	fn RequestGetNthParameterName(rq: HREQUEST => ptr, n: UINT => num, rcv: LPCWSTR_RECEIVER => ptr, rcv_param: LPVOID => ptr) -> REQUEST_RESULT => debug;
+	fn RequestGetNthParameterValue(rq: HREQUEST => ptr, n: UINT => num, rcv: LPCWSTR_RECEIVER => ptr, rcv_param: LPVOID => ptr) -> REQUEST_RESULT => debug;
+	fn RequestGetTimes(rq: HREQUEST => ptr, pStarted: &mut UINT => ptr, pEnded: &mut UINT => ptr) -> REQUEST_RESULT => debug;
+	fn RequestGetNumberOfRqHeaders(rq: HREQUEST => ptr, pNumber: &mut UINT => ptr) -> REQUEST_RESULT => debug;
+	fn RequestGetNthRqHeaderName(rq: HREQUEST => ptr, n: UINT => num, rcv: LPCWSTR_RECEIVER => ptr, rcv_param: LPVOID => ptr) -> REQUEST_RESULT => debug;
+	fn RequestGetNthRqHeaderValue(rq: HREQUEST => ptr, n: UINT => num, rcv: LPCWSTR_RECEIVER => ptr, rcv_param: LPVOID => ptr) -> REQUEST_RESULT => debug;
+	fn RequestGetNumberOfRspHeaders(rq: HREQUEST => ptr, pNumber: &mut UINT => ptr) -> REQUEST_RESULT => debug;
+	fn RequestGetNthRspHeaderName(rq: HREQUEST => ptr, n: UINT => num, rcv: LPCWSTR_RECEIVER => ptr, rcv_param: LPVOID => ptr) -> REQUEST_RESULT => debug;
+	fn RequestGetNthRspHeaderValue(rq: HREQUEST => ptr, n: UINT => num, rcv: LPCWSTR_RECEIVER => ptr, rcv_param: LPVOID => ptr) -> REQUEST_RESULT => debug;
+	fn RequestGetCompletionStatus(rq: HREQUEST => ptr, pState: &mut REQUEST_STATE => ptr, pCompletionStatus: &mut UINT => ptr) -> REQUEST_RESULT => debug;
+	fn RequestGetProxyHost(rq: HREQUEST => ptr, rcv: LPCSTR_RECEIVER => ptr, rcv_param: LPVOID => ptr) -> REQUEST_RESULT => debug;
+	fn RequestGetProxyPort(rq: HREQUEST => ptr, pPort: &mut UINT => ptr) -> REQUEST_RESULT => debug;
+	fn RequestSetSucceeded(rq: HREQUEST => ptr, status: UINT => num, dataOrNull: LPCBYTE => ptr, dataLength: UINT => num) -> REQUEST_RESULT => debug;
+	fn RequestSetFailed(rq: HREQUEST => ptr, status: UINT => num, dataOrNull: LPCBYTE => ptr, dataLength: UINT => num) -> REQUEST_RESULT => debug;
+	fn RequestAppendDataChunk(rq: HREQUEST => ptr, data: LPCBYTE => ptr, dataLength: UINT => num) -> REQUEST_RESULT => debug;
+	fn RequestSetRqHeader(rq: HREQUEST => ptr, name: LPCWSTR => wstr, value: LPCWSTR => wstr) -> REQUEST_RESULT => debug;
+	fn RequestSetRspHeader(rq: HREQUEST => ptr, name: LPCWSTR => wstr, value: LPCWSTR => wstr) -> REQUEST_RESULT => debug;
+	fn RequestSetReceivedDataType(rq: HREQUEST => ptr, _type: LPCSTR => astr) -> REQUEST_RESULT => debug;
+	fn RequestSetReceivedDataEncoding(rq: HREQUEST => ptr, encoding: LPCSTR => astr) -> REQUEST_RESULT => debug;
+	fn RequestGetData(rq: HREQUEST => ptr, rcv: LPCBYTE_RECEIVER => ptr, rcv_param: LPVOID => ptr) -> REQUEST_RESULT => debug;
+}
@@ -11,6 +11,7 @@ proxies of script functions, objects and arrays.
 You can create an empty (undefined) Sciter value with [`Value::new()`](struct.Value.html):
 
 ```
+# sciter::fake::install();
 use sciter::Value;
 
 let v = Value::new();
@@ -21,6 +22,7 @@ assert!(!v.is_null());
 Or explicitly create `Value` of the specified type:
 
 ```
+# sciter::fake::install();
 use sciter::Value;
 
 let v = Value::null();
@@ -49,6 +51,7 @@ assert!(v.len() == 0);
 Also there is conversion from Rust types:
 
 ```
+# sciter::fake::install();
 use sciter::Value;
 
 let v = Value::from(true);
@@ -67,9 +70,30 @@ let v = Value::from(b"123".as_ref());
 assert!(v.is_bytes());
 ```
 
+64-bit integers are stored as numbers while they fit into the script number range and rounded to floats beyond it,
+see [`IntOverflow`](enum.IntOverflow.html) for the other options:
+
+```
+# sciter::fake::install();
+use sciter::Value;
+
+let v = Value::from(1_i64 << 40);
+assert!(v.is_float());
+assert_eq!(v.to_i64(), Some(1 << 40));
+
+let v = Value::from(i64::MAX - 1);
+assert!(v.is_float());
+assert_eq!(v.to_i64(), None); // 2^63 is out of range
+
+let v = Value::big_int(i64::MAX);
+assert!(v.is_currency());
+assert_eq!(v.to_big_int(), Some(i64::MAX));
+```
+
 And from a sequence of objects:
 
 ```
+# sciter::fake::install();
 use sciter::Value;
 
 let v: Value = ["1","2","3"].iter().cloned().collect();
@@ -87,6 +111,7 @@ methods:
 ```
 # #[macro_use] extern crate sciter;
 # fn main() {
+# sciter::fake::install();
 let map = vmap! {
   "one" => 1,
   "two" => 2.0,
@@ -104,15 +129,30 @@ assert_eq!(array.len(), 3);
 To access its contents you should use one of [`to_`](struct.Value.html#method.to_int) methods:
 
 ```
+# sciter::fake::install();
 use sciter::Value;
 
 let v = Value::from(4);
 assert_eq!(v.to_int(), Some(4));
 ```
 
+Or match on its typed view returned by [`kind()`](struct.Value.html#method.kind):
+
+```
+# sciter::fake::install();
+use sciter::value::{Value, ValueKind};
+
+let v = Value::from("hello");
+match v.kind() {
+  ValueKind::String(s) => assert_eq!(s, "hello"),
+  other => panic!("unexpected {:?}", other),
+}
+```
+
 Note that there are two functions that convert `Value` to JSON and back:
 
 ```
+# sciter::fake::install();
 use sciter::Value;
 
 let mut v: Value = "[1, 2, 3, 4]".parse().unwrap();
@@ -122,6 +162,7 @@ let json_str = v.into_string();
 Array access:
 
 ```
+# sciter::fake::install();
 use sciter::Value;
 
 let mut v: Value = "[10, 20]".parse().unwrap();
@@ -143,6 +184,7 @@ assert!(v.get(0).is_int());
 Map access:
 
 ```
+# sciter::fake::install();
 use sciter::Value;
 
 let mut v: Value = "{one: 1, two: 2}".parse().unwrap();
@@ -154,6 +196,71 @@ v.set_item("three", 3);
 assert!(v.get_item("one").is_int());
 ```
 
+Both can be changed in place as well:
+
+```
+# sciter::fake::install();
+use sciter::Value;
+
+let mut v: Value = "[1, 2, 3]".parse().unwrap();
+v.insert(0, 0);
+v.retain(|x| x.to_int() != Some(2));
+assert_eq!(v.remove(1), Some(Value::from(1)));
+assert_eq!(v.try_get(5), None);
+
+let mut m: Value = "{one: 1, two: 2}".parse().unwrap();
+m.entry("one").and_modify(|x| *x = Value::from(11));
+m.entry("three").or_insert(3);
+assert_eq!(m.remove_item("two"), Some(Value::from(2)));
+assert_eq!(m.try_get_item("two"), None);
+```
+
+Script objects passed from script can be accessed through their properties and methods:
+
+```
+use sciter::Value;
+
+fn rename(user: &mut Value) -> Result<(), sciter::value::VALUE_RESULT> {
+  if user.class_name().as_deref() == Some("User") && user.has_property("name") {
+    let name: String = user.get_property("name").unwrap_or_default();
+    user.set_property("name", name.to_uppercase())?;
+    user.call_method("save", &[])?;
+  }
+  Ok(())
+}
+```
+
+`==` compares values by the engine rules (`ValueCompare`), while sorting and hashing use a structural order,
+see [`structural_cmp`](struct.Value.html#method.structural_cmp) and [`Structural`](struct.Structural.html):
+
+```
+# sciter::fake::install();
+use sciter::Value;
+use sciter::value::Structural;
+use std::collections::HashSet;
+
+let mut v: Value = "[3, 1, 2, 1]".parse().unwrap();
+v.sort();
+assert_eq!(v, "[1, 1, 2, 3]".parse().unwrap());
+
+let unique: HashSet<Structural> = v.values().map(Structural).collect();
+assert_eq!(unique.len(), 3);
+```
+
+A `Value` may refer to script objects, functions or DOM elements, which must stay on the UI thread.
+To pass data to other threads, make a deep copy of it with [`to_owned_data()`](struct.Value.html#method.to_owned_data):
+
+```
+# sciter::fake::install();
+use sciter::value::{OwnedValue, Value};
+
+let v: Value = "{id: 1, tags: ['a', 'b']}".parse().unwrap();
+let data: OwnedValue = v.to_owned_data().unwrap();
+
+let copy = std::thread::spawn(move || data).join().unwrap();
+assert_eq!(Value::from(copy), v);
+```
+
 .
 */
 
@@ -161,12 +268,41 @@ use ::{_API};
 
 use capi::sctypes::*;
 use capi::scvalue::{VALUE_UNIT_TYPE_STRING, VALUE_UNIT_TYPE_OBJECT, VALUE_UNIT_UNDEFINED};
-pub use capi::scvalue::{VALUE_RESULT, VALUE_STRING_CVT_TYPE, VALUE_TYPE};
+pub use capi::scvalue::{VALUE_RESULT, VALUE_STRING_CVT_TYPE, VALUE_TYPE, VALUE_UNIT_TYPE_DATE, VALUE_UNIT_TYPE_LENGTH};
 use capi::scvalue::VALUE;
 use ::om::IAsset;
 
+use std::convert::TryFrom;
+
+
+/// The largest integer which script numbers (`f64`) represent exactly, `2^53 - 1`.
+pub const MAX_SAFE_INTEGER: i64 = (1 << 53) - 1;
+
+// Date values are stored in 100ns intervals.
+#[cfg(any(feature = "chrono", feature = "time"))]
+const TICKS_PER_SECOND: i64 = 10_000_000;
+
+// Date ticks clamped to the `i64` range, which spans about 29 000 years around 1970.
+fn saturating_ticks(ticks: i128) -> i64 {
+	i64::try_from(ticks).unwrap_or(if ticks < 0 { i64::MIN } else { i64::MAX })
+}
 
-// TODO: `get`, `get_item` methods should return `Option<Value>`
+/// What to do with 64-bit integers beyond [`MAX_SAFE_INTEGER`](constant.MAX_SAFE_INTEGER.html).
+///
+/// Script numbers are 32-bit integers or doubles, so such values can not be stored as plain numbers without a loss.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum IntOverflow {
+	/// Store them as script `BigInt` values (lossless, but `typeof` is `"bigint"` then).
+	///
+	/// Only Sciter.JS has big integers: TIScript reads the same `T_CURRENCY` type as currency,
+	/// see [`Value::big_int`](struct.Value.html#method.big_int).
+	BigInt,
+	/// Round them to the nearest float.
+	#[default]
+	Float,
+	/// Fail the conversion.
+	Error,
+}
 
 /// `sciter::value` wrapper.
 ///
@@ -178,6 +314,8 @@ pub struct Value
 }
 
 /// `sciter::Value` can be transferred across thread boundaries.
+///
+/// Only data values are safe to use there, see [`to_owned_data()`](struct.Value.html#method.to_owned_data) for a checked copy.
 unsafe impl Send for Value {}
 
 impl Value {
@@ -250,7 +388,111 @@ impl Value {
 		return me;
 	}
 
+	/// Make Sciter [date](https://sciter.com/docs/content/script/Date.htm) value, in 100ns intervals since the Unix epoch.
+	///
+	/// The `flags` are [`VALUE_UNIT_TYPE_DATE`](enum.VALUE_UNIT_TYPE_DATE.html) values, e.g. `DT_HAS_DATE | DT_UTC`;
+	/// without `DT_UTC` the date is treated as a local one.
+	pub fn date<F: Into<UINT>>(ticks: i64, flags: F) -> Value {
+		let mut me = Value::new();
+		(_API.ValueInt64DataSet)(me.as_ptr(), ticks, VALUE_TYPE::T_DATE as u32, flags.into());
+		return me;
+	}
+
+	/// Make Sciter [length](https://sciter.com/docs/content/css/units.htm) value.
+	pub fn length(val: Length) -> Value {
+		let mut me = Value::new();
+		(_API.ValueFloatDataSet)(me.as_ptr(), val.value, VALUE_TYPE::T_LENGTH as u32, val.unit.to_raw());
+		return me;
+	}
+
+	/// Make Sciter currency value.
+	///
+	/// Sciter.JS uses the same type for [`big_int`](#method.big_int) values,
+	/// so which one it is depends on the script engine.
+	pub fn currency(val: Currency) -> Value {
+		let mut me = Value::new();
+		(_API.ValueInt64DataSet)(me.as_ptr(), val.raw(), VALUE_TYPE::T_CURRENCY as u32, 0);
+		return me;
+	}
+
+	/// Make a script [BigInt](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/BigInt) value.
+	///
+	/// Sciter.JS stores 64-bit integers in the `T_CURRENCY` slot, so `is_currency()` is `true` for them as well
+	/// and TIScript sees them as currency values (`val / 10000`). Read them back with [`to_big_int`](#method.to_big_int).
+	pub fn big_int(val: i64) -> Value {
+		let mut me = Value::new();
+		(_API.ValueInt64DataSet)(me.as_ptr(), val, VALUE_TYPE::T_CURRENCY as u32, 0);
+		return me;
+	}
+
+	/// Make a number from a 64-bit integer.
+	///
+	/// Integers in the `i32` range become `T_INT` values and the ones up to [`MAX_SAFE_INTEGER`](constant.MAX_SAFE_INTEGER.html)
+	/// are stored exactly as floats. Larger values are handled according to the given [`IntOverflow`](enum.IntOverflow.html) policy,
+	/// `None` is returned for `IntOverflow::Error`.
+	pub fn from_i64(val: i64, overflow: IntOverflow) -> Option<Value> {
+		if let Ok(i) = i32::try_from(val) {
+			return Some(Value::from(i));
+		}
+		if val.unsigned_abs() <= MAX_SAFE_INTEGER as u64 {
+			return Some(Value::from(val as f64));
+		}
+		match overflow {
+			IntOverflow::BigInt => Some(Value::big_int(val)),
+			IntOverflow::Float => Some(Value::from(val as f64)),
+			IntOverflow::Error => None,
+		}
+	}
+
+	/// Make a number from an unsigned 64-bit integer.
+	///
+	/// Same as [`from_i64`](#method.from_i64), but values above `i64::MAX` do not fit into a big integer,
+	/// so they are rejected by the `IntOverflow::BigInt` policy too.
+	pub fn from_u64(val: u64, overflow: IntOverflow) -> Option<Value> {
+		match i64::try_from(val) {
+			Ok(i) => Value::from_i64(i, overflow),
+			Err(_) if overflow == IntOverflow::Float => Some(Value::from(val as f64)),
+			Err(_) => None,
+		}
+	}
+
+	/// Make a native function with typed arguments, callable from script.
+	///
+	/// The script arguments are converted via [`FromValue`](trait.FromValue.html) and their count is checked,
+	/// a [`Rest`](struct.Rest.html) parameter at the end collects the remaining arguments.
+	/// Errors are returned to script as error values, worded like the ones of
+	/// [`dispatch_script_call!`](../macro.dispatch_script_call.html).
+	///
+	/// The function may keep mutable state, so it can not be reentered: a call made while
+	/// the function is still running (e.g. from script it has called) returns an error instead.
+	/// Use `Value::from` for a function taking the raw `&[Value]` slice.
+	///
+	/// ```
+	/// # sciter::fake::install();
+	/// use sciter::value::{Rest, Value};
+	///
+	/// let mut calls = 0;
+	/// let repeat = Value::function(move |s: String, n: usize| { calls += 1; s.repeat(n) });
+	/// assert_eq!(repeat.call(None, &[Value::from("ab"), Value::from(2)], None), Ok(Value::from("abab")));
+	///
+	/// let sum = Value::function(|first: i32, rest: Rest<i32>| first + rest.iter().sum::<i32>());
+	/// let args: Value = vec![1, 2, 3].into_iter().collect();
+	/// assert_eq!(sum.call(None, &args.values().collect::<Vec<_>>(), None), Ok(Value::from(6)));
+	/// ```
+	pub fn function<F, Args>(f: F) -> Value
+	where
+		F: NativeFunction<Args> + 'static,
+	{
+		let f = ::std::cell::RefCell::new(f);
+		Value::from(move |args: &[Value]| match f.try_borrow_mut() {
+			Ok(mut f) => f.invoke("function", args),
+			Err(_) => Value::error("function error: recursive calls are not supported."),
+		})
+	}
+
 	/// Parse a json string into value. Returns the number of chars left unparsed in case of error.
+	///
+	/// See also [`json::parse`](../json/fn.parse.html) which does not need the engine and reports the error location.
 	pub fn parse(val: &str) -> Result<Value, usize> {
 		return Value::parse_as(val, VALUE_STRING_CVT_TYPE::JSON_LITERAL);
 	}
@@ -305,6 +547,96 @@ impl Value {
 		return (self.data.t, self.data.u);
 	}
 
+	/// Typed view of the value for exhaustive `match`, see [`ValueKind`](enum.ValueKind.html).
+	///
+	/// Scalars are copied out, strings are converted, and reference types borrow the value itself.
+	pub fn kind(&self) -> ValueKind<'_> {
+		use capi::scvalue::VALUE_TYPE::*;
+		match self.data.t {
+			T_UNDEFINED if self.is_nothing() => ValueKind::Nothing,
+			T_UNDEFINED => ValueKind::Undefined,
+			T_NULL => ValueKind::Null,
+			T_BOOL => ValueKind::Bool(self.to_bool().unwrap_or_default()),
+			T_INT => ValueKind::Int(self.to_int().unwrap_or_default()),
+			T_FLOAT => ValueKind::Float(self.to_float().unwrap_or_default()),
+			T_STRING => {
+				let s = self.as_string().unwrap_or_default();
+				if self.is_symbol() {
+					ValueKind::Symbol(s)
+				} else if self.is_error_string() {
+					ValueKind::Error(s)
+				} else {
+					ValueKind::String(s)
+				}
+			},
+			T_BYTES => ValueKind::Bytes(self.as_bytes().unwrap_or_default()),
+			T_DATE => ValueKind::Date { ticks: self.to_date().unwrap_or_default(), flags: self.data.u },
+			T_CURRENCY => ValueKind::Currency(self.to_currency().unwrap_or_default()),
+			T_LENGTH => match self.to_length() {
+				Some(length) => ValueKind::Length(length),
+				None => ValueKind::Other(self),
+			},
+			T_DURATION => ValueKind::Duration(self.to_duration().unwrap_or_default()),
+			T_ANGLE => ValueKind::Angle(self.to_angle().unwrap_or_default()),
+			T_COLOR => ValueKind::Color(self.to_color().unwrap_or_default()),
+			T_ARRAY => ValueKind::Array(self),
+			T_MAP => ValueKind::Map(self),
+			T_FUNCTION => ValueKind::Function(self),
+			T_OBJECT => ValueKind::Object(self),
+			T_DOM_OBJECT => ValueKind::Element(self),
+			T_ASSET => ValueKind::Asset(self),
+			T_RESOURCE | T_RANGE | T_ENUM | T_UNKNOWN => ValueKind::Other(self),
+		}
+	}
+
+	/// Deep copy of the value data, which can be sent to other threads, see [`OwnedValue`](enum.OwnedValue.html).
+	///
+	/// Script arrays and objects are copied as arrays and maps.
+	/// Fails on functions, DOM elements, assets and other values that can not leave the UI thread.
+	pub fn to_owned_data(&self) -> Result<OwnedValue, ConversionError> {
+		let data = match self.kind() {
+			ValueKind::Undefined => OwnedValue::Undefined,
+			ValueKind::Nothing => OwnedValue::Nothing,
+			ValueKind::Null => OwnedValue::Null,
+			ValueKind::Bool(b) => OwnedValue::Bool(b),
+			ValueKind::Int(i) => OwnedValue::Int(i),
+			ValueKind::Float(f) => OwnedValue::Float(f),
+			ValueKind::Currency(c) => OwnedValue::Currency(c),
+			ValueKind::String(s) => OwnedValue::String(s),
+			ValueKind::Symbol(s) => OwnedValue::Symbol(s),
+			ValueKind::Error(s) => OwnedValue::Error(s),
+			ValueKind::Bytes(b) => OwnedValue::Bytes(b.to_vec()),
+			ValueKind::Date { ticks, flags } => OwnedValue::Date { ticks, flags },
+			ValueKind::Length(l) => OwnedValue::Length(l),
+			ValueKind::Duration(d) => OwnedValue::Duration(d),
+			ValueKind::Angle(a) => OwnedValue::Angle(a),
+			ValueKind::Color(c) => OwnedValue::Color(c),
+			ValueKind::Array(v) => v.to_owned_items()?,
+			ValueKind::Object(v) if v.is_object_array() => v.to_owned_items()?,
+			ValueKind::Map(v) => v.to_owned_pairs()?,
+			ValueKind::Object(v) if v.is_object_map() => v.to_owned_pairs()?,
+			_ => return Err(ConversionError::new("data value", self)),
+		};
+		Ok(data)
+	}
+
+	fn to_owned_items(&self) -> Result<OwnedValue, ConversionError> {
+		let mut items = Vec::with_capacity(self.len());
+		for (i, item) in self.values().enumerate() {
+			items.push(item.to_owned_data().map_err(|e| e.at_index(i))?);
+		}
+		Ok(OwnedValue::Array(items))
+	}
+
+	fn to_owned_pairs(&self) -> Result<OwnedValue, ConversionError> {
+		let mut items = Vec::with_capacity(self.len());
+		for (key, item) in self.items() {
+			let item = item.to_owned_data().map_err(|e| e.at_key(&key))?;
+			items.push((key.to_owned_data()?, item));
+		}
+		Ok(OwnedValue::Map(items))
+	}
+
 	/// Convert `T_OBJECT` value type to JSON `T_MAP` or `T_ARRAY` types.
 	///
 	/// Also must be used if you need to pass values between different threads.
@@ -429,6 +761,262 @@ impl Value {
 		return result;
 	}
 
+	/// Retrieve the sub-element at `index` if there is one, see [`get`](#method.get).
+	pub fn try_get(&self, index: usize) -> Option<Value> {
+		if index < self.len() {
+			Some(self.get(index))
+		} else {
+			None
+		}
+	}
+
+	/// Retrieve the value of a sub-element by key if the key is present, see [`get_item`](#method.get_item).
+	///
+	/// Unlike `get_item`, it tells missing keys from the ones holding `undefined`.
+	pub fn try_get_item<T: Into<Value>>(&self, key: T) -> Option<Value> {
+		let key = key.into();
+		if self.is_array() {
+			return key.to_int().and_then(|i| usize::try_from(i).ok()).and_then(|i| self.try_get(i));
+		}
+		let v = self.get_item(key.clone());
+		if !v.is_undefined() || self.keys().any(|k| Value::is_same_key(&k, &key)) {
+			Some(v)
+		} else {
+			None
+		}
+	}
+
+	/// Get the given key of the `T_MAP` value for in-place manipulation, see [`Entry`](struct.Entry.html).
+	pub fn entry<T: Into<Value>>(&mut self, key: T) -> Entry<'_> {
+		Entry {
+			map: self,
+			key: key.into(),
+		}
+	}
+
+	/// Remove the key from the `T_MAP` value, returning its value if the key was present.
+	///
+	/// Sciter can not shrink containers in place, so `self` is replaced with a new map without that key.
+	/// This detaches it from the other copies of the value (including the one stored in a parent container):
+	/// they keep the old contents, so store the result back with [`set_item`](#method.set_item) if needed.
+	///
+	/// Script objects (`T_OBJECT`) are left untouched, as rebuilding would turn them into plain maps,
+	/// so it returns `None` for them.
+	///
+	/// ```
+	/// # #[macro_use] extern crate sciter;
+	/// # use sciter::Value;
+	/// # fn main() {
+	/// # sciter::fake::install();
+	/// let mut parent = vmap! { "child" => vmap! { "a" => 1, "b" => 2 } };
+	/// let mut child = parent.get_item("child");
+	/// child.remove_item("a");
+	/// assert_eq!(parent.get_item("child").len(), 2);
+	///
+	/// parent.set_item("child", child);
+	/// assert_eq!(parent.get_item("child"), vmap! { "b" => 2 });
+	/// # }
+	/// ```
+	pub fn remove_item<T: Into<Value>>(&mut self, key: T) -> Option<Value> {
+		let key = key.into();
+		let mut removed = None;
+		self.rebuild_map(|k, v| {
+			if removed.is_none() && Value::is_same_key(k, &key) {
+				removed = Some(v.clone());
+				return false;
+			}
+			true
+		});
+		removed
+	}
+
+	/// Retain only the key/value pairs of the `T_MAP` value specified by the predicate.
+	///
+	/// The map is rebuilt and detached from its copies if anything gets removed, see [`remove_item`](#method.remove_item).
+	///
+	/// Returns `false` if the value is not a `T_MAP` (e.g. a script object) and was left untouched.
+	pub fn retain_items<F: FnMut(&Value, &Value) -> bool>(&mut self, keep: F) -> bool {
+		self.rebuild_map(keep)
+	}
+
+	/// Insert the value at `index` of the `T_ARRAY` value, shifting all elements after it to the right.
+	///
+	/// # Panics
+	///
+	/// Panics if `index > len`.
+	pub fn insert<T: Into<Value>>(&mut self, index: usize, src: T) {
+		let len = self.len();
+		assert!(index <= len, "insertion index (is {}) should be <= len (is {})", index, len);
+		for i in (index..len).rev() {
+			let item = self.get(i);
+			self.set(i + 1, item);
+		}
+		self.set(index, src);
+	}
+
+	/// Remove and return the element at `index` of the `T_ARRAY` value, shifting all elements after it to the left.
+	///
+	/// Sciter can not shrink containers in place, so `self` is replaced with a new array without that element,
+	/// detached from the other copies of the value, see [`remove_item`](#method.remove_item).
+	/// Script arrays (`T_OBJECT`) are left untouched, so it returns `None` for them.
+	pub fn remove(&mut self, index: usize) -> Option<Value> {
+		let removed = self.try_get(index).filter(|_| self.is_array())?;
+		self.rebuild_array(|i, _| i != index);
+		Some(removed)
+	}
+
+	/// Shorten the `T_ARRAY` value to `len` elements.
+	///
+	/// The array is rebuilt and detached from its copies if it is longer, see [`remove`](#method.remove).
+	///
+	/// Returns `false` if the value is not a `T_ARRAY` (e.g. a script array) and was left untouched.
+	pub fn truncate(&mut self, len: usize) -> bool {
+		self.rebuild_array(|i, _| i < len)
+	}
+
+	/// Retain only the elements of the `T_ARRAY` value specified by the predicate.
+	///
+	/// The array is rebuilt and detached from its copies if anything gets removed, see [`remove`](#method.remove).
+	///
+	/// Returns `false` if the value is not a `T_ARRAY` (e.g. a script array) and was left untouched.
+	pub fn retain<F: FnMut(&Value) -> bool>(&mut self, mut keep: F) -> bool {
+		self.rebuild_array(|_, v| keep(v))
+	}
+
+	/// A mutable iterator over all values, see [`values`](#method.values).
+	///
+	/// Each element is a copy which is stored back when the yielded [`ItemMut`](struct.ItemMut.html) is dropped.
+	pub fn values_mut(&mut self) -> ValuesMut<'_> {
+		ValuesMut {
+			count: self.len(),
+			base: self.as_ptr(),
+			index: 0,
+			_marker: ::std::marker::PhantomData,
+		}
+	}
+
+	/// A mutable iterator over all key-value pairs of the `T_MAP` value, see [`items`](#method.items).
+	///
+	/// The values are stored back by their keys when the yielded [`ItemMut`](struct.ItemMut.html) is dropped.
+	pub fn iter_mut(&mut self) -> ItemsMut<'_> {
+		ItemsMut {
+			keys: self.keys().collect::<Vec<_>>().into_iter(),
+			base: self.as_ptr(),
+			_marker: ::std::marker::PhantomData,
+		}
+	}
+
+	/// Sort the `T_ARRAY` value in place with the comparator function.
+	///
+	/// The sort is stable; Sciter arrays are updated element by element, so other copies of this value see the new order.
+	pub fn sort_by<F: FnMut(&Value, &Value) -> ::std::cmp::Ordering>(&mut self, compare: F) {
+		if !self.is_array() {
+			return;
+		}
+		let mut items: Vec<Value> = self.values().collect();
+		items.sort_by(compare);
+		for (i, v) in items.into_iter().enumerate() {
+			self.set(i, v);
+		}
+	}
+
+	/// Sort the `T_ARRAY` value in place by the [`structural_cmp`](#method.structural_cmp) order.
+	pub fn sort(&mut self) {
+		self.sort_by(Value::structural_cmp);
+	}
+
+	/// Sort the `T_ARRAY` value in place with the key extraction function.
+	pub fn sort_by_key<K: Ord, F: FnMut(&Value) -> K>(&mut self, mut key: F) {
+		self.sort_by(|a, b| key(a).cmp(&key(b)));
+	}
+
+	/// Total structural order: by value type, then by its units, then by content.
+	///
+	/// Floats are ordered by [`f64::total_cmp`](https://doc.rust-lang.org/std/primitive.f64.html#method.total_cmp),
+	/// arrays lexicographically and maps by their key-value pairs sorted by keys, so the insertion order does not matter.
+	/// Script objects, functions, DOM elements and assets are compared by identity.
+	///
+	/// Unlike `==`, which asks the engine, it does not depend on the script runtime,
+	/// see [`Structural`](struct.Structural.html) for the `Ord` and `Hash` implementations.
+	pub fn structural_cmp(&self, other: &Value) -> ::std::cmp::Ordering {
+		let (a, b) = (self.kind(), other.kind());
+		(self.data.t as UINT).cmp(&(other.data.t as UINT))
+			.then(self.data.u.cmp(&other.data.u))
+			.then_with(|| match (a, b) {
+				(ValueKind::Bool(a), ValueKind::Bool(b)) => a.cmp(&b),
+				(ValueKind::Int(a), ValueKind::Int(b)) => a.cmp(&b),
+				(ValueKind::Currency(a), ValueKind::Currency(b)) => a.cmp(&b),
+				(ValueKind::Float(a), ValueKind::Float(b))
+				| (ValueKind::Duration(a), ValueKind::Duration(b))
+				| (ValueKind::Angle(a), ValueKind::Angle(b)) => a.total_cmp(&b),
+				(ValueKind::Length(a), ValueKind::Length(b)) => a.value.total_cmp(&b.value),
+				(ValueKind::String(a), ValueKind::String(b))
+				| (ValueKind::Symbol(a), ValueKind::Symbol(b))
+				| (ValueKind::Error(a), ValueKind::Error(b)) => a.cmp(&b),
+				(ValueKind::Bytes(a), ValueKind::Bytes(b)) => a.cmp(b),
+				(ValueKind::Date { ticks: a, .. }, ValueKind::Date { ticks: b, .. }) => a.cmp(&b),
+				(ValueKind::Color(a), ValueKind::Color(b)) => a.cmp(&b),
+				(ValueKind::Array(a), ValueKind::Array(b)) => a.values().map(Structural).cmp(b.values().map(Structural)),
+				(ValueKind::Map(a), ValueKind::Map(b)) => a.sorted_items().cmp(&b.sorted_items()),
+				_ => self.data.d.cmp(&other.data.d),
+			})
+	}
+
+	/// Whether the values are equal structurally, see [`structural_cmp`](#method.structural_cmp).
+	pub fn structural_eq(&self, other: &Value) -> bool {
+		self.structural_cmp(other) == ::std::cmp::Ordering::Equal
+	}
+
+	// Key-value pairs of the `T_MAP` value sorted by keys, for comparison and hashing.
+	fn sorted_items(&self) -> Vec<(Structural, Structural)> {
+		let mut items: Vec<_> = self.items().into_iter().map(|(k, v)| (Structural(k), Structural(v))).collect();
+		items.sort();
+		items
+	}
+
+	// Sciter has no API to remove sub-elements, so replace `self` with a filtered copy.
+	// Only plain arrays and maps: a filtered copy of a script object would lose its class.
+	/// Whether the map keys are the same: strings and symbols (e.g. unquoted keys of a literal) match by text.
+	fn is_same_key(a: &Value, b: &Value) -> bool {
+		if a.is_string() && b.is_string() {
+			a.as_string() == b.as_string()
+		} else {
+			a == b
+		}
+	}
+
+	/// Rebuild the `T_ARRAY` value with the kept elements, `false` for other values.
+	fn rebuild_array<F: FnMut(usize, &Value) -> bool>(&mut self, mut keep: F) -> bool {
+		if !self.is_array() {
+			return false;
+		}
+		let items: Vec<Value> = self.values().collect();
+		let count = items.len();
+		let kept: Value = items.into_iter().enumerate().filter(|&(i, ref v)| keep(i, v)).map(|(_, v)| v).collect();
+		if kept.len() != count {
+			*self = kept;
+		}
+		true
+	}
+
+	/// Rebuild the `T_MAP` value with the kept pairs, `false` for other values.
+	fn rebuild_map<F: FnMut(&Value, &Value) -> bool>(&mut self, mut keep: F) -> bool {
+		if !self.is_map() {
+			return false;
+		}
+		let items = self.items();
+		let mut kept = Value::map();
+		for (k, v) in items.iter() {
+			if keep(k, v) {
+				kept.set_item(k.clone(), v.clone());
+			}
+		}
+		if kept.len() != items.len() {
+			*self = kept;
+		}
+		true
+	}
+
 	/// Value to integer.
 	pub fn to_int(&self) -> Option<i32> {
 		let mut val = 0i32;
@@ -438,6 +1026,38 @@ impl Value {
 		}
 	}
 
+	/// Value to 64-bit integer.
+	///
+	/// Accepts integers and floats without a fractional part which fit into `i64`.
+	/// Currency and big integers share a type and are not numbers in this sense, see [`to_big_int`](#method.to_big_int).
+	pub fn to_i64(&self) -> Option<i64> {
+		if self.is_int() {
+			return self.to_int().map(i64::from);
+		}
+		match self.to_float() {
+			// `i64::MAX as f64` is 2^63 which is out of range already
+			Some(x) if self.is_float() && x.fract() == 0.0 && x >= i64::MIN as f64 && x < i64::MAX as f64 => Some(x as i64),
+			_ => None
+		}
+	}
+
+	/// Value to unsigned 64-bit integer.
+	///
+	/// Accepts non-negative integers and floats without a fractional part which fit into `u64`.
+	pub fn to_u64(&self) -> Option<u64> {
+		match self.to_float() {
+			Some(x) if self.is_float() && x.fract() == 0.0 && x >= i64::MAX as f64 && x < u64::MAX as f64 => Some(x as u64),
+			_ => self.to_i64().and_then(|i| u64::try_from(i).ok()),
+		}
+	}
+
+	/// Value to big integer, see [`big_int`](#method.big_int).
+	///
+	/// Returns the raw 64-bit value of the `T_CURRENCY` type, which is a currency in TIScript.
+	pub fn to_big_int(&self) -> Option<i64> {
+		self.to_currency().map(|c| c.raw())
+	}
+
 	/// Value to bool.
 	pub fn to_bool(&self) -> Option<bool> {
 		let mut val = 0i32;
@@ -483,6 +1103,61 @@ impl Value {
 		}
 	}
 
+	/// Value to length.
+	pub fn to_length(&self) -> Option<Length> {
+		let unit = LengthUnit::from_raw(self.data.u).filter(|_| self.is_length())?;
+		let mut val = 0f64;
+		match (_API.ValueFloatData)(self.as_cptr(), &mut val) {
+			VALUE_RESULT::OK => Some(Length::new(val, unit)),
+			_ => None
+		}
+	}
+
+	/// Value to currency.
+	///
+	/// Sciter.JS big integers share the type and come out as their value in 1/10000 units,
+	/// use [`to_big_int`](#method.to_big_int) to read them.
+	pub fn to_currency(&self) -> Option<Currency> {
+		if !self.is_currency() {
+			return None;
+		}
+		let mut val = 0i64;
+		match (_API.ValueInt64Data)(self.as_cptr(), &mut val) {
+			VALUE_RESULT::OK => Some(Currency::from_raw(val)),
+			_ => None
+		}
+	}
+
+	/// Value to date, in 100ns intervals since the Unix epoch.
+	pub fn to_date(&self) -> Option<i64> {
+		if !self.is_date() {
+			return None;
+		}
+		let mut val = 0i64;
+		match (_API.ValueInt64Data)(self.as_cptr(), &mut val) {
+			VALUE_RESULT::OK => Some(val),
+			_ => None
+		}
+	}
+
+	/// Date value to `SystemTime`.
+	///
+	/// Local dates (without the `DT_UTC` flag) are converted as if they were UTC.
+	pub fn to_system_time(&self) -> Option<::std::time::SystemTime> {
+		let ticks = self.to_date()?;
+		let since = ::std::time::Duration::from_nanos(ticks.unsigned_abs().checked_mul(100)?);
+		if ticks >= 0 {
+			::std::time::UNIX_EPOCH.checked_add(since)
+		} else {
+			::std::time::UNIX_EPOCH.checked_sub(since)
+		}
+	}
+
+	/// Whether the date value has the given flag set.
+	pub fn has_date_flag(&self, flag: VALUE_UNIT_TYPE_DATE) -> bool {
+		self.is_date() && self.data.u & flag as UINT != 0
+	}
+
 	/// Value as string for `T_STRING` type.
 	pub fn as_string(&self) -> Option<String> {
 		let mut s = 0 as LPCWSTR;
@@ -535,6 +1210,85 @@ impl Value {
 		}
 	}
 
+	/// Get the property of the `T_OBJECT` (or `T_MAP`) value converted to the given type.
+	///
+	/// Missing properties are `undefined`, so use `Option<T>` for optional ones.
+	pub fn get_property<T: FromValue>(&self, name: &str) -> Result<T, ConversionError> {
+		let key = Value::from(name);
+		T::try_from_value(&self.get_item(key.clone())).map_err(|e| e.at_key(&key))
+	}
+
+	/// Set the property of the `T_OBJECT` (or `T_MAP`) value.
+	///
+	/// Unlike [`set_item`](#method.set_item), it never converts other value types to a map.
+	pub fn set_property<T: Into<Value>>(&mut self, name: &str, value: T) -> Result<(), VALUE_RESULT> {
+		if !self.is_object() && !self.is_map() {
+			return Err(VALUE_RESULT::INCOMPATIBLE_TYPE);
+		}
+		match (_API.ValueSetValueToKey)(self.as_ptr(), Value::from(name).as_cptr(), value.into().as_cptr()) {
+			VALUE_RESULT::OK => Ok(()),
+			ok => Err(ok),
+		}
+	}
+
+	/// Whether the `T_OBJECT` (or `T_MAP`) value has its own property with the given name.
+	pub fn has_property(&self, name: &str) -> bool {
+		let mut found = false;
+		self.enum_properties(|key, _| {
+			found = key.as_string().as_deref() == Some(name);
+			!found
+		});
+		found
+	}
+
+	/// Own properties of the `T_OBJECT` (or `T_MAP`) value with their names, in the order of definition.
+	///
+	/// Keys that are not strings or symbols are converted to strings.
+	pub fn properties(&self) -> Vec<(String, Value)> {
+		let mut result = Vec::with_capacity(self.len());
+		self.enum_properties(|key, value| {
+			let name = key.as_string().unwrap_or_else(|| key.to_string());
+			result.push((name, value.clone()));
+			true
+		});
+		result
+	}
+
+	/// Call the method of the `T_OBJECT` value with the object bound as `this`.
+	///
+	/// Fails with `INCOMPATIBLE_TYPE` if there is no such method.
+	pub fn call_method(&self, name: &str, args: &[Value]) -> Result<Value, VALUE_RESULT> {
+		let method = self.get_item(name);
+		if !method.is_vfunction() {
+			return Err(VALUE_RESULT::INCOMPATIBLE_TYPE);
+		}
+		method.call(Some(self.clone()), args, Some(name))
+	}
+
+	/// Class name of the `T_OBJECT` value, i.e. `constructor.name` in script.
+	pub fn class_name(&self) -> Option<String> {
+		if !self.is_object() {
+			return None;
+		}
+		self.get_item("constructor").get_item("name").as_string().filter(|name| !name.is_empty())
+	}
+
+	/// Enumerate the key-value pairs via `ValueEnumElements` until `f` returns `false`.
+	fn enum_properties<F: FnMut(&Value, &Value) -> bool>(&self, mut f: F) {
+		type Callback<'a> = &'a mut dyn FnMut(&Value, &Value) -> bool;
+
+		extern "system" fn on_pair(param: LPVOID, pkey: *const VALUE, pval: *const VALUE) -> BOOL {
+			assert!(!param.is_null());
+			let f = unsafe { &mut *(param as *mut Callback) };
+			let (key, value) = unsafe { (Value::copy_from(pkey), Value::copy_from(pval)) };
+			f(&key, &value) as BOOL
+		}
+
+		let mut callback: Callback = &mut f;
+		let ptr = &mut callback as *mut Callback;
+		(_API.ValueEnumElements)(self.as_cptr(), on_pair, ptr as LPVOID);
+	}
+
 	#[doc(hidden)]
 	pub fn pack_to(&self, dst: &mut VALUE) {
 		(_API.ValueCopy)(dst, self.as_cptr());
@@ -641,6 +1395,10 @@ impl Value {
 		self.data.t as u32 == VALUE_TYPE::T_CURRENCY as u32
 	}
 	#[allow(missing_docs)]
+	pub const fn is_length(&self) -> bool {
+		self.data.t as u32 == VALUE_TYPE::T_LENGTH as u32
+	}
+	#[allow(missing_docs)]
 	pub const fn is_color(&self) -> bool {
 		self.data.t as u32 == VALUE_TYPE::T_COLOR as u32
 	}
@@ -849,14 +1607,68 @@ impl ::std::cmp::PartialEq for Value {
 	}
 }
 
-/// Get item by index for array type.
-impl ::std::ops::Index<usize> for Value {
-	type Output = Value;
-	fn index(&self, index: usize) -> &Self::Output {
-		let tmp = self.ensure_tmp_mut();
-		(_API.ValueNthElementValue)(self.as_cptr(), index as INT, tmp.as_ptr());
-		return tmp;
-	}
+/// Value compared, ordered and hashed structurally, e.g. for `HashSet` or `BTreeMap` keys.
+///
+/// See [`Value::structural_cmp`](struct.Value.html#method.structural_cmp) for the order.
+#[derive(Clone, Debug)]
+pub struct Structural(pub Value);
+
+impl ::std::cmp::PartialEq for Structural {
+	fn eq(&self, other: &Self) -> bool {
+		self.0.structural_eq(&other.0)
+	}
+}
+
+impl ::std::cmp::Eq for Structural {}
+
+impl ::std::cmp::PartialOrd for Structural {
+	fn partial_cmp(&self, other: &Self) -> Option<::std::cmp::Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl ::std::cmp::Ord for Structural {
+	fn cmp(&self, other: &Self) -> ::std::cmp::Ordering {
+		self.0.structural_cmp(&other.0)
+	}
+}
+
+impl ::std::hash::Hash for Structural {
+	fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+		let v = &self.0;
+		(v.data.t as UINT).hash(state);
+		v.data.u.hash(state);
+		match v.kind() {
+			ValueKind::Undefined | ValueKind::Nothing | ValueKind::Null => {},
+			ValueKind::Bool(b) => b.hash(state),
+			ValueKind::Int(i) => i.hash(state),
+			ValueKind::Currency(c) => c.hash(state),
+			ValueKind::Float(f) | ValueKind::Duration(f) | ValueKind::Angle(f) => f.to_bits().hash(state),
+			ValueKind::Length(l) => l.value.to_bits().hash(state),
+			ValueKind::String(s) | ValueKind::Symbol(s) | ValueKind::Error(s) => s.hash(state),
+			ValueKind::Bytes(b) => b.hash(state),
+			ValueKind::Date { ticks, .. } => ticks.hash(state),
+			ValueKind::Color(c) => c.hash(state),
+			ValueKind::Array(a) => {
+				state.write_usize(a.len());
+				for item in a.values() {
+					Structural(item).hash(state);
+				}
+			},
+			ValueKind::Map(m) => m.sorted_items().hash(state),
+			_ => v.data.d.hash(state),
+		}
+	}
+}
+
+/// Get item by index for array type.
+impl ::std::ops::Index<usize> for Value {
+	type Output = Value;
+	fn index(&self, index: usize) -> &Self::Output {
+		let tmp = self.ensure_tmp_mut();
+		(_API.ValueNthElementValue)(self.as_cptr(), index as INT, tmp.as_ptr());
+		return tmp;
+	}
 }
 
 /// Set item by index for array type.
@@ -918,8 +1730,6 @@ impl<'a> From<&'a VALUE> for Value {
 
 /// Value from integer.
 impl From<i32> for Value {
-	// Note that there is no generic 64-bit integers at Sciter, only Date/Currency types.
-	// There is a double (f64) for large numbers as a workaround.
 	fn from(val: i32) -> Self {
 		let mut me = Value::new();
 		(_API.ValueIntDataSet)(me.as_ptr(), val, VALUE_TYPE::T_INT as UINT, 0);
@@ -929,8 +1739,6 @@ impl From<i32> for Value {
 
 /// Value from integer.
 impl From<&i32> for Value {
-	// Note that there is no generic 64-bit integers at Sciter, only Date/Currency types.
-	// There is a double (f64) for large numbers as a workaround.
 	fn from(val: &i32) -> Self {
 		let mut me = Value::new();
 		(_API.ValueIntDataSet)(me.as_ptr(), *val, VALUE_TYPE::T_INT as UINT, 0);
@@ -938,6 +1746,30 @@ impl From<&i32> for Value {
 	}
 }
 
+/// Value from 64-bit integer.
+///
+/// Values beyond [`MAX_SAFE_INTEGER`](constant.MAX_SAFE_INTEGER.html) are rounded to the nearest float,
+/// see [`Value::from_i64`](struct.Value.html#method.from_i64) for the other policies.
+impl From<i64> for Value {
+	fn from(val: i64) -> Self {
+		Value::from_i64(val, IntOverflow::Float).unwrap_or_default()
+	}
+}
+
+/// Value from unsigned 64-bit integer, see `From<i64>`.
+impl From<u64> for Value {
+	fn from(val: u64) -> Self {
+		Value::from_u64(val, IntOverflow::Float).unwrap_or_default()
+	}
+}
+
+/// Value from `usize`, see `From<u64>`.
+impl From<usize> for Value {
+	fn from(val: usize) -> Self {
+		Value::from(val as u64)
+	}
+}
+
 /// Value from float.
 impl From<f64> for Value {
 	fn from(val: f64) -> Self {
@@ -1001,6 +1833,10 @@ impl<'a> From<&'a String> for Value {
 }
 
 /// Value from json string.
+///
+/// It is [`Value::parse`](struct.Value.html#method.parse), so the text is parsed by the engine the value lives in
+/// (the `sciter::fake` one does it with [`json::parse`](../json/fn.parse.html), which only mirrors the engine's reader).
+/// Use `json::parse` directly for the engine-independent result and the error location.
 impl ::std::str::FromStr for Value {
 	type Err = VALUE_RESULT;
 	fn from_str(val: &str) -> Result<Self, Self::Err> {
@@ -1017,20 +1853,70 @@ impl<'a> From<&'a [u8]> for Value {
 	}
 }
 
+/// Value from byte string.
+impl From<Bytes> for Value {
+	fn from(val: Bytes) -> Self {
+		Value::from(val.0.as_slice())
+	}
+}
+
 /// Value from time/date.
+///
+/// Times beyond the range of the date value (about 29 000 years before or after 1970)
+/// are clamped to the earliest or the latest date.
 impl From<std::time::SystemTime> for Value {
-	/// See the [VALUE_TIME::T_DATE].
+	/// Makes a UTC [`Value::date`](struct.Value.html#method.date), truncated to 100ns.
 	fn from(val: std::time::SystemTime) -> Self {
-		let mut me = Value::new();
-		if let Ok(epoch) = val.duration_since(std::time::UNIX_EPOCH) {
-			// in 100ns from epoch
-			let ns = epoch.as_nanos() / 100;
+		// in 100ns from epoch
+		let ticks = match val.duration_since(std::time::UNIX_EPOCH) {
+			Ok(after) => i128::try_from(after.as_nanos() / 100).unwrap_or(i128::MAX),
+			Err(before) => i128::try_from(before.duration().as_nanos().div_ceil(100)).map_or(i128::MIN, |t| -t),
+		};
+		Value::date(saturating_ticks(ticks), VALUE_UNIT_TYPE_DATE::DT_UTC)
+	}
+}
 
-			// does not fail because does not check the input
-			// TODO: is SystemTime UTC or not?
-			(_API.ValueInt64DataSet)(me.as_ptr(), ns as i64, VALUE_TYPE::T_DATE as u32, 0);
-		}
-		me
+/// Value from `chrono` date and time, see `From<SystemTime>`.
+#[cfg(feature = "chrono")]
+impl<Tz: chrono::TimeZone> From<chrono::DateTime<Tz>> for Value {
+	fn from(val: chrono::DateTime<Tz>) -> Self {
+		let ticks = i128::from(val.timestamp()) * i128::from(TICKS_PER_SECOND) + i128::from(val.timestamp_subsec_nanos() / 100);
+		Value::date(saturating_ticks(ticks), VALUE_UNIT_TYPE_DATE::DT_UTC)
+	}
+}
+
+/// Value from `chrono` calendar date, stored as UTC midnight with the `DT_HAS_DATE` flag.
+///
+/// Out of range dates are clamped, see `From<SystemTime>`.
+#[cfg(feature = "chrono")]
+impl From<chrono::NaiveDate> for Value {
+	fn from(val: chrono::NaiveDate) -> Self {
+		// 1970-01-01 is the day 719163 of the common era
+		let days = i128::from(chrono::Datelike::num_days_from_ce(&val)) - 719_163;
+		Value::date(saturating_ticks(days * 86_400 * i128::from(TICKS_PER_SECOND)), VALUE_UNIT_TYPE_DATE::DT_HAS_DATE | VALUE_UNIT_TYPE_DATE::DT_UTC)
+	}
+}
+
+/// Value from `time` date and time, see `From<SystemTime>`.
+#[cfg(feature = "time")]
+impl From<time::OffsetDateTime> for Value {
+	fn from(val: time::OffsetDateTime) -> Self {
+		let ticks = val.unix_timestamp_nanos().div_euclid(100);
+		Value::date(saturating_ticks(ticks), VALUE_UNIT_TYPE_DATE::DT_UTC)
+	}
+}
+
+/// Value from length.
+impl From<Length> for Value {
+	fn from(val: Length) -> Self {
+		Value::length(val)
+	}
+}
+
+/// Value from currency.
+impl From<Currency> for Value {
+	fn from(val: Currency) -> Self {
+		Value::currency(val)
 	}
 }
 
@@ -1044,6 +1930,132 @@ impl<T, E> From<Result<T, E>> for Value where T: Into<Value>, E: std::fmt::Displ
 	}
 }
 
+/// Value from [`Option`], `None` becomes `null`.
+impl<T: Into<Value>> From<Option<T>> for Value {
+	fn from(val: Option<T>) -> Self {
+		match val {
+			Some(v) => v.into(),
+			None => Value::null(),
+		}
+	}
+}
+
+macro_rules! value_from_int {
+	($($t:ty => $via:ty),+) => {
+		$(
+			impl From<$t> for Value {
+				fn from(val: $t) -> Self {
+					Value::from(<$via>::from(val))
+				}
+			}
+		)+
+	};
+}
+
+value_from_int!(i8 => i32, i16 => i32, u8 => i32, u16 => i32, u32 => i64);
+
+/// Value from `isize`, see `From<i64>`.
+impl From<isize> for Value {
+	fn from(val: isize) -> Self {
+		Value::from(val as i64)
+	}
+}
+
+/// Value from `i128`, out of the `i64` and `u64` ranges it is rounded to a float.
+impl From<i128> for Value {
+	fn from(val: i128) -> Self {
+		if let Ok(v) = i64::try_from(val) {
+			Value::from(v)
+		} else if let Ok(v) = u64::try_from(val) {
+			Value::from(v)
+		} else {
+			Value::from(val as f64)
+		}
+	}
+}
+
+/// Value from `u128`, out of the `u64` range it is rounded to a float.
+impl From<u128> for Value {
+	fn from(val: u128) -> Self {
+		match u64::try_from(val) {
+			Ok(v) => Value::from(v),
+			Err(_) => Value::from(val as f64),
+		}
+	}
+}
+
+impl From<f32> for Value {
+	fn from(val: f32) -> Self {
+		Value::from(f64::from(val))
+	}
+}
+
+impl From<char> for Value {
+	fn from(val: char) -> Self {
+		Value::from(val.encode_utf8(&mut [0; 4]) as &str)
+	}
+}
+
+impl From<Box<str>> for Value {
+	fn from(val: Box<str>) -> Self {
+		Value::from(&*val)
+	}
+}
+
+/// Value from a path, non-Unicode sequences are replaced with `U+FFFD`.
+impl<'a> From<&'a std::path::Path> for Value {
+	fn from(val: &'a std::path::Path) -> Self {
+		Value::from(&*val.to_string_lossy())
+	}
+}
+
+/// Value from a path, non-Unicode sequences are replaced with `U+FFFD`.
+impl From<std::path::PathBuf> for Value {
+	fn from(val: std::path::PathBuf) -> Self {
+		Value::from(val.as_path())
+	}
+}
+
+/// Array value from a vector; byte strings are made from `&[u8]` instead.
+impl<T: Into<Value>> From<Vec<T>> for Value {
+	fn from(val: Vec<T>) -> Self {
+		let mut me = Value::array(0);
+		me.extend(val);
+		return me;
+	}
+}
+
+/// Array value from a fixed-size array.
+impl<T: Into<Value>, const N: usize> From<[T; N]> for Value {
+	fn from(val: [T; N]) -> Self {
+		let mut me = Value::array(0);
+		me.extend(val);
+		return me;
+	}
+}
+
+/// Map value from a `HashMap`.
+impl<K: Into<Value>, V: Into<Value>, S> From<::std::collections::HashMap<K, V, S>> for Value {
+	fn from(val: ::std::collections::HashMap<K, V, S>) -> Self {
+		let mut me = Value::map();
+		for (k, v) in val {
+			me.set_item(k, v);
+		}
+		return me;
+	}
+}
+
+/// Map value from a `BTreeMap`.
+impl<K: Into<Value>, V: Into<Value>> From<::std::collections::BTreeMap<K, V>> for Value {
+	fn from(val: ::std::collections::BTreeMap<K, V>) -> Self {
+		let mut me = Value::map();
+		for (k, v) in val {
+			me.set_item(k, v);
+		}
+		return me;
+	}
+}
+
 // /// Value from sequence of items satisfying `Into<Value>`.
 // impl ::std::iter::FromIterator<Into<Value>> for Value {
 //   fn from_iter<I: IntoIterator<Item=Into<Value>>>(iterator: I) -> Self {
@@ -1122,7 +2134,10 @@ impl ::std::iter::FromIterator<String> for Value {
 	}
 }
 
-/// Value from function.
+/// Value from function, see also [`Value::function`](struct.Value.html#method.function) for typed arguments.
+///
+/// The function can be called again while it is running (e.g. when it calls back into script),
+/// so it is `Fn`; use `Value::function` for a function with mutable state.
 impl<F, R> From<F> for Value
 where
 	F: Fn(&[Value]) -> R,
@@ -1161,9 +2176,9 @@ where
 	F: Fn(&[Value]) -> R,
 	R: Into<Value>,
 {
-	// reconstruct handler from pointer
-	let ptr = tag as *mut F;
-	let me = unsafe { &mut *ptr };
+	// reconstruct handler from pointer, shared since the calls can be reentrant
+	let ptr = tag as *const F;
+	let me = unsafe { &*ptr };
 	let retval = unsafe { &mut *retval };
 	let args = unsafe { Value::unpack_from(argv, argc) };
 	let rv = me(&args);
@@ -1172,10 +2187,122 @@ where
 }
 
 
+/// Native function with typed arguments, see [`Value::function`](struct.Value.html#method.function).
+///
+/// Implemented for `FnMut` closures of up to 10 [`ScriptArg`](trait.ScriptArg.html) parameters
+/// returning anything convertible to `Value`.
+pub trait NativeFunction<Args> {
+	/// Convert the arguments and call the function, `name` is used in the error messages.
+	fn invoke(&mut self, name: &str, args: &[Value]) -> Value;
+}
+
+/// Parameter of a [`NativeFunction`](trait.NativeFunction.html).
+///
+/// Implemented for all [`FromValue`](trait.FromValue.html) types and for [`Rest`](struct.Rest.html).
+pub trait ScriptArg: Sized {
+	#[doc(hidden)]
+	const VARIADIC: bool = false;
+
+	/// Convert the argument from the beginning of `args`; variadic parameters take all of them.
+	fn take(args: &[Value]) -> Result<Self, ConversionError>;
+}
+
+impl<T: FromValue> ScriptArg for T {
+	fn take(args: &[Value]) -> Result<Self, ConversionError> {
+		match args.first() {
+			Some(arg) => T::try_from_value(arg),
+			None => T::try_from_value(&Value::new()),
+		}
+	}
+}
+
+/// The remaining arguments of a [`NativeFunction`](trait.NativeFunction.html), must be its last parameter.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Rest<T>(pub Vec<T>);
+
+impl<T> ::std::ops::Deref for Rest<T> {
+	type Target = Vec<T>;
+	fn deref(&self) -> &Vec<T> {
+		&self.0
+	}
+}
+
+impl<T: FromValue> ScriptArg for Rest<T> {
+	const VARIADIC: bool = true;
+
+	fn take(args: &[Value]) -> Result<Self, ConversionError> {
+		let mut rest = Vec::with_capacity(args.len());
+		for (i, arg) in args.iter().enumerate() {
+			rest.push(T::try_from_value(arg).map_err(|e| e.at_index(i))?);
+		}
+		Ok(Rest(rest))
+	}
+}
+
+macro_rules! native_function {
+	($($len:expr => ($($name:ident $index:tt),*))+) => {
+		$(
+			impl<Func, R, $($name: ScriptArg),*> NativeFunction<($($name,)*)> for Func
+			where
+				Func: FnMut($($name),*) -> R,
+				R: Into<Value>,
+			{
+				#[allow(unused_variables, unused_comparisons)]
+				fn invoke(&mut self, name: &str, args: &[Value]) -> Value {
+					let variadic = [$($name::VARIADIC),*].last() == Some(&true);
+					if variadic && args.len() + 1 < $len {
+						return Value::error(&format!("{} error: {} of at least {} arguments provided.", name, args.len(), $len - 1));
+					}
+					if !variadic && args.len() != $len {
+						return Value::error(&format!("{} error: {} of {} arguments provided.", name, args.len(), $len));
+					}
+					let rv = self($(
+						match $name::take(args.get($index..).unwrap_or_default()) {
+							Ok(arg) => arg,
+							Err(e) => {
+								return Value::error(&format!("{} error: invalid type of {} argument ({} expected): {}.",
+									name, $index, short_type_name::<$name>(), e));
+							},
+						}
+					),*);
+					rv.into()
+				}
+			}
+		)+
+	};
+}
+
+native_function! {
+	0 => ()
+	1 => (A 0)
+	2 => (A 0, B 1)
+	3 => (A 0, B 1, C 2)
+	4 => (A 0, B 1, C 2, D 3)
+	5 => (A 0, B 1, C 2, D 3, E 4)
+	6 => (A 0, B 1, C 2, D 3, E 4, F 5)
+	7 => (A 0, B 1, C 2, D 3, E 4, F 5, G 6)
+	8 => (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7)
+	9 => (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8)
+	10 => (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9)
+}
+
 /// Helper trait
 pub trait FromValue {
 	/// Converts value to specified type.
 	fn from_value(v: &Value) -> Option<Self> where Self: Sized;
+
+	/// Converts value to specified type, describing why it has failed.
+	///
+	/// Containers report the path to the offending element, e.g. `expected i32, found string:"x" at [1]`.
+	fn try_from_value(v: &Value) -> Result<Self, ConversionError> where Self: Sized {
+		Self::from_value(v).ok_or_else(|| ConversionError::expecting::<Self>(v))
+	}
+
+	/// Converts `T_BYTES` data to `Vec<Self>`, which only bytes themselves do.
+	#[doc(hidden)]
+	fn from_bytes(_bytes: &[u8]) -> Option<Vec<Self>> where Self: Sized {
+		None
+	}
 }
 
 impl FromValue for Value {
@@ -1196,112 +2323,472 @@ impl FromValue for i32 {
 	}
 }
 
+impl FromValue for i64 {
+	fn from_value(v: &Value) -> Option<Self> {
+		v.to_i64()
+	}
+}
+
+impl FromValue for u64 {
+	fn from_value(v: &Value) -> Option<Self> {
+		v.to_u64()
+	}
+}
+
+impl FromValue for usize {
+	fn from_value(v: &Value) -> Option<Self> {
+		v.to_u64().and_then(|n| usize::try_from(n).ok())
+	}
+}
+
 impl FromValue for f64 {
 	fn from_value(v: &Value) -> Option<Self> {
 		v.to_float()
 	}
 }
 
-impl FromValue for Vec<u8> {
+impl FromValue for std::time::SystemTime {
 	fn from_value(v: &Value) -> Option<Self> {
-		v.to_bytes()
+		v.to_system_time()
 	}
 }
 
-impl FromValue for String {
+#[cfg(feature = "chrono")]
+impl FromValue for chrono::DateTime<chrono::Utc> {
 	fn from_value(v: &Value) -> Option<Self> {
-		v.as_string()
+		let ticks = v.to_date()?;
+		let nanos = ticks.rem_euclid(TICKS_PER_SECOND) as u32 * 100;
+		chrono::DateTime::from_timestamp(ticks.div_euclid(TICKS_PER_SECOND), nanos)
 	}
 }
 
+#[cfg(feature = "chrono")]
+impl FromValue for chrono::NaiveDate {
+	fn from_value(v: &Value) -> Option<Self> {
+		chrono::DateTime::<chrono::Utc>::from_value(v).map(|t| t.date_naive())
+	}
+}
 
-/// An iterator visiting all keys of key/value pairs in the map-like `Value` objects.
-#[doc(hidden)]
-pub struct KeyIterator<'a> {
-	base: &'a Value,
-	index: usize,
-	count: usize,
+#[cfg(feature = "time")]
+impl FromValue for time::OffsetDateTime {
+	fn from_value(v: &Value) -> Option<Self> {
+		time::OffsetDateTime::from_unix_timestamp_nanos(i128::from(v.to_date()?) * 100).ok()
+	}
 }
 
-impl<'a> ::std::iter::Iterator for KeyIterator<'a> {
-	type Item = Value;
+impl FromValue for Length {
+	fn from_value(v: &Value) -> Option<Self> {
+		v.to_length()
+	}
+}
 
-	fn next(&mut self) -> Option<Self::Item> {
-		if self.index < self.count {
-			self.index += 1;
-			Some(self.base.key_at(self.index - 1))
-		} else {
-			None
-		}
+impl FromValue for Currency {
+	fn from_value(v: &Value) -> Option<Self> {
+		v.to_currency()
 	}
+}
 
-	fn size_hint(&self) -> (usize, Option<usize>) {
-		let remain = self.count - self.index;
-		(remain, Some(remain))
+impl FromValue for String {
+	fn from_value(v: &Value) -> Option<Self> {
+		v.as_string()
 	}
+}
 
-	fn count(self) -> usize {
-		self.count
+impl FromValue for Box<str> {
+	fn from_value(v: &Value) -> Option<Self> {
+		v.as_string().map(String::into_boxed_str)
 	}
 }
 
-/// An iterator able to yield keys from both `Value`'s ends.
-impl<'a> ::std::iter::DoubleEndedIterator for KeyIterator<'a> {
-	fn next_back(&mut self) -> Option<Self::Item> {
-		if self.index == self.count || self.count == 0 {
-			None
-		} else {
-			self.count -= 1;
-			Some(self.base.key_at(self.count))
+impl FromValue for char {
+	fn from_value(v: &Value) -> Option<Self> {
+		let s = v.as_string()?;
+		let mut chars = s.chars();
+		match (chars.next(), chars.next()) {
+			(Some(c), None) => Some(c),
+			_ => None,
 		}
 	}
 }
 
+impl FromValue for std::path::PathBuf {
+	fn from_value(v: &Value) -> Option<Self> {
+		v.as_string().map(Self::from)
+	}
+}
 
-/// An iterator over the sub-elements of a `Value`.
-#[doc(hidden)]
-pub struct SeqIterator<'a> {
-	base: &'a Value,
-	index: usize,
-	count: usize,
+impl FromValue for f32 {
+	fn from_value(v: &Value) -> Option<Self> {
+		match v.to_float() {
+			// out of range values would become infinite
+			Some(f) if f.is_finite() && (f as f32).is_infinite() => None,
+			f => f.map(|f| f as f32),
+		}
+	}
 }
 
-impl<'a> ::std::iter::Iterator for SeqIterator<'a> {
-	type Item = Value;
+impl FromValue for u8 {
+	fn from_value(v: &Value) -> Option<Self> {
+		v.to_int().and_then(|i| u8::try_from(i).ok())
+	}
 
-	fn next(&mut self) -> Option<Self::Item> {
-		if self.index < self.count {
-			self.index += 1;
-			Some(self.base.get(self.index - 1))
-		} else {
-			None
-		}
+	fn from_bytes(bytes: &[u8]) -> Option<Vec<Self>> {
+		Some(bytes.to_vec())
 	}
+}
 
-	fn size_hint(&self) -> (usize, Option<usize>) {
-		let remain = self.count - self.index;
-		(remain, Some(remain))
+/// `T_BYTES` values only, see [`Bytes`](struct.Bytes.html).
+impl FromValue for Bytes {
+	fn from_value(v: &Value) -> Option<Self> {
+		v.to_bytes().map(Bytes)
 	}
+}
 
-	fn count(self) -> usize {
-		self.count
+macro_rules! from_value_int {
+	($($t:ty),+) => {
+		$(
+			impl FromValue for $t {
+				fn from_value(v: &Value) -> Option<Self> {
+					v.to_i64().and_then(|i| <$t>::try_from(i).ok())
+				}
+			}
+		)+
+	};
+}
+
+from_value_int!(i8, i16, u16, u32, isize);
+
+impl FromValue for i128 {
+	fn from_value(v: &Value) -> Option<Self> {
+		v.to_i64().map(i128::from).or_else(|| v.to_u64().map(i128::from))
 	}
 }
 
-/// An iterator able to yield sub-elements from both `Value`'s ends.
-impl<'a> ::std::iter::DoubleEndedIterator for SeqIterator<'a> {
-	fn next_back(&mut self) -> Option<Self::Item> {
-		if self.index == self.count || self.count == 0 {
-			None
+impl FromValue for u128 {
+	fn from_value(v: &Value) -> Option<Self> {
+		v.to_u64().map(u128::from)
+	}
+}
+
+/// `null` and `undefined` become `None`.
+impl<T: FromValue> FromValue for Option<T> {
+	fn from_value(v: &Value) -> Option<Self> {
+		Self::try_from_value(v).ok()
+	}
+
+	fn try_from_value(v: &Value) -> Result<Self, ConversionError> {
+		if v.is_null() || v.is_undefined() {
+			Ok(None)
 		} else {
-			self.count -= 1;
-			Some(self.base.get(self.count))
+			T::try_from_value(v).map(Some)
 		}
 	}
 }
 
-/// Conversion into an `Iterator`.
-///
+/// Error values (strings or objects) become `Err`.
+impl<T: FromValue, E: FromValue> FromValue for Result<T, E> {
+	fn from_value(v: &Value) -> Option<Self> {
+		Self::try_from_value(v).ok()
+	}
+
+	fn try_from_value(v: &Value) -> Result<Self, ConversionError> {
+		if v.is_verror() {
+			E::try_from_value(v).map(Err)
+		} else {
+			T::try_from_value(v).map(Ok)
+		}
+	}
+}
+
+/// Arrays, `Vec<u8>` also accepts byte strings (see [`Bytes`](struct.Bytes.html) for them only).
+impl<T: FromValue> FromValue for Vec<T> {
+	fn from_value(v: &Value) -> Option<Self> {
+		Self::try_from_value(v).ok()
+	}
+
+	fn try_from_value(v: &Value) -> Result<Self, ConversionError> {
+		if let Some(bytes) = v.as_bytes().and_then(T::from_bytes) {
+			return Ok(bytes);
+		}
+		if !v.is_varray() {
+			return Err(ConversionError::expecting::<Self>(v));
+		}
+		v.values().enumerate().map(|(i, item)| T::try_from_value(&item).map_err(|e| e.at_index(i))).collect()
+	}
+}
+
+impl<T: FromValue, const N: usize> FromValue for [T; N] {
+	fn from_value(v: &Value) -> Option<Self> {
+		Self::try_from_value(v).ok()
+	}
+
+	fn try_from_value(v: &Value) -> Result<Self, ConversionError> {
+		if !v.is_varray() || v.len() != N {
+			return Err(ConversionError::expecting::<Self>(v));
+		}
+		let items = Vec::<T>::try_from_value(v)?;
+		<[T; N]>::try_from(items).map_err(|_| ConversionError::expecting::<Self>(v))
+	}
+}
+
+macro_rules! from_value_tuple {
+	($($len:expr => ($($name:ident $index:tt),+))+) => {
+		$(
+			impl<$($name: FromValue),+> FromValue for ($($name,)+) {
+				fn from_value(v: &Value) -> Option<Self> {
+					Self::try_from_value(v).ok()
+				}
+
+				fn try_from_value(v: &Value) -> Result<Self, ConversionError> {
+					if !v.is_varray() || v.len() != $len {
+						return Err(ConversionError::expecting::<Self>(v));
+					}
+					Ok(($($name::try_from_value(&v.get($index)).map_err(|e| e.at_index($index))?,)+))
+				}
+			}
+
+			impl<$($name: Into<Value>),+> From<($($name,)+)> for Value {
+				fn from(val: ($($name,)+)) -> Self {
+					let mut me = Value::array(0);
+					$(me.push(val.$index);)+
+					return me;
+				}
+			}
+		)+
+	};
+}
+
+from_value_tuple! {
+	1 => (A 0)
+	2 => (A 0, B 1)
+	3 => (A 0, B 1, C 2)
+	4 => (A 0, B 1, C 2, D 3)
+	5 => (A 0, B 1, C 2, D 3, E 4)
+	6 => (A 0, B 1, C 2, D 3, E 4, F 5)
+	7 => (A 0, B 1, C 2, D 3, E 4, F 5, G 6)
+	8 => (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7)
+	9 => (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8)
+	10 => (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9)
+	11 => (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10)
+	12 => (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11)
+}
+
+// Shared by the map types, `extend` is used to collect the converted pairs.
+fn map_from_value<K, V, M>(v: &Value) -> Result<M, ConversionError>
+	where K: FromValue, V: FromValue, M: Default + Extend<(K, V)>
+{
+	if !v.is_vmap() {
+		return Err(ConversionError::expecting::<M>(v));
+	}
+	let mut map = M::default();
+	for (key, item) in v.items() {
+		let k = K::try_from_value(&key).map_err(|e| e.at_key(&key))?;
+		let v = V::try_from_value(&item).map_err(|e| e.at_key(&key))?;
+		map.extend(Some((k, v)));
+	}
+	Ok(map)
+}
+
+impl<K, V, S> FromValue for ::std::collections::HashMap<K, V, S>
+	where K: FromValue + Eq + ::std::hash::Hash, V: FromValue, S: ::std::hash::BuildHasher + Default
+{
+	fn from_value(v: &Value) -> Option<Self> {
+		Self::try_from_value(v).ok()
+	}
+
+	fn try_from_value(v: &Value) -> Result<Self, ConversionError> {
+		map_from_value(v)
+	}
+}
+
+impl<K: FromValue + Ord, V: FromValue> FromValue for ::std::collections::BTreeMap<K, V> {
+	fn from_value(v: &Value) -> Option<Self> {
+		Self::try_from_value(v).ok()
+	}
+
+	fn try_from_value(v: &Value) -> Result<Self, ConversionError> {
+		map_from_value(v)
+	}
+}
+
+
+/// Failed conversion of a `Value` to a Rust type, see [`FromValue::try_from_value`](trait.FromValue.html#method.try_from_value).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConversionError {
+	expected: String,
+	found: String,
+	path: String,
+}
+
+impl ConversionError {
+	/// Construct an error for the `found` value which is not the `expected` one.
+	pub fn new<S: Into<String>>(expected: S, found: &Value) -> Self {
+		ConversionError {
+			expected: expected.into(),
+			found: format!("{:?}", found),
+			path: String::new(),
+		}
+	}
+
+	fn expecting<T>(found: &Value) -> Self {
+		Self::new(short_type_name::<T>(), found)
+	}
+
+	/// Prepend the array index to the path of the offending element.
+	pub fn at_index(self, index: usize) -> Self {
+		self.prepend(format!("[{}]", index))
+	}
+
+	/// Prepend the map key to the path of the offending element.
+	pub fn at_key(self, key: &Value) -> Self {
+		let name = key.as_string().filter(|s| key.is_string() && !s.is_empty() && s.chars().all(|c| c.is_alphanumeric() || c == '_'));
+		self.prepend(name.unwrap_or_else(|| format!("[{}]", key)))
+	}
+
+	fn prepend(mut self, segment: String) -> Self {
+		if !self.path.is_empty() && !self.path.starts_with('[') {
+			self.path.insert(0, '.');
+		}
+		self.path.insert_str(0, &segment);
+		self
+	}
+
+	/// The expected type.
+	pub fn expected(&self) -> &str {
+		&self.expected
+	}
+
+	/// Debug representation of the offending value.
+	pub fn found(&self) -> &str {
+		&self.found
+	}
+
+	/// Path to the offending element inside the converted value, like `items[2].name`; empty for the value itself.
+	pub fn path(&self) -> &str {
+		&self.path
+	}
+}
+
+impl ::std::fmt::Display for ConversionError {
+	fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+		write!(f, "expected {}, found {}", self.expected, self.found)?;
+		if !self.path.is_empty() {
+			write!(f, " at {}", self.path)?;
+		}
+		Ok(())
+	}
+}
+
+impl ::std::error::Error for ConversionError {}
+
+// `alloc::vec::Vec<alloc::string::String>` -> `Vec<String>`
+fn short_type_name<T>() -> String {
+	let full = ::std::any::type_name::<T>();
+	let mut name = String::with_capacity(full.len());
+	let mut segment = 0;
+	let mut chars = full.chars().peekable();
+	while let Some(c) = chars.next() {
+		if c == ':' && chars.peek() == Some(&':') {
+			chars.next();
+			name.truncate(segment);
+		} else {
+			name.push(c);
+			if !(c.is_alphanumeric() || c == '_') {
+				segment = name.len();
+			}
+		}
+	}
+	name
+}
+
+
+/// An iterator visiting all keys of key/value pairs in the map-like `Value` objects.
+#[doc(hidden)]
+pub struct KeyIterator<'a> {
+	base: &'a Value,
+	index: usize,
+	count: usize,
+}
+
+impl<'a> ::std::iter::Iterator for KeyIterator<'a> {
+	type Item = Value;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.index < self.count {
+			self.index += 1;
+			Some(self.base.key_at(self.index - 1))
+		} else {
+			None
+		}
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let remain = self.count - self.index;
+		(remain, Some(remain))
+	}
+
+	fn count(self) -> usize {
+		self.count
+	}
+}
+
+/// An iterator able to yield keys from both `Value`'s ends.
+impl<'a> ::std::iter::DoubleEndedIterator for KeyIterator<'a> {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		if self.index == self.count || self.count == 0 {
+			None
+		} else {
+			self.count -= 1;
+			Some(self.base.key_at(self.count))
+		}
+	}
+}
+
+
+/// An iterator over the sub-elements of a `Value`.
+#[doc(hidden)]
+pub struct SeqIterator<'a> {
+	base: &'a Value,
+	index: usize,
+	count: usize,
+}
+
+impl<'a> ::std::iter::Iterator for SeqIterator<'a> {
+	type Item = Value;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.index < self.count {
+			self.index += 1;
+			Some(self.base.get(self.index - 1))
+		} else {
+			None
+		}
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let remain = self.count - self.index;
+		(remain, Some(remain))
+	}
+
+	fn count(self) -> usize {
+		self.count
+	}
+}
+
+/// An iterator able to yield sub-elements from both `Value`'s ends.
+impl<'a> ::std::iter::DoubleEndedIterator for SeqIterator<'a> {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		if self.index == self.count || self.count == 0 {
+			None
+		} else {
+			self.count -= 1;
+			Some(self.base.get(self.count))
+		}
+	}
+}
+
+/// Conversion into an `Iterator`.
+///
 /// Adds the `for` loop syntax support: `for subitem in &value {]`.
 impl<'a> ::std::iter::IntoIterator for &'a Value {
 	type Item = Value;
@@ -1312,6 +2799,686 @@ impl<'a> ::std::iter::IntoIterator for &'a Value {
 	}
 }
 
+/// Append values to the end of `T_ARRAY` value.
+impl<T: Into<Value>> ::std::iter::Extend<T> for Value {
+	fn extend<I: IntoIterator<Item=T>>(&mut self, iterator: I) {
+		for item in iterator {
+			self.push(item);
+		}
+	}
+}
+
+
+/// A mutable iterator over the sub-elements of a `Value`, see [`Value::values_mut()`](struct.Value.html#method.values_mut).
+pub struct ValuesMut<'a> {
+	base: *mut VALUE,
+	index: usize,
+	count: usize,
+	_marker: ::std::marker::PhantomData<&'a mut Value>,
+}
+
+impl<'a> ::std::iter::Iterator for ValuesMut<'a> {
+	type Item = ItemMut<'a>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.index < self.count {
+			let mut value = Value::new();
+			(_API.ValueNthElementValue)(self.base, self.index as INT, value.as_ptr());
+			self.index += 1;
+			Some(ItemMut {
+				base: self.base,
+				slot: ItemSlot::Index(self.index - 1),
+				value,
+				_marker: ::std::marker::PhantomData,
+			})
+		} else {
+			None
+		}
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let remain = self.count - self.index;
+		(remain, Some(remain))
+	}
+}
+
+/// A mutable iterator over the key-value pairs of a `Value`, see [`Value::iter_mut()`](struct.Value.html#method.iter_mut).
+pub struct ItemsMut<'a> {
+	base: *mut VALUE,
+	keys: ::std::vec::IntoIter<Value>,
+	_marker: ::std::marker::PhantomData<&'a mut Value>,
+}
+
+impl<'a> ::std::iter::Iterator for ItemsMut<'a> {
+	type Item = (Value, ItemMut<'a>);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let key = self.keys.next()?;
+		let mut value = Value::new();
+		(_API.ValueGetValueOfKey)(self.base, key.as_cptr(), value.as_ptr());
+		let item = ItemMut {
+			base: self.base,
+			slot: ItemSlot::Key(key.clone()),
+			value,
+			_marker: ::std::marker::PhantomData,
+		};
+		Some((key, item))
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.keys.size_hint()
+	}
+}
+
+enum ItemSlot {
+	Index(usize),
+	Key(Value),
+}
+
+/// A sub-element of a `Value` yielded by [`values_mut()`](struct.Value.html#method.values_mut)
+/// and [`iter_mut()`](struct.Value.html#method.iter_mut).
+///
+/// Dereferences to a copy of the element which is stored back into the parent value on drop.
+pub struct ItemMut<'a> {
+	base: *mut VALUE,
+	slot: ItemSlot,
+	value: Value,
+	_marker: ::std::marker::PhantomData<&'a mut Value>,
+}
+
+impl<'a> ::std::ops::Deref for ItemMut<'a> {
+	type Target = Value;
+	fn deref(&self) -> &Value {
+		&self.value
+	}
+}
+
+impl<'a> ::std::ops::DerefMut for ItemMut<'a> {
+	fn deref_mut(&mut self) -> &mut Value {
+		&mut self.value
+	}
+}
+
+impl<'a> Drop for ItemMut<'a> {
+	fn drop(&mut self) {
+		match self.slot {
+			ItemSlot::Index(index) => (_API.ValueNthElementValueSet)(self.base, index as INT, self.value.as_cptr()),
+			ItemSlot::Key(ref key) => (_API.ValueSetValueToKey)(self.base, key.as_cptr(), self.value.as_cptr()),
+		};
+	}
+}
+
+
+/// A single key of a map value, see [`Value::entry()`](struct.Value.html#method.entry).
+///
+/// Since values are returned by copy, changes of scalars should be made via [`and_modify`](#method.and_modify),
+/// while arrays and maps are reference types and can be changed through the returned value directly.
+pub struct Entry<'a> {
+	map: &'a mut Value,
+	key: Value,
+}
+
+impl<'a> Entry<'a> {
+	/// The key of this entry.
+	pub fn key(&self) -> &Value {
+		&self.key
+	}
+
+	/// The value of this entry if the key is present.
+	pub fn get(&self) -> Option<Value> {
+		self.map.try_get_item(self.key.clone())
+	}
+
+	/// Whether the key is present in the map.
+	pub fn is_occupied(&self) -> bool {
+		self.get().is_some()
+	}
+
+	/// Insert the `default` value if the key is missing and return the entry value.
+	pub fn or_insert<T: Into<Value>>(self, default: T) -> Value {
+		self.or_insert_with(|| default)
+	}
+
+	/// Insert the result of `default` if the key is missing and return the entry value.
+	pub fn or_insert_with<T: Into<Value>, F: FnOnce() -> T>(self, default: F) -> Value {
+		match self.get() {
+			Some(v) => v,
+			None => {
+				let v = default().into();
+				self.map.set_item(self.key, v.clone());
+				v
+			}
+		}
+	}
+
+	/// Modify the value in place if the key is present.
+	pub fn and_modify<F: FnOnce(&mut Value)>(self, f: F) -> Self {
+		if let Some(mut v) = self.get() {
+			f(&mut v);
+			self.map.set_item(self.key.clone(), v);
+		}
+		self
+	}
+
+	/// Set the value, returning the previous one if the key was present.
+	pub fn insert<T: Into<Value>>(self, value: T) -> Option<Value> {
+		let prev = self.get();
+		self.map.set_item(self.key, value);
+		prev
+	}
+
+	/// Remove the key from the map, see [`Value::remove_item()`](struct.Value.html#method.remove_item).
+	pub fn remove(self) -> Option<Value> {
+		self.map.remove_item(self.key)
+	}
+}
+
+
+
+/// Borrowed typed view of a [`Value`](struct.Value.html), returned by [`Value::kind()`](struct.Value.html#method.kind).
+///
+/// Converting it back with `Value::from` gives an equal value, so it can be used for construction as well:
+///
+/// ```
+/// # sciter::fake::install();
+/// use sciter::value::{Value, ValueKind};
+///
+/// fn describe(v: &Value) -> String {
+///   match v.kind() {
+///     ValueKind::Int(i) => format!("int {}", i),
+///     ValueKind::String(s) => format!("string {:?}", s),
+///     ValueKind::Array(a) => format!("array of {}", a.len()),
+///     _ => "something else".to_owned(),
+///   }
+/// }
+///
+/// let v = Value::from(ValueKind::Int(42));
+/// assert_eq!(describe(&v), "int 42");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum ValueKind<'a> {
+	/// `undefined`.
+	Undefined,
+	/// `nothing`, see [`Value::nothing()`](struct.Value.html#method.nothing).
+	Nothing,
+	/// `null`.
+	Null,
+	#[allow(missing_docs)]
+	Bool(bool),
+	#[allow(missing_docs)]
+	Int(i32),
+	#[allow(missing_docs)]
+	Float(f64),
+	/// Currency, which is also how Sciter.JS stores big integers: their value is [`Currency::raw`](struct.Currency.html#method.raw),
+	/// see [`Value::big_int()`](struct.Value.html#method.big_int).
+	Currency(Currency),
+	/// Strings of any unit except symbols and errors.
+	String(String),
+	#[allow(missing_docs)]
+	Symbol(String),
+	/// Error string.
+	Error(String),
+	#[allow(missing_docs)]
+	Bytes(&'a [u8]),
+	/// Date in 100ns intervals since the Unix epoch with its [`VALUE_UNIT_TYPE_DATE`](enum.VALUE_UNIT_TYPE_DATE.html) flags.
+	Date {
+		#[allow(missing_docs)]
+		ticks: i64,
+		#[allow(missing_docs)]
+		flags: UINT,
+	},
+	#[allow(missing_docs)]
+	Length(Length),
+	/// Duration in seconds.
+	Duration(f64),
+	/// Angle in radians.
+	Angle(f64),
+	/// Color in `0xAABBGGRR` form.
+	Color(u32),
+	#[allow(missing_docs)]
+	Array(&'a Value),
+	#[allow(missing_docs)]
+	Map(&'a Value),
+	/// Sciter internal function.
+	Function(&'a Value),
+	/// Script object, array, function or a native object, see `is_object_*` methods.
+	Object(&'a Value),
+	/// DOM element, see [`Element`](../dom/struct.Element.html).
+	Element(&'a Value),
+	#[allow(missing_docs)]
+	Asset(&'a Value),
+	/// Resources, ranges, enums and lengths with unknown units.
+	Other(&'a Value),
+}
+
+/// Construct a value back from its typed view.
+impl<'a> From<ValueKind<'a>> for Value {
+	fn from(kind: ValueKind<'a>) -> Self {
+		match kind {
+			ValueKind::Undefined => Value::new(),
+			ValueKind::Nothing => Value::nothing(),
+			ValueKind::Null => Value::null(),
+			ValueKind::Bool(b) => Value::from(b),
+			ValueKind::Int(i) => Value::from(i),
+			ValueKind::Float(f) => Value::from(f),
+			ValueKind::Currency(c) => Value::currency(c),
+			ValueKind::String(s) => Value::from(s),
+			ValueKind::Symbol(s) => Value::symbol(&s),
+			ValueKind::Error(s) => Value::error(&s),
+			ValueKind::Bytes(b) => Value::from(b),
+			ValueKind::Date { ticks, flags } => Value::date(ticks, flags),
+			ValueKind::Length(l) => Value::length(l),
+			ValueKind::Duration(d) => Value::duration(d),
+			ValueKind::Angle(a) => Value::angle(a),
+			ValueKind::Color(c) => Value::color(c),
+			ValueKind::Array(v)
+			| ValueKind::Map(v)
+			| ValueKind::Function(v)
+			| ValueKind::Object(v)
+			| ValueKind::Element(v)
+			| ValueKind::Asset(v)
+			| ValueKind::Other(v) => v.clone(),
+		}
+	}
+}
+
+
+/// Deep copy of the value data, made by [`Value::to_owned_data()`](struct.Value.html#method.to_owned_data).
+///
+/// Unlike `Value`, it holds no engine references, so it is `Send` and `Sync`
+/// and can be created, changed and dropped on any thread.
+/// Convert it back with `Value::from` on the UI thread.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum OwnedValue {
+	/// `undefined`.
+	#[default]
+	Undefined,
+	/// `nothing`, see [`Value::nothing()`](struct.Value.html#method.nothing).
+	Nothing,
+	/// `null`.
+	Null,
+	#[allow(missing_docs)]
+	Bool(bool),
+	#[allow(missing_docs)]
+	Int(i32),
+	#[allow(missing_docs)]
+	Float(f64),
+	/// Currency or a Sciter.JS big integer, see [`ValueKind::Currency`](enum.ValueKind.html#variant.Currency).
+	Currency(Currency),
+	#[allow(missing_docs)]
+	String(String),
+	#[allow(missing_docs)]
+	Symbol(String),
+	/// Error string.
+	Error(String),
+	#[allow(missing_docs)]
+	Bytes(Vec<u8>),
+	/// Date in 100ns intervals since the Unix epoch with its [`VALUE_UNIT_TYPE_DATE`](enum.VALUE_UNIT_TYPE_DATE.html) flags.
+	Date {
+		#[allow(missing_docs)]
+		ticks: i64,
+		#[allow(missing_docs)]
+		flags: UINT,
+	},
+	#[allow(missing_docs)]
+	Length(Length),
+	/// Duration in seconds.
+	Duration(f64),
+	/// Angle in radians.
+	Angle(f64),
+	/// Color in `0xAABBGGRR` form.
+	Color(u32),
+	/// Array or script array.
+	Array(Vec<OwnedValue>),
+	/// Map or script object, in the order of its keys.
+	Map(Vec<(OwnedValue, OwnedValue)>),
+}
+
+/// Construct a value from its data on the UI thread.
+impl<'a> From<&'a OwnedValue> for Value {
+	fn from(data: &'a OwnedValue) -> Self {
+		let kind = match *data {
+			OwnedValue::Undefined => ValueKind::Undefined,
+			OwnedValue::Nothing => ValueKind::Nothing,
+			OwnedValue::Null => ValueKind::Null,
+			OwnedValue::Bool(b) => ValueKind::Bool(b),
+			OwnedValue::Int(i) => ValueKind::Int(i),
+			OwnedValue::Float(f) => ValueKind::Float(f),
+			OwnedValue::Currency(c) => ValueKind::Currency(c),
+			OwnedValue::String(ref s) => return Value::from(s.as_str()),
+			OwnedValue::Symbol(ref s) => return Value::symbol(s),
+			OwnedValue::Error(ref s) => return Value::error(s),
+			OwnedValue::Bytes(ref b) => ValueKind::Bytes(b),
+			OwnedValue::Date { ticks, flags } => ValueKind::Date { ticks, flags },
+			OwnedValue::Length(l) => ValueKind::Length(l),
+			OwnedValue::Duration(d) => ValueKind::Duration(d),
+			OwnedValue::Angle(a) => ValueKind::Angle(a),
+			OwnedValue::Color(c) => ValueKind::Color(c),
+			OwnedValue::Array(ref items) => {
+				return items.iter().map(Value::from).collect();
+			},
+			OwnedValue::Map(ref items) => {
+				let mut map = Value::map();
+				for (key, item) in items {
+					map.set_item(Value::from(key), Value::from(item));
+				}
+				return map;
+			},
+		};
+		Value::from(kind)
+	}
+}
+
+/// Construct a value from its data on the UI thread.
+impl From<OwnedValue> for Value {
+	fn from(data: OwnedValue) -> Self {
+		Value::from(&data)
+	}
+}
+
+impl From<bool> for OwnedValue {
+	fn from(b: bool) -> Self {
+		OwnedValue::Bool(b)
+	}
+}
+
+impl From<i32> for OwnedValue {
+	fn from(i: i32) -> Self {
+		OwnedValue::Int(i)
+	}
+}
+
+impl From<f64> for OwnedValue {
+	fn from(f: f64) -> Self {
+		OwnedValue::Float(f)
+	}
+}
+
+impl<'a> From<&'a str> for OwnedValue {
+	fn from(s: &'a str) -> Self {
+		OwnedValue::String(s.to_owned())
+	}
+}
+
+impl From<String> for OwnedValue {
+	fn from(s: String) -> Self {
+		OwnedValue::String(s)
+	}
+}
+
+/// Deep copy of the value data, see [`Value::to_owned_data()`](struct.Value.html#method.to_owned_data).
+impl FromValue for OwnedValue {
+	fn from_value(v: &Value) -> Option<Self> {
+		v.to_owned_data().ok()
+	}
+
+	fn try_from_value(v: &Value) -> Result<Self, ConversionError> {
+		v.to_owned_data()
+	}
+}
+
+
+/// CSS length units, see [`Length`](struct.Length.html).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum LengthUnit {
+	/// Screen pixels, `px`.
+	Px,
+	/// Device independent pixels (1/96 of an inch), `dip`.
+	Dip,
+	/// Height of the element's font, `em`.
+	Em,
+	/// Height of the letter `x`, `ex`.
+	Ex,
+	/// Percents, `%`.
+	Percent,
+	/// Flex units (a.k.a. springs), `%%`.
+	Springs,
+	/// Points (1/72 of an inch), `pt`.
+	Pt,
+	/// Picas (12 points), `pc`.
+	Pc,
+	/// Inches, `in`.
+	In,
+	/// Centimeters, `cm`.
+	Cm,
+	/// Millimeters, `mm`.
+	Mm,
+}
+
+impl LengthUnit {
+	const ALL: [(LengthUnit, &'static str, VALUE_UNIT_TYPE_LENGTH); 11] = [
+		(LengthUnit::Px, "px", VALUE_UNIT_TYPE_LENGTH::PX),
+		(LengthUnit::Dip, "dip", VALUE_UNIT_TYPE_LENGTH::DIP),
+		(LengthUnit::Em, "em", VALUE_UNIT_TYPE_LENGTH::EM),
+		(LengthUnit::Ex, "ex", VALUE_UNIT_TYPE_LENGTH::EX),
+		(LengthUnit::Percent, "%", VALUE_UNIT_TYPE_LENGTH::PR),
+		(LengthUnit::Springs, "%%", VALUE_UNIT_TYPE_LENGTH::SP),
+		(LengthUnit::Pt, "pt", VALUE_UNIT_TYPE_LENGTH::PT),
+		(LengthUnit::Pc, "pc", VALUE_UNIT_TYPE_LENGTH::PC),
+		(LengthUnit::In, "in", VALUE_UNIT_TYPE_LENGTH::IN),
+		(LengthUnit::Cm, "cm", VALUE_UNIT_TYPE_LENGTH::CM),
+		(LengthUnit::Mm, "mm", VALUE_UNIT_TYPE_LENGTH::MM),
+	];
+
+	/// CSS suffix of the unit, like `px`.
+	pub fn suffix(self) -> &'static str {
+		LengthUnit::ALL.iter().find(|u| u.0 == self).map_or("", |u| u.1)
+	}
+
+	/// Unit by its CSS suffix.
+	pub fn from_suffix(suffix: &str) -> Option<LengthUnit> {
+		LengthUnit::ALL.iter().find(|u| u.1 == suffix).map(|u| u.0)
+	}
+
+	/// Unit by the raw [`VALUE_UNIT_TYPE_LENGTH`](enum.VALUE_UNIT_TYPE_LENGTH.html) value.
+	pub fn from_raw(unit: UINT) -> Option<LengthUnit> {
+		LengthUnit::ALL.iter().find(|u| u.2 as UINT == unit).map(|u| u.0)
+	}
+
+	/// The raw [`VALUE_UNIT_TYPE_LENGTH`](enum.VALUE_UNIT_TYPE_LENGTH.html) value.
+	pub fn to_raw(self) -> UINT {
+		LengthUnit::ALL.iter().find(|u| u.0 == self).map_or(0, |u| u.2 as UINT)
+	}
+
+	/// Size of the absolute units in inches.
+	fn inches(self) -> Option<f64> {
+		match self {
+			LengthUnit::Dip => Some(1.0 / 96.0),
+			LengthUnit::Pt => Some(1.0 / 72.0),
+			LengthUnit::Pc => Some(1.0 / 6.0),
+			LengthUnit::In => Some(1.0),
+			LengthUnit::Cm => Some(1.0 / 2.54),
+			LengthUnit::Mm => Some(1.0 / 25.4),
+			_ => None,
+		}
+	}
+}
+
+/// CSS length, see [`Value::length`](struct.Value.html#method.length).
+///
+/// ```
+/// use sciter::value::{Length, LengthUnit};
+///
+/// let margin = Length::new(0.5, LengthUnit::In);
+/// assert_eq!(margin.to_unit(LengthUnit::Pt), Some(Length::new(36.0, LengthUnit::Pt)));
+/// assert_eq!(margin.to_unit(LengthUnit::Em), None);
+/// assert_eq!(margin.to_string(), "0.5in");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Length {
+	/// Number of units.
+	pub value: f64,
+	/// Unit of the length.
+	pub unit: LengthUnit,
+}
+
+impl Length {
+	/// Length in the given units.
+	pub const fn new(value: f64, unit: LengthUnit) -> Length {
+		Length { value, unit }
+	}
+
+	/// Length in screen pixels.
+	pub const fn px(value: f64) -> Length {
+		Length::new(value, LengthUnit::Px)
+	}
+
+	/// Length in device independent pixels.
+	pub const fn dip(value: f64) -> Length {
+		Length::new(value, LengthUnit::Dip)
+	}
+
+	/// Length relative to the element's font.
+	pub const fn em(value: f64) -> Length {
+		Length::new(value, LengthUnit::Em)
+	}
+
+	/// Length in percents.
+	pub const fn percent(value: f64) -> Length {
+		Length::new(value, LengthUnit::Percent)
+	}
+
+	/// Convert between the absolute units (`dip`, `pt`, `pc`, `in`, `cm` and `mm`).
+	///
+	/// Screen pixels depend on the display resolution and the rest on the layout, so they are not converted.
+	pub fn to_unit(self, unit: LengthUnit) -> Option<Length> {
+		if unit == self.unit {
+			return Some(self);
+		}
+		let value = self.value * self.unit.inches()? / unit.inches()?;
+		Some(Length::new(value, unit))
+	}
+}
+
+impl ::std::fmt::Display for Length {
+	fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+		write!(f, "{}{}", self.value, self.unit.suffix())
+	}
+}
+
+/// Fixed-point currency with 4 decimal digits, see [`Value::currency`](struct.Value.html#method.currency).
+///
+/// ```
+/// use sciter::value::Currency;
+///
+/// let price = Currency::from_f64(19.99).unwrap();
+/// assert_eq!(price.raw(), 199_900);
+/// assert_eq!(price.checked_mul(3).map(|x| x.to_string()), Some("59.97".to_owned()));
+/// assert_eq!(Currency::from_int(i64::MAX), None);
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Currency(i64);
+
+impl Currency {
+	/// Number of fractional units in one currency unit.
+	pub const SCALE: i64 = 10_000;
+
+	/// Currency from the number of 1/10000 units.
+	pub const fn from_raw(raw: i64) -> Currency {
+		Currency(raw)
+	}
+
+	/// Number of 1/10000 units.
+	pub const fn raw(self) -> i64 {
+		self.0
+	}
+
+	/// Currency from whole units, `None` on overflow.
+	pub fn from_int(val: i64) -> Option<Currency> {
+		val.checked_mul(Currency::SCALE).map(Currency)
+	}
+
+	/// Currency from a float rounded to 4 decimal digits, `None` if it is out of range or not a number.
+	pub fn from_f64(val: f64) -> Option<Currency> {
+		let x = (val * Currency::SCALE as f64).round();
+		// `i64::MAX as f64` is 2^63 which is out of range already
+		if x >= i64::MIN as f64 && x < i64::MAX as f64 {
+			Some(Currency(x as i64))
+		} else {
+			None
+		}
+	}
+
+	/// Currency as float, can lose precision beyond 2^53 raw units.
+	pub fn to_f64(self) -> f64 {
+		self.0 as f64 / Currency::SCALE as f64
+	}
+
+	/// Checked addition.
+	pub fn checked_add(self, other: Currency) -> Option<Currency> {
+		self.0.checked_add(other.0).map(Currency)
+	}
+
+	/// Checked subtraction.
+	pub fn checked_sub(self, other: Currency) -> Option<Currency> {
+		self.0.checked_sub(other.0).map(Currency)
+	}
+
+	/// Checked multiplication by an integer.
+	pub fn checked_mul(self, n: i64) -> Option<Currency> {
+		self.0.checked_mul(n).map(Currency)
+	}
+}
+
+/// Currency from whole units, `i32` always fits.
+impl From<i32> for Currency {
+	fn from(val: i32) -> Self {
+		Currency(i64::from(val) * Currency::SCALE)
+	}
+}
+
+impl ::std::fmt::Display for Currency {
+	fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+		let sign = if self.0 < 0 { "-" } else { "" };
+		let abs = self.0.unsigned_abs();
+		let (whole, fraction) = (abs / Currency::SCALE as u64, abs % Currency::SCALE as u64);
+		if fraction == 0 {
+			write!(f, "{}{}", sign, whole)
+		} else {
+			let digits = format!("{:04}", fraction);
+			write!(f, "{}{}.{}", sign, whole, digits.trim_end_matches('0'))
+		}
+	}
+}
+
+/// Byte string, which is stored as a `T_BYTES` value.
+///
+/// `Vec<u8>` converts to a script array of numbers like any other `Vec<T>`
+/// (and accepts both on the way back), so wrap it to get a byte string instead:
+///
+/// ```
+/// # sciter::fake::install();
+/// use sciter::value::{Bytes, FromValue, Value};
+///
+/// let v = Value::from(Bytes(vec![1, 2]));
+/// assert!(v.is_bytes());
+/// assert_eq!(Bytes::from_value(&v), Some(Bytes(vec![1, 2])));
+/// assert_eq!(Vec::<u8>::from_value(&v), Some(vec![1, 2]));
+/// assert_eq!(Bytes::from_value(&Value::from(vec![1u8, 2])), None);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bytes(pub Vec<u8>);
+
+impl ::std::ops::Deref for Bytes {
+	type Target = [u8];
+
+	fn deref(&self) -> &[u8] {
+		&self.0
+	}
+}
+
+impl From<Vec<u8>> for Bytes {
+	fn from(val: Vec<u8>) -> Self {
+		Bytes(val)
+	}
+}
+
+impl From<Bytes> for Vec<u8> {
+	fn from(val: Bytes) -> Self {
+		val.0
+	}
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -1328,6 +3495,7 @@ mod tests {
 
 	#[test]
 	fn test_from_value() {
+		::fake::install();
 		let v = Value::from(12);
 		check1(
 			match FromValue::from_value(&v) {
@@ -1345,6 +3513,7 @@ mod tests {
 
 	#[test]
 	fn test_abi() {
+		::fake::install();
 
 		let mut data = VALUE { t: VALUE_TYPE::T_UNDEFINED, u: 0, d: 0 };
 		assert_eq!(data.t, VALUE_TYPE::T_UNDEFINED);
@@ -7,9 +7,10 @@ Host application can render custom video streams using `<video>` infrastructure.
 
 use capi::sctypes::{UINT, LPCBYTE, LPCSTR};
 use capi::scom::som_passport_t;
+use error::Error;
 
 /// A type alias for Sciter functions that return `bool`.
-pub type Result<T> = ::std::result::Result<T, ()>;
+pub type Result<T> = ::std::result::Result<T, Error>;
 
 
 /// Color space for video frame.
@@ -56,13 +57,21 @@ macro_rules! cppcall {
 }
 
 macro_rules! cppresult {
-	( $( $t:tt )* ) => {
-		if cppcall!( $($t)* ) {
+	(const $this:ident . $func:ident ( $( $arg:expr ),* )) => {
+		if cppcall!(const $this . $func ( $($arg),* )) {
 			Ok(())
 		} else {
-			Err(())
+			Err(Error::from(()).with_call(stringify!($func)))
 		}
-	}
+	};
+
+	($this:ident . $func:ident ( $( $arg:expr ),* )) => {
+		if cppcall!($this . $func ( $($arg),* )) {
+			Ok(())
+		} else {
+			Err(Error::from(()).with_call(stringify!($func)))
+		}
+	};
 }
 
 #[doc(hidden)]
@@ -505,6 +514,6 @@ impl<T: NamedInterface> AssetPtr<T> {
 	/// ```
 	pub fn try_from<U>(other: &mut AssetPtr<U>) -> Result<Self> {
 		let me = T::query_interface(other.get());
-		me.map(|p| AssetPtr::adopt(p as *mut T)).ok_or(())
+		me.map(|p| AssetPtr::adopt(p as *mut T)).ok_or_else(|| Error::from(()).with_call("get_interface"))
 	}
 }
@@ -33,6 +33,7 @@ use platform::{BaseWindow, OsWindow};
 use host::{Host, HostHandler};
 use dom::{self, event::{EventHandler}};
 use crate::Value;
+use error::ScriptError;
 
 use std::rc::Rc;
 
@@ -221,7 +222,7 @@ impl Window {
 	///
 	/// See documentation of the [`Archive`](../host/struct.Archive.html).
 	///
-  pub fn archive_handler(&mut self, resource: &[u8]) -> Result<(), ()> {
+  pub fn archive_handler(&mut self, resource: &[u8]) -> ::Result<()> {
     self.host.register_archive(resource)
   }
 
@@ -295,6 +296,11 @@ impl Window {
 		self.base.get_hwnd()
 	}
 
+	/// Get a [`Dispatcher`](../dispatch/struct.Dispatcher.html) to run code on the UI thread of the window from other threads.
+	pub fn dispatcher(&self) -> ::dispatch::Dispatcher {
+		self.host.dispatcher()
+	}
+
 	/// Minimize or hide the window.
 	pub fn collapse(&self, hide: bool) {
 		self.base.collapse(hide)
@@ -356,7 +362,7 @@ impl Window {
 		if ok == dom::SCDOM_RESULT::OK {
 			Ok(())
 		} else {
-			Err(ok)
+			Err(::Error::from(ok).with_call("SciterSetVariable").with_path(path))
 		}
 	}
 
@@ -373,10 +379,31 @@ impl Window {
 		if ok == dom::SCDOM_RESULT::OK {
 			Ok(value)
 		} else {
-			Err(ok)
+			Err(::Error::from(ok).with_call("SciterGetVariable").with_path(path))
 		}
 	}
 
+	/// Evaluate the given script in context of the current document.
+	///
+	/// See [`Host::eval_script`](../host/struct.Host.html#method.eval_script).
+	pub fn eval_script(&self, script: &str) -> Result<Value, ScriptError> {
+		self.host.eval_script(script)
+	}
+
+	/// Evaluate the given script named by the `url` in context of the current document.
+	///
+	/// See [`Host::eval_script_with_url`](../host/struct.Host.html#method.eval_script_with_url).
+	pub fn eval_script_with_url(&self, script: &str, url: &str) -> Result<Value, ScriptError> {
+		self.host.eval_script_with_url(script, url)
+	}
+
+	/// Call a script function defined in the global namespace.
+	///
+	/// See [`Host::call_function`](../host/struct.Host.html#method.call_function).
+	pub fn call_function(&self, name: &str, args: &[Value]) -> Result<Value, ScriptError> {
+		self.host.call_function(name, args)
+	}
+
 	/// Show window and run the main app message loop until the main window is closed.
 	pub fn run_app(self) {
 		self.base.expand(false);
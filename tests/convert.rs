@@ -0,0 +1,148 @@
+#[macro_use]
+extern crate sciter;
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+
+use sciter::error::ScriptError;
+use sciter::value::{Bytes, FromValue, Value};
+
+fn setup() {
+	sciter::fake::install();
+}
+
+fn round_trip<T: Clone + Into<Value> + FromValue + PartialEq + ::std::fmt::Debug>(v: T) {
+	let value: Value = v.clone().into();
+	assert_eq!(T::try_from_value(&value), Ok(v));
+}
+
+
+#[test]
+fn scalars_work() {
+	setup();
+
+	round_trip(-5i8);
+	round_trip(300i16);
+	round_trip(255u8);
+	round_trip(u16::MAX);
+	round_trip(u32::MAX);
+	round_trip(-1isize);
+	round_trip(i128::from(i64::MIN));
+	round_trip(u128::from(u64::MAX >> 11));
+	round_trip(0.5f32);
+	round_trip(f32::INFINITY);
+	assert_eq!(f32::from_value(&Value::from(1e300)), None);
+	assert_eq!(f32::from_value(&Value::from(f64::MAX)), None);
+	round_trip('é');
+	round_trip(Box::<str>::from("boxed"));
+	round_trip(PathBuf::from("/tmp/file.txt"));
+
+	assert_eq!(u8::from_value(&Value::from(256)), None);
+	assert_eq!(char::from_value(&Value::from("ab")), None);
+	assert_eq!(Value::from('x'), Value::from("x"));
+}
+
+#[test]
+fn containers_work() {
+	setup();
+
+	round_trip(vec![1, 2, 3]);
+	round_trip([1.5, 2.5]);
+	round_trip((1, "two".to_owned(), true));
+	round_trip((1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, "twelve".to_owned()));
+	round_trip(Some(vec![Some(1), None]));
+	round_trip(None::<i32>);
+
+	let mut map = HashMap::new();
+	map.insert("one".to_owned(), vec![1]);
+	map.insert("two".to_owned(), vec![2, 2]);
+	round_trip(map);
+
+	let mut tree = BTreeMap::new();
+	tree.insert("a".to_owned(), (1, 'a'));
+	round_trip(tree);
+
+	assert_eq!(Value::from(None::<i32>), Value::null());
+	assert_eq!(Option::<i32>::from_value(&Value::new()), Some(None));
+	assert_eq!(Value::from(vec!["a", "b"]), varray!["a", "b"]);
+	assert_eq!(Value::from((1, "a")), varray![1, "a"]);
+
+	// byte strings and arrays of numbers are told apart
+	round_trip(vec![1u8, 2]);
+	round_trip(Bytes(vec![1, 2]));
+	assert!(Value::from(Bytes(vec![1])).is_bytes());
+	assert!(Value::from(vec![1u8]).is_array());
+	assert_eq!(Bytes::from_value(&Value::from(b"\x01\x02".as_ref())), Some(Bytes(vec![1, 2])));
+	assert_eq!(Bytes::from_value(&varray![1, 2]), None);
+	assert_eq!(Vec::<u8>::from_value(&Value::from(b"\x01\x02".as_ref())), Some(vec![1, 2]));
+	assert!(Vec::<u8>::from_value(&Value::from(&b"ab"[..])).is_some());
+	assert_eq!(Vec::<u8>::from_value(&varray![1, 2]), Some(vec![1, 2]));
+	assert_eq!(Vec::<i32>::from_value(&Value::from(b"\x01\x02".as_ref())), None);
+	assert_eq!(<[i32; 2]>::from_value(&varray![1, 2, 3]), None);
+	assert_eq!(<(i32, i32)>::from_value(&varray![1]), None);
+}
+
+#[test]
+fn results_work() {
+	setup();
+
+	assert_eq!(Result::<i32, String>::from_value(&Value::from(1)), Some(Ok(1)));
+	assert_eq!(Result::<i32, String>::from_value(&Value::error("boom")), Some(Err("boom".to_owned())));
+
+	let err = Result::<i32, ScriptError>::from_value(&Value::error("TypeError: boom")).unwrap().unwrap_err();
+	assert_eq!(err.message(), "TypeError: boom");
+}
+
+#[test]
+fn errors_work() {
+	setup();
+
+	let err = i32::try_from_value(&Value::from("x")).unwrap_err();
+	assert_eq!((err.expected(), err.path()), ("i32", ""));
+	assert_eq!(err.to_string(), r#"expected i32, found string:"x""#);
+
+	let v = vmap! { "items" => varray![1, "two"] };
+	let err = HashMap::<String, Vec<i32>>::try_from_value(&v).unwrap_err();
+	assert_eq!(err.path(), "items[1]");
+
+	let v = varray![vmap! { "user" => vmap! { "age" => "old" } }];
+	let err = Vec::<BTreeMap<String, HashMap<String, u8>>>::try_from_value(&v).unwrap_err();
+	assert_eq!(err.to_string(), r#"expected u8, found string:"old" at [0].user.age"#);
+
+	let err = Vec::<String>::try_from_value(&Value::from(1)).unwrap_err();
+	assert_eq!(err.expected(), "Vec<String>");
+
+	let api: sciter::Error = err.into();
+	assert_eq!(api, sciter::value::VALUE_RESULT::INCOMPATIBLE_TYPE);
+}
+
+#[test]
+fn script_calls_work() {
+	setup();
+
+	struct Handler;
+
+	impl Handler {
+		fn total(&self, items: Vec<i32>, scale: Option<f64>) -> (i32, f64) {
+			let sum = items.iter().sum::<i32>();
+			(sum, f64::from(sum) * scale.unwrap_or(1.0))
+		}
+	}
+
+	impl sciter::EventHandler for Handler {
+		dispatch_script_call! {
+			fn total(Vec<i32>, Option<f64>);
+		}
+	}
+
+	let host = sciter::Host::attach(sciter::fake::create_window());
+	host.event_handler(Handler);
+	host.load_html(b"<html></html>", None);
+
+	assert_eq!(host.eval_script("view.total([1, 2], 0.5)"), Ok(varray![3, 1.5]));
+	assert_eq!(host.eval_script("view.total([1, 2], null)"), Ok(varray![3, 3.0]));
+
+	let err = host.eval_script("view.total([1, 'x'], 1.0)").unwrap();
+	assert!(err.is_error_string());
+	assert_eq!(err.as_string().unwrap(), r#"total error: invalid type of 0 argument (Vec<i32> expected): expected i32, found string:"x" at [1]."#);
+}
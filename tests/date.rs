@@ -0,0 +1,92 @@
+extern crate sciter;
+
+use std::time::{Duration, UNIX_EPOCH};
+
+use sciter::json;
+use sciter::value::{FromValue, Value, VALUE_UNIT_TYPE_DATE};
+use sciter::value::VALUE_UNIT_TYPE_DATE::*;
+
+fn setup() {
+	sciter::fake::install();
+}
+
+
+#[test]
+fn dates_work() {
+	setup();
+
+	let v = Value::date(10_000_000, DT_HAS_DATE | DT_HAS_TIME | DT_UTC);
+	assert!(v.is_date());
+	assert_eq!(v.to_date(), Some(10_000_000));
+	assert_eq!(v.to_system_time(), Some(UNIX_EPOCH + Duration::from_secs(1)));
+	assert!(v.has_date_flag(DT_UTC));
+	assert!(!v.has_date_flag(DT_HAS_SECONDS));
+	assert_eq!(v.full_type().1, 0x13);
+
+	assert_eq!(Value::from(1).to_date(), None);
+	assert!(!Value::from(1).has_date_flag(DT_UTC));
+
+	let local = Value::date(0, DT_HAS_DATE);
+	assert!(!local.has_date_flag(VALUE_UNIT_TYPE_DATE::DT_UTC));
+	assert_eq!(json::to_string(&local), "1970-01-01T00:00:00");
+}
+
+#[test]
+fn system_time_works() {
+	setup();
+
+	let at = UNIX_EPOCH + Duration::from_nanos(1_600_000_000_123_456_789);
+	let v = Value::from(at);
+	assert!(v.has_date_flag(DT_UTC));
+	assert_eq!(v.to_date(), Some(16_000_000_001_234_567));
+	assert_eq!(v.to_system_time(), Some(UNIX_EPOCH + Duration::from_nanos(1_600_000_000_123_456_700)));
+
+	// pre-epoch times are floored to 100ns as well
+	let before = UNIX_EPOCH - Duration::from_nanos(150);
+	assert_eq!(Value::from(before).to_date(), Some(-2));
+	assert_eq!(std::time::SystemTime::from_value(&Value::from(before)), Some(UNIX_EPOCH - Duration::from_nanos(200)));
+
+	// out of range times are clamped
+	let far = UNIX_EPOCH + Duration::from_secs(1 << 40);
+	assert_eq!(Value::from(far).to_date(), Some(i64::MAX));
+	let far = UNIX_EPOCH - Duration::from_secs(1 << 40);
+	assert_eq!(Value::from(far).to_date(), Some(i64::MIN));
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn chrono_works() {
+	setup();
+
+	let t = chrono::DateTime::from_timestamp(-1, 100).unwrap();
+	let v = Value::from(t);
+	assert_eq!(v.to_date(), Some(-9_999_999));
+	assert_eq!(chrono::DateTime::<chrono::Utc>::from_value(&v), Some(t));
+
+	let d = chrono::NaiveDate::from_ymd_opt(1969, 12, 31).unwrap();
+	let v = Value::from(d);
+	assert!(v.has_date_flag(DT_HAS_DATE));
+	assert_eq!(json::to_string(&v), "1969-12-31T00:00:00Z");
+	assert_eq!(chrono::NaiveDate::from_value(&v), Some(d));
+
+	// out of range dates are clamped
+	assert_eq!(Value::from(chrono::NaiveDate::MAX).to_date(), Some(i64::MAX));
+	assert_eq!(Value::from(chrono::NaiveDate::MIN).to_date(), Some(i64::MIN));
+	assert_eq!(Value::from(chrono::DateTime::<chrono::Utc>::MAX_UTC).to_date(), Some(i64::MAX));
+	assert_eq!(Value::from(chrono::DateTime::<chrono::Utc>::MIN_UTC).to_date(), Some(i64::MIN));
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn time_works() {
+	setup();
+
+	let t = time::OffsetDateTime::from_unix_timestamp_nanos(-150).unwrap();
+	let v = Value::from(t);
+	assert_eq!(v.to_date(), Some(-2));
+	assert_eq!(time::OffsetDateTime::from_value(&v).map(|t| t.unix_timestamp_nanos()), Some(-200));
+
+	// the whole `time` range fits
+	let v = Value::from(time::PrimitiveDateTime::MAX.assume_utc());
+	assert_eq!(time::OffsetDateTime::from_value(&v), Some(time::PrimitiveDateTime::MAX.assume_utc().replace_nanosecond(999_999_900).unwrap()));
+}
@@ -0,0 +1,118 @@
+extern crate sciter;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use sciter::dom::Element;
+use sciter::error::Code;
+use sciter::host::{HostHandler, SCN_POSTED_NOTIFICATION};
+use sciter::Host;
+
+fn setup() -> Host {
+	sciter::fake::install();
+	let host = Host::attach(sciter::fake::create_window());
+	host.load_html(b"<html><body></body></html>", None);
+	host
+}
+
+
+#[test]
+fn post_works() {
+	let host = setup();
+	let ui = host.dispatcher();
+	assert!(ui.is_ui_thread());
+
+	let counter = Arc::new(AtomicUsize::new(0));
+	let worker = {
+		let counter = counter.clone();
+		thread::spawn(move || {
+			assert!(!ui.is_ui_thread());
+			for _ in 0..3 {
+				let counter = counter.clone();
+				let window = ui.clone();
+				ui.post(move || {
+					let mut root = Element::from_window(window.get_hwnd()).unwrap();
+					root.set_attribute("count", &counter.fetch_add(1, Ordering::SeqCst).to_string()).unwrap();
+				}).unwrap();
+			}
+		})
+	};
+	worker.join().unwrap();
+	assert_eq!(counter.load(Ordering::SeqCst), 0);
+
+	assert_eq!(sciter::fake::pump(), 3);
+	assert_eq!(counter.load(Ordering::SeqCst), 3);
+	assert_eq!(host.get_root().unwrap().get_attribute("count"), Some("2".to_owned()));
+}
+
+#[test]
+fn call_works() {
+	let host = setup();
+	let ui = host.dispatcher();
+
+	// executed immediately on the UI thread
+	assert_eq!(ui.call(|| 1), Ok(1));
+
+	let worker = thread::spawn(move || {
+		let window = ui.clone();
+		ui.call(move || Element::from_window(window.get_hwnd()).unwrap().get_tag())
+	});
+	while !worker.is_finished() {
+		sciter::fake::pump();
+		thread::yield_now();
+	}
+	assert_eq!(worker.join().unwrap(), Ok("html".to_owned()));
+
+	let ui = host.dispatcher();
+	let err = thread::spawn(move || ui.call_timeout(Duration::from_millis(10), || 1)).join().unwrap().unwrap_err();
+	assert_eq!(err.code(), Code::Timeout);
+	assert_eq!(err.to_string(), "Dispatcher::call_timeout failed with operation timed out");
+
+	// the late task is still executed
+	assert_eq!(sciter::fake::pump(), 1);
+}
+
+#[test]
+fn refused_posts_work() {
+	let host = setup();
+	let ui = host.dispatcher();
+	sciter::fake::destroy_window(host.get_hwnd());
+
+	// the refused closure is dropped at once
+	let task = Arc::new(());
+	let owned = task.clone();
+	let err = ui.post(move || drop(owned)).unwrap_err();
+	assert_eq!(err.to_string(), "Dispatcher::post failed: the task was not accepted");
+	assert_eq!(Arc::strong_count(&task), 1);
+
+	// and the waiting calls fail instead of blocking
+	let err = thread::spawn(move || ui.call(|| 1)).join().unwrap().unwrap_err();
+	assert_eq!(err.code(), Code::Failed);
+	assert_eq!(sciter::fake::pump(), 0);
+}
+
+#[derive(Default)]
+struct Posted {
+	log: Arc<Mutex<Vec<(usize, usize)>>>,
+}
+
+impl HostHandler for Posted {
+	fn on_posted_notification(&mut self, pnm: &mut SCN_POSTED_NOTIFICATION) {
+		self.log.lock().unwrap().push((pnm.wparam, pnm.lparam));
+	}
+}
+
+#[test]
+fn foreign_posts_work() {
+	sciter::fake::install();
+	let handler = Posted::default();
+	let log = handler.log.clone();
+	let host = Host::attach_with(sciter::fake::create_window(), handler);
+
+	host.dispatcher().post(|| ()).unwrap();
+	(sciter::SciterAPI().SciterPostCallback)(host.get_hwnd(), 1, 2, 0);
+	assert_eq!(sciter::fake::pump(), 2);
+	assert_eq!(*log.lock().unwrap(), [(1, 2)]);
+}
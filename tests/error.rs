@@ -0,0 +1,131 @@
+#[macro_use]
+extern crate sciter;
+
+use sciter::dom::{Element, SCDOM_RESULT};
+use sciter::error::{Code, Context, ScriptError};
+use sciter::{Host, Value};
+
+fn setup() {
+	sciter::fake::install();
+}
+
+
+#[test]
+fn dom_errors_carry_context() {
+	setup();
+
+	let list = Element::create("ul").unwrap();
+	let err = list.find_first("li[").unwrap_err();
+	assert_eq!(err, SCDOM_RESULT::INVALID_PARAMETER);
+	assert_eq!(err.code(), Code::Dom(SCDOM_RESULT::INVALID_PARAMETER));
+	assert_eq!(err.call(), Some("SciterSelectElements"));
+	assert_eq!(err.context(), Some(&Context::Element { tag: "ul".to_owned(), uid: list.get_uid() }));
+	assert_eq!(err.to_string(), format!("SciterSelectElements failed with INVALID_PARAMETER on <ul> #{}: selector \"li[\"", list.get_uid()));
+
+	let source: &dyn std::error::Error = &err;
+	assert_eq!(source.source().unwrap().to_string(), "INVALID_PARAMETER");
+}
+
+#[test]
+fn variables_carry_path() {
+	setup();
+
+	let err = sciter::set_variable("", Value::from(1)).unwrap_err();
+	assert_eq!(err.call(), Some("SciterSetVariable"));
+	assert_eq!(err.context(), Some(&Context::Path("".to_owned())));
+}
+
+#[test]
+fn question_mark_works_across_modules() {
+	setup();
+
+	fn build(host: &Host) -> sciter::Result<Element> {
+		host.set_home_url("app://")?;
+		let mut root = Element::from_window(host.get_hwnd())?;
+		let item = Element::with_text("p", "hello")?;
+		root.append(&item)?;
+		Ok(item)
+	}
+
+	let host = Host::attach(sciter::fake::create_window());
+	let err = build(&host).unwrap_err();
+	assert_eq!(err, SCDOM_RESULT::OK_NOT_HANDLED);
+
+	host.load_html(b"<html></html>", None);
+	assert_eq!(build(&host).unwrap().get_text(), "hello");
+}
+
+#[test]
+fn formatting_works() {
+	let err = sciter::Error::from(()).with_call("SciterLoadFile").with_url("file://missing.htm");
+	assert_eq!(err.code(), Code::Failed);
+	assert_eq!(err.to_string(), "SciterLoadFile failed for \"file://missing.htm\"");
+
+	let err = sciter::Error::new(Code::Library).with_path("/nowhere").with_message("not found");
+	assert_eq!(err.to_string(), "library is not loaded at \"/nowhere\": not found");
+}
+
+#[test]
+fn script_errors_work() {
+	setup();
+
+	let host = Host::attach(sciter::fake::create_window());
+	host.load_html(b"<html><body></body></html>", Some("app://main.htm"));
+
+	let err = host.eval_script("missing(1)").unwrap_err();
+	assert_eq!(err.message(), "missing is not a function");
+	assert_eq!(err.url(), None);
+	assert!(err.value().is_error_string());
+
+	let err = host.eval_script_with_url("missing(1)", "app://snippet.js").unwrap_err();
+	assert_eq!(err.url(), None);
+	assert_eq!(err.source_url(), Some("app://snippet.js"));
+	assert_eq!(err.to_string(), "missing is not a function in app://snippet.js");
+	assert_eq!(host.eval_script_with_url("[1, 2]", "app://snippet.js"), Ok(varray![1, 2]));
+
+	let body = host.get_root().unwrap().find_first("body").unwrap().unwrap();
+	assert_eq!(body.call_function("missing", &[]).unwrap_err().message(), "missing is not a function");
+	assert_eq!(body.eval_script_with_url("nothing", "app://body.js").unwrap_err().source_url(), Some("app://body.js"));
+
+	let api: sciter::Error = host.call_function("missing", &[]).unwrap_err().into();
+	assert_eq!(api.code(), Code::Script);
+	assert_eq!(api.message(), Some("missing is not a function"));
+}
+
+#[test]
+fn script_errors_are_extracted() {
+	setup();
+
+	let stack = "TypeError: x is undefined\n    at foo (app://main.js:12:5)\n    at app://main.js:20:1";
+	let err = ScriptError::from(Value::error(stack));
+	assert_eq!(err.message(), "TypeError: x is undefined");
+	assert_eq!((err.url(), err.line(), err.column()), (Some("app://main.js"), Some(12), Some(5)));
+	assert_eq!(err.stack(), Some("    at foo (app://main.js:12:5)\n    at app://main.js:20:1"));
+	assert_eq!(err.to_string(), "TypeError: x is undefined at app://main.js:12:5");
+
+	let err = ScriptError::from(Value::error("Error: boom\nfile://app.tis(7)"));
+	assert_eq!((err.url(), err.line(), err.column()), (Some("file://app.tis"), Some(7), None));
+
+	let mut object = Value::map();
+	object.set_item("message", "boom");
+	object.set_item("fileName", "app://main.js");
+	object.set_item("lineNumber", 3);
+	let err = ScriptError::from(object.clone());
+	assert_eq!((err.message(), err.url(), err.line(), err.stack()), ("boom", Some("app://main.js"), Some(3), None));
+
+	object.set_item("lineNumber", -1);
+	object.set_item("columnNumber", 1e12);
+	let err = ScriptError::from(object);
+	assert_eq!((err.line(), err.column()), (None, None));
+
+	let mut dead = Element::create("div").unwrap();
+	let copy = dead.clone();
+	dead.destroy().unwrap();
+	let err = copy.eval_script("1").unwrap_err();
+	assert_eq!(err.api_error().map(sciter::Error::call), Some(Some("SciterEvalElementScript")));
+
+	let mut copy = copy;
+	let err = copy.destroy().unwrap_err();
+	assert_eq!(err.call(), Some("SciterDeleteElement"));
+	assert!(copy.as_ptr().is_null());
+}
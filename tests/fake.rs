@@ -0,0 +1,249 @@
+#[macro_use]
+extern crate sciter;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use sciter::dom::event::*;
+use sciter::dom::{Element, HELEMENT};
+use sciter::value::Value;
+use sciter::Host;
+
+fn setup() {
+	sciter::fake::install();
+}
+
+fn load(html: &str) -> (Host, Element) {
+	setup();
+	let host = Host::attach(sciter::fake::create_window());
+	assert!(host.load_html(html.as_bytes(), None));
+	let root = host.get_root().expect("document root");
+	(host, root)
+}
+
+
+#[test]
+fn values_work() {
+	setup();
+
+	let v = Value::from("hello");
+	assert!(v.is_string());
+	assert_eq!(v.as_string(), Some("hello".to_owned()));
+
+	let mut map = Value::new();
+	map.set_item("one", 1);
+	map.set_item("two", varray![1, 2]);
+	assert_eq!(map.len(), 2);
+	assert_eq!(map.get_item("one"), Value::from(1));
+	assert_eq!(map.get_item("two").len(), 2);
+
+	let parsed: Value = "{one: 1, two: [1, 2]}".parse().unwrap();
+	assert_eq!(parsed, map);
+	assert_eq!(map.to_string(), r#"{"one":1,"two":[1,2]}"#);
+
+	let copy = map.clone();
+	map.set_item("one", "changed");
+	assert_eq!(copy.get_item("one"), Value::from("changed"));
+}
+
+#[test]
+fn native_functions_work() {
+	setup();
+
+	let sum = Value::from(|args: &[Value]| args.iter().filter_map(|v| v.to_int()).sum::<i32>());
+	assert!(sum.is_native_function());
+	assert_eq!(sum.call(None, &make_args!(1, 2, 3), None), Ok(Value::from(6)));
+}
+
+#[test]
+fn elements_work() {
+	setup();
+
+	let mut list = Element::create("ul").unwrap();
+	for text in &["one", "two", "three"] {
+		let item = Element::with_text("li", text).unwrap();
+		list.append(&item).unwrap();
+	}
+	assert_eq!(list.len(), 3);
+	assert_eq!(list.get_text(), "onetwothree");
+	assert_eq!(list.child(1).unwrap().get_text(), "two");
+	assert_eq!(list.child(1).unwrap().parent(), Some(list.clone()));
+	assert_eq!(list.child(2).unwrap().index(), 2);
+
+	list.set_attribute("id", "list").unwrap();
+	assert_eq!(list.get_attribute("id"), Some("list".to_owned()));
+	assert_eq!(list.get_attribute("class"), None);
+	list.remove_attribute("id").unwrap();
+	assert_eq!(list.attribute_count(), 0);
+
+	let mut last = list.last_child().unwrap();
+	last.detach().unwrap();
+	assert_eq!(list.len(), 2);
+	assert_eq!(list.get_html(true), b"<ul><li>one</li><li>two</li></ul>".to_vec());
+
+	list.set_html(b"<li class=new>four &amp; five</li>", None).unwrap();
+	assert_eq!(list.len(), 1);
+	assert_eq!(list.child(0).unwrap().get_text(), "four & five");
+	assert_eq!(list.child(0).unwrap().get_attribute("class"), Some("new".to_owned()));
+}
+
+#[test]
+fn selectors_work() {
+	let (_host, root) = load(r#"
+		<html>
+			<body>
+				<ul id="list">
+					<li class="item first">one</li>
+					<li class="item" data-x="2">two</li>
+					<li class="item">three</li>
+				</ul>
+				<p>text</p>
+			</body>
+		</html>"#);
+
+	assert_eq!(root.get_tag(), "html");
+	assert_eq!(root.find_all("li").unwrap().unwrap().len(), 3);
+	assert_eq!(root.find_all("#list > .item").unwrap().unwrap().len(), 3);
+	assert_eq!(root.find_first("li.first").unwrap().unwrap().get_text(), "one");
+	assert_eq!(root.find_first("li[data-x=2]").unwrap().unwrap().get_text(), "two");
+	assert_eq!(root.find_first("li:last-child").unwrap().unwrap().get_text(), "three");
+	assert_eq!(root.find_first("ul + p").unwrap().unwrap().get_text(), "text");
+	assert!(root.find_first("li.missing").unwrap().is_none());
+	assert!(root.find_first("li[").is_err());
+
+	let item = root.find_first("li").unwrap().unwrap();
+	let list = item.find_nearest_parent("ul").unwrap().unwrap();
+	assert_eq!(list.get_attribute("id"), Some("list".to_owned()));
+	assert!(item.test("li.item"));
+	assert!(!item.test("ul"));
+}
+
+#[derive(Default)]
+struct Recorder {
+	log: Rc<RefCell<Vec<String>>>,
+	name: &'static str,
+}
+
+impl Recorder {
+	fn new(name: &'static str, log: &Rc<RefCell<Vec<String>>>) -> Recorder {
+		Recorder { log: log.clone(), name }
+	}
+
+	fn push(&self, what: String) {
+		self.log.borrow_mut().push(format!("{}: {}", self.name, what));
+	}
+}
+
+impl EventHandler for Recorder {
+	fn get_subscription(&mut self) -> Option<EVENT_GROUPS> {
+		Some(EVENT_GROUPS::HANDLE_ALL)
+	}
+
+	fn attached(&mut self, _root: HELEMENT) {
+		self.push("attached".to_owned());
+	}
+
+	fn detached(&mut self, _root: HELEMENT) {
+		self.push("detached".to_owned());
+	}
+
+	fn document_complete(&mut self, _root: HELEMENT, _target: HELEMENT) {
+		self.push("complete".to_owned());
+	}
+
+	fn on_event(&mut self, _root: HELEMENT, _source: HELEMENT, _target: HELEMENT, code: BEHAVIOR_EVENTS, phase: PHASE_MASK, _reason: EventReason) -> bool {
+		if code == BEHAVIOR_EVENTS::BUTTON_CLICK {
+			self.push(format!("click {:?}", phase));
+		}
+		false
+	}
+
+	fn on_timer(&mut self, _root: HELEMENT, timer_id: u64) -> bool {
+		self.push(format!("timer {}", timer_id));
+		self.log.borrow().len() < 3
+	}
+
+	fn on_script_call(&mut self, _root: HELEMENT, name: &str, args: &[Value]) -> Option<Value> {
+		match name {
+			"sum" => Some(Value::from(args.iter().filter_map(|v| v.to_int()).sum::<i32>())),
+			_ => None,
+		}
+	}
+}
+
+#[test]
+fn events_work() {
+	let (_host, root) = load("<html><body><button>OK</button></body></html>");
+	let log = Rc::new(RefCell::new(Vec::new()));
+
+	let mut body = root.find_first("body").unwrap().unwrap();
+	let mut button = root.find_first("button").unwrap().unwrap();
+	let token = body.attach_handler(Recorder::new("body", &log)).unwrap();
+	button.attach_handler(Recorder::new("button", &log)).unwrap();
+
+	assert!(!button.send_event(BEHAVIOR_EVENTS::BUTTON_CLICK, None, None).unwrap());
+	assert_eq!(*log.borrow(), [
+		"body: attached",
+		"button: attached",
+		"body: click SINKING",
+		"button: click SINKING",
+		"button: click BUBBLING",
+		"body: click BUBBLING",
+	]);
+
+	log.borrow_mut().clear();
+	body.detach_handler::<Recorder>(token).unwrap();
+	button.post_event(BEHAVIOR_EVENTS::BUTTON_CLICK, None, None).unwrap();
+	assert!(log.borrow().iter().all(|s| !s.contains("click")));
+	assert_eq!(sciter::fake::pump(), 1);
+	assert_eq!(*log.borrow(), [
+		"body: detached",
+		"button: click SINKING",
+		"button: click BUBBLING",
+	]);
+}
+
+#[test]
+fn timers_work() {
+	setup();
+	let log = Rc::new(RefCell::new(Vec::new()));
+
+	let mut e = Element::create("div").unwrap();
+	e.attach_handler(Recorder::new("div", &log)).unwrap();
+	log.borrow_mut().clear();
+
+	e.start_timer(100, 7).unwrap();
+	sciter::fake::advance(99);
+	assert!(log.borrow().is_empty());
+	sciter::fake::advance(1);
+	assert_eq!(*log.borrow(), ["div: timer 7"]);
+
+	// the handler stops the timer on the third tick
+	sciter::fake::advance(1000);
+	assert_eq!(log.borrow().len(), 3);
+
+	drop(e);
+	assert_eq!(log.borrow().last().unwrap(), "div: detached");
+}
+
+#[test]
+fn host_works() {
+	setup();
+	let log = Rc::new(RefCell::new(Vec::new()));
+
+	let host = Host::attach(sciter::fake::create_window());
+	host.event_handler(Recorder::new("view", &log));
+	host.load_html(b"<html><body><p>hello</p></body></html>", Some("app://main.htm"));
+	assert_eq!(*log.borrow(), ["view: complete"]);
+
+	assert_eq!(host.eval_script("view.sum(1, 2, 3)"), Ok(Value::from(6)));
+	assert_eq!(host.eval_script("[1, 'two']"), Ok(varray![1, "two"]));
+	assert!(host.eval_script("missing(1)").is_err());
+
+	let root = host.get_root().unwrap();
+	assert_eq!(root.find_first("p").unwrap().unwrap().get_text(), "hello");
+
+	sciter::set_variable("greet", Value::from(|args: &[Value]| format!("hello, {}", args[0].as_string().unwrap_or_default()))).unwrap();
+	assert_eq!(host.call_function("greet", &make_args!("world")), Ok(Value::from("hello, world")));
+	assert!(host.call_function("missing", &[]).is_err());
+}
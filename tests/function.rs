@@ -0,0 +1,105 @@
+#[macro_use]
+extern crate sciter;
+
+use std::cell::RefCell;
+use std::slice;
+
+use sciter::value::{Rest, Value};
+
+fn setup() {
+	sciter::fake::install();
+}
+
+fn call(f: &Value, args: &[Value]) -> Value {
+	f.call(None, args, None).expect("native function")
+}
+
+
+#[test]
+fn typed_arguments_work() {
+	setup();
+
+	let answer = Value::function(|| 42);
+	assert!(answer.is_native_function());
+	assert_eq!(call(&answer, &[]), Value::from(42));
+
+	let format = Value::function(|name: String, age: Option<u8>, tags: Vec<String>| {
+		format!("{} {:?} {}", name, age, tags.join(","))
+	});
+	assert_eq!(call(&format, &make_args!("ann", 7, varray!["a", "b"])), Value::from("ann Some(7) a,b"));
+	assert_eq!(call(&format, &make_args!("bob", Value::null(), Value::array(0))), Value::from("bob None "));
+
+	let divide = Value::function(|a: f64, b: f64| if b == 0.0 { Err("division by zero") } else { Ok(a / b) });
+	assert_eq!(call(&divide, &make_args!(1.0, 4.0)), Value::from(0.25));
+	assert_eq!(call(&divide, &make_args!(1.0, 0.0)), Value::error("division by zero"));
+}
+
+#[test]
+fn state_works() {
+	setup();
+
+	let mut total = 0;
+	let counter = Value::function(move |n: i32| { total += n; total });
+	assert_eq!(call(&counter, &make_args!(2)), Value::from(2));
+	assert_eq!(call(&counter, &make_args!(3)), Value::from(5));
+	assert_eq!(call(&counter.clone(), &make_args!(-5)), Value::from(0));
+
+	let log = RefCell::new(Vec::new());
+	let raw = Value::from(move |args: &[Value]| { log.borrow_mut().extend_from_slice(args); log.borrow().len() as i32 });
+	assert_eq!(call(&raw, &make_args!(1, 2)), Value::from(2));
+	assert_eq!(call(&raw, &make_args!(3)), Value::from(3));
+}
+
+#[test]
+fn variadic_works() {
+	setup();
+
+	let join = Value::function(|sep: String, parts: Rest<String>| parts.join(&sep));
+	assert_eq!(call(&join, &make_args!("-")), Value::from(""));
+	assert_eq!(call(&join, &make_args!("-", "a", "b", "c")), Value::from("a-b-c"));
+
+	let count = Value::function(|all: Rest<Value>| all.len() as i32);
+	assert_eq!(call(&count, &[]), Value::from(0));
+	assert_eq!(call(&count, &make_args!(1, "2", 3.0)), Value::from(3));
+}
+
+#[test]
+fn errors_work() {
+	setup();
+
+	let add = Value::function(|a: i32, b: i32| a + b);
+	assert_eq!(call(&add, &make_args!(1)), Value::error("function error: 1 of 2 arguments provided."));
+	assert_eq!(call(&add, &make_args!(1, 2, 3)), Value::error("function error: 3 of 2 arguments provided."));
+	assert_eq!(call(&add, &make_args!(1, "2")),
+		Value::error("function error: invalid type of 1 argument (i32 expected): expected i32, found string:\"2\"."));
+
+	let sum = Value::function(|first: i32, rest: Rest<i32>| first + rest.iter().sum::<i32>());
+	assert_eq!(call(&sum, &[]), Value::error("function error: 0 of at least 1 arguments provided."));
+	assert_eq!(call(&sum, &make_args!(1, 2, "x")),
+		Value::error("function error: invalid type of 1 argument (Rest<i32> expected): expected i32, found string:\"x\" at [1]."));
+}
+
+#[test]
+fn reentrancy_works() {
+	setup();
+
+	// the function gets a function and calls it while running
+	let mut calls = 0;
+	let nested = Value::function(move |f: Value| {
+		calls += 1;
+		f.call(None, &[Value::from(calls)], None).unwrap()
+	});
+	let echo = Value::function(|n: i32| n);
+	assert_eq!(call(&nested, slice::from_ref(&echo)), Value::from(1));
+
+	// calling itself is refused, and it is callable again after that
+	assert_eq!(call(&nested, slice::from_ref(&nested)), Value::error("function error: recursive calls are not supported."));
+	assert_eq!(call(&nested, &[echo]), Value::from(3));
+
+	// raw functions are `Fn` and can be reentered
+	let raw = Value::from(|args: &[Value]| match args.first() {
+		Some(f) if f.is_native_function() => f.call(None, &[Value::from(1)], None).unwrap(),
+		_ => Value::from(2),
+	});
+	assert_eq!(call(&raw, slice::from_ref(&raw)), Value::from(2));
+}
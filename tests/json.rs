@@ -0,0 +1,181 @@
+#[macro_use]
+extern crate sciter;
+
+use sciter::json;
+use sciter::value::{Value, VALUE_UNIT_TYPE_DATE};
+
+fn parse(text: &str) -> Value {
+	sciter::fake::install();
+	json::parse(text).unwrap_or_else(|e| panic!("{}: {}", text, e))
+}
+
+fn error(text: &str) -> (usize, usize, String) {
+	sciter::fake::install();
+	let e = json::parse(text).unwrap_err();
+	(e.line(), e.column(), e.message().to_owned())
+}
+
+
+#[test]
+fn plain_json_works() {
+	assert_eq!(parse("null"), Value::null());
+	assert_eq!(parse("true"), Value::from(true));
+	assert_eq!(parse("42"), Value::from(42));
+	assert_eq!(parse("-1.5e3"), Value::from(-1500.0));
+	assert!(parse("1.0").is_float());
+	assert!(parse("3000000000").is_float());
+	assert_eq!(parse("-9223372036854775808n"), Value::big_int(i64::MIN));
+	assert_eq!(json::to_string(&Value::from(u32::MAX as i64 * 3)), "12884901885.0");
+	assert_eq!(json::to_string(&Value::big_int(42)), "42n");
+	assert_eq!(parse(r#""a\"b\né😀""#), Value::from("a\"b\né😀"));
+	assert_eq!(parse("[1, 'two', [], {}]"), varray![1, "two", Value::array(0), Value::map()]);
+
+	let map = parse(r#"{"one": 1, "two": [2]}"#);
+	assert!(map.is_map());
+	assert_eq!(map.get_item("one"), Value::from(1));
+	assert_eq!(map.get_item("two"), varray![2]);
+}
+
+#[test]
+fn extensions_work() {
+	let map = parse("
+		// comment
+		{
+			key: sym, /* another comment */
+			'quoted': 'single',
+			list: [1, 2,],
+		}");
+	assert_eq!(map.get_item("key"), Value::symbol("sym"));
+	assert_eq!(map.get_item("quoted"), Value::from("single"));
+	assert_eq!(map.get_item("list"), varray![1, 2]);
+
+	assert!(parse("undefined").is_undefined());
+	assert!(parse("nothing").is_nothing());
+	assert!(parse("NaN").to_float().unwrap().is_nan());
+	assert_eq!(parse("-Infinity"), Value::from(f64::NEG_INFINITY));
+}
+
+#[test]
+fn units_work() {
+	assert_eq!(parse("#f00"), Value::color(0xFF0000FF));
+	assert_eq!(parse("#00ff0080"), Value::color(0x8000FF00));
+	assert_eq!(parse("#1234"), Value::color(0x44332211));
+
+	assert_eq!(parse("1s"), Value::duration(1.0));
+	assert_eq!(parse("250ms"), Value::duration(0.25));
+	assert_eq!(parse("1rad"), Value::angle(1.0));
+	assert_eq!(parse("180deg"), Value::angle(::std::f64::consts::PI));
+	assert_eq!(parse("0.5turn"), Value::angle(::std::f64::consts::PI));
+
+	for text in &["10px", "1.5em", "50%", "2%%", "-3mm", "12pt", "1dip"] {
+		assert_eq!(json::to_string(&parse(text)), *text);
+	}
+	assert_ne!(parse("10px"), parse("10em"));
+}
+
+#[test]
+fn dates_work() {
+	use std::time::{Duration, UNIX_EPOCH};
+
+	assert_eq!(parse("1970-01-02"), Value::from(UNIX_EPOCH + Duration::from_secs(86400)));
+	assert_eq!(parse("2000-02-29T12:30:15.25Z"), Value::from(UNIX_EPOCH + Duration::from_millis(951_827_415_250)));
+	assert_eq!(parse("2000-02-29T14:30:15.25+02:00"), parse("2000-02-29T12:30:15.25Z"));
+	assert_eq!(json::to_string(&parse("2000-02-29T12:30:15.25Z")), "2000-02-29T12:30:15.25Z");
+	assert_eq!(json::to_string(&parse("1969-12-31")), "1969-12-31T00:00:00Z");
+
+	// no offset means a local time
+	let local = parse("2020-01-02T03:04");
+	assert!(local.is_date());
+	assert!(!local.has_date_flag(VALUE_UNIT_TYPE_DATE::DT_UTC));
+	assert!(parse("2020-01-02T03:04Z").has_date_flag(VALUE_UNIT_TYPE_DATE::DT_UTC));
+	assert!(parse("2020-01-02").has_date_flag(VALUE_UNIT_TYPE_DATE::DT_UTC));
+	assert_ne!(local, parse("2020-01-02T03:04Z"));
+	assert_eq!(json::to_string(&local), "2020-01-02T03:04:00");
+
+	// the fake engine reads the same way
+	assert_eq!("2020-01-02T03:04".parse::<Value>(), Ok(local));
+
+	assert_eq!(error("2001-02-29").2, "invalid date");
+	assert_eq!(error("2001-13-01").2, "invalid date");
+}
+
+#[test]
+fn errors_work() {
+	assert_eq!(error(""), (1, 1, "unexpected end of input".to_owned()));
+	assert_eq!(error("[1,\n  2 3]"), (2, 5, "expected `,` or `]`".to_owned()));
+	assert_eq!(error("{a 1}"), (1, 4, "expected `:`".to_owned()));
+	assert_eq!(error("{\n 'é': \"x}"), (2, 7, "unterminated string".to_owned()));
+	assert_eq!(error("10kg"), (1, 3, "unknown unit `kg`".to_owned()));
+	assert_eq!(error("1.5n"), (1, 4, "unknown unit `n`".to_owned()));
+	assert_eq!(error("9223372036854775808n"), (1, 1, "big integer is out of range".to_owned()));
+	assert_eq!(error("#12345"), (1, 1, "invalid color".to_owned()));
+	assert_eq!(error("1 2"), (1, 3, "unexpected characters after the value".to_owned()));
+	assert_eq!(error("/* 1"), (1, 1, "unterminated comment".to_owned()));
+	assert_eq!(error(&"[".repeat(1_000_000)), (1, 129, "nesting is too deep".to_owned()));
+	assert_eq!(error(&"{a:".repeat(1_000_000)), (1, 385, "nesting is too deep".to_owned()));
+	assert!(json::parse(&format!("{}{}", "[".repeat(128), "]".repeat(128))).is_ok());
+
+	let e = json::parse("[@]").unwrap_err();
+	assert_eq!(e.offset(), 1);
+	assert_eq!(e.to_string(), "unexpected character `@` at line 1 column 2");
+}
+
+#[test]
+fn writer_works() {
+	let map = parse("{ s: 'a\"b', n: 1, f: 2.0, sym: abc, c: #ff000080, list: [null, true, undefined] }");
+	let text = json::to_string(&map);
+	assert_eq!(text, r#"{"s":"a\"b","n":1,"f":2.0,"sym":abc,"c":#ff000080,"list":[null,true,undefined]}"#);
+	assert_eq!(parse(&text), map);
+
+	assert_eq!(json::to_string(&Value::symbol("not a symbol")), r#""not a symbol""#);
+	assert_eq!(json::to_string(&Value::from(|_: &[Value]| 0)), "null");
+}
+
+#[test]
+fn fake_engine_uses_it() {
+	sciter::fake::install();
+
+	let v: Value = "{ width: 10px, at: 2020-01-01 }".parse().unwrap();
+	assert_eq!(v.to_string(), r#"{"width":10px,"at":2020-01-01T00:00:00Z}"#);
+	assert_eq!(Value::parse("[1, 2 3]"), Err(2));
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn serde_json_works() {
+	use std::convert::TryFrom;
+	use sciter::FromValue;
+
+	sciter::fake::install();
+
+	let json = serde_json::json!({ "n": 1, "f": 2.5, "s": "x", "list": [null, true], "empty": {} });
+	let v = Value::from(&json);
+	// `serde_json` sorts the keys
+	assert!(v.structural_eq(&vmap! {
+		"n" => 1,
+		"f" => 2.5,
+		"s" => "x",
+		"list" => varray![Value::null(), true],
+		"empty" => Value::map(),
+	}));
+	assert_eq!(serde_json::Value::try_from(&v), Ok(json));
+
+	// out of the script number range
+	assert_eq!(Value::from(&serde_json::json!(9_007_199_254_740_993_i64)), Value::from(9_007_199_254_740_992.0));
+	assert_eq!(serde_json::Value::try_from(&Value::big_int(i64::MAX)), Ok(serde_json::json!(i64::MAX)));
+
+	let v = parse("{ c: #ff000080, w: 10px, d: 250ms, a: 1rad, at: 2020-12-31T23:59:59.5Z, sym: abc, n: nothing, nan: NaN }");
+	let expected = serde_json::json!({ "c": "#ff000080", "w": "10px", "d": "0.25s", "a": "1rad", "at": "2020-12-31T23:59:59.5Z", "sym": "abc", "n": null, "nan": null });
+	assert_eq!(serde_json::Value::try_from_value(&v), Ok(expected));
+
+	let bytes = [b"".as_ref(), b"f", b"fo", b"foo", b"foob"].iter().map(|b| Value::from(*b)).collect::<Value>();
+	assert_eq!(serde_json::Value::try_from(&bytes), Ok(serde_json::json!(["", "Zg==", "Zm8=", "Zm9v", "Zm9vYg=="])));
+
+	let mut keys = Value::map();
+	keys.set_item(1, "one");
+	assert_eq!(serde_json::Value::try_from(&keys), Ok(serde_json::json!({ "1": "one" })));
+
+	let f = vmap! { "items" => varray![1, Value::from(|_: &[Value]| 0)] };
+	let e = serde_json::Value::try_from(&f).unwrap_err();
+	assert_eq!((e.expected(), e.path()), ("JSON value", "items[1]"));
+}
@@ -0,0 +1,84 @@
+#[macro_use]
+extern crate sciter;
+
+use std::convert::TryFrom;
+
+use sciter::dom::Element;
+use sciter::value::{Currency, Length, Value, ValueKind, VALUE_UNIT_TYPE_DATE};
+
+fn setup() {
+	sciter::fake::install();
+}
+
+
+#[test]
+fn scalars_work() {
+	setup();
+
+	assert_eq!(Value::new().kind(), ValueKind::Undefined);
+	assert_eq!(Value::nothing().kind(), ValueKind::Nothing);
+	assert_eq!(Value::null().kind(), ValueKind::Null);
+	assert_eq!(Value::from(true).kind(), ValueKind::Bool(true));
+	assert_eq!(Value::from(-7).kind(), ValueKind::Int(-7));
+	assert_eq!(Value::from(2.5).kind(), ValueKind::Float(2.5));
+	assert_eq!(Value::currency(Currency::from(5)).kind(), ValueKind::Currency(Currency::from_raw(50_000)));
+	assert_eq!(Value::big_int(i64::MAX).kind(), ValueKind::Currency(Currency::from_raw(i64::MAX)));
+	assert_eq!(Value::from("text").kind(), ValueKind::String("text".to_owned()));
+	assert_eq!(Value::symbol("sym").kind(), ValueKind::Symbol("sym".to_owned()));
+	assert_eq!(Value::error("boom").kind(), ValueKind::Error("boom".to_owned()));
+	assert_eq!(Value::from(b"\x01\x02".as_ref()).kind(), ValueKind::Bytes(&[1, 2]));
+	assert_eq!(Value::length(Length::em(1.5)).kind(), ValueKind::Length(Length::em(1.5)));
+	assert_eq!(Value::duration(0.25).kind(), ValueKind::Duration(0.25));
+	assert_eq!(Value::angle(1.0).kind(), ValueKind::Angle(1.0));
+	assert_eq!(Value::color(0xFF00_00FF).kind(), ValueKind::Color(0xFF00_00FF));
+
+	let flags = VALUE_UNIT_TYPE_DATE::DT_HAS_DATE | VALUE_UNIT_TYPE_DATE::DT_UTC;
+	assert_eq!(Value::date(10, flags).kind(), ValueKind::Date { ticks: 10, flags });
+}
+
+#[test]
+fn references_work() {
+	setup();
+
+	let array = varray![1, 2];
+	assert_eq!(array.kind(), ValueKind::Array(&array));
+
+	let map = vmap! { "one" => 1 };
+	match map.kind() {
+		ValueKind::Map(m) => assert_eq!(m.get_item("one"), Value::from(1)),
+		other => panic!("unexpected {:?}", other),
+	}
+
+	let element = Value::try_from(Element::create("div").unwrap()).unwrap();
+	assert_eq!(element.kind(), ValueKind::Element(&element));
+}
+
+#[test]
+fn round_trip_works() {
+	setup();
+
+	let values = [
+		Value::new(),
+		Value::nothing(),
+		Value::null(),
+		Value::from(false),
+		Value::from(42),
+		Value::from(0.5),
+		Value::big_int(-1 << 60),
+		Value::currency(Currency::from_f64(-2.5).unwrap()),
+		Value::from("text"),
+		Value::symbol("sym"),
+		Value::error("boom"),
+		Value::from(b"bytes".as_ref()),
+		Value::date(-5, VALUE_UNIT_TYPE_DATE::DT_HAS_TIME),
+		Value::length(Length::px(3.0)),
+		Value::duration(1.5),
+		Value::angle(3.0),
+		Value::color(0x8000_FF00),
+		varray![1, "two"],
+		vmap! { "key" => "value" },
+	];
+	for v in values.iter() {
+		assert_eq!(&Value::from(v.kind()), v, "{:?}", v.kind());
+	}
+}
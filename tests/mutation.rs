@@ -0,0 +1,143 @@
+#[macro_use]
+extern crate sciter;
+
+use sciter::value::Value;
+
+fn setup() {
+	sciter::fake::install();
+}
+
+
+#[test]
+fn arrays_work() {
+	setup();
+
+	let mut v = varray![1, 2, 3];
+	v.insert(0, 0);
+	v.insert(4, 4);
+	assert_eq!(v, varray![0, 1, 2, 3, 4]);
+
+	assert_eq!(v.remove(1), Some(Value::from(1)));
+	assert_eq!(v.remove(10), None);
+	assert_eq!(v, varray![0, 2, 3, 4]);
+
+	assert!(v.retain(|x| x.to_int().unwrap() % 2 == 0));
+	assert_eq!(v, varray![0, 2, 4]);
+
+	assert!(v.truncate(2));
+	assert_eq!(v, varray![0, 2]);
+
+	v.extend(vec![5, 6]);
+	assert_eq!(v, varray![0, 2, 5, 6]);
+
+	assert_eq!(v.try_get(3), Some(Value::from(6)));
+	assert_eq!(v.try_get(4), None);
+	assert_eq!(v.try_get_item(0), Some(Value::from(0)));
+	assert_eq!(v.try_get_item(-1), None);
+}
+
+#[test]
+#[should_panic(expected = "insertion index")]
+fn insert_checks_index() {
+	setup();
+
+	let mut v = varray![1];
+	v.insert(2, 0);
+}
+
+#[test]
+fn maps_work() {
+	setup();
+
+	let mut m = vmap! { "one" => 1, "two" => 2, "none" => Value::new() };
+	assert_eq!(m.try_get_item("none"), Some(Value::new()));
+	assert_eq!(m.try_get_item("missing"), None);
+
+	assert_eq!(m.remove_item("two"), Some(Value::from(2)));
+	assert_eq!(m.remove_item("two"), None);
+	assert_eq!(m.len(), 2);
+
+	assert!(m.retain_items(|_, v| !v.is_undefined()));
+	assert_eq!(m, vmap! { "one" => 1 });
+}
+
+#[test]
+fn removal_detaches() {
+	setup();
+
+	// copies share the container until it is rebuilt
+	let mut v = varray![1, 2, 3];
+	let alias = v.clone();
+	v.remove(0);
+	assert_eq!(v, varray![2, 3]);
+	assert_eq!(alias, varray![1, 2, 3]);
+
+	// nothing removed, nothing rebuilt
+	v.retain(|_| true);
+	v.truncate(5);
+	let alias = v.clone();
+	v.set(0, 20);
+	assert_eq!(alias, varray![20, 3]);
+
+	let mut parent = vmap! { "child" => vmap! { "a" => 1, "b" => 2 } };
+	let mut child = parent.get_item("child");
+	child.remove_item("a");
+	assert_eq!(child, vmap! { "b" => 2 });
+	assert_eq!(parent.get_item("child"), vmap! { "a" => 1, "b" => 2 });
+	parent.set_item("child", child);
+	assert_eq!(parent.get_item("child"), vmap! { "b" => 2 });
+
+	// script objects keep their class
+	let mut object = sciter::fake::script_object("Point", &vmap! { "x" => 1 });
+	assert_eq!(object.remove_item("x"), None);
+	assert!(!object.retain_items(|_, _| false));
+	assert!(!object.truncate(0));
+	assert_eq!(object.remove(0), None);
+	assert!(object.is_object());
+	assert_eq!(object.get_item("x"), Value::from(1));
+
+	// and so do other types
+	let mut number = Value::from(1);
+	assert!(!number.retain(|_| false));
+	assert!(!number.retain_items(|_, _| false));
+	assert_eq!(number, Value::from(1));
+}
+
+#[test]
+fn entries_work() {
+	setup();
+
+	let mut m = vmap! { "count" => 1 };
+	m.entry("count").and_modify(|v| *v = Value::from(v.to_int().unwrap() + 1)).or_insert(0);
+	m.entry("other").and_modify(|_| unreachable!()).or_insert(0);
+	assert_eq!(m, vmap! { "count" => 2, "other" => 0 });
+
+	let list = m.entry("list").or_insert_with(|| Value::array(0));
+	let mut shared = list.clone();
+	shared.push("item");
+	assert_eq!(m.get_item("list"), varray!["item"]);
+
+	assert!(m.entry("count").is_occupied());
+	assert_eq!(m.entry("count").insert(10), Some(Value::from(2)));
+	assert_eq!(m.entry("count").remove(), Some(Value::from(10)));
+	assert!(!m.entry("count").is_occupied());
+	assert_eq!(m.entry("missing").key(), &Value::from("missing"));
+}
+
+#[test]
+fn iter_mut_works() {
+	setup();
+
+	let mut v = varray![1, 2, 3];
+	for mut item in v.values_mut() {
+		let x = item.to_int().unwrap();
+		*item = Value::from(x * 10);
+	}
+	assert_eq!(v, varray![10, 20, 30]);
+
+	let mut m = vmap! { "a" => 1, "b" => 2 };
+	for (key, mut item) in m.iter_mut() {
+		*item = Value::from(format!("{}{}", key.as_string().unwrap(), item.to_int().unwrap()));
+	}
+	assert_eq!(m, vmap! { "a" => "a1", "b" => "b2" });
+}
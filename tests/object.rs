@@ -0,0 +1,77 @@
+#[macro_use]
+extern crate sciter;
+
+use std::sync::{Arc, Mutex};
+
+use sciter::value::{VALUE_RESULT, Value};
+
+fn setup() {
+	sciter::fake::install();
+}
+
+fn user() -> Value {
+	sciter::fake::script_object("User", &vmap! { "name" => "john", "age" => 42 })
+}
+
+
+#[test]
+fn properties_work() {
+	setup();
+
+	let mut obj = user();
+	assert!(obj.is_object_map());
+	assert_eq!(obj.class_name(), Some("User".to_owned()));
+
+	assert_eq!(obj.get_property::<String>("name"), Ok("john".to_owned()));
+	assert_eq!(obj.get_property::<Option<i32>>("missing"), Ok(None));
+	let err = obj.get_property::<i32>("name").unwrap_err();
+	assert_eq!(err.path(), "name");
+
+	assert!(obj.has_property("age"));
+	assert!(!obj.has_property("constructor"));
+
+	obj.set_property("age", 43).unwrap();
+	obj.set_property("admin", true).unwrap();
+	assert_eq!(obj.properties(), vec![
+		("name".to_owned(), Value::from("john")),
+		("age".to_owned(), Value::from(43)),
+		("admin".to_owned(), Value::from(true)),
+	]);
+
+	let mut number = Value::from(1);
+	assert_eq!(number.set_property("x", 1), Err(VALUE_RESULT::INCOMPATIBLE_TYPE));
+	assert!(number.is_int());
+	assert_eq!(number.class_name(), None);
+	assert_eq!(vmap! { "a" => 1 }.class_name(), None);
+}
+
+#[test]
+fn methods_work() {
+	setup();
+
+	let calls = Arc::new(Mutex::new(Vec::new()));
+	let mut obj = user();
+	let log = calls.clone();
+	obj.set_property("greet", move |args: &[Value]| {
+		log.lock().unwrap().push(args.len());
+		format!("hello, {}", args[0].as_string().unwrap_or_default())
+	}).unwrap();
+
+	assert_eq!(obj.call_method("greet", &make_args!("world")), Ok(Value::from("hello, world")));
+	assert_eq!(*calls.lock().unwrap(), [1]);
+
+	// the method reads `this`
+	obj.set_property("introduce", sciter::fake::script_function(|this: &Value, args: &[Value]| {
+		let name = this.get_property::<String>("name").unwrap_or_default();
+		Value::from(format!("{}, I am {}", args[0].as_string().unwrap_or_default(), name))
+	})).unwrap();
+	assert_eq!(obj.call_method("introduce", &make_args!("hi")), Ok(Value::from("hi, I am john")));
+	obj.set_property("name", "jane").unwrap();
+	assert_eq!(obj.call_method("introduce", &make_args!("hey")), Ok(Value::from("hey, I am jane")));
+	assert_eq!(obj.call_method("name", &[]), Err(VALUE_RESULT::INCOMPATIBLE_TYPE));
+	assert_eq!(obj.call_method("missing", &[]), Err(VALUE_RESULT::INCOMPATIBLE_TYPE));
+
+	let copy = obj.clone();
+	assert_eq!(copy, obj);
+	assert_ne!(copy, user());
+}
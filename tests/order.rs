@@ -0,0 +1,103 @@
+#[macro_use]
+extern crate sciter;
+
+use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeSet, HashSet};
+use std::hash::{Hash, Hasher};
+
+use sciter::Value;
+use sciter::value::Structural;
+
+fn setup() {
+	sciter::fake::install();
+}
+
+fn hash(v: &Value) -> u64 {
+	let mut hasher = DefaultHasher::new();
+	Structural(v.clone()).hash(&mut hasher);
+	hasher.finish()
+}
+
+fn less(a: Value, b: Value) -> bool {
+	a.structural_cmp(&b) == Ordering::Less
+}
+
+
+#[test]
+fn equality_works() {
+	setup();
+
+	assert!(Value::from(1).structural_eq(&Value::from(1)));
+	assert!(!Value::from(1).structural_eq(&Value::from(1.0)));
+	assert!(!Value::from("a").structural_eq(&Value::symbol("a")));
+	assert!(Value::from(f64::NAN).structural_eq(&Value::from(f64::NAN)));
+	assert!(!Value::from(0.0).structural_eq(&Value::from(-0.0)));
+
+	let a = vmap! { "one" => 1, "two" => varray![2, "2"] };
+	let b = vmap! { "two" => varray![2, "2"], "one" => 1 };
+	assert!(a.structural_eq(&b));
+	assert_eq!(Structural(a.clone()), Structural(b.clone()));
+	assert_eq!(hash(&a), hash(&b));
+	assert!(!a.structural_eq(&vmap! { "one" => 1, "two" => varray!["2", 2] }));
+
+	let f = Value::from(|_: &[Value]| Value::null());
+	assert!(f.structural_eq(&f.clone()));
+	assert!(!f.structural_eq(&Value::from(|_: &[Value]| Value::null())));
+
+	// `==` is up to the engine
+	assert_eq!(Value::from(1), Value::from(1));
+	assert_eq!(a, a.clone());
+	assert_ne!(Value::from(1), Value::from(2));
+}
+
+#[test]
+fn ordering_works() {
+	setup();
+
+	assert!(less(Value::new(), Value::null()));
+	assert!(less(Value::null(), Value::from(false)));
+	assert!(less(Value::from(false), Value::from(true)));
+	assert!(less(Value::from(i32::MAX), Value::from(-1.0)));
+	assert!(less(Value::from(-1.5), Value::from(1.0)));
+	assert!(less(Value::from("a"), Value::from("b")));
+	assert!(less(varray![1, 2], varray![1, 2, 0]));
+	assert!(less(varray![1, 2, 0], varray![1, 3]));
+	assert_eq!(vmap! { "a" => 1 }.structural_cmp(&vmap! { "a" => 2 }), Ordering::Less);
+
+	let set: BTreeSet<Structural> = vec![Value::from(3), Value::from("x"), Value::from(1), Value::from(3)].into_iter().map(Structural).collect();
+	assert_eq!(set.into_iter().map(|v| v.0).collect::<Vec<_>>(), vec![Value::from(1), Value::from(3), Value::from("x")]);
+}
+
+#[test]
+fn hashing_works() {
+	setup();
+
+	let items = vec![varray![1, 2], Value::from("1"), varray![1, 2], Value::from(1), vmap! { "a" => 1 }, vmap! { "a" => 1 }];
+	let unique: HashSet<Structural> = items.into_iter().map(Structural).collect();
+	assert_eq!(unique.len(), 4);
+	assert!(unique.contains(&Structural(Value::from(1))));
+	assert!(!unique.contains(&Structural(Value::from(1.0))));
+}
+
+#[test]
+fn sort_works() {
+	setup();
+
+	let mut v = varray![3, "b", 1, "a", 2.5];
+	v.sort();
+	assert_eq!(v, varray![1, 3, 2.5, "a", "b"]);
+
+	let copy = v.clone();
+	v.sort_by(|a, b| b.structural_cmp(a));
+	assert_eq!(v, varray!["b", "a", 2.5, 3, 1]);
+	assert_eq!(copy, v);
+
+	let mut v = varray![vmap! { "n" => 2 }, vmap! { "n" => 1 }];
+	v.sort_by_key(|x| x.get_item("n").to_int());
+	assert_eq!(v[0], vmap! { "n" => 1 });
+
+	let mut m = vmap! { "a" => 2, "b" => 1 };
+	m.sort();
+	assert_eq!(m.keys().collect::<Vec<_>>(), vec![Value::from("a"), Value::from("b")]);
+}
@@ -0,0 +1,74 @@
+#[macro_use]
+extern crate sciter;
+
+use std::thread;
+
+use sciter::value::{Currency, FromValue, Length, OwnedValue, Value};
+
+fn setup() {
+	sciter::fake::install();
+}
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+
+#[test]
+fn copies_work() {
+	setup();
+	assert_send_sync::<OwnedValue>();
+
+	let v = vmap! {
+		"id" => 1,
+		"name" => "report",
+		"tags" => varray!["a", Value::symbol("b"), Value::null()],
+		"size" => Value::length(Length::px(12.0)),
+		"data" => Value::from(b"\x01\x02".as_ref()),
+		"total" => Value::big_int(i64::MAX),
+	};
+	let data = v.to_owned_data().unwrap();
+	assert_eq!(data, OwnedValue::try_from_value(&v).unwrap());
+
+	match data {
+		OwnedValue::Map(ref items) => {
+			assert_eq!(items[0], (OwnedValue::String("id".to_owned()), OwnedValue::Int(1)));
+			assert_eq!(items[2].1, OwnedValue::Array(vec![
+				OwnedValue::String("a".to_owned()),
+				OwnedValue::Symbol("b".to_owned()),
+				OwnedValue::Null,
+			]));
+			assert_eq!(items[4].1, OwnedValue::Bytes(vec![1, 2]));
+			assert_eq!(items[5].1, OwnedValue::Currency(Currency::from_raw(i64::MAX)));
+		},
+		ref other => panic!("unexpected {:?}", other),
+	}
+
+	let data = thread::spawn(move || {
+		let mut data = data;
+		if let OwnedValue::Map(ref mut items) = data {
+			items.push((OwnedValue::from("done"), OwnedValue::from(true)));
+		}
+		data
+	}).join().unwrap();
+
+	let copy = Value::from(data);
+	assert_eq!(copy.len(), 7);
+	assert_eq!(copy.get_item("tags"), v.get_item("tags"));
+	assert_eq!(copy.get_item("size"), v.get_item("size"));
+	assert_eq!(copy.get_item("total"), v.get_item("total"));
+	assert_eq!(copy.get_item("done"), Value::from(true));
+
+	assert_eq!(OwnedValue::from(1), OwnedValue::Int(1));
+	assert_eq!(OwnedValue::from(0.5), OwnedValue::Float(0.5));
+	assert_eq!(OwnedValue::from("a".to_owned()), OwnedValue::String("a".to_owned()));
+}
+
+#[test]
+fn errors_work() {
+	setup();
+
+	let v = vmap! { "items" => varray![1, Value::from(|_: &[Value]| Value::null())] };
+	let err = v.to_owned_data().unwrap_err();
+	assert_eq!(err.expected(), "data value");
+	assert_eq!(err.path(), "items[1]");
+	assert_eq!(OwnedValue::from_value(&v), None);
+}
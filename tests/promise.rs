@@ -0,0 +1,139 @@
+#[macro_use]
+extern crate sciter;
+
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use sciter::promise::Promise;
+use sciter::value::{OwnedValue, Value};
+use sciter::types::HWINDOW;
+use sciter::Host;
+
+fn setup() -> Host {
+	sciter::fake::install();
+	Host::attach(sciter::fake::create_window())
+}
+
+type Log = Arc<Mutex<Vec<String>>>;
+
+/// Call `then()` of the script promise with callbacks logging the results.
+fn subscribe(promise: &Value, log: &Log) {
+	let on_fulfilled = { let log = log.clone(); move |args: &[Value]| { log.lock().unwrap().push(format!("ok: {}", args[0])); Value::new() } };
+	let on_rejected = { let log = log.clone(); move |args: &[Value]| { log.lock().unwrap().push(format!("error: {}", args[0])); Value::new() } };
+	promise.get_item("then").call(None, &make_args!(on_fulfilled, on_rejected), None).unwrap();
+}
+
+fn results(log: &Log) -> Vec<String> {
+	log.lock().unwrap().clone()
+}
+
+
+#[test]
+fn settlement_works() {
+	let host = setup();
+	let log = Log::default();
+
+	let promise = Promise::new(host.get_hwnd());
+	let resolver = promise.resolver();
+	let promise = Value::from(promise);
+	subscribe(&promise, &log);
+
+	let worker = thread::spawn(move || {
+		// values are not created off the UI thread
+		assert!(resolver.resolve(OwnedValue::Map(vec![(OwnedValue::from("id"), OwnedValue::from(1))])));
+		assert!(!resolver.reject("late"));
+		assert!(resolver.is_settled());
+	});
+	worker.join().unwrap();
+	assert!(results(&log).is_empty());
+
+	assert_eq!(sciter::fake::pump(), 1);
+	assert_eq!(results(&log), ["ok: {\"id\":1}"]);
+
+	// already settled
+	subscribe(&promise, &log);
+	assert_eq!(results(&log).len(), 2);
+
+	let promise = Promise::new(host.get_hwnd());
+	let resolver = promise.resolver();
+	let promise = Value::from(promise);
+	subscribe(&promise, &log);
+	thread::spawn(move || resolver.settle(Err::<i32, _>("no report"))).join().unwrap();
+	sciter::fake::pump();
+	assert_eq!(results(&log)[2], "error: \"no report\"");
+}
+
+#[test]
+fn dropped_resolver_rejects() {
+	let host = setup();
+	let log = Log::default();
+
+	let promise = Promise::new(host.get_hwnd());
+	let resolver = promise.resolver();
+	let promise = Value::from(promise);
+	subscribe(&promise, &log);
+
+	thread::spawn(move || drop(resolver)).join().unwrap();
+	sciter::fake::pump();
+	assert_eq!(results(&log), ["error: \"promise is dropped without settlement\""]);
+}
+
+#[test]
+fn destroyed_window_works() {
+	let host = setup();
+	let log = Log::default();
+
+	let promise = Promise::new(host.get_hwnd());
+	let resolver = promise.resolver();
+	let promise = Value::from(promise);
+	subscribe(&promise, &log);
+	sciter::fake::destroy_window(host.get_hwnd());
+
+	// the settlement can not be posted and is lost
+	thread::spawn(move || assert!(resolver.resolve(1))).join().unwrap();
+	assert_eq!(sciter::fake::pump(), 0);
+	assert!(results(&log).is_empty());
+
+	// on the UI thread as well
+	let promise = Promise::new(host.get_hwnd());
+	assert!(promise.resolver().reject("late"));
+	drop(promise);
+	assert_eq!(sciter::fake::pump(), 0);
+}
+
+
+struct Handler {
+	hwnd: HWINDOW,
+}
+
+impl Handler {
+	fn load_report(&self, id: i32) -> Promise {
+		let promise = Promise::new(self.hwnd);
+		let resolver = promise.resolver();
+		thread::spawn(move || resolver.resolve(format!("report #{}", id)));
+		promise
+	}
+}
+
+impl sciter::EventHandler for Handler {
+	dispatch_script_call! {
+		fn load_report(i32);
+	}
+}
+
+#[test]
+fn script_calls_work() {
+	let host = setup();
+	let log = Log::default();
+
+	host.event_handler(Handler { hwnd: host.get_hwnd() });
+	host.load_html(b"<html><body></body></html>", None);
+
+	let promise = host.eval_script("view.load_report(7)").unwrap();
+	subscribe(&promise, &log);
+	while results(&log).is_empty() {
+		sciter::fake::pump();
+		thread::yield_now();
+	}
+	assert_eq!(results(&log), ["ok: \"report #7\""]);
+}
@@ -0,0 +1,46 @@
+extern crate sciter;
+
+use std::fs;
+
+use sciter::dom::Element;
+use sciter::trace::Target;
+use sciter::{RuntimeOptions, Value};
+
+
+#[test]
+fn calls_are_recorded() {
+	let path = ::std::env::temp_dir().join(format!("sciter-trace-{}.log", ::std::process::id()));
+	let target = Target::File(path.to_str().unwrap());
+
+	// must be the first Sciter call
+	sciter::set_options(RuntimeOptions::TraceApi(target)).unwrap();
+	sciter::fake::install();
+
+	let mut div = Element::with_text("div", "hello").unwrap();
+	div.set_attribute("id", "main").unwrap();
+	assert!(div.child(5).is_none());
+	let text = Value::from("text");
+	assert_eq!(text.as_string(), Some("text".to_owned()));
+
+	sciter::set_options(RuntimeOptions::TraceApi(Target::Off)).unwrap();
+	let _span = Element::create("span").unwrap();
+
+	let log = fs::read_to_string(&path).unwrap();
+	let _ = fs::remove_file(&path);
+
+	assert!(log.contains(r#"SciterCreateElement(tagname="div", textOrNull="hello") -> OK phe=#"#));
+	assert!(log.contains(r#"name="id", value="main") -> OK ["#));
+	// out-parameters of failed calls are not read
+	assert!(log.contains(", n=5) -> INVALID_PARAMETER ["));
+	assert!(log.contains(r#"ValueStringDataSet(pval=undefined, chars="text", numChars=4, units=0) -> OK ["#));
+	assert!(log.contains("ValueStringData(pval=string, "));
+	assert!(!log.contains("span"));
+
+	// tracing can be resumed
+	sciter::set_options(RuntimeOptions::TraceApi(Target::File(path.to_str().unwrap()))).unwrap();
+	drop(div);
+	sciter::set_options(RuntimeOptions::TraceApi(Target::Off)).unwrap();
+	let log = fs::read_to_string(&path).unwrap();
+	let _ = fs::remove_file(&path);
+	assert!(log.starts_with("Sciter_UnuseElement(he=#"));
+}
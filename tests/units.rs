@@ -0,0 +1,63 @@
+extern crate sciter;
+
+use sciter::json;
+use sciter::value::{Currency, FromValue, Length, LengthUnit, Value};
+
+fn setup() {
+	sciter::fake::install();
+}
+
+
+#[test]
+fn lengths_work() {
+	setup();
+
+	let v = Value::length(Length::dip(12.5));
+	assert!(v.is_length());
+	assert_eq!(v.to_length(), Some(Length::new(12.5, LengthUnit::Dip)));
+	assert_eq!(v.to_float(), Some(12.5));
+	assert_eq!(json::to_string(&v), "12.5dip");
+	assert_eq!(json::parse("50%").unwrap().to_length(), Some(Length::percent(50.0)));
+
+	assert_eq!(Value::from(Length::em(2.0)), Value::length(Length::em(2.0)));
+	assert_ne!(Value::from(Length::em(2.0)), Value::from(Length::px(2.0)));
+	assert_eq!(Length::from_value(&Value::from(2.0)), None);
+
+	for unit in &[LengthUnit::Px, LengthUnit::Springs, LengthUnit::Mm] {
+		assert_eq!(LengthUnit::from_suffix(unit.suffix()), Some(*unit));
+		assert_eq!(LengthUnit::from_raw(unit.to_raw()), Some(*unit));
+	}
+}
+
+#[test]
+fn length_conversions_work() {
+	let inch = Length::new(1.0, LengthUnit::In);
+	assert_eq!(inch.to_unit(LengthUnit::Dip), Some(Length::dip(96.0)));
+	assert_eq!(inch.to_unit(LengthUnit::Pc), Some(Length::new(6.0, LengthUnit::Pc)));
+	assert_eq!(Length::new(25.4, LengthUnit::Mm).to_unit(LengthUnit::Pt).map(|l| l.value.round()), Some(72.0));
+	assert_eq!(Length::px(10.0).to_unit(LengthUnit::Dip), None);
+	assert_eq!(Length::px(10.0).to_unit(LengthUnit::Px), Some(Length::px(10.0)));
+	assert_eq!(Length::percent(10.0).to_string(), "10%");
+}
+
+#[test]
+fn currency_works() {
+	setup();
+
+	let price = Currency::from_f64(-12.3456789).unwrap();
+	assert_eq!(price.raw(), -123_457);
+	assert_eq!(price.to_string(), "-12.3457");
+	assert_eq!(Currency::from(7).to_string(), "7");
+	assert_eq!(Currency::from_int(5).and_then(|x| x.checked_sub(Currency::from_raw(2_500))).map(Currency::to_f64), Some(4.75));
+
+	assert_eq!(Currency::from_f64(f64::NAN), None);
+	assert_eq!(Currency::from_f64(1e30), None);
+	assert_eq!(Currency::from_raw(i64::MAX).checked_add(Currency::from_raw(1)), None);
+	assert_eq!(Currency::from_raw(i64::MIN).checked_mul(-1), None);
+
+	let v = Value::currency(price);
+	assert!(v.is_currency());
+	assert_eq!(v.to_currency(), Some(price));
+	assert_eq!(Currency::from_value(&Value::from(price)), Some(price));
+	assert_eq!(Value::from(1).to_currency(), None);
+}
@@ -8,6 +8,7 @@ use sciter::value::*;
 
 #[test]
 fn new_works() {
+	sciter::fake::install();
 	let v = Value::new();
 	assert!(v.is_undefined());
 	assert!(!v.is_null());
@@ -15,6 +16,7 @@ fn new_works() {
 
 #[test]
 fn varray_works() {
+  sciter::fake::install();
   let val = varray![];
   assert!(val.is_array());
   assert_eq!(val.len(), 0);
@@ -30,6 +32,7 @@ fn varray_works() {
 
 #[test]
 fn vmap_works() {
+  sciter::fake::install();
   let map = vmap!{};
   assert!(map.is_map());
   assert_eq!(map.len(), 0);
@@ -51,6 +54,7 @@ fn vmap_works() {
 
 #[test]
 fn null_works() {
+ 	sciter::fake::install();
  	let v = Value::null();
 	assert!(!v.is_undefined());
 	assert!(v.is_null());
@@ -58,6 +62,7 @@ fn null_works() {
 
 #[test]
 fn clear_works() {
+	sciter::fake::install();
 	let mut v = Value::null();
 	assert!(v.is_null());
 
@@ -68,6 +73,7 @@ fn clear_works() {
 
 #[test]
 fn symbol_works() {
+	sciter::fake::install();
 	let mut v = Value::symbol("hello");
 	assert!(v.is_symbol());
 	assert!(v.is_string());
@@ -83,6 +89,7 @@ fn is_color_supported() -> bool {
 
 #[test]
 fn color_works() {
+	sciter::fake::install();
 	if !is_color_supported() { return; }
 
 	// yellow R255, G255, B000
@@ -94,6 +101,7 @@ fn color_works() {
 
 #[test]
 fn duration_works() {
+	sciter::fake::install();
 	if !is_color_supported() { return; }
 
 	let v = Value::duration(12.5);
@@ -103,6 +111,7 @@ fn duration_works() {
 
 #[test]
 fn angle_works() {
+	sciter::fake::install();
 	if !is_color_supported() { return; }
 
 	let v = Value::angle(1.0);
@@ -112,6 +121,7 @@ fn angle_works() {
 
 #[test]
 fn array_works() {
+	sciter::fake::install();
 	let v = Value::array(0);
 	assert!(v.is_array());
 	assert!(v.is_empty());
@@ -123,6 +133,7 @@ fn array_works() {
 
 #[test]
 fn map_works() {
+	sciter::fake::install();
 	let v = Value::map();
 	assert!(v.is_map());
 	assert!(v.is_empty());
@@ -130,6 +141,7 @@ fn map_works() {
 
 #[test]
 fn from_bool_works() {
+	sciter::fake::install();
 	let v = Value::from(true);
 	assert!(v.is_bool());
 	let v = Value::from(false);
@@ -138,6 +150,7 @@ fn from_bool_works() {
 
 #[test]
 fn from_int_works() {
+	sciter::fake::install();
 	let v = Value::from(1);
 	assert!(v.is_int());
 	assert!(!v.is_bool());
@@ -148,13 +161,51 @@ fn from_int_works() {
 
 #[test]
 fn from_float_works() {
+	sciter::fake::install();
 	let v = Value::from(1.0);
 	assert!(v.is_float());
 }
 
+#[test]
+fn from_int64_works() {
+	sciter::fake::install();
+	let v = Value::from(7i64);
+	assert!(v.is_int());
+	assert_eq!(v.to_i64(), Some(7));
+
+	let v = Value::from(MAX_SAFE_INTEGER);
+	assert!(v.is_float());
+	assert_eq!(v.to_i64(), Some(MAX_SAFE_INTEGER));
+
+	let v = Value::from(i64::MIN);
+	assert!(v.is_float());
+	assert_eq!(v.to_i64(), Some(i64::MIN));
+	assert_eq!(v.to_u64(), None);
+
+	let v = Value::from_i64(i64::MIN, IntOverflow::BigInt).unwrap();
+	assert!(v.is_currency());
+	assert_eq!(v.to_big_int(), Some(i64::MIN));
+	assert_eq!(v.to_i64(), None);
+	assert_eq!(v.to_currency().map(|c| c.raw()), Some(i64::MIN));
+
+	let v = Value::from(1u64 << 63);
+	assert!(v.is_float());
+	assert_eq!(v.to_u64(), Some(1 << 63));
+	assert_eq!(v.to_i64(), None);
+	assert_eq!(Value::from(u64::MAX).to_u64(), None);
+
+	assert_eq!(Value::from_i64(MAX_SAFE_INTEGER + 2, IntOverflow::Float), Some(Value::from((MAX_SAFE_INTEGER + 2) as f64)));
+	assert_eq!(Value::from_i64(MAX_SAFE_INTEGER + 2, IntOverflow::Error), None);
+	assert_eq!(Value::from_u64(u64::MAX, IntOverflow::BigInt), None);
+
+	assert_eq!(Value::from(1.5).to_i64(), None);
+	assert_eq!(usize::from_value(&Value::from(42usize)), Some(42));
+}
+
 
 #[test]
 fn from_str_works() {
+	sciter::fake::install();
 	use std::str::FromStr;
 
 	let v = Value::from("hello");
@@ -169,6 +220,7 @@ fn from_str_works() {
 
 #[test]
 fn from_int_seq_works() {
+	sciter::fake::install();
 	let v: Value = [1,2,3].iter().cloned().collect();
 	assert!(v.is_array());
 	assert_eq!(v.len(), 3);
@@ -176,6 +228,7 @@ fn from_int_seq_works() {
 
 #[test]
 fn from_str_seq_works() {
+	sciter::fake::install();
 	// &str
 	let v: Value = ["1","2","3"].iter().cloned().collect();
 	assert!(v.is_array());
@@ -190,6 +243,7 @@ fn from_str_seq_works() {
 
 #[test]
 fn from_function_works() {
+	sciter::fake::install();
 	// create from lambda
 	let v = Value::from(|args: &[Value]| Value::from(args.len() as i32));
 	assert!(v.is_native_function());
@@ -211,6 +265,7 @@ fn from_function_works() {
 
 #[test]
 fn from_result_works() {
+	sciter::fake::install();
 	// create Err variant
 	let result: Result<i32, String> = Err("unknown error".to_string());
 	let v = Value::from(result);
@@ -224,6 +279,7 @@ fn from_result_works() {
 
 #[test]
 fn parse_works() {
+	sciter::fake::install();
 	let items = ["", "null", "1", "\"2\"", "2.0", "true", "[3, 4]", r##"{"5": 5, "6": 6, seven: "seven"}"##];
 	for item in &items {
 		let r = Value::parse(item);
@@ -242,6 +298,7 @@ fn parse_works() {
 #[test]	// crashes with 1.7.0 i686-pc-windows-msvc
 #[should_panic(expected="failed on character")]
 fn parse_fail_works() {
+	sciter::fake::install();
 	let item = "{item: "; // invalid json
 	let r = Value::parse(item);
 	if let Err(num) = r {
@@ -251,6 +308,7 @@ fn parse_fail_works() {
 
 #[test]
 fn pack_args_works() {
+	sciter::fake::install();
 	let args = pack_args!();
 	assert_eq!(args.len(), 0);
 
@@ -271,6 +329,7 @@ fn pack_args_works() {
 
 #[test]
 fn make_args_works() {
+	sciter::fake::install();
 	let args = make_args!();
 	assert_eq!(args.len(), 0);
 
@@ -286,6 +345,7 @@ fn make_args_works() {
 
 #[test]
 fn append_works() {
+	sciter::fake::install();
 	let mut v = Value::new();
 	v.push(Value::from(1));
 	v.push(Value::from("2"));
@@ -298,6 +358,7 @@ fn append_works() {
 
 #[test]
 fn to_works() {
+	sciter::fake::install();
 	// Value has some implicit conversions:
 	// bool or int -> int
 	// int or float or length -> float
@@ -323,6 +384,7 @@ fn to_works() {
 
 #[test]
 fn into_works() {
+	sciter::fake::install();
 
 	let v = Value::from(1);
 	assert!(v.is_int());
@@ -347,6 +409,7 @@ fn into_works() {
 
 #[test]
 fn bytes_work() {
+	sciter::fake::install();
 	let b = [1,2,3];
 	let v = Value::from(&b[..]);
 	assert!(v.is_bytes());
@@ -355,6 +418,7 @@ fn bytes_work() {
 
 #[test]
 fn index_works() {
+	sciter::fake::install();
 	let mut v = Value::new();
 	v.push(Value::from(1));
 	v.push(Value::from(2));
@@ -388,6 +452,7 @@ fn index_works() {
 
 #[test]
 fn display_works() {
+	sciter::fake::install();
 	println!("\nvalue strings: new {}, null {}, bool {}, int {}, float {}, symbol {}, str {}",
 		Value::new(), Value::null(), Value::from(true), Value::from(123), Value::from(4.2),
 		Value::symbol("symbol"), Value::from("hello"));
@@ -397,6 +462,7 @@ fn display_works() {
 
 #[test]
 fn debug_works() {
+	sciter::fake::install();
 	println!("\nvalue strings: {:?}, {:?}, {:?}, {:?}, {:?}, {:?}, {:?}",
 		Value::new(), Value::null(), Value::from(true), Value::from(123), Value::from(4.2),
 		Value::symbol("symbol"), Value::from("hello"));
@@ -406,6 +472,7 @@ fn debug_works() {
 
 #[test]
 fn thread_works() {
+	sciter::fake::install();
 	let mut v = Value::map();
 	let tid = std::thread::spawn(move || {
 		v.set_item("seven", 7);
@@ -416,6 +483,7 @@ fn thread_works() {
 
 #[test]
 fn iterators_work() {
+	sciter::fake::install();
 	let v: Value = [1,2,3].iter().cloned().collect();
 
 	// `&v` == `v.into_iter()`
@@ -430,6 +498,7 @@ fn iterators_work() {
 
 #[test]
 fn back_iter() {
+	sciter::fake::install();
 	let v: Value = [1,2,3].iter().cloned().collect();
 
 	let mut iter = v.into_iter();
@@ -444,6 +513,7 @@ fn back_iter() {
 
 #[test]
 fn keys_work() {
+	sciter::fake::install();
 	let v = Value::parse("five: 5, seven: 7").unwrap();
 	for k in v.keys() {
 		assert!(k.is_string());
@@ -452,6 +522,7 @@ fn keys_work() {
 
 #[test]
 fn values_work() {
+	sciter::fake::install();
 	let v = Value::parse("five: 5, seven: 7").unwrap();
 	for a in v.values() {
 		assert!(a.is_int());
@@ -460,6 +531,7 @@ fn values_work() {
 
 #[test]
 fn items_work() {
+	sciter::fake::install();
 	let v = Value::parse("five: 5, seven: 7").unwrap();
 	for (k,a) in v.items() {
 		assert!(k.is_string());